@@ -0,0 +1,168 @@
+use super::traits::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+use flo_curves::bezier::path::{SimpleBezierPath};
+use flo_canvas_animation::*;
+
+use std::sync::*;
+use std::ops::{Range};
+use std::time::{Duration};
+
+///
+/// Returns the times to sample when baking the specified range at a particular frame rate
+///
+/// Samples are taken at the start of each frame: a frame is only included if it starts before the end of the range.
+///
+pub fn bake_sample_times(when: Range<Duration>, frames_per_second: f64) -> Vec<Duration> {
+    if frames_per_second <= 0.0 || when.end <= when.start {
+        return vec![];
+    }
+
+    let length      = (when.end - when.start).as_secs_f64();
+    let num_frames  = (length * frames_per_second - 1e-9).ceil().max(0.0) as u64;
+
+    (0..num_frames)
+        .map(|frame_num| when.start + Duration::from_secs_f64((frame_num as f64) / frames_per_second))
+        .collect()
+}
+
+///
+/// Converts a set of bezier paths (such as those generated by the canvas animation layer) into path components
+///
+pub (crate) fn path_components_for_bezier_paths(paths: &[SimpleBezierPath]) -> Arc<Vec<PathComponent>> {
+    let mut components = vec![];
+
+    for (start_point, curves) in paths.iter() {
+        components.push(PathComponent::Move(PathPoint::new(start_point.x() as _, start_point.y() as _)));
+
+        for (cp1, cp2, end_point) in curves.iter() {
+            components.push(PathComponent::Bezier(
+                PathPoint::new(end_point.x() as _, end_point.y() as _),
+                PathPoint::new(cp1.x() as _, cp1.y() as _),
+                PathPoint::new(cp2.x() as _, cp2.y() as _)));
+        }
+
+        components.push(PathComponent::Close);
+    }
+
+    Arc::new(components)
+}
+
+///
+/// Generates the edits to 'bake' the animated content of a layer into a set of plain keyframes
+///
+/// The layer is rendered (with all of its animation regions and their effects applied) at each frame in the
+/// range, sampled at the specified frame rate. Every sample becomes a keyframe in the target layer, containing
+/// the rendered content as plain paths, so the result can be exported to formats that don't support animation
+/// regions. The target layer should already exist and have no keyframes in the baked range.
+///
+/// Each frame is sampled at the exact time it starts: motion that happens between frames is not preserved, so
+/// the frame rate should be at least the rate that the exported animation will be played back at. Only filled
+/// content is baked: strokes, textures and gradients are skipped.
+///
+pub fn bake_layer(layer: &dyn Layer, target_layer_id: u64, when: Range<Duration>, frames_per_second: f64) -> Vec<AnimationEdit> {
+    let mut edits = vec![];
+
+    for sample_time in bake_sample_times(when, frames_per_second) {
+        // Render the frame with its animations applied
+        let frame           = layer.get_frame_at_time(sample_time);
+        let mut drawing     = vec![];
+        frame.render_to(&mut drawing);
+
+        // Each sample becomes a new keyframe
+        edits.push(AnimationEdit::Layer(target_layer_id, LayerEdit::AddKeyFrame(sample_time)));
+
+        // Convert the rendering to paths
+        let mut to_paths    = LayerDrawingToPaths::new();
+
+        for path in to_paths.draw(drawing) {
+            let color = match path.attributes {
+                AnimationPathAttribute::Fill(_, color, _)   => color,
+                _                                           => { continue; }
+            };
+
+            let properties = BrushProperties { color: color, opacity: 1.0, ..BrushProperties::new() };
+
+            edits.extend(LayerEdit::create_ink_path(sample_time, properties, path_components_for_bezier_paths(&path.path))
+                .into_iter()
+                .map(|edit| AnimationEdit::Layer(target_layer_id, edit)));
+        }
+    }
+
+    edits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::editor::*;
+    use crate::storage::*;
+
+    use flo_curves::arc::*;
+    use flo_canvas_animation::description::*;
+
+    use futures::prelude::*;
+
+    #[test]
+    fn sample_times_at_12fps() {
+        let times = bake_sample_times(Duration::from_millis(0)..Duration::from_millis(1000), 12.0);
+
+        assert!(times.len() == 12);
+        assert!(times[0] == Duration::from_millis(0));
+        assert!(times[11] < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn no_samples_for_empty_range() {
+        assert!(bake_sample_times(Duration::from_millis(500)..Duration::from_millis(500), 12.0).len() == 0);
+        assert!(bake_sample_times(Duration::from_millis(0)..Duration::from_millis(1000), 0.0).len() == 0);
+    }
+
+    #[test]
+    fn bake_motion_into_keyframes() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        // Circle that moves 100 units to the right over 1 second
+        let circle          = Circle::new(Coord2(100.0, 100.0), 50.0).to_path::<SimpleBezierPath>();
+        let region          = Circle::new(Coord2(100.0, 100.0), 60.0).to_path::<SimpleBezierPath>();
+        let motion          = EffectDescription::Move(Duration::from_millis(1000), BezierPath(Point2D(100.0, 100.0), vec![BezierPoint(Point2D(133.0, 100.0), Point2D(166.0, 100.0), Point2D(200.0, 100.0))]));
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), path_components_for_bezier_paths(&[circle.clone()])))),
+            AnimationEdit::Layer(1, LayerEdit::CreateAnimation(Duration::from_millis(0), ElementId::Assigned(101), RegionDescription(vec![region.into()], motion)))
+        ]);
+
+        // Bake the motion into layer 2
+        let layer           = anim.get_layer_with_id(1).unwrap();
+        let baked           = bake_layer(&*layer, 2, Duration::from_millis(0)..Duration::from_millis(1000), 12.0);
+        anim.perform_edits(baked);
+
+        let baked_layer     = anim.get_layer_with_id(2).unwrap();
+        let key_frames      = baked_layer.get_key_frames().collect::<Vec<_>>();
+
+        assert!(key_frames.len() == 12);
+
+        // Content should move further to the right in each keyframe
+        let left_edges      = key_frames.iter()
+            .map(|when| {
+                let frame = baked_layer.get_frame_at_time(*when);
+                frame.vector_elements().unwrap()
+                    .filter_map(|element| match element {
+                        Vector::Path(path)  => Some(path.path().bounding_box().x1),
+                        _                   => None
+                    })
+                    .fold(f32::MAX, |a, b| a.min(b))
+            })
+            .collect::<Vec<_>>();
+
+        assert!(left_edges[0] < 51.0 && left_edges[0] > 49.0);
+        assert!(left_edges.windows(2).all(|pair| pair[1] > pair[0]));
+    }
+}
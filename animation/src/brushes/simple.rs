@@ -37,6 +37,21 @@ impl Brush for SimpleBrush {
         (BrushDefinition::Simple, BrushDrawingStyle::Draw)
     }
 
+    ///
+    /// Simple brushes stroke their paths using the default line width
+    ///
+    fn stroke_width(&self, _properties: &BrushProperties) -> Option<f64> {
+        Some(1.0)
+    }
+
+    ///
+    /// Simple brushes render using the brush colour directly
+    ///
+    fn render_alpha(&self, properties: &BrushProperties) -> f32 {
+        let (_, _, _, alpha) = properties.color.to_rgba_components();
+        alpha
+    }
+
     ///
     /// Returns the brush points for rendering given a particular set of raw points
     ///
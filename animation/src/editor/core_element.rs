@@ -10,6 +10,7 @@ use crate::serializer::*;
 use futures::prelude::*;
 use futures::future::{BoxFuture};
 use ::desync::*;
+use smallvec::*;
 
 use std::sync::*;
 use std::time::{Duration};
@@ -176,6 +177,14 @@ impl StreamAnimationCore {
                     self.set_control_points(&element_ids, new_points, *when).await
                 }
 
+                SetOpacity(when, opacity)           => {
+                    self.update_opacity(&element_ids, |keyframes| keyframes.set_opacity(*when, *opacity)).await
+                }
+
+                SetVisibility(when, visible)        => {
+                    self.update_opacity(&element_ids, |keyframes| keyframes.set_visibility(*when, *visible)).await
+                }
+
                 SetAnimationDescription(new_description) => {
                     let mut reversed = ReversedEdits::new();
 
@@ -566,6 +575,49 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Replaces an attachment of an element in a keyframe with a new attachment, returning the edits that restore the original attachment
+    ///
+    /// The new attachment is created by a function that is passed the ID assigned to it. Only the specified element has its
+    /// attachment replaced, so an existing attachment that is shared with other elements is left unchanged.
+    ///
+    pub fn replace_attachment<'a, CreateFn>(&'a mut self, frame: Arc<Desync<KeyFrameCore>>, element_id: i64, existing_attachment_id: Option<ElementId>, create_attachment: CreateFn) -> impl 'a+Future<Output=ReversedEdits>
+    where CreateFn: 'a+Send+FnOnce(ElementId) -> Vector {
+        async move {
+            // Create the new attachment
+            let attachment_id           = self.assign_element_id(ElementId::Unassigned).await;
+            let attachment_wrapper      = ElementWrapper::unattached_with_element(create_attachment(attachment_id), Duration::from_millis(0));
+            let mut update_elements     = vec![];
+
+            // Reversal is to remove the new attachment, restore the old attachment and then delete the new attachment
+            let mut reversed            = ReversedEdits::new();
+            reversed.push(AnimationEdit::Element(vec![ElementId::Assigned(element_id)], ElementEdit::RemoveAttachment(attachment_id)));
+            if let Some(existing_attachment_id) = existing_attachment_id {
+                reversed.push(AnimationEdit::Element(vec![ElementId::Assigned(element_id)], ElementEdit::AddAttachment(existing_attachment_id)));
+            }
+            reversed.push(AnimationEdit::Element(vec![attachment_id], ElementEdit::Delete));
+
+            frame.sync(|frame| {
+                // Create the element and add it to the frame
+                update_elements.push(StorageCommand::WriteElement(attachment_id.id().unwrap(), attachment_wrapper.serialize_to_string()));
+                update_elements.push(StorageCommand::AttachElementToLayer(frame.layer_id, attachment_id.id().unwrap(), frame.start));
+
+                frame.elements.insert(attachment_id, attachment_wrapper);
+                frame.invalidate();
+            });
+
+            self.request(update_elements).await;
+
+            // Replace the existing attachment with the new one
+            if let Some(existing_attachment_id) = existing_attachment_id {
+                self.update_elements(vec![element_id], |_wrapper| ElementUpdate::RemoveAttachments(vec![existing_attachment_id])).await;
+            }
+            self.update_elements(vec![element_id], |_wrapper| ElementUpdate::AddAttachments(vec![attachment_id])).await;
+
+            reversed
+        }
+    }
+
     ///
     /// Updates the control points for a list of elements
     ///
@@ -744,7 +796,7 @@ impl StreamAnimationCore {
             };
 
             // Modify the frame
-            let (reversed, updates) = frame.future_sync(move |frame| {
+            let (reversed, updates, elements, group_transforms) = frame.future_sync(move |frame| {
                 let mut reversed = ReversedEdits::new();
 
                 async move {
@@ -754,9 +806,25 @@ impl StreamAnimationCore {
                     // Fetch the element as a group
                     let group_wrapper = match frame.elements.get(&ElementId::Assigned(group_element_id)) {
                         Some(wrapper)   => wrapper,
-                        None            => { return (reversed, vec![]); }
+                        None            => { return (reversed, vec![], vec![], smallvec![]); }
                     };
 
+                    // Fetch any transformations applied to a normal group (these are moved to the elements when they're ungrouped).
+                    // Other group types share the attachments of their first element, so there's nothing to move
+                    let mut group_transforms: SmallVec<[Transformation; 2]> = smallvec![];
+                    let mut transform_ids   = vec![];
+                    let is_normal_group     = match &group_wrapper.element {
+                        Vector::Group(group)    => group.group_type() == GroupType::Normal,
+                        _                       => false
+                    };
+
+                    for attachment_id in group_wrapper.attachments.iter().filter(|_| is_normal_group) {
+                        if let Some(Vector::Transformation((_, transforms))) = frame.elements.get(attachment_id).map(|wrapper| &wrapper.element) {
+                            group_transforms.extend(transforms.iter().cloned());
+                            transform_ids.push(*attachment_id);
+                        }
+                    }
+
                     // Gather information on where the grouped elements will go
                     let parent          = group_wrapper.parent;
                     let order_after     = frame.element_before(ElementId::Assigned(group_element_id));
@@ -765,14 +833,17 @@ impl StreamAnimationCore {
                     // Fetch the group elements
                     let elements    = match &group_wrapper.element {
                         Vector::Group(group)    => group.elements().map(|elem| elem.id()).collect::<Vec<_>>(),
-                        _                       => { return (reversed, vec![]); }
+                        _                       => { return (reversed, vec![], vec![], smallvec![]); }
                     };
                     let group_type  = match &group_wrapper.element {
                         Vector::Group(group)    => group.group_type(),
-                        _                       => { return (reversed, vec![]); },
+                        _                       => { return (reversed, vec![], vec![], smallvec![]); },
                     };
 
-                    // Reverse re-groups the elements
+                    // Reverse re-groups the elements (and restores the transformations attached to the group)
+                    for transform_id in transform_ids {
+                        reversed.push(AnimationEdit::Element(vec![ElementId::Assigned(group_element_id)], ElementEdit::AddAttachment(transform_id)));
+                    }
                     if let Some(order_before) = order_before {
                         reversed.push(AnimationEdit::Element(vec![ElementId::Assigned(group_element_id)], ElementEdit::Order(ElementOrdering::Before(order_before))));
                     }
@@ -792,7 +863,7 @@ impl StreamAnimationCore {
                     }
 
                     // Result is the updates
-                    (reversed, updates)
+                    (reversed, updates, elements, group_transforms)
                 }.boxed()
             }).await.unwrap();
 
@@ -801,6 +872,16 @@ impl StreamAnimationCore {
             // Recreate any groups in reverse order
             let mut reversed = reversed;
             reversed.reverse();
+
+            // The elements keep their position after they leave the group by taking on its transformations
+            if group_transforms.len() > 0 {
+                for elem in elements {
+                    if let Some(elem_id) = elem.id() {
+                        reversed.add_to_start(self.prepend_transformations(elem_id, group_transforms.clone()).await);
+                    }
+                }
+            }
+
             reversed
         }
     }
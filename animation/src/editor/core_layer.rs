@@ -12,6 +12,7 @@ use flo_canvas_animation::description::*;
 
 use std::sync::*;
 use std::time::{Duration};
+use std::collections::{HashSet};
 
 impl StreamAnimationCore {
     ///
@@ -36,6 +37,8 @@ impl StreamAnimationCore {
                 SetName(new_name)                                           => { self.set_layer_name(layer_id, new_name).await }
                 SetOrdering(ordering)                                       => { self.set_layer_ordering(layer_id, *ordering).await }
                 SetAlpha(alpha)                                             => { self.set_layer_alpha(layer_id, *alpha).await }
+                SetBlendMode(blend_mode)                                    => { self.set_layer_blend_mode(layer_id, *blend_mode).await }
+                SetLocked(locked)                                           => { self.set_layer_locked(layer_id, *locked).await }
                 Cut { path, when, inside_group }   => { 
                     let cut = self.layer_cut(layer_id, *when, Arc::clone(path)).await;
                     self.apply_layer_cut(layer_id, *when, cut, *inside_group).await
@@ -251,8 +254,9 @@ impl StreamAnimationCore {
             let mut serialized  = String::new();
             properties.serialize(&mut serialized);
 
-            // Add the layer
+            // Add the layer (new layers are never locked)
             self.request_one(StorageCommand::AddLayer(layer_id, serialized)).await;
+            self.locked_layers.as_mut().map(|locked_layers| locked_layers.remove(&layer_id));
 
             ReversedEdits::with_edit(AnimationEdit::RemoveLayer(layer_id))
         }
@@ -268,6 +272,7 @@ impl StreamAnimationCore {
 
             // Remove the layer
             self.request_one(StorageCommand::DeleteLayer(layer_id)).await;
+            self.locked_layers.as_mut().map(|locked_layers| locked_layers.remove(&layer_id));
 
             reverse
         }
@@ -328,4 +333,96 @@ impl StreamAnimationCore {
             ReversedEdits::with_edit(AnimationEdit::Layer(layer_id, LayerEdit::SetAlpha(old_alpha)))
         } 
     }
+
+    ///
+    /// Sets how a layer is composited with the layers underneath it
+    ///
+    pub fn set_layer_blend_mode<'a>(&'a mut self, layer_id: u64, blend_mode: LayerBlendMode) -> impl 'a+Future<Output=ReversedEdits> { 
+        async move {
+            // Read the current properties for this layer
+            let mut properties = match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
+                Some(StorageResponse::LayerProperties(_, properties)) => {
+                    LayerProperties::deserialize(&mut properties.chars())
+                        .unwrap_or_else(|| LayerProperties::default())
+                }
+
+                _ => LayerProperties::default()
+            };
+
+            // Update the blend mode
+            let old_blend_mode      = properties.blend_mode;
+            properties.blend_mode   = blend_mode;
+
+            // Save back to the storage
+            let mut serialized = String::new();
+            properties.serialize(&mut serialized);
+            self.request_one(StorageCommand::WriteLayerProperties(layer_id, serialized)).await;
+
+            ReversedEdits::with_edit(AnimationEdit::Layer(layer_id, LayerEdit::SetBlendMode(old_blend_mode)))
+        } 
+    }
+
+    ///
+    /// Locks or unlocks a layer
+    ///
+    pub fn set_layer_locked<'a>(&'a mut self, layer_id: u64, locked: bool) -> impl 'a+Future<Output=ReversedEdits> { 
+        async move {
+            // Read the current properties for this layer
+            let mut properties = match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
+                Some(StorageResponse::LayerProperties(_, properties)) => {
+                    LayerProperties::deserialize(&mut properties.chars())
+                        .unwrap_or_else(|| LayerProperties::default())
+                }
+
+                _ => LayerProperties::default()
+            };
+
+            // Update the locked flag
+            let old_locked      = properties.locked;
+            properties.locked   = locked;
+
+            if let Some(locked_layers) = self.locked_layers.as_mut() {
+                if locked { locked_layers.insert(layer_id); } else { locked_layers.remove(&layer_id); }
+            }
+
+            // Save back to the storage
+            let mut serialized = String::new();
+            properties.serialize(&mut serialized);
+            self.request_one(StorageCommand::WriteLayerProperties(layer_id, serialized)).await;
+
+            ReversedEdits::with_edit(AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(old_locked)))
+        } 
+    }
+
+    ///
+    /// Returns the set of layers that are locked, reading the layer properties from storage if they aren't cached yet
+    ///
+    pub fn locked_layers<'a>(&'a mut self) -> impl 'a+Future<Output=&'a HashSet<u64>> {
+        async move {
+            if self.locked_layers.is_none() {
+                let layers = self.request(vec![StorageCommand::ReadLayers]).await.unwrap_or_else(|| vec![]);
+                let locked = layers.into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::LayerProperties(layer_id, properties)  => Some((layer_id, LayerProperties::deserialize(&mut properties.chars())?)),
+                        _                                                       => None
+                    })
+                    .filter(|(_layer_id, properties)| properties.locked)
+                    .map(|(layer_id, _properties)| layer_id)
+                    .collect();
+
+                self.locked_layers = Some(locked);
+            }
+
+            self.locked_layers.get_or_insert_with(|| HashSet::new())
+        }
+    }
+
+    ///
+    /// Returns true if the specified layer is locked
+    ///
+    pub fn is_layer_locked<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=bool> {
+        async move {
+            self.locked_layers().await.contains(&layer_id)
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use super::keyframe_core::*;
+use super::stream_animation_core::*;
+use crate::undo::*;
+use crate::traits::*;
+
+use flo_canvas_animation::*;
+
+use futures::prelude::*;
+
+impl StreamAnimationCore {
+    ///
+    /// Finds the opacity attachment for an element and returns its ID and keyframes (if it has one)
+    ///
+    fn read_opacity(frame: &KeyFrameCore, element_id: i64) -> Option<(ElementId, OpacityKeyFrames)> {
+        let element_wrapper = frame.elements.get(&ElementId::Assigned(element_id))?;
+
+        // Use the first opacity attachment if there are several (this is the one that will be replaced)
+        for attachment_id in element_wrapper.attachments.iter() {
+            if let Some(attachment_wrapper) = frame.elements.get(attachment_id) {
+                if let Vector::Opacity(opacity) = &attachment_wrapper.element {
+                    return Some((*attachment_id, opacity.keyframes().clone()));
+                }
+            }
+        }
+
+        None
+    }
+
+    ///
+    /// Updates the opacity keyframes for a set of elements
+    ///
+    /// The updated keyframes are written to a new opacity attachment for each element, which replaces any existing
+    /// opacity attachment (so attachments shared with other elements are left unchanged)
+    ///
+    pub fn update_opacity<'a, UpdateFn>(&'a mut self, element_ids: &'a Vec<i64>, update_opacity: UpdateFn) -> impl 'a+Send+Future<Output=ReversedEdits>
+    where UpdateFn: 'a+Send+Fn(&mut OpacityKeyFrames) {
+        async move {
+            let mut reversed = ReversedEdits::new();
+
+            for element_id in element_ids.iter() {
+                let element_id = *element_id;
+
+                let frame = match self.edit_keyframe_for_element(element_id).await {
+                    Some(frame) => frame,
+                    None        => { continue; }
+                };
+
+                // Read the existing opacity for this element and update it
+                let existing_opacity        = frame.sync(move |frame| Self::read_opacity(frame, element_id));
+                let existing_attachment_id  = existing_opacity.as_ref().map(|(attachment_id, _)| *attachment_id);
+                let mut keyframes           = existing_opacity.map(|(_, keyframes)| keyframes).unwrap_or_else(|| OpacityKeyFrames::new());
+
+                update_opacity(&mut keyframes);
+
+                // Replace the existing opacity attachment with a new one
+                let element_reversed = self.replace_attachment(frame, element_id, existing_attachment_id, move |attachment_id| Vector::Opacity(OpacityElement::new(attachment_id, keyframes))).await;
+                reversed.add_to_start(element_reversed);
+            }
+
+            reversed
+        }
+    }
+}
@@ -380,4 +380,38 @@ impl StreamAnimationCore {
             reversed
         }
     }
+
+    ///
+    /// Applies a set of transformations to an element before any transformations it already has
+    ///
+    /// This is the order that the transformations of a group are applied to the elements inside it, so the elements of
+    /// a group can be given its transformations when they're removed from it. The combined transformations are written
+    /// to a new attachment, so an existing transformation attachment that is shared with other elements is left unchanged.
+    ///
+    pub fn prepend_transformations<'a>(&'a mut self, element_id: i64, transformations: SmallVec<[Transformation; 2]>) -> impl 'a+Send+Future<Output=ReversedEdits> {
+        async move {
+            // Nothing to do if there are no transformations
+            if transformations.len() == 0 {
+                return ReversedEdits::empty();
+            }
+
+            let frame = match self.edit_keyframe_for_element(element_id).await {
+                Some(frame) => frame,
+                None        => { return ReversedEdits::empty(); }
+            };
+
+            // The new transformations are applied before the existing ones
+            let mut element_transforms  = TransformsForElements::new();
+            let element_transforms_ref  = &mut element_transforms;
+            frame.sync(move |frame| element_transforms_ref.read_transformation(element_id, frame));
+
+            let existing_attachment_id  = element_transforms.current_transform_element_id.get(&element_id).cloned();
+            let mut new_transformations = transformations;
+            new_transformations.extend(element_transforms.transformations_for_element.remove(&element_id).into_iter().flatten());
+            Transformation::compact(&mut new_transformations);
+
+            // Replace the existing transformation attachment with a new one
+            self.replace_attachment(frame, element_id, existing_attachment_id, move |attachment_id| Vector::Transformation((attachment_id, new_transformations))).await
+        }
+    }
 }
@@ -156,6 +156,29 @@ impl ElementWrapper {
         }
     }
 
+    ///
+    /// True if a serialized element wrapper contains an element that is drawn (rather than something like a brush definition
+    /// that changes how other elements are drawn)
+    ///
+    /// Only the type of the element is read, so this is much faster than deserializing the whole element. Groups are not
+    /// drawn elements: the elements inside them are stored separately and are counted instead.
+    ///
+    pub fn is_serialized_drawn_element(serialized: &str) -> bool {
+        let mut data = serialized.chars();
+
+        match data.next_small_u64() {
+            0 => {
+                match data.next_chr() {
+                    // Brush definitions, brush properties, motions, transformations, opacity and groups
+                    'D' | 'P' | 'm' | 't' | 'o' | 'g'   => false,
+                    _                                   => true
+                }
+            }
+
+            _ => false
+        }
+    }
+
     ///
     /// Deserializes from a data source
     ///
@@ -246,6 +246,7 @@ impl KeyFrameCore {
         // Set up the properties
         let mut properties;
         let mut active_attachments  = vec![];
+        let mut active_opacity      = None;
         let mut gc                  = layer.graphics_context();
 
         // Everything is rendered at t=0 for the purposes of the animation layer
@@ -291,6 +292,13 @@ impl KeyFrameCore {
                             properties.render_animated(&mut gc, attach_element.element.clone(), when);
                         }
                     }
+
+                    // The animation layer measures time from the start of the keyframe
+                    let opacity = properties.opacity.as_ref().map(|opacity| Arc::new(opacity.earlier_by(core.start)));
+                    if opacity != active_opacity {
+                        active_opacity = opacity;
+                        gc.set_opacity(active_opacity.clone());
+                    }
                 }
 
                 // Render the element
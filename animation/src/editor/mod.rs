@@ -18,15 +18,19 @@ mod layer_cut;
 pub (crate) mod element_wrapper;
 mod element_collide;
 mod element_transform;
+mod element_opacity;
 mod element_convert_to_path;
 mod stream_layer;
 mod stream_frame;
 mod stream_layer_cache;
 mod recreate_element;
 mod recreate_frames;
+mod validate_edits;
 
 #[cfg(test)] mod tests;
 
+pub use self::validate_edits::*;
+
 use self::stream_animation::*;
 use crate::storage::*;
 use crate::traits::*;
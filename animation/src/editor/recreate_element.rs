@@ -133,7 +133,8 @@ impl ReversedEdits {
             Path(_)                 |
             Shape(_)                |
             AnimationRegion(_)      |
-            Transformation((_, _))  => {
+            Transformation((_, _))  |
+            Opacity(_)              => {
                 if wrapper.unattached && wrapper.parent.is_none() {
                     reversed.push(AnimationEdit::Layer(layer_id, LayerEdit::CreateElementUnattachedToFrame(wrapper.start_time, wrapper.element.id(), wrapper.element.clone())))
                 } else {
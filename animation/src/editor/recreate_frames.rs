@@ -109,11 +109,14 @@ impl ReversedEdits {
             if all_layer_ids.len() == 0 { return ReversedEdits::empty(); }
 
             // Start by recreating the layer and setting its properties
-            let mut recreate_layer = ReversedEdits::with_edit(AnimationEdit::AddNewLayer(layer_id));
+            let mut recreate_layer  = ReversedEdits::with_edit(AnimationEdit::AddNewLayer(layer_id));
+            let mut locked          = false;
 
             if let Some(layer_properties) = storage_connection.read_layer_properties(layer_id).await {
                 recreate_layer.push(AnimationEdit::Layer(layer_id, LayerEdit::SetName(layer_properties.name)));
                 recreate_layer.push(AnimationEdit::Layer(layer_id, LayerEdit::SetAlpha(layer_properties.alpha)));
+                recreate_layer.push(AnimationEdit::Layer(layer_id, LayerEdit::SetBlendMode(layer_properties.blend_mode)));
+                locked = layer_properties.locked;
             }
 
             // Order it relative to other layers
@@ -126,13 +129,19 @@ impl ReversedEdits {
             // Fetch the keyframes for this layer
             let forever     = Duration::from_millis(0)..Duration::from_micros(i64::MAX as u64);
             let keyframes   = storage_connection.read_keyframes_for_layer(layer_id, forever).await;
-            let keyframes   = if let Some(keyframes) = keyframes { keyframes } else { return recreate_layer; };
 
             // Recreate each keyframe in turn
-            let mut created_elements = HashSet::new();
-            for keyframe in keyframes {
-                let recreate_keyframe = Self::with_recreated_keyframe(layer_id, keyframe.start, &mut created_elements, storage_connection).await;
-                recreate_layer.extend(recreate_keyframe);
+            if let Some(keyframes) = keyframes {
+                let mut created_elements = HashSet::new();
+                for keyframe in keyframes {
+                    let recreate_keyframe = Self::with_recreated_keyframe(layer_id, keyframe.start, &mut created_elements, storage_connection).await;
+                    recreate_layer.extend(recreate_keyframe);
+                }
+            }
+
+            // Locked layers are locked again after their content has been recreated (as the content can't be edited after that)
+            if locked {
+                recreate_layer.push(AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(true)));
             }
 
             // Return the instructions to recreate the layer
@@ -52,6 +52,7 @@ impl StreamAnimation {
             next_element_id:        None,
             cached_layers:          HashMap::new(),
             cached_keyframe:        None,
+            locked_layers:          None,
             brush_defn:             None,
             brush_props:            None,
             path_brush_defn:        None,
@@ -395,7 +396,8 @@ impl EditableAnimation for StreamAnimation {
     ///
     fn flush_caches(&self) {
         self.core.desync(|core| {
-            core.cached_keyframe = None;
+            core.cached_keyframe    = None;
+            core.locked_layers      = None;
         });
     }
 }
@@ -59,6 +59,9 @@ pub (super) struct StreamAnimationCore {
     /// The keyframe that is currently being edited, if there is one
     pub (super) cached_keyframe: Option<Arc<Desync<KeyFrameCore>>>,
 
+    /// The IDs of the layers that are locked (None if the layer properties haven't been read yet)
+    pub (super) locked_layers: Option<HashSet<u64>>,
+
     /// The brush definition to attach to brush strokes
     pub (super) brush_defn: Option<ElementId>,
 
@@ -280,13 +283,21 @@ impl StreamAnimationCore {
     pub fn perform_edits<'a>(&'a mut self, edits: Vec<AnimationEdit>) -> impl 'a+Future<Output=RetiredEdit> {
         async move {
             let mut reversed_edits  = ReversedEdits::new();
+            let mut performed_edits = Vec::with_capacity(edits.len());
+            let mut rejected_edits  = vec![];
 
             // Process the edits in the order that they arrive
-            for edit in edits.iter() {
+            for edit in edits.into_iter() {
                 use self::AnimationEdit::*;
 
+                // Edits that change the content of a locked layer are rejected (they're reported separately in the retired edits)
+                if self.locked_layer_for_edit(&edit).await.is_some() {
+                    rejected_edits.push(edit);
+                    continue;
+                }
+
                 // Edit the elements
-                match edit {
+                match &edit {
                     Layer(layer_id, layer_edit)             => { reversed_edits.add_to_start(self.layer_edit(*layer_id, layer_edit).await); }
                     Element(element_ids, element_edit)      => { reversed_edits.add_to_start(self.element_edit(element_ids, element_edit).await); }
                     Motion(motion_id, motion_edit)          => { reversed_edits.add_to_start(self.motion_edit(*motion_id, motion_edit).await); }
@@ -297,9 +308,103 @@ impl StreamAnimationCore {
                     AddNewLayer(layer_id)                   => { reversed_edits.add_to_start(self.add_new_layer(*layer_id).await); }
                     RemoveLayer(layer_id)                   => { reversed_edits.add_to_start(self.remove_layer(*layer_id).await); }
                 }
+
+                performed_edits.push(edit);
+            }
+
+            // Any pre-rendered frames for the layers that were changed are now out of date
+            self.retire_rendered_frames(&performed_edits).await;
+
+            RetiredEdit::new(Arc::new(performed_edits), reversed_edits.into())
+                .with_rejected_edits(Arc::new(rejected_edits))
+        }
+    }
+
+    ///
+    /// Removes the pre-rendered frames from the cache of any layer whose content might have been changed by a set of edits
+    ///
+    pub fn retire_rendered_frames<'a>(&'a mut self, edits: &'a [AnimationEdit]) -> impl 'a+Future<Output=()> {
+        async move {
+            use self::AnimationEdit::*;
+
+            let mut changed_layers  = HashSet::new();
+            let mut all_layers      = false;
+
+            for edit in edits.iter() {
+                match edit {
+                    Layer(layer_id, layer_edit)     => { if layer_edit.changes_content() { changed_layers.insert(*layer_id); } }
+
+                    // Elements and motions can appear in any layer, and the size and frame length affect every frame
+                    Element(_, _)                   |
+                    Motion(_, _)                    |
+                    SetSize(_, _)                   |
+                    SetFrameLength(_)               => { all_layers = true; }
+
+                    Undo(_)                         |
+                    SetLength(_)                    |
+                    AddNewLayer(_)                  |
+                    RemoveLayer(_)                  => { }
+                }
+            }
+
+            if all_layers {
+                let layers = self.request(vec![StorageCommand::ReadLayers]).await.unwrap_or_else(|| vec![]);
+
+                changed_layers.extend(layers.into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::LayerProperties(layer_id, _)   => Some(layer_id),
+                        _                                               => None
+                    }));
             }
 
-            RetiredEdit::new(Arc::new(edits), reversed_edits.into())
+            if !changed_layers.is_empty() {
+                let mut key = String::new();
+                CacheType::RenderedFrame.serialize(&mut key);
+
+                self.request(changed_layers.into_iter().map(|layer_id| StorageCommand::DeleteLayerCacheType(layer_id, key.clone()))).await;
+            }
+        }
+    }
+
+    ///
+    /// If an edit would change the content of a locked layer, returns the ID of that layer
+    ///
+    pub fn locked_layer_for_edit<'a>(&'a mut self, edit: &'a AnimationEdit) -> impl 'a+Future<Output=Option<u64>> {
+        async move {
+            match edit {
+                AnimationEdit::Layer(layer_id, layer_edit) => {
+                    if layer_edit.changes_content() && self.is_layer_locked(*layer_id).await {
+                        Some(*layer_id)
+                    } else {
+                        None
+                    }
+                }
+
+                AnimationEdit::Element(element_ids, _element_edit) => {
+                    // Finding the layers the elements are in needs a storage request, which can be skipped if no layers are locked
+                    if self.locked_layers().await.is_empty() {
+                        return None;
+                    }
+
+                    // Element edits are rejected if any of the elements are in a locked layer
+                    for element_id in element_ids.iter().filter_map(|element_id| element_id.id()) {
+                        let layers = match self.request_one(StorageCommand::ReadElementAttachments(element_id)).await {
+                            Some(StorageResponse::ElementAttachments(_elem, keyframes)) => keyframes.into_iter().map(|(layer_id, _when)| layer_id).collect::<Vec<_>>(),
+                            _                                                           => vec![]
+                        };
+
+                        for layer_id in layers {
+                            if self.is_layer_locked(layer_id).await {
+                                return Some(layer_id);
+                            }
+                        }
+                    }
+
+                    None
+                }
+
+                _ => None
+            }
         }
     }
 
@@ -1,4 +1,5 @@
 use super::stream_frame::*;
+use super::element_wrapper::*;
 use super::stream_layer_cache::*;
 use super::stream_animation_core::*;
 use crate::storage::*;
@@ -79,6 +80,20 @@ impl Layer for StreamLayer {
         self.properties.alpha
     }
 
+    ///
+    /// How this layer is composited with the layers underneath it
+    ///
+    fn blend_mode(&self) -> LayerBlendMode {
+        self.properties.blend_mode
+    }
+
+    ///
+    /// True if the content of this layer cannot be edited
+    ///
+    fn is_locked(&self) -> bool {
+        self.properties.locked
+    }
+
     ///
     /// Retrieves a frame from this layer with the specified parameters
     ///
@@ -105,6 +120,24 @@ impl Layer for StreamLayer {
         Arc::new(StreamFrame::new(time_index, keyframe_core))
     }
 
+    ///
+    /// Counts the drawn elements in the keyframe at the specified time, without loading the whole keyframe
+    ///
+    fn get_num_elements_in_key_frame(&self, when: Duration) -> usize {
+        // Only the type of each element needs to be read, so the elements aren't deserialized
+        let elements = self.request_sync(vec![StorageCommand::ReadElementsForKeyFrame(self.layer_id, when)]);
+
+        elements.unwrap_or_else(|| vec![])
+            .into_iter()
+            .filter(|response| {
+                match response {
+                    StorageResponse::Element(_element_id, serialized)   => ElementWrapper::is_serialized_drawn_element(serialized),
+                    _                                                   => false
+                }
+            })
+            .count()
+    }
+
     ///
     /// Retrieves the times where key frames exist during a specified time range
     ///
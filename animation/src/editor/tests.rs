@@ -65,3 +65,42 @@ fn send_retired_instructions() {
 
     assert!(edits == Arc::new(vec![AnimationEdit::SetSize(1080.0, 720.0)]));
 }
+
+#[test]
+fn edits_to_locked_layers_are_reported_as_rejected() {
+    // Create an animation with a locked layer
+    let in_memory_store     = InMemoryStorage::new();
+    let animation           = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+    let mut edits           = animation.edit();
+
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true))
+    ]);
+
+    let mut retired_edits   = animation.retired_edits();
+
+    // Try to add a keyframe to the locked layer alongside an edit that's allowed
+    executor::block_on(async {
+        edits.publish(Arc::new(vec![
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+            AnimationEdit::Layer(2, LayerEdit::SetName("Locked".to_string()))
+        ])).await;
+        edits.when_empty().await;
+    });
+
+    let retired             = executor::block_on(async {
+        let retirement_timeout  = Delay::new(Duration::from_secs(10));
+        select(retired_edits.next(), retirement_timeout).await
+    });
+
+    let retired             = match retired {
+        Either::Right(_)        => { assert!(false, "Timed out"); unimplemented!() }
+        Either::Left(retired)   => retired.0.unwrap()
+    };
+
+    // The keyframe edit is reported as rejected rather than committed
+    assert!(retired.committed_edits() == Arc::new(vec![AnimationEdit::Layer(2, LayerEdit::SetName("Locked".to_string()))]));
+    assert!(retired.rejected_edits() == Arc::new(vec![AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000)))]));
+}
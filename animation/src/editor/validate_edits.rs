@@ -0,0 +1,247 @@
+use crate::traits::*;
+
+use std::collections::{HashMap, BTreeSet};
+use std::time::{Duration};
+
+///
+/// Reasons why an edit might fail validation
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum EditErrorReason {
+    /// The edit refers to a layer that does not exist
+    MissingLayer(u64),
+
+    /// The edit changes the content of a layer that is locked
+    LayerLocked(u64),
+
+    /// The edit refers to a time in a layer where there is no keyframe
+    MissingKeyFrame(u64, Duration),
+
+    /// The edit refers to an element that does not exist
+    MissingElement(ElementId)
+}
+
+///
+/// Describes an edit that failed validation
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct EditError {
+    /// The index of the edit that failed validation
+    pub index: usize,
+
+    /// Why the edit is not valid
+    pub reason: EditErrorReason
+}
+
+///
+/// The state of a layer as seen by the edits being validated
+///
+struct ValidationLayer {
+    /// The times of the keyframes in this layer
+    key_frames: BTreeSet<Duration>,
+
+    /// True if this layer is locked
+    locked: bool
+}
+
+///
+/// Tracks the state of an animation as a list of edits is validated against it
+///
+struct EditValidator<'a, Anim: ?Sized> {
+    /// The animation that the edits will be applied to
+    animation: &'a Anim,
+
+    /// The layers that exist after the edits so far
+    layers: HashMap<u64, ValidationLayer>,
+
+    /// Whether or not the elements that have been checked so far exist
+    elements: HashMap<i64, bool>,
+
+    /// The errors found so far
+    errors: Vec<EditError>
+}
+
+impl<'a, Anim: ?Sized+Animation> EditValidator<'a, Anim> {
+    ///
+    /// Creates a validator for the current state of an animation
+    ///
+    fn new(animation: &'a Anim) -> EditValidator<'a, Anim> {
+        let layers = animation.get_layer_ids().into_iter()
+            .filter_map(|layer_id| animation.get_layer_with_id(layer_id))
+            .map(|layer| (layer.id(), ValidationLayer { key_frames: layer.get_key_frames().collect(), locked: layer.is_locked() }))
+            .collect();
+
+        EditValidator {
+            animation:  animation,
+            layers:     layers,
+            elements:   HashMap::new(),
+            errors:     vec![]
+        }
+    }
+
+    ///
+    /// Records an error for the edit with the specified index
+    ///
+    fn error(&mut self, index: usize, reason: EditErrorReason) {
+        self.errors.push(EditError { index, reason });
+    }
+
+    ///
+    /// True if the specified element exists at this point in the edits
+    ///
+    fn element_exists(&mut self, element_id: i64) -> bool {
+        if let Some(exists) = self.elements.get(&element_id) {
+            return *exists;
+        }
+
+        // Search the keyframes of the animation for the element
+        let animation   = self.animation;
+        let exists      = animation.get_layer_ids().into_iter()
+            .filter_map(|layer_id| animation.get_layer_with_id(layer_id))
+            .any(|layer| layer.get_key_frames()
+                .any(|when| layer.get_frame_at_time(when).element_with_id(ElementId::Assigned(element_id)).is_some()));
+
+        self.elements.insert(element_id, exists);
+        exists
+    }
+
+    ///
+    /// Checks that the specified elements exist, recording an error for each one that does not
+    ///
+    fn check_elements<ElementIter: IntoIterator<Item=ElementId>>(&mut self, index: usize, element_ids: ElementIter) {
+        for element_id in element_ids {
+            if let Some(id) = element_id.id() {
+                if !self.element_exists(id) {
+                    self.error(index, EditErrorReason::MissingElement(element_id));
+                }
+            }
+        }
+    }
+
+    ///
+    /// Marks the specified elements as existing
+    ///
+    fn create_elements<ElementIter: IntoIterator<Item=ElementId>>(&mut self, element_ids: ElementIter) {
+        for element_id in element_ids.into_iter().filter_map(|element_id| element_id.id()) {
+            self.elements.insert(element_id, true);
+        }
+    }
+
+    ///
+    /// Validates an edit to a layer
+    ///
+    fn validate_layer_edit(&mut self, index: usize, layer_id: u64, layer_edit: &LayerEdit) {
+        use self::LayerEdit::*;
+
+        let layer = match self.layers.get_mut(&layer_id) {
+            Some(layer) => layer,
+            None        => { self.error(index, EditErrorReason::MissingLayer(layer_id)); return; }
+        };
+
+        if layer_edit.changes_content() && layer.locked {
+            self.error(index, EditErrorReason::LayerLocked(layer_id));
+            return;
+        }
+
+        // Update the layer, and find the time where the edit needs a keyframe
+        let needs_keyframe = match layer_edit {
+            AddKeyFrame(when)                       => { layer.key_frames.insert(*when); None }
+            RemoveKeyFrame(when)                    => if layer.key_frames.remove(when) { None } else { Some(*when) },
+            SetLocked(locked)                       => { layer.locked = *locked; None }
+
+            Paint(when, _)                          |
+            Path(when, _)                           |
+            CreateAnimation(when, _, _)             |
+            CreateElement(when, _, _)               |
+            Cut { when, .. }                        => if layer.key_frames.range(..=*when).next_back().is_none() { Some(*when) } else { None },
+
+            CreateElementUnattachedToFrame(_, _, _) |
+            SetName(_)                              |
+            SetOrdering(_)                          |
+            SetAlpha(_)                             |
+            SetBlendMode(_)                         => None
+        };
+
+        if let Some(when) = needs_keyframe {
+            self.error(index, EditErrorReason::MissingKeyFrame(layer_id, when));
+            return;
+        }
+
+        // Cuts refer to an existing group: the other layer edits create the elements that they use
+        match layer_edit {
+            Cut { inside_group, .. }                            => self.check_elements(index, vec![*inside_group]),
+            CreateElementUnattachedToFrame(_, element_id, _)    => self.create_elements(vec![*element_id]),
+            other                                               => self.create_elements(other.used_element_ids())
+        }
+    }
+
+    ///
+    /// Validates an edit to a set of elements
+    ///
+    fn validate_element_edit(&mut self, index: usize, element_ids: &Vec<ElementId>, element_edit: &ElementEdit) {
+        // The elements being edited must already exist
+        self.check_elements(index, element_ids.iter().cloned());
+
+        match element_edit {
+            ElementEdit::Group(group_id, _) => { self.create_elements(vec![*group_id]); }
+            ElementEdit::Delete             => {
+                for element_id in element_ids.iter().filter_map(|element_id| element_id.id()) {
+                    self.elements.insert(element_id, false);
+                }
+            }
+
+            other                           => { self.check_elements(index, other.used_element_ids()); }
+        }
+    }
+
+    ///
+    /// Validates a single edit, updating the state of the validator
+    ///
+    fn validate_edit(&mut self, index: usize, edit: &AnimationEdit) {
+        use self::AnimationEdit::*;
+
+        match edit {
+            AddNewLayer(layer_id)               => {
+                // Adding a layer that already exists has no effect
+                self.layers.entry(*layer_id).or_insert_with(|| ValidationLayer { key_frames: BTreeSet::new(), locked: false });
+            }
+
+            RemoveLayer(layer_id)               => {
+                if self.layers.remove(layer_id).is_none() {
+                    self.error(index, EditErrorReason::MissingLayer(*layer_id));
+                }
+            }
+
+            Layer(layer_id, layer_edit)         => self.validate_layer_edit(index, *layer_id, layer_edit),
+            Element(element_ids, element_edit)  => self.validate_element_edit(index, element_ids, element_edit),
+
+            // Motions are not stored in frames so are not checked
+            Motion(_, _)                        |
+            Undo(_)                             |
+            SetSize(_, _)                       |
+            SetFrameLength(_)                   |
+            SetLength(_)                        => { }
+        }
+    }
+}
+
+///
+/// Checks that a list of edits can be applied to the current state of an animation, without changing the animation
+///
+/// Edits are checked in order, so an edit can refer to layers, keyframes and elements created by the edits before it. The
+/// result is an error for each edit that refers to a missing layer, keyframe or element, or that changes the content of
+/// a locked layer.
+///
+pub fn validate_edits<Anim: ?Sized+Animation>(animation: &Anim, edits: &[AnimationEdit]) -> Result<(), Vec<EditError>> {
+    let mut validator = EditValidator::new(animation);
+
+    for (index, edit) in edits.iter().enumerate() {
+        validator.validate_edit(index, edit);
+    }
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
@@ -0,0 +1,147 @@
+use super::traits::*;
+
+use flo_canvas::*;
+
+use futures::prelude::*;
+use futures::future::{BoxFuture};
+
+use std::sync::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::{HashSet};
+use std::time::{Duration};
+
+///
+/// Computes or retrieves the rendering of a layer at a specified time
+///
+pub fn rendered_frame_for_layer(layer: Arc<dyn Layer>, when: Duration) -> CacheProcess<Arc<Vec<Draw>>, BoxFuture<'static, Arc<Vec<Draw>>>> {
+    layer.get_canvas_cache_at_time(when)
+        .retrieve_or_generate(CacheType::RenderedFrame, Box::new(move || {
+            let frame           = layer.get_frame_at_time(when);
+            let mut drawing     = vec![];
+
+            frame.render_to(&mut drawing);
+
+            Arc::new(drawing)
+        }))
+}
+
+///
+/// Pre-renders the frames around a point in time into the layer caches, so that scrubbing the timeline doesn't have
+/// to wait for each frame to render
+///
+/// Only one window of frames is kept warm at a time: starting to warm a new window cancels any warm-up that's still
+/// in progress, and removes the rendered frames that are outside of the new window from the cache.
+///
+pub struct FrameCacheWarmer<Anim: Animation> {
+    /// The animation whose frames are being warmed up
+    animation: Arc<Anim>,
+
+    /// The number of frames before the current time to render
+    frames_before: usize,
+
+    /// The number of frames after the current time to render
+    frames_after: usize,
+
+    /// Incremented whenever a warm-up is started or cancelled (a warm-up stops once this changes)
+    generation: Arc<AtomicUsize>,
+
+    /// The layers and times of the frames that have been rendered into the cache
+    warmed: Arc<Mutex<HashSet<(u64, Duration)>>>
+}
+
+impl<Anim: Animation> FrameCacheWarmer<Anim> {
+    ///
+    /// Creates a new cache warmer that renders the specified number of frames around the current time
+    ///
+    pub fn new(animation: Arc<Anim>, frames_before: usize, frames_after: usize) -> FrameCacheWarmer<Anim> {
+        FrameCacheWarmer {
+            animation:      animation,
+            frames_before:  frames_before,
+            frames_after:   frames_after,
+            generation:     Arc::new(AtomicUsize::new(0)),
+            warmed:         Arc::new(Mutex::new(HashSet::new()))
+        }
+    }
+
+    ///
+    /// Returns the start times of the frames that are warmed up around a particular time, nearest first
+    ///
+    pub fn frame_times_around(&self, when: Duration) -> Vec<Duration> {
+        let frame_length = self.animation.frame_length().as_nanos() as u64;
+        if frame_length == 0 {
+            return vec![when];
+        }
+
+        // Frames start at multiples of the frame length
+        let current_frame   = (when.as_nanos() as u64) / frame_length;
+        let first_frame     = current_frame.saturating_sub(self.frames_before as u64);
+        let last_frame      = current_frame + (self.frames_after as u64);
+
+        let mut frames      = (first_frame..=last_frame).collect::<Vec<_>>();
+        frames.sort_by_key(|frame| if *frame < current_frame { current_frame - *frame } else { *frame - current_frame });
+
+        frames.into_iter()
+            .map(|frame| Duration::from_nanos(frame * frame_length))
+            .collect()
+    }
+
+    ///
+    /// Cancels any warm-up that's in progress
+    ///
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    ///
+    /// Returns a future that renders the frames around the specified time into the cache, returning the number of
+    /// frames that were rendered (counting each layer separately)
+    ///
+    /// The future should be run in the background. Any warm-up that's already in progress is cancelled, and rendered
+    /// frames outside of the new window are removed from the cache so the amount of memory used stays bounded.
+    ///
+    pub fn warm_around(&self, when: Duration) -> BoxFuture<'static, usize> {
+        // Starting a new warm-up cancels the old one
+        let generation      = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let current         = Arc::clone(&self.generation);
+        let warmed          = Arc::clone(&self.warmed);
+
+        let times           = self.frame_times_around(when);
+        let layers          = self.animation.get_layer_ids().into_iter()
+            .filter_map(|layer_id| self.animation.get_layer_with_id(layer_id).map(|layer| (layer_id, layer)))
+            .collect::<Vec<_>>();
+
+        // Evict the frames that are outside the new window
+        {
+            let window      = times.iter().cloned().collect::<HashSet<_>>();
+            let mut warmed  = self.warmed.lock().unwrap();
+
+            for (layer_id, time) in warmed.iter().filter(|(_, time)| !window.contains(time)) {
+                if let Some(layer) = self.animation.get_layer_with_id(*layer_id) {
+                    layer.get_canvas_cache_at_time(*time).invalidate(CacheType::RenderedFrame);
+                }
+            }
+
+            warmed.retain(|(_, time)| window.contains(time));
+        }
+
+        async move {
+            let mut num_rendered = 0;
+
+            for time in times {
+                for (layer_id, layer) in layers.iter() {
+                    // Stop if another warm-up has started or this one has been cancelled
+                    if current.load(Ordering::SeqCst) != generation {
+                        return num_rendered;
+                    }
+
+                    rendered_frame_for_layer(Arc::clone(layer), time).await;
+
+                    warmed.lock().unwrap().insert((*layer_id, time));
+                    num_rendered += 1;
+                }
+            }
+
+            num_rendered
+        }.boxed()
+    }
+}
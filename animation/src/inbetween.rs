@@ -0,0 +1,315 @@
+use super::traits::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// A drawn element from a keyframe, converted to a path
+///
+#[derive(Clone, Debug)]
+struct KeyFrameShape {
+    /// The ID of the element that this shape was generated from
+    id: ElementId,
+
+    /// The outline of the element
+    path: Path,
+
+    /// The brush properties used to draw the element
+    properties: BrushProperties
+}
+
+///
+/// A shape generated by interpolating between two keyframes
+///
+#[derive(Clone, Debug)]
+pub struct InbetweenShape {
+    /// The outline of the shape (filled using the colour from the properties)
+    pub path: Path,
+
+    /// The colour and opacity of the shape
+    pub properties: BrushProperties
+}
+
+///
+/// Converts the drawn elements in a frame into shapes
+///
+fn shapes_for_frame(frame: &Arc<dyn Frame>) -> Vec<KeyFrameShape> {
+    let mut shapes      = vec![];
+    let mut properties  = Arc::new(VectorProperties::default());
+
+    for element in frame.vector_elements().into_iter().flatten() {
+        properties = frame.apply_properties_for_element(&element, properties);
+
+        // Only elements that are drawn directly are interpolated
+        match element {
+            Vector::BrushDefinition(_) | Vector::BrushProperties(_) | Vector::Motion(_) | Vector::Transformation(_) | Vector::Opacity(_) | Vector::AnimationRegion(_) | Vector::Error => { continue; }
+            _ => { }
+        }
+
+        let paths   = element.to_path(&properties, PathConversion::Fastest).unwrap_or(vec![]);
+        let path    = Path::from_paths(&paths);
+
+        if path.len() > 0 {
+            shapes.push(KeyFrameShape {
+                id:         element.id(),
+                path:       path,
+                properties: properties.brush_properties
+            });
+        }
+    }
+
+    shapes
+}
+
+///
+/// True if two paths have the same components in the same order (so can be interpolated point by point)
+///
+fn same_topology(a: &Path, b: &Path) -> bool {
+    use self::PathComponent::*;
+
+    a.len() == b.len() && a.elements_ref().zip(b.elements_ref()).all(|components| match components {
+        (Move(_), Move(_))                      => true,
+        (Line(_), Line(_))                      => true,
+        (Bezier(_, _, _), Bezier(_, _, _))      => true,
+        (Close, Close)                          => true,
+        _                                       => false
+    })
+}
+
+///
+/// Finds the matching shapes between two keyframes
+///
+/// Shapes are matched by element ID first, and then in drawing order by finding shapes with the same topology. The result
+/// is a list of pairs of indexes into the 'from' and 'to' lists: shapes with no match have `None` on one side.
+///
+fn match_shapes(from: &[KeyFrameShape], to: &[KeyFrameShape]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut to_matched  = vec![false; to.len()];
+    let mut from_match  = vec![None; from.len()];
+
+    // Match by ID (only if the topology is the same, as otherwise the shapes can't be interpolated)
+    for (from_idx, from_shape) in from.iter().enumerate() {
+        if from_shape.id.is_unassigned() { continue; }
+
+        if let Some(to_idx) = (0..to.len()).filter(|to_idx| !to_matched[*to_idx] && to[*to_idx].id == from_shape.id).nth(0) {
+            if same_topology(&from_shape.path, &to[to_idx].path) {
+                from_match[from_idx]    = Some(to_idx);
+                to_matched[to_idx]      = true;
+            }
+        }
+    }
+
+    // Match the remaining shapes by their topology
+    for (from_idx, from_shape) in from.iter().enumerate() {
+        if from_match[from_idx].is_some() { continue; }
+
+        if let Some(to_idx) = (0..to.len()).filter(|to_idx| !to_matched[*to_idx] && same_topology(&from_shape.path, &to[*to_idx].path)).nth(0) {
+            from_match[from_idx]    = Some(to_idx);
+            to_matched[to_idx]      = true;
+        }
+    }
+
+    // Generate the list of matches
+    let mut matches = from_match.into_iter().enumerate()
+        .map(|(from_idx, to_idx)| (Some(from_idx), to_idx))
+        .collect::<Vec<_>>();
+    matches.extend(to_matched.into_iter().enumerate()
+        .filter(|(_, matched)| !matched)
+        .map(|(to_idx, _)| (None, Some(to_idx))));
+
+    matches
+}
+
+///
+/// Linearly interpolates between two points
+///
+fn interpolate_point(a: &PathPoint, b: &PathPoint, t: f64) -> PathPoint {
+    PathPoint {
+        position: (a.position.0 + (b.position.0 - a.position.0) * t, a.position.1 + (b.position.1 - a.position.1) * t)
+    }
+}
+
+///
+/// Interpolates between two paths with the same topology
+///
+fn interpolate_path(a: &Path, b: &Path, t: f64) -> Path {
+    use self::PathComponent::*;
+
+    Path::from_elements(a.elements_ref().zip(b.elements_ref()).map(|components| match components {
+        (Move(a), Move(b))                          => Move(interpolate_point(a, b, t)),
+        (Line(a), Line(b))                          => Line(interpolate_point(a, b, t)),
+        (Bezier(a1, a2, a3), Bezier(b1, b2, b3))    => Bezier(interpolate_point(a1, b1, t), interpolate_point(a2, b2, t), interpolate_point(a3, b3, t)),
+        _                                           => Close
+    }))
+}
+
+///
+/// Interpolates between two sets of brush properties
+///
+fn interpolate_properties(a: &BrushProperties, b: &BrushProperties, t: f64) -> BrushProperties {
+    let t                       = t as f32;
+    let (ar, ag, ab, aa)        = a.color.to_rgba_components();
+    let (br, bg, bb, ba)        = b.color.to_rgba_components();
+
+    BrushProperties {
+        size:       a.size + (b.size - a.size) * t,
+        opacity:    a.opacity + (b.opacity - a.opacity) * t,
+        color:      Color::Rgba(ar + (br-ar)*t, ag + (bg-ag)*t, ab + (bb-ab)*t, aa + (ba-aa)*t)
+    }
+}
+
+///
+/// Generates the shapes for an intermediate frame between two keyframes
+///
+/// `t` is the position between the two frames, from 0.0 (the 'from' frame) to 1.0 (the 'to' frame). Elements are matched
+/// between the frames by ID, or by finding elements with the same topology (same number and type of path components)
+/// in drawing order, and matched elements have their outline, colour and opacity interpolated. Elements that only appear
+/// in one of the frames fade in or out. This includes elements whose topology changes between the frames: these are
+/// cross-faded as they can't be interpolated point by point.
+///
+pub fn inbetween_frames(from: &Arc<dyn Frame>, to: &Arc<dyn Frame>, t: f64) -> Vec<InbetweenShape> {
+    let t           = t.max(0.0).min(1.0);
+    let from_shapes = shapes_for_frame(from);
+    let to_shapes   = shapes_for_frame(to);
+
+    match_shapes(&from_shapes, &to_shapes).into_iter()
+        .map(|shape_match| match shape_match {
+            (Some(from_idx), Some(to_idx))  => InbetweenShape {
+                path:       interpolate_path(&from_shapes[from_idx].path, &to_shapes[to_idx].path, t),
+                properties: interpolate_properties(&from_shapes[from_idx].properties, &to_shapes[to_idx].properties, t)
+            },
+
+            (Some(from_idx), None)          => InbetweenShape {
+                path:       from_shapes[from_idx].path.clone(),
+                properties: BrushProperties { opacity: from_shapes[from_idx].properties.opacity * (1.0 - t as f32), ..from_shapes[from_idx].properties }
+            },
+
+            (None, Some(to_idx))            => InbetweenShape {
+                path:       to_shapes[to_idx].path.clone(),
+                properties: BrushProperties { opacity: to_shapes[to_idx].properties.opacity * (t as f32), ..to_shapes[to_idx].properties }
+            },
+
+            (None, None)                    => unreachable!()
+        })
+        .collect()
+}
+
+///
+/// Generates the edits to add an intermediate keyframe between two keyframes of a layer
+///
+/// The new keyframe is added to the target layer (which can be the same as the source layer) at the time `t` of the way
+/// between the two keyframes, and contains the shapes generated by `inbetween_frames()`.
+///
+pub fn inbetween_layer(layer: &dyn Layer, target_layer_id: u64, from: Duration, to: Duration, t: f64) -> Vec<AnimationEdit> {
+    let t           = t.max(0.0).min(1.0);
+    let when        = from + Duration::from_secs_f64((to.as_secs_f64() - from.as_secs_f64()).max(0.0) * t);
+    let from_frame  = layer.get_frame_at_time(from);
+    let to_frame    = layer.get_frame_at_time(to);
+
+    let mut edits   = vec![AnimationEdit::Layer(target_layer_id, LayerEdit::AddKeyFrame(when))];
+
+    for shape in inbetween_frames(&from_frame, &to_frame, t) {
+        edits.extend(LayerEdit::create_ink_path(when, shape.properties, Arc::clone(&shape.path.elements))
+            .into_iter()
+            .map(|edit| AnimationEdit::Layer(target_layer_id, edit)));
+    }
+
+    edits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bake::*;
+    use crate::editor::*;
+    use crate::storage::*;
+
+    use flo_curves::*;
+    use flo_curves::arc::*;
+    use flo_curves::bezier::path::{SimpleBezierPath};
+
+    use futures::prelude::*;
+
+    fn circle_at(x: f64, y: f64) -> Arc<Vec<PathComponent>> {
+        path_components_for_bezier_paths(&[Circle::new(Coord2(x, y), 50.0).to_path::<SimpleBezierPath>()])
+    }
+
+    fn path_bounds(frame: &Arc<dyn Frame>) -> Vec<Rect> {
+        frame.vector_elements().unwrap()
+            .filter_map(|element| match element {
+                Vector::Path(path)  => Some(path.path().bounding_box()),
+                _                   => None
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inbetween_moved_circle() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), circle_at(100.0, 100.0)))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::CreatePath(ElementId::Assigned(200), circle_at(300.0, 100.0))))
+        ]);
+
+        // Generate the frame halfway between the two keyframes
+        let layer           = anim.get_layer_with_id(1).unwrap();
+        let inbetween       = inbetween_layer(&*layer, 1, Duration::from_millis(0), Duration::from_millis(1000), 0.5);
+        anim.perform_edits(inbetween);
+
+        let layer           = anim.get_layer_with_id(1).unwrap();
+        let key_frames      = layer.get_key_frames().collect::<Vec<_>>();
+        assert!(key_frames == vec![Duration::from_millis(0), Duration::from_millis(500), Duration::from_millis(1000)]);
+
+        // Should be one circle, at the midpoint
+        let bounds          = path_bounds(&layer.get_frame_at_time(Duration::from_millis(500)));
+        assert!(bounds.len() == 1);
+
+        let center_x        = (bounds[0].x1 + bounds[0].x2) / 2.0;
+        let center_y        = (bounds[0].y1 + bounds[0].y2) / 2.0;
+        assert!((center_x - 200.0).abs() < 0.5);
+        assert!((center_y - 100.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn unmatched_shapes_fade() {
+        let in_memory_store = InMemoryStorage::new();
+        let anim            = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let square          = Arc::new(vec![
+            PathComponent::Move(PathPoint::new(0.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 10.0)),
+            PathComponent::Line(PathPoint::new(0.0, 10.0)),
+            PathComponent::Close
+        ]);
+
+        anim.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), circle_at(100.0, 100.0)))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(1000), PathEdit::CreatePath(ElementId::Assigned(200), square)))
+        ]);
+
+        // The circle and the square have different topologies, so they cross-fade
+        let layer           = anim.get_layer_with_id(1).unwrap();
+        let shapes          = inbetween_frames(&layer.get_frame_at_time(Duration::from_millis(0)), &layer.get_frame_at_time(Duration::from_millis(1000)), 0.25);
+
+        assert!(shapes.len() == 2);
+        assert!((shapes[0].properties.opacity - 0.75).abs() < 0.01);
+        assert!((shapes[1].properties.opacity - 0.25).abs() < 0.01);
+    }
+}
@@ -26,6 +26,10 @@ extern crate log;
 
 mod traits;
 mod onion_skin;
+mod bake;
+mod inbetween;
+mod scene_export;
+mod frame_cache_warmer;
 pub mod brushes;
 pub mod raycast;
 pub mod serializer;
@@ -35,3 +39,7 @@ pub mod undo;
 
 pub use self::traits::*;
 pub use self::onion_skin::*;
+pub use self::bake::*;
+pub use self::inbetween::*;
+pub use self::scene_export::*;
+pub use self::frame_cache_warmer::*;
@@ -3,12 +3,223 @@ use super::traits::*;
 use flo_canvas::*;
 use flo_curves::bezier::path::*;
 
+use futures::prelude::*;
 use futures::future::{BoxFuture};
 
 use std::iter;
 use std::sync::*;
 use std::time::Duration;
 
+///
+/// Describes how the opacity of the onion skins changes as they get further away from the current frame
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OnionSkinFalloff {
+    /// Every onion skin is drawn at the maximum opacity
+    Constant,
+
+    /// The nearest onion skin is drawn at the maximum opacity, decreasing linearly to the minimum opacity for the farthest onion skin
+    Linear
+}
+
+///
+/// Describes how the onion skins before and after the current frame are tinted
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OnionSkinTint {
+    /// The colour of the onion skins before the current frame
+    pub before_color: Color,
+
+    /// The colour of the onion skins after the current frame
+    pub after_color: Color,
+
+    /// The opacity of the onion skin nearest to the current frame
+    pub max_opacity: f32,
+
+    /// The opacity of the onion skin farthest from the current frame
+    pub min_opacity: f32,
+
+    /// How the opacity changes between the nearest and the farthest onion skin
+    pub falloff: OnionSkinFalloff
+}
+
+impl Default for OnionSkinTint {
+    fn default() -> OnionSkinTint {
+        OnionSkinTint {
+            before_color:   Color::Rgba(1.0, 0.0, 0.0, 1.0),
+            after_color:    Color::Rgba(0.0, 1.0, 0.0, 1.0),
+            max_opacity:    0.5,
+            min_opacity:    0.1,
+            falloff:        OnionSkinFalloff::Linear
+        }
+    }
+}
+
+impl OnionSkinTint {
+    ///
+    /// Returns the colour to use for the onion skin at the specified frame offset from the current frame (negative
+    /// offsets are before the current frame, positive offsets are after)
+    ///
+    /// The result is `None` if the offset is 0 or lies outside of the range set by `frames_before` and `frames_after`.
+    /// Setting either of these to 0 disables the onion skins on that side of the current frame.
+    ///
+    pub fn color_for_offset(&self, offset: i64, frames_before: usize, frames_after: usize) -> Option<Color> {
+        // Pick the side of the current frame that this offset is on
+        let (color, num_frames) = if offset < 0 {
+            (self.before_color, frames_before)
+        } else if offset > 0 {
+            (self.after_color, frames_after)
+        } else {
+            return None;
+        };
+
+        // Frames outside of the range are not displayed
+        let distance = offset.abs() as usize;
+        if distance > num_frames {
+            return None;
+        }
+
+        // Work out the opacity from the falloff
+        let opacity = match self.falloff {
+            OnionSkinFalloff::Constant  => self.max_opacity,
+            OnionSkinFalloff::Linear    => {
+                if num_frames <= 1 {
+                    self.max_opacity
+                } else {
+                    let ratio = ((distance-1) as f32)/((num_frames-1) as f32);
+                    self.max_opacity + (self.min_opacity-self.max_opacity)*ratio
+                }
+            }
+        };
+
+        Some(color.with_alpha(opacity))
+    }
+}
+
+///
+/// Describes which onion skins are displayed around the current frame and how they look
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OnionSkinOptions {
+    /// The number of onion skins to display before the current frame
+    pub frames_before: usize,
+
+    /// The number of onion skins to display after the current frame
+    pub frames_after: usize,
+
+    /// The colour and opacity of the onion skins
+    pub tint: OnionSkinTint
+}
+
+impl Default for OnionSkinOptions {
+    fn default() -> OnionSkinOptions {
+        OnionSkinOptions {
+            frames_before:  3,
+            frames_after:   3,
+            tint:           OnionSkinTint::default()
+        }
+    }
+}
+
+impl OnionSkinOptions {
+    ///
+    /// Returns the frame offsets and times of the onion skins to display around a particular time, ordered from the
+    /// nearest to the farthest onion skin (with the frame before coming first when two are the same distance away)
+    ///
+    /// Onion skins that would be before the start or at or after the end of the animation are left out, so asking for more
+    /// frames than there are just produces fewer onion skins.
+    ///
+    pub fn onion_skin_times(&self, when: Duration, frame_length: Duration, duration: Duration) -> Vec<(i64, Duration)> {
+        if frame_length == Duration::from_millis(0) {
+            return vec![];
+        }
+
+        // Clamp to the number of frames that actually exist on either side of the current time (the animation ends just before `duration`)
+        let frame_nanos     = frame_length.as_nanos();
+        let frames_before   = self.frames_before.min((when.as_nanos() / frame_nanos) as usize);
+        let frames_after    = if duration > when {
+            self.frames_after.min(((duration - when).as_nanos().saturating_sub(1) / frame_nanos) as usize)
+        } else {
+            0
+        };
+
+        let mut times       = vec![];
+        for frame_num in 1..=frames_before.max(frames_after) {
+            let offset = frame_length * (frame_num as u32);
+
+            if frame_num <= frames_before {
+                times.push((-(frame_num as i64), when - offset));
+            }
+
+            if frame_num <= frames_after {
+                times.push((frame_num as i64, when + offset));
+            }
+        }
+
+        times
+    }
+
+    ///
+    /// Generates the drawing instructions to render a set of onion skins (as generated by `onion_skin_for_layer()`),
+    /// tinted according to their frame offsets
+    ///
+    /// The farthest onion skins are drawn first, so the nearer (and more opaque) onion skins are drawn on top of them.
+    /// Onion skins with offsets outside of the range set by `frames_before` and `frames_after` are left out.
+    ///
+    pub fn draw_onion_skins(&self, onion_skins: &[(i64, Arc<Vec<Draw>>)]) -> Vec<Draw> {
+        let mut onion_skins = onion_skins.iter().collect::<Vec<_>>();
+        onion_skins.sort_by_key(|(offset, _)| -offset.abs());
+
+        onion_skins.into_iter()
+            .flat_map(|(offset, drawing)| {
+                // Work out the colour and opacity of this item
+                let color   = match self.tint.color_for_offset(*offset, self.frames_before, self.frames_after) {
+                    Some(color) => color,
+                    None        => { return vec![]; }
+                };
+                let opacity = color.to_rgba_components().3;
+
+                iter::once(Draw::Path(PathOp::NewPath))
+                    .chain(drawing.iter().cloned())
+                    .chain(vec![
+                        Draw::FillColor(color),
+                        Draw::Fill,
+                        Draw::LineWidthPixels(2.0),
+                        Draw::StrokeColor(Color::Rgba(1.0, 1.0, 1.0, opacity)),
+                        Draw::Stroke,
+                        Draw::LineWidthPixels(0.5),
+                        Draw::StrokeColor(color.with_alpha(opacity+0.1)),
+                        Draw::Stroke
+                    ])
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+///
+/// Generates the drawing instructions for the tinted onion skins around a particular time in a layer of an animation
+///
+/// Onion skins that are already in the layer's cache are reused, and any that aren't are generated and cached.
+///
+pub fn tinted_onion_skins_for_layer<Anim: Animation>(animation: &Anim, layer_id: u64, when: Duration, options: &OnionSkinOptions) -> BoxFuture<'static, Vec<Draw>> {
+    let layer       = animation.get_layer_with_id(layer_id);
+    let times       = options.onion_skin_times(when, animation.frame_length(), animation.duration());
+    let options     = *options;
+
+    async move {
+        let layer = if let Some(layer) = layer { layer } else { return vec![]; };
+
+        // Fetch or generate the onion skin for each frame
+        let mut onion_skins = vec![];
+        for (offset, time) in times {
+            onion_skins.push((offset, onion_skin_for_layer(Arc::clone(&layer), time).await));
+        }
+
+        options.draw_onion_skins(&onion_skins)
+    }.boxed()
+}
+
 ///
 /// Computes or retrieves the onion skin for a particular layer at a specified time.specified
 ///
@@ -82,3 +293,54 @@ pub fn onion_skin_for_layer(layer: Arc<dyn Layer>, when: Duration) -> CacheProce
                 .collect())
         }))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nearer_frames_are_more_opaque() {
+        let tint        = OnionSkinTint { before_color: Color::Rgba(1.0, 0.0, 0.0, 1.0), falloff: OnionSkinFalloff::Linear, ..OnionSkinTint::default() };
+
+        let nearest     = tint.color_for_offset(-1, 2, 0).unwrap();
+        let farthest    = tint.color_for_offset(-2, 2, 0).unwrap();
+
+        let (r, g, b, nearest_alpha)    = nearest.to_rgba_components();
+        let (_, _, _, farthest_alpha)   = farthest.to_rgba_components();
+
+        assert!((r-1.0).abs() < 0.01 && g.abs() < 0.01 && b.abs() < 0.01);
+        assert!(nearest_alpha > farthest_alpha);
+        assert!((nearest_alpha-0.5).abs() < 0.01);
+        assert!((farthest_alpha-0.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn zero_frames_disables_side() {
+        let tint = OnionSkinTint::default();
+
+        assert!(tint.color_for_offset(-1, 2, 0).is_some());
+        assert!(tint.color_for_offset(1, 2, 0).is_none());
+        assert!(tint.color_for_offset(-3, 2, 0).is_none());
+        assert!(tint.color_for_offset(0, 2, 2).is_none());
+    }
+
+    #[test]
+    fn onion_skin_times_are_clamped_to_animation() {
+        let options = OnionSkinOptions { frames_before: 3, frames_after: 2, ..OnionSkinOptions::default() };
+        let times   = options.onion_skin_times(Duration::from_millis(100), Duration::from_millis(100), Duration::from_millis(1000));
+
+        // Only one frame before the current time exists
+        assert!(times == vec![
+            (-1, Duration::from_millis(0)),
+            (1, Duration::from_millis(200)),
+            (2, Duration::from_millis(300))
+        ]);
+
+        // Frames at or after the end of the animation are left out
+        let times   = options.onion_skin_times(Duration::from_millis(800), Duration::from_millis(100), Duration::from_millis(1000));
+        assert!(times.iter().map(|(offset, _)| *offset).collect::<Vec<_>>() == vec![-1, 1, -2, -3]);
+
+        let times   = options.onion_skin_times(Duration::from_millis(900), Duration::from_millis(100), Duration::from_millis(1000));
+        assert!(times.iter().map(|(offset, _)| *offset).collect::<Vec<_>>() == vec![-1, -2, -3]);
+    }
+}
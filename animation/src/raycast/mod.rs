@@ -6,7 +6,9 @@ pub (crate) mod edge;
 mod path_combine;
 mod vector_frame_raycast;
 mod point_in_path;
+mod raycast_all;
 
 pub use self::vector_frame_raycast::*;
 pub use self::path_combine::*;
 pub use self::point_in_path::*;
+pub use self::raycast_all::*;
@@ -0,0 +1,133 @@
+use super::point_in_path::*;
+use super::super::traits::*;
+
+use flo_curves::*;
+use flo_curves::bezier::*;
+
+use std::sync::*;
+
+/// Number of line segments used to approximate each curve when measuring the distance to a stroked path
+const STROKE_SEGMENTS_PER_CURVE: usize = 16;
+
+///
+/// Options that change which elements are returned by `raycast_all_with_options()`
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RaycastAllOptions {
+    /// True if elements that are drawn with a fully transparent colour should be included in the results
+    pub include_transparent: bool
+}
+
+impl Default for RaycastAllOptions {
+    fn default() -> RaycastAllOptions {
+        RaycastAllOptions {
+            include_transparent: true
+        }
+    }
+}
+
+///
+/// Returns the IDs of all of the elements in a frame that are under a particular point, ordered front-to-back
+///
+pub fn raycast_all<FrameType: Frame>(frame: &FrameType, point: PathPoint) -> Vec<ElementId> {
+    raycast_all_with_options(frame, point, &RaycastAllOptions::default())
+}
+
+///
+/// Returns the IDs of all of the elements in a frame that are under a particular point, ordered front-to-back
+///
+/// Elements drawn with a brush that fills its paths are hit if the point is inside them. Elements drawn with a brush
+/// that strokes its paths are hit if the point is within the line drawn by the brush. Elements drawn with an eraser
+/// are never returned.
+///
+pub fn raycast_all_with_options<FrameType: Frame>(frame: &FrameType, point: PathPoint, options: &RaycastAllOptions) -> Vec<ElementId> {
+    // Frames that are not vector frames have no elements to hit
+    let elements = match frame.vector_elements() {
+        Some(elements)  => elements,
+        None            => { return vec![]; }
+    };
+
+    // The elements are returned back-to-front, so we'll reverse the hits at the end
+    let mut hits = vec![];
+
+    for element in elements {
+        // Animation regions are not drawn as part of the frame
+        if let Vector::AnimationRegion(_) = element { continue; }
+
+        let properties = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+
+        // Erased areas and (optionally) invisible elements can't be hit
+        if properties.brush.drawing_style() == BrushDrawingStyle::Erase {
+            continue;
+        }
+
+        if !options.include_transparent && properties.brush.render_alpha(&properties.brush_properties) <= 0.0 {
+            continue;
+        }
+
+        // Stroked paths are hit along their length, and filled paths are hit anywhere inside them
+        let stroke_width    = properties.brush.stroke_width(&properties.brush_properties);
+        let conversion      = if stroke_width.is_some() { PathConversion::Fastest } else { PathConversion::RemoveInteriorPoints };
+
+        // Elements with no paths are not drawn so can't be hit
+        let paths = match element.to_path(&properties, conversion) {
+            Some(paths) => paths,
+            None        => { continue; }
+        };
+
+        let is_hit = match stroke_width {
+            Some(width) => paths.iter().any(|path| distance_to_path(path, &point) <= width / 2.0),
+            None        => paths.iter().any(|path| point_is_in_path(&path.to_subpaths(), &point))
+        };
+
+        if is_hit {
+            hits.push(element.id());
+        }
+    }
+
+    hits.reverse();
+    hits
+}
+
+///
+/// Finds the distance from a point to the nearest edge of a path
+///
+fn distance_to_path(path: &Path, point: &PathPoint) -> f64 {
+    let mut min_distance = f64::MAX;
+
+    for curve in path.to_curves() {
+        // Approximate the curve as a series of line segments and measure the distance to each one
+        let mut last_point = curve.start_point();
+
+        for segment in 1..=STROKE_SEGMENTS_PER_CURVE {
+            let next_point  = curve.point_at_pos((segment as f64) / (STROKE_SEGMENTS_PER_CURVE as f64));
+            let distance    = distance_to_line_segment(point, &last_point, &next_point);
+
+            if distance < min_distance {
+                min_distance = distance;
+            }
+
+            last_point = next_point;
+        }
+    }
+
+    min_distance
+}
+
+///
+/// Finds the distance from a point to a line segment
+///
+fn distance_to_line_segment(point: &PathPoint, start: &PathPoint, end: &PathPoint) -> f64 {
+    let (px, py)    = (point.x() as f64, point.y() as f64);
+    let (sx, sy)    = (start.x() as f64, start.y() as f64);
+    let (ex, ey)    = (end.x() as f64, end.y() as f64);
+
+    let (dx, dy)    = (ex-sx, ey-sy);
+    let length_sq   = dx*dx + dy*dy;
+
+    // Find the closest position along the line segment
+    let t           = if length_sq > 0.0 { (((px-sx)*dx + (py-sy)*dy) / length_sq).max(0.0).min(1.0) } else { 0.0 };
+    let (cx, cy)    = (sx + dx*t, sy + dy*t);
+
+    ((px-cx)*(px-cx) + (py-cy)*(py-cy)).sqrt()
+}
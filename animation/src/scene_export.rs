@@ -0,0 +1,191 @@
+use super::traits::*;
+
+use std::fmt::{Write};
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// A whole animation exported as a scene for a lightweight web player
+///
+/// All times in the scene are in milliseconds. Elements are exported as filled paths (using SVG path data), so a
+/// player can draw them with a `Path2D` on a HTML canvas without needing to know about brushes. The web front-end
+/// plays scenes using `flo_canvas.play_scene()`, and the `export-scene` diagnostic command writes them out.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonScene {
+    /// The width and height of the animation
+    pub size: (f64, f64),
+
+    /// The length of the animation in milliseconds
+    pub duration_ms: f64,
+
+    /// The length of a single frame in milliseconds
+    pub frame_length_ms: f64,
+
+    /// The layers in the animation, in the order they should be drawn
+    pub layers: Vec<JsonSceneLayer>
+}
+
+///
+/// A layer in an exported scene
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonSceneLayer {
+    /// The ID of this layer
+    pub id: u64,
+
+    /// The name of this layer, if it has one
+    pub name: Option<String>,
+
+    /// The opacity of this layer
+    pub alpha: f64,
+
+    /// The keyframes in this layer, in time order
+    pub keyframes: Vec<JsonSceneKeyFrame>
+}
+
+///
+/// A keyframe in an exported scene
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonSceneKeyFrame {
+    /// The time where this keyframe starts, in milliseconds
+    pub start_ms: f64,
+
+    /// The time where this keyframe is replaced by the next keyframe (or the animation ends), in milliseconds
+    pub end_ms: f64,
+
+    /// The elements in this keyframe, in the order they should be drawn
+    pub elements: Vec<JsonSceneElement>
+}
+
+///
+/// An element in an exported scene
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonSceneElement {
+    /// The ID of this element, if it has one
+    pub id: Option<i64>,
+
+    /// The outline of this element, as SVG path data
+    pub path: String,
+
+    /// The colour to fill the path with, as RGBA components (with the brush opacity applied to the alpha component)
+    pub color: (f32, f32, f32, f32),
+
+    /// True if this element erases the elements underneath it rather than drawing over them
+    pub erase: bool
+}
+
+///
+/// Converts a duration to a number of milliseconds
+///
+fn to_ms(when: Duration) -> f64 {
+    when.as_secs_f64() * 1000.0
+}
+
+///
+/// Converts a path to SVG path data
+///
+fn svg_path_data(path: &Path) -> String {
+    let mut data = String::new();
+
+    for component in path.elements() {
+        if !data.is_empty() { data.push(' '); }
+
+        match component {
+            PathComponent::Move(point)                  => { write!(data, "M{} {}", point.x(), point.y()).ok(); }
+            PathComponent::Line(point)                  => { write!(data, "L{} {}", point.x(), point.y()).ok(); }
+            PathComponent::Bezier(point, cp1, cp2)      => { write!(data, "C{} {} {} {} {} {}", cp1.x(), cp1.y(), cp2.x(), cp2.y(), point.x(), point.y()).ok(); }
+            PathComponent::Close                        => { data.push('Z'); }
+        }
+    }
+
+    data
+}
+
+///
+/// Exports the elements in a frame
+///
+/// Elements that don't have an outline (such as brush definitions or motions) are left out of the result. Brush strokes
+/// are converted to the path that the brush would fill.
+///
+fn scene_elements_for_frame(frame: &dyn Frame) -> Vec<JsonSceneElement> {
+    let elements        = frame.vector_elements().unwrap_or_else(|| Box::new(vec![].into_iter()));
+    let mut result      = vec![];
+
+    for element in elements {
+        let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+        let paths       = match element.to_path(&properties, PathConversion::Fastest) {
+            Some(paths) => paths,
+            None        => { continue; }
+        };
+
+        let path        = paths.iter().map(|path| svg_path_data(path)).filter(|data| !data.is_empty()).collect::<Vec<_>>().join(" ");
+        if path.is_empty() {
+            continue;
+        }
+
+        let (r, g, b, a) = properties.brush_properties.color.to_rgba_components();
+
+        result.push(JsonSceneElement {
+            id:     element.id().id(),
+            path:   path,
+            color:  (r, g, b, a * properties.brush_properties.opacity),
+            erase:  properties.brush.drawing_style() == BrushDrawingStyle::Erase
+        });
+    }
+
+    result
+}
+
+///
+/// Exports an animation as a scene that can be played back without the full animation engine
+///
+pub fn json_scene_for_animation<Anim: Animation+?Sized>(animation: &Anim) -> JsonScene {
+    let duration    = animation.duration();
+    let mut layers  = vec![];
+
+    for layer_id in animation.get_layer_ids() {
+        let layer           = match animation.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { continue; }
+        };
+
+        // Each keyframe lasts until the next keyframe, or until the end of the animation
+        let key_frames      = layer.get_key_frames().collect::<Vec<_>>();
+        let mut keyframes   = vec![];
+
+        for (index, start) in key_frames.iter().enumerate() {
+            let end     = key_frames.get(index+1).cloned().unwrap_or_else(|| duration.max(*start));
+            let frame   = layer.get_frame_at_time(*start);
+
+            keyframes.push(JsonSceneKeyFrame {
+                start_ms:   to_ms(*start),
+                end_ms:     to_ms(end),
+                elements:   scene_elements_for_frame(&*frame)
+            });
+        }
+
+        layers.push(JsonSceneLayer {
+            id:         layer_id,
+            name:       layer.name(),
+            alpha:      layer.alpha(),
+            keyframes:  keyframes
+        });
+    }
+
+    JsonScene {
+        size:               animation.size(),
+        duration_ms:        to_ms(duration),
+        frame_length_ms:    to_ms(animation.frame_length()),
+        layers:             layers
+    }
+}
+
+///
+/// Exports an animation as a JSON scene that can be played back without the full animation engine
+///
+pub fn export_json_scene<Anim: Animation+?Sized>(animation: &Anim) -> String {
+    serde_json::to_string(&json_scene_for_animation(animation)).unwrap()
+}
@@ -8,7 +8,8 @@ impl CacheType {
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
         match self {
-            CacheType::OnionSkinLayer   => data.write_chr('O')
+            CacheType::OnionSkinLayer   => data.write_chr('O'),
+            CacheType::RenderedFrame    => data.write_chr('F')
         }
     }
 
@@ -18,6 +19,7 @@ impl CacheType {
     pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<CacheType> {
         match data.next_chr() {
             'O' => Some(CacheType::OnionSkinLayer),
+            'F' => Some(CacheType::RenderedFrame),
             _   => None
         }
     }
@@ -5,6 +5,21 @@ use crate::traits::*;
 use std::str::{Chars};
 use std::time::{Duration};
 
+/// The version of the format written by `serialize_animation_as_edits()`
+///
+/// Files without a version header were written before the header was added and are treated as version 0. Versions 0
+/// and 1 use the same encoding for the edits themselves.
+pub const EDIT_FORMAT_VERSION: u64 = 1;
+
+///
+/// Errors that can occur while reading a serialized animation
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EditFormatError {
+    /// The edits were written using a newer version of the format than can be read
+    UnsupportedVersion(u64)
+}
+
 impl AnimationEdit {
     ///
     /// Returns true if this edit should be written to the log
@@ -123,6 +138,13 @@ pub fn serialize_animation_as_edits<'a, Tgt: AnimationDataTarget, Edits: IntoIte
 
     data.write_chr('\n');
 
+    // The version header indicates how the edits that follow are encoded
+    data.write_chr('V');
+    data.write_small_u64(EDIT_FORMAT_VERSION);
+    data.write_chr('\n');
+
+    data.write_chr('\n');
+
     // Write out the actual animation edits
     animation.into_iter().for_each(|edit| {
         edit.serialize(data);
@@ -139,6 +161,28 @@ pub fn serialize_animation_as_edits<'a, Tgt: AnimationDataTarget, Edits: IntoIte
     data.write_chr(' ');
 }
 
+///
+/// Reads the version header from a line of a serialized animation
+///
+/// Returns None (and leaves the data unchanged) if the line is not a version header. Lines containing edits should only
+/// be read once the version is known: files that have no version header are version 0.
+///
+pub fn deserialize_edit_format_version(data: &mut Chars) -> Option<Result<u64, EditFormatError>> {
+    let mut header = data.clone();
+    if header.next() != Some('V') {
+        return None;
+    }
+
+    *data       = header;
+    let version = data.next_small_u64();
+
+    if version > EDIT_FORMAT_VERSION {
+        Some(Err(EditFormatError::UnsupportedVersion(version)))
+    } else {
+        Some(Ok(version))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -219,4 +263,32 @@ mod test {
 
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn animation_has_version_header() {
+        let mut encoded = String::new();
+        serialize_animation_as_edits(&mut encoded, &vec![AnimationEdit::AddNewLayer(1)], "Test");
+
+        let version_line = encoded.lines().filter(|line| line.starts_with('V')).next().unwrap();
+        assert!(deserialize_edit_format_version(&mut version_line.chars()) == Some(Ok(EDIT_FORMAT_VERSION)));
+    }
+
+    #[test]
+    fn edits_are_not_version_headers() {
+        let mut encoded = String::new();
+        AnimationEdit::AddNewLayer(1).serialize(&mut encoded);
+
+        let mut source = encoded.chars();
+        assert!(deserialize_edit_format_version(&mut source) == None);
+        assert!(AnimationEdit::deserialize(&mut source) == Some(AnimationEdit::AddNewLayer(1)));
+    }
+
+    #[test]
+    fn reject_newer_versions() {
+        let mut encoded = String::new();
+        encoded.write_chr('V');
+        encoded.write_small_u64(EDIT_FORMAT_VERSION + 1);
+
+        assert!(deserialize_edit_format_version(&mut encoded.chars()) == Some(Err(EditFormatError::UnsupportedVersion(EDIT_FORMAT_VERSION + 1))));
+    }
 }
@@ -29,6 +29,8 @@ impl ElementEdit {
             SetAnimationBaseType(desc)              => { data.write_chr('1'); data.write_str(&json::to_string(desc).unwrap()); }
             AddAnimationEffect(desc)                => { data.write_chr('2'); data.write_str(&json::to_string(desc).unwrap()); }
             ReplaceAnimationEffect(address, desc)   => { data.write_chr('3'); data.write_str(&json::to_string(address).unwrap()); data.write_str(&json::to_string(desc).unwrap()); }
+            SetOpacity(when, opacity)               => { data.write_chr('o'); data.write_duration(*when); data.write_f64(*opacity); }
+            SetVisibility(when, visible)            => { data.write_chr('v'); data.write_duration(*when); data.write_chr(if *visible { 'V' } else { 'H' }); }
 
             SetControlPoints(points, when)          => { 
                 data.write_chr('c');
@@ -196,6 +198,24 @@ impl ElementEdit {
                 Some(ElementEdit::Transform(transforms))
             }
 
+            'o' => {
+                let when    = data.next_duration();
+                let opacity = data.next_f64();
+
+                Some(ElementEdit::SetOpacity(when, opacity))
+            }
+
+            'v' => {
+                let when    = data.next_duration();
+                let visible = match data.next_chr() {
+                    'V' => true,
+                    'H' => false,
+                    _   => { return None; }
+                };
+
+                Some(ElementEdit::SetVisibility(when, visible))
+            }
+
             _   => None
         }
     }
@@ -348,4 +368,20 @@ mod test {
 
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Transform(vec![ElementTransform::SetAnchor(6.0, 7.0), ElementTransform::MoveTo(2.0, 3.0)])));
     }
+
+    #[test]
+    fn set_opacity() {
+        let mut encoded = String::new();
+        ElementEdit::SetOpacity(Duration::from_millis(1000), 0.5).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetOpacity(Duration::from_millis(1000), 0.5)));
+    }
+
+    #[test]
+    fn set_visibility() {
+        let mut encoded = String::new();
+        ElementEdit::SetVisibility(Duration::from_millis(1000), false).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetVisibility(Duration::from_millis(1000), false)));
+    }
 }
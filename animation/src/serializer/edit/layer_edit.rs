@@ -23,6 +23,8 @@ impl LayerEdit {
             SetName(name)                                       => { data.write_chr('N'); data.write_str(name); },
             SetOrdering(ordering)                               => { data.write_chr('O'); data.write_u64(*ordering); }
             SetAlpha(alpha)                                     => { data.write_chr('a'); data.write_f64(*alpha); }
+            SetBlendMode(blend_mode)                            => { data.write_chr('B'); blend_mode.serialize(data); }
+            SetLocked(locked)                                   => { data.write_chr('L'); data.write_chr(if *locked { 'L' } else { 'U' }); }
             CreateAnimation(when, id, description)              => { data.write_chr('A'); data.write_duration(*when); id.serialize(data); data.write_str(&json::to_string(description).unwrap()); }
             CreateElement(when, id, vector)                     => { data.write_chr('V'); data.write_duration(*when); id.serialize(data); vector.serialize(data); },
             CreateElementUnattachedToFrame(when, id, vector)    => { data.write_chr('v'); data.write_duration(*when); id.serialize(data); vector.serialize(data); },
@@ -64,6 +66,14 @@ impl LayerEdit {
             'O' => { Some(LayerEdit::SetOrdering(data.next_u64())) }
             'A' => { Some(LayerEdit::CreateAnimation(data.next_duration(), ElementId::deserialize(data)?, json::from_str(&data.next_string()).ok()?)) }
             'a' => { Some(LayerEdit::SetAlpha(data.next_f64())) }
+            'B' => { Some(LayerEdit::SetBlendMode(LayerBlendMode::deserialize(data)?)) }
+            'L' => {
+                match data.next_chr() {
+                    'L' => Some(LayerEdit::SetLocked(true)),
+                    'U' => Some(LayerEdit::SetLocked(false)),
+                    _   => None
+                }
+            }
 
             'V' => { 
                 let when    = data.next_duration();
@@ -201,4 +211,22 @@ mod test {
 
         assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn set_blend_mode() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetBlendMode(LayerBlendMode::Multiply);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_locked() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetLocked(true);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
 }
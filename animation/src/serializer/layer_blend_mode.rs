@@ -0,0 +1,49 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl LayerBlendMode {
+    ///
+    /// Generates a serialized version of this blend mode on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::LayerBlendMode::*;
+
+        match self {
+            SourceOver  => { data.write_chr('o'); }
+            Multiply    => { data.write_chr('m'); }
+            Screen      => { data.write_chr('s'); }
+            Darken      => { data.write_chr('d'); }
+            Lighten     => { data.write_chr('l'); }
+        }
+    }
+
+    ///
+    /// Deserializes this blend mode from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<LayerBlendMode> {
+        match data.next_chr() {
+            'o' => Some(LayerBlendMode::SourceOver),
+            'm' => Some(LayerBlendMode::Multiply),
+            's' => Some(LayerBlendMode::Screen),
+            'd' => Some(LayerBlendMode::Darken),
+            'l' => Some(LayerBlendMode::Lighten),
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip_all() {
+        for blend_mode in vec![LayerBlendMode::SourceOver, LayerBlendMode::Multiply, LayerBlendMode::Screen, LayerBlendMode::Darken, LayerBlendMode::Lighten] {
+            let mut encoded = String::new();
+            blend_mode.serialize(&mut encoded);
+
+            assert!(LayerBlendMode::deserialize(&mut encoded.chars()) == Some(blend_mode));
+        }
+    }
+}
@@ -19,6 +19,7 @@ mod cache_type;
 mod element_id;
 mod fill_option;
 mod drawing_style;
+mod layer_blend_mode;
 mod path_component;
 mod brush_definition;
 mod brush_properties;
@@ -34,6 +35,7 @@ pub use self::cache_type::*;
 pub use self::element_id::*;
 pub use self::fill_option::*;
 pub use self::drawing_style::*;
+pub use self::layer_blend_mode::*;
 pub use self::path_component::*;
 pub use self::brush_definition::*;
 pub use self::brush_properties::*;
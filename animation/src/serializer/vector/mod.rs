@@ -3,6 +3,7 @@ mod group;
 mod shape;
 mod vector;
 mod motion;
+mod opacity;
 mod transformed;
 mod brush_point;
 mod brush_stroke;
@@ -17,6 +18,7 @@ pub use self::group::*;
 pub use self::shape::*;
 pub use self::vector::*;
 pub use self::motion::*;
+pub use self::opacity::*;
 pub use self::transformed::*;
 pub use self::brush_point::*;
 pub use self::brush_stroke::*;
@@ -0,0 +1,84 @@
+use crate::serializer::source::*;
+use crate::serializer::target::*;
+use crate::traits::*;
+
+use flo_canvas_animation::*;
+
+impl OpacityElement {
+    ///
+    /// Generates a serialized version of this opacity element on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        let keyframes = self.keyframes();
+
+        data.write_usize(keyframes.opacity_keyframes().len());
+        for (when, opacity) in keyframes.opacity_keyframes().iter() {
+            data.write_duration(*when);
+            data.write_f64(*opacity);
+        }
+
+        data.write_usize(keyframes.visibility_keyframes().len());
+        for (when, visible) in keyframes.visibility_keyframes().iter() {
+            data.write_duration(*when);
+            data.write_chr(if *visible { 'V' } else { 'H' });
+        }
+    }
+
+    ///
+    /// Deserializes an opacity element from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<OpacityElement> {
+        let mut keyframes = OpacityKeyFrames::new();
+
+        let num_opacity = data.next_usize();
+        for _ in 0..num_opacity {
+            let when    = data.next_duration();
+            let opacity = data.next_f64();
+
+            keyframes.set_opacity(when, opacity);
+        }
+
+        let num_visibility = data.next_usize();
+        for _ in 0..num_visibility {
+            let when    = data.next_duration();
+            let visible = match data.next_chr() {
+                'V' => true,
+                'H' => false,
+                _   => { return None; }
+            };
+
+            keyframes.set_visibility(when, visible);
+        }
+
+        Some(OpacityElement::new(element_id, keyframes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::serializer::*;
+
+    use std::time::{Duration};
+
+    #[test]
+    fn vector_opacity_element() {
+        let mut keyframes = OpacityKeyFrames::new();
+        keyframes.set_opacity(Duration::from_millis(0), 0.0);
+        keyframes.set_opacity(Duration::from_millis(1000), 0.75);
+        keyframes.set_visibility(Duration::from_millis(500), false);
+
+        let mut encoded = String::new();
+        let vector      = Vector::Opacity(OpacityElement::new(ElementId::Assigned(1), keyframes.clone()));
+        vector.serialize(&mut encoded);
+
+        let decoded     = Vector::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap().resolve(&mut |_id| None).unwrap();
+
+        if let Vector::Opacity(decoded) = decoded {
+            assert!(decoded.keyframes() == &keyframes);
+        } else {
+            assert!(false);
+        }
+    }
+}
@@ -33,6 +33,7 @@ impl Vector {
             Shape(_shape)                       => { false }
             AnimationRegion(_region)            => { false }
             Transformation((_id, _transform))   => { false }
+            Opacity(_opacity)                   => { false }
         }
     }
 
@@ -54,6 +55,7 @@ impl Vector {
             Motion(motion)                  => { data.write_chr('m'); motion.serialize(data); }
             Group(group)                    => { data.write_chr('g'); group.serialize(data); }
             AnimationRegion(region)         => { data.write_chr('A'); region.serialize(data); }
+            Opacity(opacity)                => { data.write_chr('o'); opacity.serialize(data); }
             Error                           => { data.write_chr('?'); }
 
             Transformation((id, transform)) => { 
@@ -109,6 +111,10 @@ impl Vector {
                     Some(Vector::AnimationRegion(animation_element))
                 }))
             }
+            'o' => {
+                OpacityElement::deserialize(element_id, data)
+                    .map(|opacity| box_fn(move |_| Some(Vector::Opacity(opacity))))
+            }
             'm' => { 
                 MotionElement::deserialize(element_id, data)
                     .map(|motion| box_fn(move |_| Some(Vector::Motion(motion))))
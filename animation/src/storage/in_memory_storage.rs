@@ -476,6 +476,17 @@ impl InMemoryStorageCore {
                     }
                 }
 
+                DeleteLayerCacheType(layer_id, key)                 => {
+                    if let Some(layer) = self.layers.get_mut(&layer_id) {
+                        // Remove every item with this key
+                        layer.cache.retain(|cache_item| cache_item.key != key);
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        // Layer not present
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
                 ReadLayerCache(layer_id, when, key)                 => {
                     if let Some(layer) = self.layers.get(&layer_id) {
                         // Search for this cache item
@@ -1,4 +1,5 @@
 use super::super::serializer::*;
+use super::super::traits::*;
 
 use std::i64;
 
@@ -12,6 +13,12 @@ pub struct LayerProperties {
     /// The alpha blending factor for this layer
     pub alpha: f64,
 
+    /// How this layer is composited with the layers underneath it
+    pub blend_mode: LayerBlendMode,
+
+    /// True if the content of this layer cannot be edited
+    pub locked: bool,
+
     /// The ordering of this layer, relative to other layers
     pub ordering: i64
 }
@@ -22,6 +29,8 @@ impl Default for LayerProperties {
         LayerProperties {
             name:       "".to_string(),
             alpha:      1.0,
+            blend_mode: LayerBlendMode::SourceOver,
+            locked:     false,
             ordering:   i64::max_value()
         }
     }
@@ -32,12 +41,14 @@ impl LayerProperties {
     /// Serializes these file properties to a target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // Version 1 of the properties
-        data.write_small_u64(1);
+        // Version 3 of the properties
+        data.write_small_u64(3);
 
         data.write_str(&self.name);
         data.write_f64(self.alpha);
         data.write_i64(self.ordering);
+        self.blend_mode.serialize(data);
+        data.write_chr(if self.locked { 'L' } else { 'U' });
     }
 
     ///
@@ -63,6 +74,29 @@ impl LayerProperties {
                 Some(result)
             }
 
+            2 => {
+                result.name         = data.next_string();
+                result.alpha        = data.next_f64();
+                result.ordering     = data.next_i64();
+                result.blend_mode   = LayerBlendMode::deserialize(data)?;
+
+                Some(result)
+            }
+
+            3 => {
+                result.name         = data.next_string();
+                result.alpha        = data.next_f64();
+                result.ordering     = data.next_i64();
+                result.blend_mode   = LayerBlendMode::deserialize(data)?;
+                result.locked       = match data.next_chr() {
+                    'L' => true,
+                    'U' => false,
+                    _   => { return None; }
+                };
+
+                Some(result)
+            }
+
             _ => None
         }
     }
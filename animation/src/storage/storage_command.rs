@@ -78,6 +78,9 @@ pub enum StorageCommand {
     /// Removes an entry from the layer cache
     DeleteLayerCache(u64, Duration, String),
 
+    /// Removes every entry with a particular key from the layer cache, whatever time it was stored for (parameters are layer id and key)
+    DeleteLayerCacheType(u64, String),
+
     /// Reads from the layer cache (parameters are layer id, cache time and key)
     ReadLayerCache(u64, Duration, String)
 }
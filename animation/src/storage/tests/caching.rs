@@ -84,3 +84,118 @@ fn retrieve_cached_onionskin_from_different_time() {
 
     assert!(cached_drawing == None);
 }
+
+///
+/// Creates an animation with a single layer containing a square, with a frame length of 100ms
+///
+fn animation_with_square() -> Arc<impl EditableAnimation> {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(0.0, 0.0)),
+                PathComponent::Line(PathPoint::new(10.0, 0.0)),
+                PathComponent::Line(PathPoint::new(10.0, 10.0)),
+                PathComponent::Close
+            ]))))
+    ]);
+
+    Arc::new(anim)
+}
+
+fn is_warm(anim: &impl Animation, when_ms: u64) -> bool {
+    let layer = anim.get_layer_with_id(24).unwrap();
+    layer.get_canvas_cache_at_time(Duration::from_millis(when_ms)).retrieve(CacheType::RenderedFrame).is_some()
+}
+
+#[test]
+fn warm_frames_around_time() {
+    let anim    = animation_with_square();
+    let warmer  = FrameCacheWarmer::new(Arc::clone(&anim), 2, 2);
+
+    let num_rendered = executor::block_on(warmer.warm_around(Duration::from_millis(1050)));
+    assert!(num_rendered == 5);
+
+    // The frames around the time should be in the cache
+    assert!(is_warm(&*anim, 800));
+    assert!(is_warm(&*anim, 900));
+    assert!(is_warm(&*anim, 1000));
+    assert!(is_warm(&*anim, 1100));
+    assert!(is_warm(&*anim, 1200));
+
+    // ... but not the frames outside of the window
+    assert!(!is_warm(&*anim, 700));
+    assert!(!is_warm(&*anim, 1300));
+
+    // The cached frame is the rendering of the layer
+    let cached = anim.get_layer_with_id(24).unwrap().get_canvas_cache_at_time(Duration::from_millis(1000)).retrieve(CacheType::RenderedFrame).unwrap();
+    assert!(cached.iter().any(|draw| match draw { Draw::Fill | Draw::Stroke => true, _ => false }));
+}
+
+#[test]
+fn warming_elsewhere_cancels_and_evicts() {
+    let anim    = animation_with_square();
+    let warmer  = FrameCacheWarmer::new(Arc::clone(&anim), 1, 1);
+
+    executor::block_on(warmer.warm_around(Duration::from_millis(1000)));
+    assert!(is_warm(&*anim, 1000));
+
+    // A warm-up that's replaced before it runs renders nothing
+    let cancelled   = warmer.warm_around(Duration::from_millis(3000));
+    let replacement = warmer.warm_around(Duration::from_millis(5000));
+
+    assert!(executor::block_on(cancelled) == 0);
+    assert!(executor::block_on(replacement) == 3);
+
+    // The frames from the old window are removed from the cache
+    assert!(!is_warm(&*anim, 900));
+    assert!(!is_warm(&*anim, 1000));
+    assert!(!is_warm(&*anim, 1100));
+    assert!(!is_warm(&*anim, 3000));
+    assert!(is_warm(&*anim, 5000));
+}
+
+#[test]
+fn editing_a_layer_retires_its_rendered_frames() {
+    let anim    = animation_with_square();
+    let warmer  = FrameCacheWarmer::new(Arc::clone(&anim), 1, 1);
+
+    executor::block_on(warmer.warm_around(Duration::from_millis(1000)));
+    assert!(is_warm(&*anim, 1000));
+
+    // Drawing on the layer makes the rendered frames out of date
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(20.0, 20.0)),
+                PathComponent::Line(PathPoint::new(30.0, 20.0)),
+                PathComponent::Line(PathPoint::new(30.0, 30.0)),
+                PathComponent::Close
+            ]))))
+    ]);
+
+    assert!(!is_warm(&*anim, 900));
+    assert!(!is_warm(&*anim, 1000));
+    assert!(!is_warm(&*anim, 1100));
+}
+
+#[test]
+fn changing_layer_alpha_keeps_rendered_frames() {
+    let anim    = animation_with_square();
+    let warmer  = FrameCacheWarmer::new(Arc::clone(&anim), 1, 1);
+
+    executor::block_on(warmer.warm_around(Duration::from_millis(1000)));
+
+    // The alpha is applied when the layer is composited, so the frame itself is unchanged
+    anim.perform_edits(vec![AnimationEdit::Layer(24, LayerEdit::SetAlpha(0.5))]);
+
+    assert!(is_warm(&*anim, 1000));
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+use flo_canvas::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates a square path with the specified size
+///
+fn square(size: f32) -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(0.0, 0.0)),
+        PathComponent::Line(PathPoint::new(size, 0.0)),
+        PathComponent::Line(PathPoint::new(size, size)),
+        PathComponent::Line(PathPoint::new(0.0, size)),
+        PathComponent::Close
+    ])
+}
+
+///
+/// Creates an animation with 100ms frames and keyframes at frames 0 and 3 on layer 24
+///
+fn animation_with_two_keyframes() -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(100), square(10.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), square(20.0)))),
+    ]);
+
+    anim
+}
+
+#[test]
+fn frames_between_keyframes_match_single_frame_rendering() {
+    let anim    = animation_with_two_keyframes();
+    let frames  = executor::block_on(anim.frames_in_range(24, 0, 5).collect::<Vec<_>>());
+
+    assert!(frames.iter().map(|(frame_index, _)| *frame_index).collect::<Vec<_>>() == vec![0, 1, 2, 3, 4]);
+
+    // Each frame should be rendered the same way as a frame retrieved directly from the layer
+    let layer = anim.get_layer_with_id(24).unwrap();
+    for (frame_index, drawing) in frames.iter() {
+        let frame           = layer.get_frame_at_time(Duration::from_millis(100 * frame_index));
+        let mut expected    = vec![];
+        frame.render_to(&mut expected);
+
+        assert!(drawing == &expected);
+    }
+
+    // Frames between keyframes hold the content of the preceding keyframe
+    assert!(frames[1].1 == frames[0].1);
+    assert!(frames[2].1 == frames[0].1);
+    assert!(frames[4].1 == frames[3].1);
+    assert!(frames[3].1 != frames[0].1);
+}
+
+#[test]
+fn empty_range_produces_no_frames() {
+    let anim = animation_with_two_keyframes();
+
+    assert!(executor::block_on(anim.frames_in_range(24, 2, 2).collect::<Vec<_>>()).len() == 0);
+    assert!(executor::block_on(anim.frames_in_range(24, 3, 1).collect::<Vec<_>>()).len() == 0);
+    assert!(executor::block_on(anim.frames_in_range(25, 0, 5).collect::<Vec<_>>()).len() == 0);
+}
@@ -1,5 +1,6 @@
 use super::*;
 
+use std::sync::*;
 use std::time::{Duration};
 
 #[test]
@@ -488,3 +489,121 @@ fn reorder_group_element_to_top() {
         assert!(group_ids == vec![7, 8, 6]);
     }
 }
+
+///
+/// Creates an animation with two square paths, 100 and 101, in layer 1
+///
+fn two_squares() -> impl EditableAnimation {
+    let square = |x: f32, y: f32| Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y+10.0)),
+        PathComponent::Line(PathPoint::new(x, y+10.0)),
+        PathComponent::Close
+    ]);
+
+    let animation = create_animation();
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), square(10.0, 20.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(101), square(50.0, 60.0))))
+    ]);
+
+    animation
+}
+
+///
+/// Returns the top-left corner of an element as it's rendered, applying the properties of any groups it's in (outermost first)
+///
+fn rendered_position<Anim: EditableAnimation>(animation: &Anim, groups: Vec<i64>, element_id: i64) -> (f32, f32) {
+    animation.flush_caches();
+
+    let layer           = animation.get_layer_with_id(1).unwrap();
+    let frame           = layer.get_frame_at_time(Duration::from_millis(0));
+    let mut properties  = Arc::new(VectorProperties::default());
+
+    for group_id in groups.into_iter() {
+        let group   = frame.element_with_id(ElementId::Assigned(group_id)).unwrap();
+        properties  = frame.apply_properties_for_element(&group, properties);
+    }
+
+    let element     = frame.element_with_id(ElementId::Assigned(element_id)).unwrap();
+    let properties  = frame.apply_properties_for_element(&element, properties);
+    let bounds      = element.to_path(&*properties, PathConversion::Fastest).unwrap()[0].bounding_box();
+
+    (bounds.x1, bounds.y1)
+}
+
+fn is_near(pos: (f32, f32), expected: (f32, f32)) -> bool {
+    (pos.0-expected.0).abs() < 0.01 && (pos.1-expected.1).abs() < 0.01
+}
+
+#[test]
+fn move_group_moves_both_elements() {
+    let animation = two_squares();
+
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(200), GroupType::Normal)),
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(10.0, 20.0), ElementTransform::MoveTo(110.0, 70.0)]))
+    ]);
+
+    // Both elements should move by the same offset
+    assert!(is_near(rendered_position(&animation, vec![200], 100), (110.0, 70.0)));
+    assert!(is_near(rendered_position(&animation, vec![200], 101), (150.0, 110.0)));
+}
+
+#[test]
+fn ungrouped_elements_stay_where_group_moved_them() {
+    let animation = two_squares();
+
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(200), GroupType::Normal)),
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(10.0, 20.0), ElementTransform::MoveTo(110.0, 70.0)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Ungroup)
+    ]);
+
+    assert!(is_near(rendered_position(&animation, vec![], 100), (110.0, 70.0)));
+    assert!(is_near(rendered_position(&animation, vec![], 101), (150.0, 110.0)));
+
+    // The elements should now move independently
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(110.0, 70.0), ElementTransform::MoveTo(0.0, 0.0)]))
+    ]);
+
+    assert!(is_near(rendered_position(&animation, vec![], 100), (0.0, 0.0)));
+    assert!(is_near(rendered_position(&animation, vec![], 101), (150.0, 110.0)));
+}
+
+#[test]
+fn move_nested_groups() {
+    let animation = two_squares();
+
+    // Group 100 on its own, then group that with 101, and move both groups
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Group(ElementId::Assigned(200), GroupType::Normal)),
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(10.0, 20.0), ElementTransform::MoveTo(20.0, 20.0)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(200), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(201), GroupType::Normal)),
+        AnimationEdit::Element(vec![ElementId::Assigned(201)], ElementEdit::Transform(vec![ElementTransform::SetAnchor(0.0, 0.0), ElementTransform::MoveTo(100.0, 100.0)]))
+    ]);
+
+    assert!(is_near(rendered_position(&animation, vec![201, 200], 100), (120.0, 120.0)));
+    assert!(is_near(rendered_position(&animation, vec![201], 101), (150.0, 160.0)));
+
+    // Ungrouping the outer group leaves the inner group in the same place
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(201)], ElementEdit::Ungroup)
+    ]);
+
+    assert!(is_near(rendered_position(&animation, vec![200], 100), (120.0, 120.0)));
+    assert!(is_near(rendered_position(&animation, vec![], 101), (150.0, 160.0)));
+
+    // Ungrouping the inner group leaves the element in the same place
+    animation.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Ungroup)
+    ]);
+
+    assert!(is_near(rendered_position(&animation, vec![], 100), (120.0, 120.0)));
+}
@@ -434,3 +434,102 @@ fn reorder_1() {
     println!("{:?}", layers);
     assert!(layers == vec![2, 0, 1]);
 }
+
+#[test]
+fn locked_layer_rejects_edits() {
+    let anim = create_animation();
+
+    // Create a square
+    let square = Arc::new(vec![
+        PathComponent::Move(PathPoint::new(100.0, 100.0)),
+        PathComponent::Line(PathPoint::new(200.0, 100.0)),
+        PathComponent::Line(PathPoint::new(200.0, 200.0)),
+        PathComponent::Line(PathPoint::new(100.0, 200.0)),
+        PathComponent::Line(PathPoint::new(100.0, 100.0))
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(
+                ElementId::Assigned(1),
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            ))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(2), BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(3), square.clone()))),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true))
+    ]);
+
+    assert!(anim.get_layer_with_id(2).unwrap().is_locked());
+
+    // Neither adding a new path nor editing the existing one should change the layer while it's locked
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(4), square.clone()))),
+        AnimationEdit::Element(vec![ElementId::Assigned(3)], ElementEdit::Delete)
+    ]);
+
+    // Locked layers can still be read
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+    assert!(elements[0].id() == ElementId::Assigned(3));
+
+    // Unlocking the layer allows the edit to succeed
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(false)),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(4), square.clone())))
+    ]);
+
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(!layer.is_locked());
+    assert!(elements.len() == 2);
+    assert!(elements[1].id() == ElementId::Assigned(4));
+}
+
+#[test]
+fn count_drawn_elements_in_keyframe() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), circle_path((100.0, 100.0), 50.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(101), circle_path((200.0, 100.0), 50.0))))
+    ]);
+
+    let layer = anim.get_layer_with_id(1).unwrap();
+
+    // The brush definition and properties aren't drawn, so only the paths are counted
+    assert!(layer.get_num_elements_in_key_frame(Duration::from_millis(0)) == 2);
+    assert!(layer.get_num_elements_in_key_frame(Duration::from_millis(100)) == 0);
+}
+
+#[test]
+fn count_grouped_elements_in_keyframe() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), circle_path((100.0, 100.0), 50.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(101), circle_path((200.0, 100.0), 50.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(102), circle_path((300.0, 100.0), 50.0)))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(103), GroupType::Normal))
+    ]);
+
+    let layer = anim.get_layer_with_id(1).unwrap();
+
+    // The group itself isn't counted, but the elements inside it are
+    assert!(layer.get_num_elements_in_key_frame(Duration::from_millis(0)) == 3);
+}
@@ -16,6 +16,12 @@ mod collide_paths;
 mod grouping;
 mod transformation;
 mod fill_paths;
+mod scene_export;
+mod opacity;
+mod onion_skin;
+mod raycast_all;
+mod validate_edits;
+mod frames_in_range;
 
 ///
 /// Creates an in-memory animaton for the tests
@@ -0,0 +1,44 @@
+use super::*;
+
+use flo_canvas::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+#[test]
+fn tinted_onion_skins_around_frame() {
+    let anim = create_animation();
+
+    // A single brush stroke in a keyframe at time 0, in an animation with 100ms frames
+    anim.perform_edits(vec![
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::SetLength(Duration::from_millis(1000)),
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+            PathComponent::Move(PathPoint::new(10.0, 20.0)),
+            PathComponent::Line(PathPoint::new(20.0, 30.0)),
+            PathComponent::Bezier(PathPoint::new(40.0, 40.0), PathPoint::new(30.0, 30.0), PathPoint::new(20.0, 20.0)),
+            PathComponent::Close
+        ])))),
+    ]);
+
+    // Three frames before and two after at 200ms: the third frame before would be before the start of the animation
+    let options     = OnionSkinOptions { frames_before: 3, frames_after: 2, tint: OnionSkinTint::default() };
+    let drawing     = executor::block_on(tinted_onion_skins_for_layer(&anim, 24, Duration::from_millis(200), &options));
+
+    let fill_colors = drawing.iter()
+        .filter_map(|draw| if let Draw::FillColor(color) = draw { Some(*color) } else { None })
+        .collect::<Vec<_>>();
+
+    // Farthest onion skins are drawn first
+    let expected    = [-2, 2, -1, 1].iter()
+        .map(|offset| options.tint.color_for_offset(*offset, 3, 2).unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(fill_colors == expected);
+}
@@ -0,0 +1,111 @@
+use super::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates an animation containing a single brush stroke (with element ID 100) in a keyframe at time 0
+///
+fn animation_with_path() -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Bezier(PathPoint::new(40.0, 40.0), PathPoint::new(30.0, 30.0), PathPoint::new(20.0, 20.0)),
+                PathComponent::Close
+            ])))),
+    ]);
+
+    anim
+}
+
+///
+/// Renders layer 24 at the specified time and returns the alpha values of the fill colours that were used
+///
+fn fill_alphas<Anim: EditableAnimation>(anim: &Anim, when: Duration) -> Vec<f32> {
+    let layer       = anim.get_layer_with_id(24).unwrap();
+    let frame       = layer.get_frame_at_time(when);
+    let mut drawing = vec![];
+
+    frame.render_to(&mut drawing);
+
+    drawing.into_iter()
+        .filter_map(|draw| if let Draw::FillColor(color) = draw { Some(color.to_rgba_components().3) } else { None })
+        .collect()
+}
+
+#[test]
+fn set_opacity_adds_attachment() {
+    let anim = animation_with_path();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.0)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(1000), 1.0)),
+    ]);
+
+    let layer       = anim.get_layer_with_id(24).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let attachments = frame.attached_elements(ElementId::Assigned(100));
+
+    // The second edit replaces the attachment created by the first, so there should only be one opacity attachment with both keyframes
+    let opacity     = attachments.iter()
+        .filter_map(|(attachment_id, _)| if let Some(Vector::Opacity(opacity)) = frame.element_with_id(*attachment_id) { Some(opacity) } else { None })
+        .collect::<Vec<_>>();
+
+    assert!(opacity.len() == 1);
+    assert!(opacity[0].keyframes().opacity_keyframes() == &vec![(Duration::from_millis(0), 0.0), (Duration::from_millis(1000), 1.0)]);
+}
+
+#[test]
+fn fade_in_over_a_second() {
+    let anim = animation_with_path();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.0)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(1000), 1.0)),
+    ]);
+
+    // Fully opaque after the last keyframe
+    let opaque  = fill_alphas(&anim, Duration::from_millis(1000));
+    assert!(opaque.len() > 0);
+    assert!(opaque.iter().all(|alpha| *alpha > 0.0));
+
+    // Half-way through the fade, the element should be rendered at half its usual opacity
+    let faded   = fill_alphas(&anim, Duration::from_millis(500));
+    assert!(faded.len() == opaque.len());
+    assert!(faded.iter().zip(opaque.iter()).all(|(faded, opaque)| (faded - opaque*0.5).abs() < 0.01));
+
+    // Nothing is drawn while the element is completely transparent
+    let hidden  = fill_alphas(&anim, Duration::from_millis(0));
+    assert!(hidden.len() == 0);
+}
+
+#[test]
+fn visibility_is_not_interpolated() {
+    let anim = animation_with_path();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetVisibility(Duration::from_millis(0), true)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetVisibility(Duration::from_millis(500), false)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetVisibility(Duration::from_millis(1000), true)),
+    ]);
+
+    assert!(fill_alphas(&anim, Duration::from_millis(0)).len() > 0);
+    assert!(fill_alphas(&anim, Duration::from_millis(499)).len() > 0);
+    assert!(fill_alphas(&anim, Duration::from_millis(500)).len() == 0);
+    assert!(fill_alphas(&anim, Duration::from_millis(999)).len() == 0);
+    assert!(fill_alphas(&anim, Duration::from_millis(1000)).len() > 0);
+}
@@ -0,0 +1,100 @@
+use super::*;
+use crate::raycast::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates a square path with the specified bounds
+///
+fn square(x1: f32, y1: f32, x2: f32, y2: f32) -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x1, y1)),
+        PathComponent::Line(PathPoint::new(x2, y1)),
+        PathComponent::Line(PathPoint::new(x2, y2)),
+        PathComponent::Line(PathPoint::new(x1, y2)),
+        PathComponent::Line(PathPoint::new(x1, y1)),
+        PathComponent::Close
+    ])
+}
+
+///
+/// Creates an animation with two overlapping squares (elements 100 and 101), with the second one drawn in the specified colour
+///
+fn overlapping_squares(front_color: Color) -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(100), square(0.0, 0.0, 100.0, 100.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: front_color, opacity: front_color.to_rgba_components().3, ..BrushProperties::new() }))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(101), square(50.0, 50.0, 150.0, 150.0)))),
+    ]);
+
+    anim
+}
+
+#[test]
+fn all_elements_under_point_front_to_back() {
+    let anim    = overlapping_squares(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    let layer   = anim.get_layer_with_id(24).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+
+    assert!(raycast_all(&frame, PathPoint::new(75.0, 75.0)) == vec![ElementId::Assigned(101), ElementId::Assigned(100)]);
+    assert!(raycast_all(&frame, PathPoint::new(25.0, 25.0)) == vec![ElementId::Assigned(100)]);
+    assert!(raycast_all(&frame, PathPoint::new(125.0, 125.0)) == vec![ElementId::Assigned(101)]);
+    assert!(raycast_all(&frame, PathPoint::new(200.0, 200.0)) == vec![]);
+}
+
+#[test]
+fn exclude_transparent_elements() {
+    let anim    = overlapping_squares(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+    let layer   = anim.get_layer_with_id(24).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+
+    let options = RaycastAllOptions { include_transparent: false };
+
+    assert!(raycast_all(&frame, PathPoint::new(75.0, 75.0)) == vec![ElementId::Assigned(101), ElementId::Assigned(100)]);
+    assert!(raycast_all_with_options(&frame, PathPoint::new(75.0, 75.0), &options) == vec![ElementId::Assigned(100)]);
+}
+
+#[test]
+fn lines_are_hit_along_their_stroke() {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(0),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(0.0, 0.0)),
+                PathComponent::Line(PathPoint::new(100.0, 0.0))
+            ])))),
+    ]);
+
+    let layer   = anim.get_layer_with_id(24).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+
+    // The line has no area, but is drawn with a width
+    assert!(raycast_all(&frame, PathPoint::new(50.0, 0.0)) == vec![ElementId::Assigned(100)]);
+    assert!(raycast_all(&frame, PathPoint::new(50.0, 0.4)) == vec![ElementId::Assigned(100)]);
+    assert!(raycast_all(&frame, PathPoint::new(50.0, 5.0)) == vec![]);
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Splits SVG path data into its parts, rounding the coordinates (paths lose some precision when they're stored)
+///
+fn path_tokens(path: &str) -> Vec<String> {
+    path.split(' ')
+        .map(|token| {
+            let (command, number) = token.split_at(token.find(|chr: char| !chr.is_ascii_alphabetic()).unwrap_or(token.len()));
+
+            if number.is_empty() {
+                command.to_string()
+            } else {
+                format!("{}{}", command, number.parse::<f64>().unwrap().round())
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn export_one_element_scene() {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetSize(800.0, 600.0),
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 20.0)),
+                PathComponent::Line(PathPoint::new(20.0, 30.0)),
+                PathComponent::Bezier(PathPoint::new(40.0, 40.0), PathPoint::new(30.0, 30.0), PathPoint::new(20.0, 20.0)),
+                PathComponent::Close
+            ]))))
+    ]);
+
+    let json    = export_json_scene(&anim);
+    let scene   = serde_json::from_str::<JsonScene>(&json).unwrap();
+
+    assert!(scene.size == (800.0, 600.0));
+    assert!(scene.layers.len() == 1);
+    assert!(scene.layers[0].id == 24);
+
+    let keyframes = &scene.layers[0].keyframes;
+    assert!(keyframes.len() == 1);
+    assert!(keyframes[0].start_ms == 300.0);
+    assert!(keyframes[0].end_ms == scene.duration_ms.max(300.0));
+
+    let elements = &keyframes[0].elements;
+    assert!(elements.len() == 1);
+    assert!(elements[0].id == Some(100));
+    assert!(path_tokens(&elements[0].path) == vec!["M10", "20", "L20", "30", "C30", "30", "20", "20", "40", "40", "Z"]);
+    assert!(!elements[0].erase);
+    assert!(json.contains("\"start_ms\":300.0"));
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// A path that can be added to the test animations
+///
+fn square() -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(10.0, 10.0)),
+        PathComponent::Line(PathPoint::new(20.0, 10.0)),
+        PathComponent::Line(PathPoint::new(20.0, 20.0)),
+        PathComponent::Line(PathPoint::new(10.0, 20.0)),
+        PathComponent::Close
+    ])
+}
+
+#[test]
+fn valid_edits_pass_validation() {
+    let anim = create_animation();
+
+    let edits = vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(100), PathEdit::CreatePath(ElementId::Assigned(100), square()))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.5)),
+    ];
+
+    assert!(validate_edits(&anim, &edits) == Ok(()));
+
+    // Validating the edits should not perform them
+    assert!(anim.get_num_edits() == 0);
+    assert!(anim.get_layer_ids().len() == 0);
+}
+
+#[test]
+fn report_missing_references() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(1000), PathEdit::CreatePath(ElementId::Assigned(100), square()))),
+    ]);
+
+    let edits = vec![
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(101), square()))),
+        AnimationEdit::Layer(25, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.5)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.5)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::AttachTo(ElementId::Assigned(101))),
+    ];
+
+    assert!(validate_edits(&anim, &edits) == Err(vec![
+        EditError { index: 0, reason: EditErrorReason::MissingKeyFrame(24, Duration::from_millis(0)) },
+        EditError { index: 1, reason: EditErrorReason::MissingLayer(25) },
+        EditError { index: 4, reason: EditErrorReason::MissingElement(ElementId::Assigned(100)) },
+        EditError { index: 5, reason: EditErrorReason::MissingElement(ElementId::Assigned(100)) },
+        EditError { index: 5, reason: EditErrorReason::MissingElement(ElementId::Assigned(101)) },
+    ]));
+}
+
+#[test]
+fn locked_layers_cannot_be_edited() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::SetLocked(true)),
+    ]);
+
+    let edits = vec![
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), square()))),
+        AnimationEdit::Layer(24, LayerEdit::SetLocked(false)),
+        AnimationEdit::Layer(24, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(101), square()))),
+    ];
+
+    assert!(validate_edits(&anim, &edits) == Err(vec![
+        EditError { index: 0, reason: EditErrorReason::LayerLocked(24) },
+    ]));
+}
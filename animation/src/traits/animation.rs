@@ -2,13 +2,21 @@ use super::edit::*;
 use super::layer::*;
 
 use flo_stream::*;
+use flo_canvas::{Draw};
 
+use futures::prelude::*;
+use futures::stream;
 use futures::stream::{BoxStream};
 
 use std::time::Duration;
 use std::sync::*;
 use std::ops::Range;
 
+///
+/// The index of a frame in an animation (frame 0 starts at time 0)
+///
+pub type FrameIndex = u64;
+
 ///
 /// Represents an animation
 ///
@@ -47,6 +55,33 @@ pub trait Animation : Unpin+Send+Sync {
     /// Reads from the edit log for this animation
     ///
     fn read_edit_log<'a>(&'a self, range: Range<usize>) -> BoxStream<'a, AnimationEdit>;
+
+    ///
+    /// Renders the frames from `start` up to (but not including) `end` for a layer
+    ///
+    /// Each frame is rendered only when it's read from the stream, in the same way as a single frame retrieved from the
+    /// layer (so frames between keyframes show the content of the preceding keyframe, with any animation applied). The
+    /// stream is empty if the range is empty or if the layer does not exist.
+    ///
+    fn frames_in_range(&self, layer_id: u64, start: FrameIndex, end: FrameIndex) -> BoxStream<'static, (FrameIndex, Vec<Draw>)> {
+        let layer = match self.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { return stream::empty().boxed(); }
+        };
+
+        let frame_length = self.frame_length().as_nanos() as u64;
+
+        stream::iter(start..end)
+            .map(move |frame_index| {
+                let frame       = layer.get_frame_at_time(Duration::from_nanos(frame_length * frame_index));
+                let mut drawing = vec![];
+
+                frame.render_to(&mut drawing);
+
+                (frame_index, drawing)
+            })
+            .boxed()
+    }
 }
 
 ///
@@ -145,6 +145,20 @@ pub trait Brush : Send+Sync {
         self.to_definition().1
     }
 
+    ///
+    /// The width of the line this brush draws along its paths, or None if this brush fills its paths instead
+    ///
+    fn stroke_width(&self, _properties: &BrushProperties) -> Option<f64> {
+        None
+    }
+
+    ///
+    /// The alpha value of the colour that this brush renders with
+    ///
+    fn render_alpha(&self, properties: &BrushProperties) -> f32 {
+        properties.opacity
+    }
+
     ///
     /// Attempts to combine this brush stroke with the specified vector element. Returns the combined element if successful
     ///
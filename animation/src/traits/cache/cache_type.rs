@@ -4,5 +4,8 @@
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum CacheType {
     /// The layer rendered as an onion skin
-    OnionSkinLayer
+    OnionSkinLayer,
+
+    /// The drawing instructions for the layer at a particular time
+    RenderedFrame
 }
@@ -98,6 +98,14 @@ pub enum ElementEdit {
     /// If this element is an animation element, replaces the subeffect at the specified address with a new description
     /// (Follow the same rules as `EffectDescription::replace_sub_effect()` when the effect is nested: ie, will preserve the nested contents of the effect)
     ReplaceAnimationEffect(Vec<usize>, EffectDescription),
+
+    /// Sets the opacity of the elements at the specified time (from the start of the animation). Opacity is interpolated
+    /// linearly between the times where it's set.
+    SetOpacity(Duration, f64),
+
+    /// Sets whether or not the elements are visible from the specified time (from the start of the animation). Unlike
+    /// opacity, visibility is not interpolated: it stays the same until the next time it's set.
+    SetVisibility(Duration, bool),
 }
 
 impl ElementEdit {
@@ -127,6 +135,8 @@ impl ElementEdit {
             SetAnimationBaseType(_)         => smallvec![],
             AddAnimationEffect(_)           => smallvec![],
             ReplaceAnimationEffect(_, _)    => smallvec![],
+            SetOpacity(_, _)                => smallvec![],
+            SetVisibility(_, _)             => smallvec![],
         }
     }
 }
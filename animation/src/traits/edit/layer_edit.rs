@@ -1,8 +1,12 @@
 use super::frame_edit::*;
 use super::element_id::*;
 use crate::traits::vector::*;
+use crate::traits::layer::*;
 
 use crate::traits::path::*;
+use crate::traits::brush_properties::*;
+use crate::traits::brush_definition::*;
+use crate::traits::brush_drawing_style::*;
 
 use flo_canvas_animation::description::*;
 
@@ -67,9 +71,26 @@ pub enum LayerEdit {
 
     /// Sets the layer alpha blend (0.0-1.0)
     SetAlpha(f64),
+
+    /// Sets how the layer is composited with the layers underneath it
+    SetBlendMode(LayerBlendMode),
+
+    /// Locks or unlocks the layer. Edits that change the content of a locked layer or any of its elements are rejected
+    SetLocked(bool),
 }
 
 impl LayerEdit {
+    ///
+    /// Returns the edits that create a new ink path with the specified brush properties in the keyframe at the specified time
+    ///
+    pub fn create_ink_path(when: Duration, properties: BrushProperties, path: Arc<Vec<PathComponent>>) -> Vec<LayerEdit> {
+        vec![
+            LayerEdit::Path(when, PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw)),
+            LayerEdit::Path(when, PathEdit::BrushProperties(ElementId::Unassigned, properties)),
+            LayerEdit::Path(when, PathEdit::CreatePath(ElementId::Unassigned, path))
+        ]
+    }
+
     ///
     /// Retrieves the element IDs used by this edit
     ///
@@ -89,7 +110,35 @@ impl LayerEdit {
             RemoveKeyFrame(_)                       |
             SetName(_)                              |
             SetOrdering(_)                          |
-            SetAlpha(_)                             => smallvec![]
+            SetAlpha(_)                             |
+            SetBlendMode(_)                         |
+            SetLocked(_)                            => smallvec![]
+        }
+    }
+
+    ///
+    /// True if this edit changes the content of the layer, or false if it only changes one of its properties (such as its name)
+    ///
+    /// Edits that change the content of a layer are rejected when the layer is locked.
+    ///
+    pub fn changes_content(&self) -> bool {
+        use LayerEdit::*;
+
+        match self {
+            Paint(_, _)                             |
+            Path(_, _)                              |
+            CreateAnimation(_, _, _)                |
+            CreateElement(_, _, _)                  |
+            CreateElementUnattachedToFrame(_, _, _) |
+            Cut { .. }                              |
+            AddKeyFrame(_)                          |
+            RemoveKeyFrame(_)                       => true,
+
+            SetName(_)                              |
+            SetOrdering(_)                          |
+            SetAlpha(_)                             |
+            SetBlendMode(_)                         |
+            SetLocked(_)                            => false
         }
     }
 
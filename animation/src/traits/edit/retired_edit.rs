@@ -12,6 +12,9 @@ pub struct RetiredEdit {
 
     /// The actions that will reverse these edits
     reverse:    Arc<Vec<AnimationEdit>>,
+
+    /// The edits that were rejected because they would have changed the content of a locked layer
+    rejected:   Arc<Vec<AnimationEdit>>
 }
 
 impl RetiredEdit {
@@ -22,6 +25,17 @@ impl RetiredEdit {
         RetiredEdit {
             committed:  Arc::clone(&committed),
             reverse:    Arc::clone(&reverse),
+            rejected:   Arc::new(vec![])
+        }
+    }
+
+    ///
+    /// Adds the edits that were rejected (and so are not in the list of committed edits) to this structure
+    ///
+    pub fn with_rejected_edits(self, rejected: Arc<Vec<AnimationEdit>>) -> RetiredEdit {
+        RetiredEdit {
+            rejected: rejected,
+            ..self
         }
     }
 
@@ -38,4 +52,11 @@ impl RetiredEdit {
     pub fn reverse_edits(&self) -> Arc<Vec<AnimationEdit>> {
         Arc::clone(&self.reverse)
     }
+
+    ///
+    /// Returns the list of edits that were rejected because they would have changed the content of a locked layer
+    ///
+    pub fn rejected_edits(&self) -> Arc<Vec<AnimationEdit>> {
+        Arc::clone(&self.rejected)
+    }
 }
@@ -31,6 +31,12 @@ pub enum UndoFailureReason {
 
     /// The animation failed to report the undo success/failure properly
     BadEditingSequence,
+
+    /// There is no checkpoint with the requested name
+    UnknownCheckpoint,
+
+    /// The requested checkpoint was undone and then discarded by later edits, so it's no longer in the undo history
+    CheckpointInvalidated,
 }
 
 impl From<StorageError> for UndoFailureReason {
@@ -0,0 +1,40 @@
+use flo_canvas::*;
+
+///
+/// How a layer is composited with the layers underneath it
+///
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub enum LayerBlendMode {
+    /// The layer is drawn on top of the layers underneath it (the default)
+    SourceOver,
+
+    /// The colours of the layer are multiplied with the colours underneath it
+    Multiply,
+
+    /// The inverse of the colours of the layer are multiplied with the inverse of the colours underneath it
+    Screen,
+
+    /// The darker of the layer and the layers underneath it is used for each pixel
+    Darken,
+
+    /// The lighter of the layer and the layers underneath it is used for each pixel
+    Lighten
+}
+
+impl Default for LayerBlendMode {
+    fn default() -> LayerBlendMode {
+        LayerBlendMode::SourceOver
+    }
+}
+
+impl From<LayerBlendMode> for BlendMode {
+    fn from(blend_mode: LayerBlendMode) -> BlendMode {
+        match blend_mode {
+            LayerBlendMode::SourceOver  => BlendMode::SourceOver,
+            LayerBlendMode::Multiply    => BlendMode::Multiply,
+            LayerBlendMode::Screen      => BlendMode::Screen,
+            LayerBlendMode::Darken      => BlendMode::Darken,
+            LayerBlendMode::Lighten     => BlendMode::Lighten
+        }
+    }
+}
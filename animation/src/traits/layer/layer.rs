@@ -1,5 +1,7 @@
 use super::vector::*;
+use super::blend_mode::*;
 use super::super::edit::*;
+use super::super::vector::*;
 use super::super::frame::*;
 use super::super::cache::*;
 
@@ -8,6 +10,23 @@ use std::sync::*;
 use std::time::Duration;
 use std::ops::{Range, Deref};
 
+///
+/// Counts the elements that are drawn by a vector element, including the elements inside a group
+///
+fn count_drawn_elements(element: &Vector) -> usize {
+    match element {
+        Vector::Group(group)            => group.elements().map(|element| count_drawn_elements(element)).sum(),
+
+        Vector::BrushDefinition(_)
+        | Vector::BrushProperties(_)
+        | Vector::Motion(_)
+        | Vector::Transformation(_)
+        | Vector::Opacity(_)            => 0,
+
+        _                               => 1
+    }
+}
+
 ///
 /// A layer represents a renderable plane in an animation
 ///
@@ -27,6 +46,16 @@ pub trait Layer : Send+Sync {
     ///
     fn alpha(&self) -> f64;
 
+    ///
+    /// How this layer is composited with the layers underneath it
+    ///
+    fn blend_mode(&self) -> LayerBlendMode;
+
+    ///
+    /// True if the content of this layer cannot be edited (edits to its content or its elements are rejected)
+    ///
+    fn is_locked(&self) -> bool;
+
     ///
     /// The types of edit that are supported by this layer
     ///
@@ -47,6 +76,18 @@ pub trait Layer : Send+Sync {
     ///
     fn get_key_frames_during_time(&self, when: Range<Duration>) -> Box<dyn Iterator<Item=Duration>>;
 
+    ///
+    /// Counts the drawn elements in the keyframe at the specified time, without loading the whole keyframe
+    ///
+    /// Elements that affect how other elements are drawn (such as brush definitions) are not counted. Elements that are
+    /// inside groups are counted individually.
+    ///
+    fn get_num_elements_in_key_frame(&self, when: Duration) -> usize {
+        self.get_frame_at_time(when).vector_elements()
+            .map(|elements| elements.map(|element| count_drawn_elements(&element)).sum())
+            .unwrap_or(0)
+    }
+
     ///
     /// Returns the time of the keyframe that will be active at the specified time
     ///
@@ -1,5 +1,7 @@
 mod layer;
 mod vector;
+mod blend_mode;
 
 pub use self::layer::*;
 pub use self::vector::*;
+pub use self::blend_mode::*;
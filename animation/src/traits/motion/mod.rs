@@ -2,8 +2,10 @@ mod transform;
 mod motion;
 mod translate;
 mod motion_type;
+mod motion_blur;
 
 pub use self::transform::*;
 pub use self::motion::*;
 pub use self::translate::*;
 pub use self::motion_type::*;
+pub use self::motion_blur::*;
@@ -0,0 +1,355 @@
+use super::transform::*;
+
+use flo_canvas::*;
+use flo_canvas_animation::*;
+use flo_curves::*;
+use flo_curves::bezier::path::{SimpleBezierPath};
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// A single 'ghost' copy of an element drawn to produce a motion blur effect
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MotionBlurGhost {
+    /// The offset from the element's real position to draw this ghost at
+    pub offset: (f64, f64),
+
+    /// The opacity (0-1) to draw this ghost with
+    pub opacity: f32
+}
+
+///
+/// Rendering hint that describes how fast-moving elements should be blurred
+///
+/// Elements are blurred by drawing 'ghost' copies of them trailing behind their direction of motion. The
+/// number of ghosts depends on how far the element has moved since the last frame, so faster elements
+/// produce a longer smear. The velocity is re-evaluated every frame, so the smear always trails behind
+/// the element's most recent direction of travel even when it changes direction.
+///
+/// Animated elements are blurred when this is set as the `motion_blur` property in their `VectorProperties`.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MotionBlur {
+    /// The length of a frame (the velocity of an element is the distance it moves over this time)
+    pub frame_length: Duration,
+
+    /// The minimum distance an element must move in a frame before it's blurred at all
+    pub min_speed: f64,
+
+    /// The distance between each ghost copy of the element
+    pub ghost_spacing: f64,
+
+    /// The maximum number of ghosts to generate for a single element
+    pub max_ghosts: usize,
+
+    /// The opacity of the ghost nearest the element (ghosts further away fade out from this value)
+    pub ghost_opacity: f32
+}
+
+impl Default for MotionBlur {
+    fn default() -> MotionBlur {
+        MotionBlur {
+            frame_length:   Duration::new(0, 33_333_333),
+            min_speed:      5.0,
+            ghost_spacing:  10.0,
+            max_ghosts:     16,
+            ghost_opacity:  0.4
+        }
+    }
+}
+
+///
+/// Works out the velocity (in units per frame) of a point that's being moved by a motion
+///
+/// The velocity is the distance the point has moved between the previous frame and the frame at `when`.
+/// Motions are sampled at the start of time if there's no previous frame.
+///
+pub fn motion_velocity<M: MotionTransform>(motion: &M, origin: (f64, f64), when: Duration, frame_length: Duration) -> (f64, f64) {
+    let previous_frame  = if when > frame_length { when - frame_length } else { Duration::from_millis(0) };
+    let transform_at    = |time| {
+        motion.transformation(time).iter()
+            .fold(Coord2(origin.0, origin.1), |point, transform| transform.transform_point(&point))
+    };
+
+    let start           = transform_at(previous_frame);
+    let end             = transform_at(when);
+
+    (end.x()-start.x(), end.y()-start.y())
+}
+
+///
+/// Multiplies the alpha component of a colour by an opacity
+///
+fn fade_color(color: &Color, opacity: f32) -> Color {
+    let (_, _, _, alpha) = color.to_rgba_components();
+
+    color.with_alpha(alpha * opacity)
+}
+
+impl MotionBlur {
+    ///
+    /// Returns the ghosts that should be drawn for an element moving at the specified velocity (in units per frame)
+    ///
+    /// Ghosts are returned nearest-first, trailing behind the direction of motion. Elements moving slower than
+    /// `min_speed` produce no ghosts.
+    ///
+    pub fn ghosts(&self, velocity: (f64, f64)) -> Vec<MotionBlurGhost> {
+        let (dx, dy)    = velocity;
+        let speed       = (dx*dx + dy*dy).sqrt();
+
+        if speed < self.min_speed || self.ghost_spacing <= 0.0 {
+            return vec![];
+        }
+
+        // Number of ghosts is proportional to the speed
+        let num_ghosts  = ((speed / self.ghost_spacing).floor() as usize).min(self.max_ghosts);
+        if num_ghosts == 0 {
+            return vec![];
+        }
+
+        // Ghosts are evenly spaced across the distance travelled in the last frame, fading as they get further away
+        let step        = (dx / (num_ghosts as f64), dy / (num_ghosts as f64));
+
+        (1..=num_ghosts)
+            .map(|ghost_num| {
+                let fade = 1.0 - ((ghost_num-1) as f32) / (num_ghosts as f32);
+
+                MotionBlurGhost {
+                    offset:     (-step.0 * (ghost_num as f64), -step.1 * (ghost_num as f64)),
+                    opacity:    self.ghost_opacity * fade
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Generates the draw instructions for the ghosts of a drawing moving at the specified velocity
+    ///
+    /// The result contains only the ghosts, furthest first, so it should be rendered before the drawing itself.
+    ///
+    pub fn blur_drawing(&self, drawing: &[Draw], velocity: (f64, f64)) -> Vec<Draw> {
+        let mut result = vec![];
+
+        for ghost in self.ghosts(velocity).into_iter().rev() {
+            result.push(Draw::PushState);
+            result.push(Draw::MultiplyTransform(Transform2D::translate(ghost.offset.0 as f32, ghost.offset.1 as f32)));
+
+            // Ghosts are drawn as faded out copies of the original drawing
+            result.extend(drawing.iter().map(|draw| {
+                match draw {
+                    Draw::FillColor(color)      => Draw::FillColor(fade_color(color, ghost.opacity)),
+                    Draw::StrokeColor(color)    => Draw::StrokeColor(fade_color(color, ghost.opacity)),
+                    other                       => other.clone()
+                }
+            }));
+
+            result.push(Draw::PopState);
+        }
+
+        result
+    }
+}
+
+///
+/// Animation region that draws ghosts trailing behind the content of another region while it's moving
+///
+pub struct MotionBlurRegion {
+    /// The region whose content is blurred
+    region: Arc<dyn AnimationRegion>,
+
+    /// How the content of the region is blurred
+    blur: MotionBlur
+}
+
+impl MotionBlurRegion {
+    ///
+    /// Creates a region that blurs the content of another region when it moves
+    ///
+    pub fn new(region: Arc<dyn AnimationRegion>, blur: MotionBlur) -> MotionBlurRegion {
+        MotionBlurRegion {
+            region: region,
+            blur:   blur
+        }
+    }
+
+    ///
+    /// Returns the center of the bounding box of the paths in some region content
+    ///
+    fn center(content: &AnimationRegionContent) -> Option<(f64, f64)> {
+        let mut bounds: Option<(Coord2, Coord2)> = None;
+
+        for path in content.paths() {
+            for (start_point, curves) in path.path.iter() {
+                let points = std::iter::once(start_point).chain(curves.iter().map(|(_, _, end_point)| end_point));
+
+                for point in points {
+                    bounds = Some(match bounds {
+                        None                => (*point, *point),
+                        Some((min, max))    => (Coord2(min.x().min(point.x()), min.y().min(point.y())), Coord2(max.x().max(point.x()), max.y().max(point.y())))
+                    });
+                }
+            }
+        }
+
+        bounds.map(|(min, max)| ((min.x()+max.x())/2.0, (min.y()+max.y())/2.0))
+    }
+
+    ///
+    /// Animates the content of a region, adding ghosts behind it if it's moving fast enough
+    ///
+    fn blur_content(region: &Arc<dyn AnimationRegion>, blur: &MotionBlur, region_contents: Arc<AnimationRegionContent>, time: Duration) -> Arc<AnimationRegionContent> {
+        // The velocity is how far the content has moved since the previous frame
+        let previous_time   = if time > blur.frame_length { time - blur.frame_length } else { Duration::from_millis(0) };
+        let current         = region.animate(Arc::clone(&region_contents), time);
+        let previous        = region.animate(region_contents, previous_time);
+
+        let velocity        = match (Self::center(&current), Self::center(&previous)) {
+            (Some((x1, y1)), Some((x0, y0)))    => (x1-x0, y1-y0),
+            _                                   => (0.0, 0.0)
+        };
+
+        let ghosts          = blur.ghosts(velocity);
+        if ghosts.len() == 0 {
+            return current;
+        }
+
+        // Ghosts are faded out copies of the content, drawn furthest first underneath the content itself
+        let mut ghost_paths = vec![];
+
+        for ghost in ghosts.into_iter().rev() {
+            for path in current.paths() {
+                let mut ghost_path          = path.offset_by(Coord2(ghost.offset.0, ghost.offset.1));
+                ghost_path.attributes       = ghost_path.attributes.with_opacity(ghost.opacity);
+
+                ghost_paths.push(ghost_path);
+            }
+        }
+
+        Arc::new(AnimationRegionContent::from_paths(ghost_paths).with_suffix(current))
+    }
+}
+
+impl AnimationEffect for MotionBlurRegion {
+    fn duration(&self) -> Option<f64> {
+        self.region.duration()
+    }
+
+    fn animate(&self, region_contents: Arc<AnimationRegionContent>, time: Duration) -> Arc<AnimationRegionContent> {
+        Self::blur_content(&self.region, &self.blur, region_contents, time)
+    }
+
+    fn animate_cached(&self, region_contents: Arc<AnimationRegionContent>) -> Box<dyn Send+Fn(Duration) -> Arc<AnimationRegionContent>> {
+        let region  = Arc::clone(&self.region);
+        let blur    = self.blur;
+
+        Box::new(move |time| Self::blur_content(&region, &blur, Arc::clone(&region_contents), time))
+    }
+}
+
+impl AnimationRegion for MotionBlurRegion {
+    fn region(&self, time: Duration) -> Vec<SimpleBezierPath> {
+        self.region.region(time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::translate::*;
+    use super::super::super::time_path::*;
+
+    #[test]
+    fn slow_motion_is_not_blurred() {
+        let blur = MotionBlur::default();
+
+        assert!(blur.ghosts((1.0, 1.0)).len() == 0);
+        assert!(blur.blur_drawing(&[Draw::Fill], (1.0, 1.0)).len() == 0);
+    }
+
+    #[test]
+    fn fast_motion_generates_ghosts_scaled_by_velocity() {
+        let blur        = MotionBlur { frame_length: Duration::from_millis(100), min_speed: 5.0, ghost_spacing: 10.0, max_ghosts: 16, ghost_opacity: 0.5 };
+        let drawing     = vec![Draw::FillColor(Color::Rgba(1.0, 0.0, 0.0, 1.0)), Draw::Fill];
+
+        let fast        = blur.blur_drawing(&drawing, (100.0, 0.0));
+        let slower      = blur.blur_drawing(&drawing, (50.0, 0.0));
+
+        // 10 ghosts for the fast element, each needing push, transform, the drawing and pop
+        assert!(fast.len() == 10 * (drawing.len() + 3));
+        assert!(slower.len() == 5 * (drawing.len() + 3));
+
+        // Nearest ghost trails 10 units behind the element
+        let ghosts      = blur.ghosts((100.0, 0.0));
+        assert!((ghosts[0].offset.0 - -10.0).abs() < 0.001);
+        assert!((ghosts[9].offset.0 - -100.0).abs() < 0.001);
+        assert!(ghosts[0].opacity > ghosts[9].opacity);
+    }
+
+    #[test]
+    fn ghosts_follow_direction_change() {
+        let blur        = MotionBlur::default();
+        let right       = blur.ghosts((100.0, 0.0));
+        let left        = blur.ghosts((-100.0, 0.0));
+
+        assert!(right[0].offset.0 < 0.0);
+        assert!(left[0].offset.0 > 0.0);
+    }
+
+    fn render_moving_square(motion_blur: Option<MotionBlur>) -> Vec<Draw> {
+        use super::super::super::vector::*;
+        use super::super::super::edit::*;
+        use flo_canvas_animation::description;
+
+        // A 100x100 region that moves 1000 units to the right over a second
+        let point           = |x, y| description::BezierPoint(description::Point2D(x, y), description::Point2D(x, y), description::Point2D(x, y));
+        let square          = description::BezierPath(description::Point2D(0.0, 0.0), vec![point(100.0, 0.0), point(100.0, 100.0), point(0.0, 100.0), point(0.0, 0.0)]);
+        let movement        = description::BezierPath(description::Point2D(0.0, 0.0), vec![description::BezierPoint(description::Point2D(333.0, 0.0), description::Point2D(666.0, 0.0), description::Point2D(1000.0, 0.0))]);
+        let element         = AnimationElement::new(ElementId::Assigned(1), description::RegionDescription(vec![square], description::EffectDescription::Move(Duration::from_millis(1000), movement)));
+
+        let mut properties  = VectorProperties::default();
+        properties.motion_blur = motion_blur;
+
+        // Render the region followed by some content inside it
+        let mut layer       = AnimationLayer::new();
+        {
+            let mut gc      = layer.graphics_context();
+            properties.render_animated(&mut gc, Vector::AnimationRegion(element), Duration::from_millis(0));
+
+            gc.new_path();
+            gc.rect(10.0, 10.0, 90.0, 90.0);
+            gc.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+            gc.fill();
+        }
+
+        let mut drawing     = vec![];
+        layer.render_sync(Duration::from_millis(500), &mut drawing);
+
+        drawing
+    }
+
+    #[test]
+    fn moving_element_is_blurred_when_rendered() {
+        let unblurred       = render_moving_square(None);
+        let blurred         = render_moving_square(Some(MotionBlur::default()));
+
+        let unblurred_fills = unblurred.iter().filter(|draw| **draw == Draw::Fill).count();
+        let blurred_fills   = blurred.iter().filter(|draw| **draw == Draw::Fill).count();
+
+        // The blurred element is drawn along with faded ghosts trailing behind it
+        assert!(unblurred_fills == 1);
+        assert!(blurred_fills > unblurred_fills);
+    }
+
+    #[test]
+    fn velocity_from_translate_motion() {
+        let mut motion  = TranslateMotion::default();
+        motion.set_path(TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(1000.0, 0.0, 1000.0)));
+
+        let velocity    = motion_velocity(&motion, (0.0, 0.0), Duration::from_millis(500), Duration::from_millis(100));
+
+        assert!(velocity.0 > 0.0);
+        assert!(velocity.1.abs() < 0.001);
+    }
+}
@@ -6,6 +6,7 @@ use super::vector_element::*;
 use super::path_conversion_options::*;
 use super::super::path::*;
 use super::super::edit::*;
+use super::super::motion::{MotionBlurRegion};
 use crate::raycast::*;
 
 use flo_canvas::*;
@@ -162,6 +163,10 @@ impl VectorElement for AnimationElement {
             });
         }
 
+        if let Some(motion_blur) = properties.motion_blur {
+            region = Arc::new(MotionBlurRegion::new(region, motion_blur));
+        }
+
         gc.add_region(region);
         self.render_static(gc, properties, when);
     }
@@ -7,6 +7,7 @@ mod control_point;
 mod error_element;
 mod brush_element;
 mod group_element;
+mod opacity_element;
 mod shape_element;
 mod motion_element;
 mod animation_element;
@@ -25,6 +26,7 @@ pub use self::control_point::*;
 pub use self::error_element::*;
 pub use self::brush_element::*;
 pub use self::group_element::*;
+pub use self::opacity_element::*;
 pub use self::shape_element::*;
 pub use self::motion_element::*;
 pub use self::transformation::*;
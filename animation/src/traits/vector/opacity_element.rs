@@ -0,0 +1,107 @@
+use super::vector::*;
+use super::properties::*;
+use super::control_point::*;
+use super::vector_element::*;
+use super::path_conversion_options::*;
+use super::super::path::*;
+use super::super::edit::*;
+
+use flo_canvas::*;
+use flo_canvas_animation::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Element that is attached to other elements to change their opacity and visibility over time
+///
+/// The times of the keyframes are measured from the start of the animation.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct OpacityElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// How the opacity of the elements this is attached to changes over time
+    keyframes: Arc<OpacityKeyFrames>
+}
+
+impl OpacityElement {
+    ///
+    /// Creates a new opacity element
+    ///
+    pub fn new(id: ElementId, keyframes: OpacityKeyFrames) -> OpacityElement {
+        OpacityElement {
+            id:         id,
+            keyframes:  Arc::new(keyframes)
+        }
+    }
+
+    ///
+    /// Retrieves the opacity keyframes for this element
+    ///
+    pub fn keyframes<'a>(&'a self) -> &'a OpacityKeyFrames {
+        &*self.keyframes
+    }
+}
+
+impl VectorElement for OpacityElement {
+    ///
+    /// The ID of this vector element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Modifies this element to have a new ID
+    ///
+    fn set_id(&mut self, new_id: ElementId) {
+        self.id = new_id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    fn to_path(&self, _properties: &VectorProperties, _options: PathConversion) -> Option<Vec<Path>> {
+        None
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render_static(&self, _gc: &mut dyn GraphicsContext, _properties: &VectorProperties, _when: Duration) {
+
+    }
+
+    ///
+    /// Updates the vector properties for future elements
+    ///
+    fn update_properties(&self, properties: Arc<VectorProperties>, _when: Duration) -> Arc<VectorProperties> {
+        let mut properties  = (*properties).clone();
+        properties.opacity  = Some(Arc::clone(&self.keyframes));
+
+        Arc::new(properties)
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self, _properties: &VectorProperties) -> Vec<ControlPoint> {
+        vec![]
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    fn with_adjusted_control_points(&self, _new_positions: Vec<(f32, f32)>, _properties: &VectorProperties) -> Vector {
+        Vector::Opacity(self.clone())
+    }
+}
+
+impl Into<Vector> for OpacityElement {
+    #[inline]
+    fn into(self) -> Vector {
+        Vector::Opacity(self)
+    }
+}
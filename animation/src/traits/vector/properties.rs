@@ -4,6 +4,7 @@ use super::super::vector::*;
 use super::super::brush_properties::*;
 use super::super::brush_definition::*;
 use super::super::brush_drawing_style::*;
+use super::super::motion::{MotionBlur};
 use super::super::super::brushes::*;
 
 use flo_curves::*;
@@ -32,6 +33,12 @@ pub struct VectorProperties {
     /// Transformations to apply to the element during rendering
     pub transformations: Arc<Vec<Transformation>>,
 
+    /// How the opacity of the element changes over time (times are measured from the start of the animation)
+    pub opacity: Option<Arc<OpacityKeyFrames>>,
+
+    /// How animated elements are blurred when they move quickly (None if they're not blurred)
+    pub motion_blur: Option<MotionBlur>,
+
     /// Returns the 
     pub retrieve_attachments: Arc<dyn (Fn(ElementId) -> Vec<Vector>) + Sync+Send>,
 
@@ -51,6 +58,8 @@ impl VectorProperties {
             brush:                  Arc::new(InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Draw)),
             brush_properties:       BrushProperties::new(),
             transformations:        Arc::new(vec![]),
+            opacity:                None,
+            motion_blur:            None,
             retrieve_attachments:   Arc::new(|_| vec![]),
             render_vector_static:   Arc::new(|gc, vector, when, properties| vector.render_static(gc, properties, when)),
             render_vector_animated: Arc::new(|gc, vector, when, properties| vector.render_animated(gc, properties, when))
@@ -135,6 +144,8 @@ impl VectorProperties {
             brush:                  Arc::clone(&self.brush),
             brush_properties:       self.brush_properties.clone(),
             transformations:        Arc::new(inverted_transformations),
+            opacity:                self.opacity.clone(),
+            motion_blur:            self.motion_blur,
             retrieve_attachments:   Arc::clone(&self.retrieve_attachments),
             render_vector_static:   Arc::clone(&self.render_vector_static),
             render_vector_animated: Arc::clone(&self.render_vector_animated)
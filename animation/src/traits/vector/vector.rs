@@ -4,6 +4,7 @@ use super::shape_element::*;
 use super::group_element::*;
 use super::error_element::*;
 use super::motion_element::*;
+use super::opacity_element::*;
 use super::vector_element::*;
 use super::transformation::*;
 use super::animation_element::*;
@@ -52,6 +53,9 @@ pub enum Vector {
     /// Attached to an element to indicate a transformation that should be applied to it when rendering
     Transformation((ElementId, SmallVec<[Transformation; 2]>)),
 
+    /// Attached to an element to change its opacity and visibility over time
+    Opacity(OpacityElement),
+
     /// An element representing an animation region for the keyframe
     AnimationRegion(AnimationElement),
 
@@ -202,6 +206,7 @@ impl DerefMut for Vector {
             Motion(elem)                    => elem,
             Group(elem)                     => elem,
             Transformation(elem)            => elem,
+            Opacity(elem)                   => elem,
             AnimationRegion(elem)           => elem,
             Error                           => panic!("Cannot edit an error element")
         }
@@ -228,6 +233,7 @@ impl Deref for Vector {
             Group(elem)                     => elem,
             AnimationRegion(elem)           => elem,
             Transformation(transform)       => transform,
+            Opacity(elem)                   => elem,
             Error                           => &*ERROR_ELEMENT
         }
     }
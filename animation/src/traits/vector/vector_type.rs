@@ -29,6 +29,9 @@ pub enum VectorType {
     /// A property describing a transformation that can be applied to another element
     Transformation,
 
+    /// A property describing how the opacity of another element changes over time
+    Opacity,
+
     /// Represents a region of the canvas that has an animation effect applied to it
     AnimationRegion,
 
@@ -50,6 +53,7 @@ impl From<&Vector> for VectorType {
             Motion(_)                       => VectorType::Motion,
             Group(_)                        => VectorType::Group,
             Transformation(_)               => VectorType::Transformation,
+            Opacity(_)                      => VectorType::Opacity,
             AnimationRegion(_)              => VectorType::AnimationRegion,
             Error                           => VectorType::Error
         }
@@ -640,3 +640,93 @@ fn double_undo_with_errant_finish_action() {
         assert!(elements.len() == 0);
     });
 }
+
+#[test]
+fn undo_coalesced_edits() {
+    executor::block_on(async {
+        use AnimationEdit::*;
+
+        // Create the animation
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let animation       = UndoableAnimation::new(animation);
+
+        // Setup a layer
+        animation.edit().publish(Arc::new(vec![
+            AddNewLayer(0),
+            Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            Layer(0, LayerEdit::SetName("Original".into())),
+            Undo(UndoEdit::FinishAction),
+        ])).await;
+
+        // Rename the layer three times in quick succession
+        animation.begin_coalesce(Duration::from_secs(10));
+
+        for name in ["One", "Two", "Three"].iter() {
+            animation.edit().publish(Arc::new(vec![
+                Layer(0, LayerEdit::SetName(name.to_string())),
+                Undo(UndoEdit::FinishAction),
+            ])).await;
+        }
+
+        // Wait for the edits to commit
+        animation.edit().when_empty().await;
+        assert!(animation.get_layer_with_id(0).unwrap().name() == Some("Three".to_string()));
+
+        // A single undo should revert all three edits
+        let timeout     = Delay::new(Duration::from_secs(10));
+        let undo_result = match select(animation.undo().boxed(), timeout).await {
+            Either::Right(_)        => { assert!(false, "Timed out"); unimplemented!() }
+            Either::Left(result)    => result.0,
+        };
+        assert!(undo_result.is_ok());
+
+        assert!(animation.get_layer_with_id(0).unwrap().name() == Some("Original".to_string()));
+    });
+}
+
+#[test]
+fn undo_to_named_checkpoint() {
+    executor::block_on(async {
+        use AnimationEdit::*;
+
+        // Create the animation
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let animation       = UndoableAnimation::new(animation);
+
+        // Setup a layer and mark it as saved
+        animation.edit().publish(Arc::new(vec![
+            AddNewLayer(0),
+            Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            Layer(0, LayerEdit::SetName("Saved".into())),
+            Undo(UndoEdit::FinishAction),
+        ])).await;
+        animation.edit().when_empty().await;
+
+        animation.set_checkpoint("Save".into());
+
+        // Make some more changes
+        for name in ["One", "Two"].iter() {
+            animation.edit().publish(Arc::new(vec![
+                Layer(0, LayerEdit::SetName(name.to_string())),
+                Layer(0, LayerEdit::SetAlpha(0.5)),
+                Undo(UndoEdit::FinishAction),
+            ])).await;
+        }
+
+        animation.edit().when_empty().await;
+        assert!(animation.get_layer_with_id(0).unwrap().name() == Some("Two".to_string()));
+
+        // Revert to the checkpoint
+        let timeout     = Delay::new(Duration::from_secs(10));
+        let undo_result = match select(animation.undo_to_checkpoint("Save").boxed(), timeout).await {
+            Either::Right(_)        => { assert!(false, "Timed out"); unimplemented!() }
+            Either::Left(result)    => result.0,
+        };
+        assert!(undo_result.is_ok());
+
+        assert!(animation.get_layer_with_id(0).unwrap().name() == Some("Saved".to_string()));
+        assert!(animation.get_layer_with_id(0).unwrap().alpha() == 1.0);
+    });
+}
@@ -27,6 +27,12 @@ pub struct LayerData {
     /// The alpha value of the layer
     pub alpha: f64,
 
+    /// The blend mode of the layer
+    pub blend_mode: LayerBlendMode,
+
+    /// Whether or not the layer is locked
+    pub locked: bool,
+
     /// Data at each of the keyframes for this layer
     pub keyframes: HashMap<Duration, FrameData>
 }
@@ -39,6 +45,8 @@ pub async fn read_layer(animation: &impl Animation, layer_id: u64) -> Option<Lay
     let keyframe_times      = layer.get_key_frames();
     let layer_name          = layer.name().expect("Missing layer name");
     let layer_alpha         = layer.alpha();
+    let layer_blend_mode    = layer.blend_mode();
+    let layer_locked        = layer.is_locked();
 
     let mut keyframe_data   = HashMap::new();
 
@@ -51,7 +59,9 @@ pub async fn read_layer(animation: &impl Animation, layer_id: u64) -> Option<Lay
     Some(LayerData {
         keyframes:  keyframe_data,
         name:       layer_name,
-        alpha:      layer_alpha
+        alpha:      layer_alpha,
+        blend_mode: layer_blend_mode,
+        locked:     layer_locked
     })
 }
 
@@ -336,6 +346,100 @@ fn set_alpha() {
     });
 }
 
+#[test]
+fn set_blend_mode() {
+    executor::block_on(async {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        test_layer_edit_undo(
+            vec![
+                AddNewLayer(0),
+                Layer(0, AddKeyFrame(Duration::from_millis(0))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(100), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                Layer(0, Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(101), BrushProperties::new()))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(0), circle_path((100.0, 100.0), 50.0)))),
+            ],
+            vec![
+                Layer(0, SetBlendMode(LayerBlendMode::Multiply)),
+            ]
+        ).await;
+    });
+}
+
+#[test]
+fn remove_layer_with_blend_mode() {
+    executor::block_on(async {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        test_layer_edit_undo(
+            vec![
+                AddNewLayer(0),
+                Layer(0, AddKeyFrame(Duration::from_millis(0))),
+                Layer(0, SetBlendMode(LayerBlendMode::Screen)),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(100), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                Layer(0, Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(101), BrushProperties::new()))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(0), circle_path((100.0, 100.0), 50.0)))),
+            ],
+            vec![
+                RemoveLayer(0)
+            ]
+        ).await;
+    });
+}
+
+#[test]
+fn set_locked() {
+    executor::block_on(async {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        test_layer_edit_undo(
+            vec![
+                AddNewLayer(0),
+                Layer(0, AddKeyFrame(Duration::from_millis(0))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(100), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                Layer(0, Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(101), BrushProperties::new()))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(0), circle_path((100.0, 100.0), 50.0)))),
+            ],
+            vec![
+                Layer(0, SetLocked(true)),
+            ]
+        ).await;
+    });
+}
+
+#[test]
+fn remove_locked_layer() {
+    executor::block_on(async {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        test_layer_edit_undo(
+            vec![
+                AddNewLayer(0),
+                Layer(0, AddKeyFrame(Duration::from_millis(0))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(100), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                Layer(0, Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(101), BrushProperties::new()))),
+
+                Layer(0, Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(0), circle_path((100.0, 100.0), 50.0)))),
+                Layer(0, SetLocked(true)),
+            ],
+            vec![
+                RemoveLayer(0)
+            ]
+        ).await;
+    });
+}
+
 #[test]
 fn remove_layer_with_multiple_keyframes() {
     executor::block_on(async {
@@ -1,7 +1,9 @@
 use crate::undo::undo_log::*;
 use crate::traits::*;
 
+use std::thread;
 use std::sync::*;
+use std::time::{Duration};
 
 #[test]
 fn commits_final_finish_action() {
@@ -28,4 +30,123 @@ fn does_not_commit_empty_action() {
 
     // The last 'FinishAction' should not create a new entry
     assert!(log.undo_depth() == 1);
-}
\ No newline at end of file
+}
+///
+/// Creates a retired edit that renames layer 0, followed by a 'finish action'
+///
+fn rename_layer(old_name: &str, new_name: &str) -> RetiredEdit {
+    RetiredEdit::new(
+        Arc::new(vec![AnimationEdit::Layer(0, LayerEdit::SetName(new_name.into())), AnimationEdit::Undo(UndoEdit::FinishAction)]), 
+        Arc::new(vec![AnimationEdit::Layer(0, LayerEdit::SetName(old_name.into()))]))
+}
+
+#[test]
+fn coalesce_edits_of_the_same_kind() {
+    let mut log = UndoLog::new();
+
+    log.begin_coalesce(Duration::from_secs(10));
+    log.retire(rename_layer("Original", "One"));
+    log.retire(rename_layer("One", "Two"));
+    log.retire(rename_layer("Two", "Three"));
+
+    assert!(log.undo_depth() == 1);
+
+    // Undoing the step should restore the name from before the first edit
+    match log.undo() {
+        Some(UndoEdit::PerformUndo { original_actions, undo_actions }) => {
+            assert!(original_actions.len() == 6);
+            assert!(undo_actions.last() == Some(&AnimationEdit::Layer(0, LayerEdit::SetName("Original".into()))));
+        }
+
+        _ => { assert!(false, "Expected an undo action") }
+    }
+}
+
+#[test]
+fn different_edit_kind_stops_coalescing() {
+    let mut log = UndoLog::new();
+
+    log.begin_coalesce(Duration::from_secs(10));
+    log.retire(rename_layer("Original", "One"));
+    log.retire(RetiredEdit::new(Arc::new(vec![AnimationEdit::Layer(0, LayerEdit::SetAlpha(0.5)), AnimationEdit::Undo(UndoEdit::FinishAction)]), Arc::new(vec![])));
+    log.retire(rename_layer("One", "Two"));
+
+    assert!(log.undo_depth() == 3);
+}
+
+#[test]
+fn commit_undo_group_stops_coalescing() {
+    let mut log = UndoLog::new();
+
+    log.begin_coalesce(Duration::from_secs(10));
+    log.retire(rename_layer("Original", "One"));
+    log.retire(rename_layer("One", "Two"));
+    log.commit_undo_group();
+    log.retire(rename_layer("Two", "Three"));
+
+    assert!(log.undo_depth() == 2);
+}
+
+#[test]
+fn edits_outside_window_are_not_coalesced() {
+    let mut log = UndoLog::new();
+
+    log.begin_coalesce(Duration::from_millis(0));
+    log.retire(rename_layer("Original", "One"));
+    thread::sleep(Duration::from_millis(5));
+    log.retire(rename_layer("One", "Two"));
+
+    assert!(log.undo_depth() == 2);
+}
+
+#[test]
+fn undo_to_checkpoint() {
+    let mut log = UndoLog::new();
+
+    log.retire(rename_layer("Original", "One"));
+    log.set_checkpoint("Saved".into());
+    log.retire(rename_layer("One", "Two"));
+    log.retire(rename_layer("Two", "Three"));
+
+    assert!(log.undo_depth() == 3);
+
+    // Should undo the two edits after the checkpoint in one go
+    match log.undo_to_checkpoint("Saved") {
+        Ok(UndoEdit::PerformUndo { original_actions, undo_actions }) => {
+            assert!(original_actions.len() == 4);
+            assert!(undo_actions.last() == Some(&AnimationEdit::Layer(0, LayerEdit::SetName("One".into()))));
+        }
+
+        _ => { assert!(false, "Expected an undo action") }
+    }
+
+    assert!(log.undo_depth() == 1);
+    assert!(log.redo_depth() == 2);
+
+    // Nothing more to undo to get to the checkpoint
+    assert!(log.undo_to_checkpoint("Saved") == Err(UndoFailureReason::NothingToUndo));
+}
+
+#[test]
+fn unknown_checkpoint() {
+    let mut log = UndoLog::new();
+
+    log.retire(rename_layer("Original", "One"));
+
+    assert!(log.undo_to_checkpoint("Saved") == Err(UndoFailureReason::UnknownCheckpoint));
+}
+
+#[test]
+fn checkpoint_discarded_by_new_edits() {
+    let mut log = UndoLog::new();
+
+    log.retire(rename_layer("Original", "One"));
+    log.retire(rename_layer("One", "Two"));
+    log.set_checkpoint("Saved".into());
+
+    // Undo past the checkpoint, then perform a new edit (which discards the redo steps including the checkpoint)
+    log.undo();
+    log.retire(rename_layer("One", "Three"));
+
+    assert!(log.undo_to_checkpoint("Saved") == Err(UndoFailureReason::CheckpointInvalidated));
+}
@@ -1,7 +1,38 @@
 use super::undo_step::*;
 use crate::traits::*;
 
+use std::mem;
 use std::sync::*;
+use std::time::{Duration, Instant};
+use std::collections::{HashMap, HashSet};
+
+///
+/// The kind of an edit, used to decide which edits can be coalesced into a single undo step
+///
+#[derive(Clone, PartialEq, Debug)]
+enum EditKind {
+    Layer(u64, mem::Discriminant<LayerEdit>),
+    Element(Vec<ElementId>, mem::Discriminant<ElementEdit>),
+    Motion(ElementId, mem::Discriminant<MotionEdit>),
+    Other(mem::Discriminant<AnimationEdit>)
+}
+
+impl EditKind {
+    ///
+    /// Returns the kinds of the edits in a list, ignoring any undo edits
+    ///
+    fn for_edits(edits: &Vec<AnimationEdit>) -> Vec<EditKind> {
+        edits.iter()
+            .filter_map(|edit| match edit {
+                AnimationEdit::Undo(_)                      => None,
+                AnimationEdit::Layer(layer_id, edit)        => Some(EditKind::Layer(*layer_id, mem::discriminant(edit))),
+                AnimationEdit::Element(element_ids, edit)   => Some(EditKind::Element(element_ids.clone(), mem::discriminant(edit))),
+                AnimationEdit::Motion(element_id, edit)     => Some(EditKind::Motion(*element_id, mem::discriminant(edit))),
+                other                                       => Some(EditKind::Other(mem::discriminant(other)))
+            })
+            .collect()
+    }
+}
 
 ///
 /// A log of undo elements
@@ -15,6 +46,18 @@ pub struct UndoLog {
 
     /// Steps that have been undone and which can be re-done
     redo: Vec<UndoStep>,
+
+    /// If edits are being coalesced, the maximum time between edits that are merged into the same undo step
+    coalesce_window: Option<Duration>,
+
+    /// The kind of the most recent edit that can be coalesced with later edits, and when it was retired
+    last_coalesced_edit: Option<(Vec<EditKind>, Instant)>,
+
+    /// The named checkpoints, and the number of undo steps that were in the log when they were set
+    checkpoints: HashMap<String, usize>,
+
+    /// The checkpoints that were undone and then discarded by later edits
+    invalidated_checkpoints: HashSet<String>,
 }
 
 impl UndoLog {
@@ -23,9 +66,13 @@ impl UndoLog {
     ///
     pub fn new() -> UndoLog {
         UndoLog {
-            undoing:    false,
-            undo:       vec![],
-            redo:       vec![],
+            undoing:                    false,
+            undo:                       vec![],
+            redo:                       vec![],
+            coalesce_window:            None,
+            last_coalesced_edit:        None,
+            checkpoints:                HashMap::new(),
+            invalidated_checkpoints:    HashSet::new(),
         }
     }
 
@@ -48,6 +95,13 @@ impl UndoLog {
         self.redo.len()
     }
 
+    ///
+    /// The number of undo steps that contain edits (the log can contain empty steps, which are skipped when undoing)
+    ///
+    fn num_committed_steps(&self) -> usize {
+        self.undo.iter().filter(|step| !step.is_empty()).count()
+    }
+
     ///
     /// Indicates that we're about to start an undo or a redo action
     ///
@@ -62,6 +116,49 @@ impl UndoLog {
         self.undoing = false;
     }
 
+    ///
+    /// Starts coalescing edits: edits of the same kind that are retired within the specified time of each other are
+    /// merged into a single undo step, even if there are `FinishAction` edits between them
+    ///
+    /// Edits are the same kind if they make the same type of change to the same layer or elements (eg, repeatedly
+    /// setting the control points of a particular element). Coalescing continues until `end_coalesce()` is called,
+    /// and an edit of a different kind or a call to `commit_undo_group()` starts a new undo step.
+    ///
+    pub fn begin_coalesce(&mut self, window: Duration) {
+        self.coalesce_window        = Some(window);
+        self.last_coalesced_edit    = None;
+    }
+
+    ///
+    /// Stops coalescing edits, so every action becomes a separate undo step again
+    ///
+    pub fn end_coalesce(&mut self) {
+        self.commit_undo_group();
+        self.coalesce_window = None;
+    }
+
+    ///
+    /// Finishes the current undo step, so that the next edit will start a new one even if it could be coalesced with the
+    /// previous edit
+    ///
+    pub fn commit_undo_group(&mut self) {
+        self.last_coalesced_edit = None;
+
+        if self.undo.last().map(|last_step| !last_step.is_empty()).unwrap_or(false) {
+            self.undo.push(UndoStep::new());
+        }
+    }
+
+    ///
+    /// True if an edit of the specified kind retired at the specified time should be added to the same undo step as the previous edit
+    ///
+    fn continues_coalesced_edit(&self, edit_kind: &Vec<EditKind>, when: Instant) -> bool {
+        match (self.coalesce_window, &self.last_coalesced_edit) {
+            (Some(window), Some((last_kind, last_time)))    => !edit_kind.is_empty() && edit_kind == last_kind && when.duration_since(*last_time) <= window,
+            _                                               => false
+        }
+    }
+
     ///
     /// Retires an edit to this undo log
     ///
@@ -95,8 +192,37 @@ impl UndoLog {
             self.undo.push(UndoStep::new());
         }
 
-        // Any redo actions are destroyed when a new action is created
-        self.redo.drain(..);
+        // Any redo actions are destroyed when a new action is created, along with the checkpoints that were set after them
+        if !self.redo.is_empty() {
+            let num_steps   = self.num_committed_steps();
+            let discarded   = self.checkpoints.iter()
+                .filter(|(_, position)| **position > num_steps)
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+
+            for name in discarded {
+                self.checkpoints.remove(&name);
+                self.invalidated_checkpoints.insert(name);
+            }
+
+            self.redo.drain(..);
+        }
+
+        // When coalescing, an edit that is the same kind as the previous one is added to the previous step (instead of
+        // the empty step started by the 'finish action' that followed it)
+        let now         = Instant::now();
+        let edit_kind   = EditKind::for_edits(&committed_edits);
+
+        if self.continues_coalesced_edit(&edit_kind, now) {
+            if self.undo.len() > 1 && self.undo.last().map(|last_step| last_step.is_empty()).unwrap_or(false) {
+                self.undo.pop();
+            }
+        }
+
+        // Edits that are only undo edits (eg, 'finish action') don't affect coalescing
+        if self.coalesce_window.is_some() && !edit_kind.is_empty() {
+            self.last_coalesced_edit = Some((edit_kind, now));
+        }
 
         // Determine if the edit finishes an action group
         let finishes_action_group = committed_edits.iter().any(|edit| match edit {
@@ -128,6 +254,9 @@ impl UndoLog {
         // Add as a redo action
         self.redo.push(most_recent_action);
 
+        // Future edits can't be coalesced with the step that was undone
+        self.last_coalesced_edit = None;
+
         // Add a new action group to the undo list so if there are any future actions, they won't extend an existing one
         self.undo.push(UndoStep::new());
 
@@ -144,7 +273,65 @@ impl UndoLog {
 
         // Add as an undo action
         self.undo.push(next_redo);
+        self.last_coalesced_edit = None;
 
         Some(redo_edit)
     }
+
+    ///
+    /// Marks the current point in the undo history with a name, so it can be returned to with `undo_to_checkpoint()`
+    ///
+    /// This finishes the current undo step, and replaces any existing checkpoint with the same name.
+    ///
+    pub fn set_checkpoint(&mut self, name: String) {
+        self.commit_undo_group();
+
+        let position = self.num_committed_steps();
+        self.invalidated_checkpoints.remove(&name);
+        self.checkpoints.insert(name, position);
+    }
+
+    ///
+    /// Pops all of the actions performed since the named checkpoint was set and moves them to the redo stack. Returns a
+    /// single edit that will undo all of the actions.
+    ///
+    /// The actions can be redone one at a time afterwards. This returns an error if the checkpoint doesn't exist, if it
+    /// was discarded (by undoing past it and then performing new edits), or if there's nothing to undo.
+    ///
+    pub fn undo_to_checkpoint(&mut self, name: &str) -> Result<UndoEdit, UndoFailureReason> {
+        let position = match self.checkpoints.get(name) {
+            Some(position)                                          => *position,
+            None if self.invalidated_checkpoints.contains(name)    => { return Err(UndoFailureReason::CheckpointInvalidated); }
+            None                                                    => { return Err(UndoFailureReason::UnknownCheckpoint); }
+        };
+
+        if self.num_committed_steps() <= position {
+            return Err(UndoFailureReason::NothingToUndo);
+        }
+
+        // Pop the steps since the checkpoint, most recent first
+        let mut undone_steps = vec![];
+        while self.num_committed_steps() > position {
+            let step = self.undo.pop().unwrap();
+
+            if !step.is_empty() {
+                undone_steps.push(step);
+            }
+        }
+
+        // Combine the steps into a single step so they can be undone in one go
+        let mut combined_step = UndoStep::new();
+        for step in undone_steps.iter().rev() {
+            combined_step.append(step.clone());
+        }
+
+        // Add as redo actions (so the oldest step is redone first)
+        self.redo.extend(undone_steps);
+
+        // Start a new action group for any future actions
+        self.undo.push(UndoStep::new());
+        self.last_coalesced_edit = None;
+
+        Ok(combined_step.undo_edit())
+    }
 }
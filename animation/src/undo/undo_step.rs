@@ -37,12 +37,22 @@ impl UndoStep {
         self.edits.push(edit);
     }
 
+    ///
+    /// Adds the edits from another step to the end of this one
+    ///
+    pub fn append(&mut self, step: UndoStep) {
+        self.edits.extend(step.edits);
+    }
+
     ///
     /// Creates the undo edit for this step
     ///
+    /// The edits are reversed starting with the most recent one, so a step containing several edits is restored to the
+    /// state from before the first of them
+    ///
     pub fn undo_edit(&self) -> UndoEdit {
         let original_actions    = self.edits.iter().flat_map(|edit| edit.committed_edits().iter().cloned().collect::<Vec<_>>()).collect();
-        let undo_actions        = self.edits.iter().flat_map(|edit| edit.reverse_edits().iter().cloned().collect::<Vec<_>>()).collect();
+        let undo_actions        = self.edits.iter().rev().flat_map(|edit| edit.reverse_edits().iter().cloned().collect::<Vec<_>>()).collect();
         let original_actions    = Arc::new(original_actions);
         let undo_actions        = Arc::new(undo_actions);
 
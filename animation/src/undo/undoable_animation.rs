@@ -95,6 +95,32 @@ impl<Anim: 'static+Unpin+EditableAnimation> UndoableAnimation<Anim> {
     /// Undoes the last action performed on this animation
     ///
     pub fn undo<'a>(&'a self) -> impl 'a + Future<Output=Result<(), UndoFailureReason>> {
+        self.perform_undo_from_log(|undo_log| undo_log.undo().ok_or(UndoFailureReason::NothingToUndo))
+    }
+
+    ///
+    /// Marks the current point in the undo history with a name, so it can be returned to with `undo_to_checkpoint()`
+    ///
+    pub fn set_checkpoint(&self, name: String) {
+        self.core.desync(move |core| core.undo_log.set_checkpoint(name));
+    }
+
+    ///
+    /// Undoes all of the actions performed since the named checkpoint was set, as a single undo operation
+    ///
+    /// The actions can be redone one at a time afterwards. This fails if the checkpoint doesn't exist or has been
+    /// discarded by undoing past it and then performing new edits.
+    ///
+    pub fn undo_to_checkpoint<'a>(&'a self, name: &str) -> impl 'a + Future<Output=Result<(), UndoFailureReason>> {
+        let name = name.to_string();
+        self.perform_undo_from_log(move |undo_log| undo_log.undo_to_checkpoint(&name))
+    }
+
+    ///
+    /// Performs an undo edit retrieved from the undo log
+    ///
+    fn perform_undo_from_log<'a, TFetchUndo>(&'a self, fetch_undo: TFetchUndo) -> impl 'a + Future<Output=Result<(), UndoFailureReason>>
+    where TFetchUndo: 'static+Send+FnOnce(&mut UndoLog) -> Result<UndoEdit, UndoFailureReason> {
         async move {
             // Scheduling on the animation desync will prevent any further edits from occurring while we're performing the undo
             let core = self.core.clone();
@@ -117,19 +143,20 @@ impl<Anim: 'static+Unpin+EditableAnimation> UndoableAnimation<Anim> {
                     }
 
                     // Fetch the undo action that we're about to perform
-                    let undo_edit = core.future_sync(|core| async move {
+                    let undo_edit = core.future_sync(move |core| async move {
                         core.undo_log.start_undoing();
-                        core.undo_log.undo()
+                        fetch_undo(&mut core.undo_log)
                     }.boxed()).await.unwrap();
 
-                    let undo_edit = if let Some(undo_edit) = undo_edit { 
-                        undo_edit 
-                    } else { 
-                        core.future_sync(|core| async move { 
-                            core.undo_log.finish_undoing(); 
-                            core.update_undo_log_size().await;
-                        }.boxed()).await.unwrap();
-                        return Err(UndoFailureReason::NothingToUndo); 
+                    let undo_edit = match undo_edit {
+                        Ok(undo_edit)   => undo_edit,
+                        Err(failure)    => {
+                            core.future_sync(|core| async move { 
+                                core.undo_log.finish_undoing(); 
+                                core.update_undo_log_size().await;
+                            }.boxed()).await.unwrap();
+                            return Err(failure);
+                        }
                     };
 
                     // Carry out the undo action on the animation
@@ -219,6 +246,31 @@ impl<Anim: 'static+Unpin+EditableAnimation> UndoableAnimation<Anim> {
         }
     }
 
+    ///
+    /// Starts merging edits of the same kind that are retired within the specified window of each other into a single
+    /// undo step (eg, so that dragging a control point can be undone in one go)
+    ///
+    pub fn begin_coalesce(&self, window: Duration) {
+        self.core.desync(move |core| core.undo_log.begin_coalesce(window));
+    }
+
+    ///
+    /// Stops merging edits into a single undo step
+    ///
+    pub fn end_coalesce(&self) {
+        self.core.desync(|core| core.undo_log.end_coalesce());
+    }
+
+    ///
+    /// Finishes the current undo step, so the next edit starts a new one even if it could be coalesced with the previous edit
+    ///
+    /// This applies to the edits that have been retired when it's called: wait for the edit publisher to be empty first
+    /// if there are edits that should be part of the current step.
+    ///
+    pub fn commit_undo_group(&self) {
+        self.core.desync(|core| core.undo_log.commit_undo_group());
+    }
+
     ///
     /// Retrieves a stream that tracks the size of the undo log (this is an expiring stream, so backpressure will cause
     /// updates to be discarded: ie, reads will always return the lastest value)
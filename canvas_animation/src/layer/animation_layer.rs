@@ -132,6 +132,13 @@ impl AnimationLayer {
         self.layer_state.set_time(drawing_time);
     }
 
+    ///
+    /// Sets the opacity keyframes for paths added to this layer (or None if they should always be opaque)
+    ///
+    pub fn set_opacity(&mut self, opacity: Option<Arc<OpacityKeyFrames>>) {
+        self.layer_state.set_opacity(opacity);
+    }
+
     ///
     /// Adds a new path to this layer
     ///
@@ -316,6 +323,20 @@ impl<'a> AnimationLayerContext<'a> {
         // Set the time for future drawing instructions
         self.animation_layer.set_time(time);
     }
+
+    ///
+    /// Updates the opacity keyframes for the current set of drawing (None to always draw it fully opaque)
+    ///
+    #[inline]
+    pub fn set_opacity(&mut self, opacity: Option<Arc<OpacityKeyFrames>>) {
+        // Flush the cache so the existing drawing keeps its opacity
+        if self.cache.len() > 0 {
+            self.animation_layer.draw(self.cache.drain(..));
+        }
+
+        // Set the opacity for future drawing instructions
+        self.animation_layer.set_opacity(opacity);
+    }
 }
 
 impl<'a> Drop for AnimationLayerContext<'a> {
@@ -1,4 +1,5 @@
 use crate::path::animation_path_attributes::*;
+use crate::path::opacity_keyframes::*;
 
 use flo_canvas::*;
 use flo_curves::bezier::path::*;
@@ -18,6 +19,9 @@ pub struct AnimationPath {
     /// The attributes describe how this path is rendered
     pub attributes: AnimationPathAttribute,
 
+    /// If the opacity of this path changes over time, the keyframes describing how it changes
+    pub opacity: Option<Arc<OpacityKeyFrames>>,
+
     /// The path that will be rendered by this animation
     pub path: Arc<Vec<SimpleBezierPath>>
 }
@@ -125,8 +129,8 @@ impl AnimationPath {
         AnimationPath {
             appearance_time:    appearance_time,
             attributes:         attributes,
+            opacity:            None,
             path:               Arc::new(paths)
-
         }
     }
 
@@ -169,6 +173,7 @@ impl AnimationPath {
         AnimationPath {
             appearance_time:    self.appearance_time,
             attributes:         attributes,
+            opacity:            self.opacity.clone(),
             path:               Arc::new(offset_path)
         }
     }
@@ -218,6 +223,7 @@ impl AnimationPath {
         AnimationPath {
             appearance_time:    self.appearance_time,
             attributes:         attributes,
+            opacity:            self.opacity.clone(),
             path:               Arc::new(offset_path)
         }
     }
@@ -243,6 +249,7 @@ impl AnimationPath {
         AnimationPath {
             appearance_time:    self.appearance_time,
             attributes:         self.attributes,
+            opacity:            self.opacity.clone(),
             path:               new_path
         }
     }
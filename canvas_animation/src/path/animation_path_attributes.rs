@@ -20,3 +20,32 @@ pub enum AnimationPathAttribute {
     /// Path is filled with the specified gradient
     FillGradient(BlendMode, GradientId, (f32, f32), (f32, f32), Option<Transform2D>, WindingRule)
 }
+
+///
+/// Multiplies the alpha component of a colour by an opacity value
+///
+#[inline]
+fn fade_color(color: Color, opacity: f32) -> Color {
+    let (r, g, b, a) = color.to_rgba_components();
+    Color::Rgba(r, g, b, a * opacity)
+}
+
+impl AnimationPathAttribute {
+    ///
+    /// Returns these attributes with the opacity of the colour multiplied by the specified value
+    ///
+    /// Textures and gradients are left unchanged, as their opacity is defined elsewhere.
+    ///
+    pub fn with_opacity(self, opacity: f32) -> AnimationPathAttribute {
+        use self::AnimationPathAttribute::*;
+
+        match self {
+            Stroke(blend_mode, width, color, join, cap)         => Stroke(blend_mode, width, fade_color(color, opacity), join, cap),
+            StrokePixels(blend_mode, width, color, join, cap)   => StrokePixels(blend_mode, width, fade_color(color, opacity), join, cap),
+            Fill(blend_mode, color, winding_rule)               => Fill(blend_mode, fade_color(color, opacity), winding_rule),
+
+            FillTexture(..)                                     => self,
+            FillGradient(..)                                    => self
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::path::layer_state::*;
 use crate::path::animation_path::*;
+use crate::path::opacity_keyframes::*;
 
 use flo_canvas::*;
 
@@ -34,6 +35,13 @@ impl LayerDrawingToPaths {
         self.state.current_time = drawing_time;
     }
 
+    ///
+    /// Sets the opacity keyframes for the paths added to this layer (or None if they should always be opaque)
+    ///
+    pub fn set_opacity(&mut self, opacity: Option<Arc<OpacityKeyFrames>>) {
+        self.state.current_opacity = opacity;
+    }
+
     ///
     /// Sends some drawing instructions to this layer
     ///
@@ -64,7 +72,11 @@ impl LayerDrawingToPaths {
                         // Turn the fill state into attributes
                         let attributes = (&self.state.fill).into_attributes(self.state.blend_mode);
 
-                        return Some(AnimationPath::from_path_ops(path.iter(), self.state.current_time, attributes));
+                        // Generate the path with the opacity keyframes that are in effect
+                        let mut path    = AnimationPath::from_path_ops(path.iter(), self.state.current_time, attributes);
+                        path.opacity    = self.state.current_opacity.clone();
+
+                        return Some(path);
                     },
                     Stroke                                          => {
                         // Retrieve the current path (re-use if already cached)
@@ -79,7 +91,11 @@ impl LayerDrawingToPaths {
                         // Turn the stroke state into attributes
                         let attributes = (&self.state.stroke).into_attributes(self.state.blend_mode);
 
-                        return Some(AnimationPath::from_path_ops(path.iter(), self.state.current_time, attributes));
+                        // Generate the path with the opacity keyframes that are in effect
+                        let mut path    = AnimationPath::from_path_ops(path.iter(), self.state.current_time, attributes);
+                        path.opacity    = self.state.current_opacity.clone();
+
+                        return Some(path);
                     },
 
                     StrokeColor(stroke_color)                       => { self.state.stroke.color        = stroke_color; },
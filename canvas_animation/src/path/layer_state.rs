@@ -1,4 +1,5 @@
 use crate::path::animation_path_attributes::*;
+use crate::path::opacity_keyframes::*;
 
 use flo_canvas::*;
 
@@ -13,6 +14,9 @@ pub struct LayerState {
     /// The current time that rendering is occurring
     pub current_time:   Duration,
 
+    /// The opacity keyframes to apply to paths that are being rendered (None if the paths are always opaque)
+    pub current_opacity: Option<Arc<OpacityKeyFrames>>,
+
     /// The path that is currently defined for this layer
     pub current_path:   Vec<PathOp>,
 
@@ -100,6 +104,7 @@ impl Default for LayerState {
     fn default() -> LayerState {
         LayerState {
             current_time:   Duration::from_millis(0),
+            current_opacity: None,
             current_path:   vec![],
             cached_path:    None,
             stroke:         StrokeState::default(),
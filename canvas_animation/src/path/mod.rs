@@ -3,10 +3,12 @@ mod region_path;
 mod animation_path;
 mod path_in_region;
 mod drawing_to_path;
+mod opacity_keyframes;
 mod animation_path_attributes;
 
 pub use self::region_path::*;
 pub use self::animation_path::*;
 pub use self::path_in_region::*;
 pub use self::drawing_to_path::*;
+pub use self::opacity_keyframes::*;
 pub use self::animation_path_attributes::*;
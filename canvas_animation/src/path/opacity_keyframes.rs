@@ -0,0 +1,137 @@
+use serde::{Serialize, Deserialize};
+
+use std::time::{Duration};
+
+///
+/// Describes how the opacity and visibility of a set of paths change over time
+///
+/// Opacity is interpolated linearly between keyframes, and holds the value of the nearest keyframe before the first
+/// or after the last keyframe. Visibility is not interpolated: it changes at each visibility keyframe and stays
+/// the same until the next one. Paths are fully opaque and visible at times where there are no keyframes.
+///
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct OpacityKeyFrames {
+    /// The opacity keyframes, ordered by time
+    opacity: Vec<(Duration, f64)>,
+
+    /// The visibility keyframes, ordered by time
+    visibility: Vec<(Duration, bool)>
+}
+
+impl OpacityKeyFrames {
+    ///
+    /// Creates a new set of opacity keyframes, with no keyframes set
+    ///
+    pub fn new() -> OpacityKeyFrames {
+        OpacityKeyFrames::default()
+    }
+
+    ///
+    /// True if there are no keyframes
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.opacity.is_empty() && self.visibility.is_empty()
+    }
+
+    ///
+    /// The opacity keyframes, ordered by time
+    ///
+    pub fn opacity_keyframes(&self) -> &Vec<(Duration, f64)> {
+        &self.opacity
+    }
+
+    ///
+    /// The visibility keyframes, ordered by time
+    ///
+    pub fn visibility_keyframes(&self) -> &Vec<(Duration, bool)> {
+        &self.visibility
+    }
+
+    ///
+    /// Sets the opacity at a particular time (replacing any existing opacity keyframe at that time)
+    ///
+    pub fn set_opacity(&mut self, when: Duration, opacity: f64) {
+        let opacity = opacity.max(0.0).min(1.0);
+
+        match self.opacity.binary_search_by_key(&when, |(time, _)| *time) {
+            Ok(idx)     => { self.opacity[idx].1 = opacity; }
+            Err(idx)    => { self.opacity.insert(idx, (when, opacity)); }
+        }
+    }
+
+    ///
+    /// Sets whether or not the paths are visible from a particular time (replacing any existing visibility keyframe at that time)
+    ///
+    pub fn set_visibility(&mut self, when: Duration, visible: bool) {
+        match self.visibility.binary_search_by_key(&when, |(time, _)| *time) {
+            Ok(idx)     => { self.visibility[idx].1 = visible; }
+            Err(idx)    => { self.visibility.insert(idx, (when, visible)); }
+        }
+    }
+
+    ///
+    /// Returns a copy of these keyframes with all of the times moved earlier by the specified offset
+    ///
+    /// Keyframes that would end up before time 0 are moved to time 0 (with later keyframes taking priority if several
+    /// are moved to the same time)
+    ///
+    pub fn earlier_by(&self, offset: Duration) -> OpacityKeyFrames {
+        let mut result = OpacityKeyFrames::new();
+
+        for (when, opacity) in self.opacity.iter() {
+            result.set_opacity(when.checked_sub(offset).unwrap_or(Duration::from_millis(0)), *opacity);
+        }
+
+        for (when, visible) in self.visibility.iter() {
+            result.set_visibility(when.checked_sub(offset).unwrap_or(Duration::from_millis(0)), *visible);
+        }
+
+        result
+    }
+
+    ///
+    /// Returns the interpolated opacity at the specified time
+    ///
+    pub fn opacity_at_time(&self, when: Duration) -> f64 {
+        // The index of the first keyframe after this time
+        let next_idx = match self.opacity.binary_search_by_key(&when, |(time, _)| *time) {
+            Ok(idx)     => { return self.opacity[idx].1; }
+            Err(idx)    => idx
+        };
+
+        match (next_idx.checked_sub(1).and_then(|idx| self.opacity.get(idx)), self.opacity.get(next_idx)) {
+            (None, None)                                    => 1.0,
+            (Some((_, opacity)), None)                      => *opacity,
+            (None, Some((_, opacity)))                      => *opacity,
+            (Some((start, from)), Some((end, to)))          => {
+                let t = (when - *start).as_secs_f64() / (*end - *start).as_secs_f64();
+                from + (to - from) * t
+            }
+        }
+    }
+
+    ///
+    /// Returns whether or not the paths are visible at the specified time
+    ///
+    pub fn is_visible_at_time(&self, when: Duration) -> bool {
+        // The value of the last keyframe at or before this time (or the first keyframe if the time is before all of them)
+        let idx = match self.visibility.binary_search_by_key(&when, |(time, _)| *time) {
+            Ok(idx)     => idx,
+            Err(idx)    => idx.saturating_sub(1)
+        };
+
+        self.visibility.get(idx).map(|(_, visible)| *visible).unwrap_or(true)
+    }
+
+    ///
+    /// Returns the alpha multiplier to use for rendering at the specified time (0.0 if the paths are hidden)
+    ///
+    pub fn alpha_at_time(&self, when: Duration) -> f64 {
+        if self.is_visible_at_time(when) {
+            self.opacity_at_time(when)
+        } else {
+            0.0
+        }
+    }
+}
+
@@ -121,6 +121,16 @@ impl AnimationRegionContent {
             // Paths that are not visible at this time are skipped
             if path.appearance_time > time { continue; }
 
+            // Paths with opacity keyframes are faded according to the time (or skipped if they're hidden)
+            let attributes = if let Some(opacity) = &path.opacity {
+                let alpha = opacity.alpha_at_time(time) as f32;
+                if alpha <= 0.0 { continue; }
+
+                path.attributes.with_opacity(alpha)
+            } else {
+                path.attributes
+            };
+
             // Load the path
             drawing.push(Draw::Path(PathOp::NewPath));
             drawing.extend(path.to_path_ops().map(|pathop| Draw::Path(pathop)));
@@ -128,7 +138,7 @@ impl AnimationRegionContent {
             // Apply any changed attributes for these paths, and render them
             use self::AnimationPathAttribute::*;
 
-            match path.attributes {
+            match attributes {
                 Stroke(blend_mode, width, colour, join, cap) => {
                     if any_blend_mode != Some(blend_mode) {
                         any_blend_mode = Some(blend_mode);
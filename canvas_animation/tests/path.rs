@@ -3,6 +3,7 @@ use flo_curves::arc::*;
 use flo_curves::bezier::path::*;
 use flo_canvas_animation::*;
 
+use std::sync::*;
 use std::time::{Duration};
 
 #[test]
@@ -97,3 +98,57 @@ pub fn simple_circle_outside_circle() {
 
     assert!(circle_overlaps == PathRegionType::OutsideRegion);
 }
+
+#[test]
+pub fn opacity_is_interpolated_between_keyframes() {
+    let mut keyframes = OpacityKeyFrames::new();
+    keyframes.set_opacity(Duration::from_millis(1000), 1.0);
+    keyframes.set_opacity(Duration::from_millis(0), 0.0);
+
+    assert!(keyframes.opacity_at_time(Duration::from_millis(0)) == 0.0);
+    assert!((keyframes.opacity_at_time(Duration::from_millis(250)) - 0.25).abs() < 0.0001);
+    assert!(keyframes.opacity_at_time(Duration::from_millis(1000)) == 1.0);
+    assert!(keyframes.opacity_at_time(Duration::from_millis(2000)) == 1.0);
+}
+
+#[test]
+pub fn visibility_is_not_interpolated() {
+    let mut keyframes = OpacityKeyFrames::new();
+    keyframes.set_visibility(Duration::from_millis(100), false);
+    keyframes.set_visibility(Duration::from_millis(200), true);
+
+    assert!(!keyframes.is_visible_at_time(Duration::from_millis(0)));
+    assert!(!keyframes.is_visible_at_time(Duration::from_millis(199)));
+    assert!(keyframes.is_visible_at_time(Duration::from_millis(200)));
+    assert!(keyframes.alpha_at_time(Duration::from_millis(150)) == 0.0);
+    assert!(keyframes.alpha_at_time(Duration::from_millis(250)) == 1.0);
+}
+
+#[test]
+pub fn paths_pick_up_opacity_keyframes() {
+    let mut drawing         = vec![];
+    let mut drawing_to_path = LayerDrawingToPaths::new();
+    let mut keyframes       = OpacityKeyFrames::new();
+
+    keyframes.set_opacity(Duration::from_millis(0), 0.0);
+    keyframes.set_opacity(Duration::from_millis(1000), 1.0);
+    drawing_to_path.set_opacity(Some(Arc::new(keyframes.clone())));
+
+    drawing.circle(100.0, 200.0, 50.0);
+    drawing.fill_color(Color::Rgba(0.3, 0.4, 0.5, 0.6));
+    drawing.fill();
+
+    let paths               = drawing_to_path.draw(drawing).collect::<Vec<_>>();
+
+    assert!(paths.len()                 == 1);
+    assert!(paths[0].opacity.as_deref() == Some(&keyframes));
+
+    // The fill colour is faded according to the keyframes
+    let content             = AnimationRegionContent::from_paths(paths);
+    let fill_colors         = content.to_drawing(Duration::from_millis(500)).into_iter()
+        .filter_map(|draw| if let Draw::FillColor(color) = draw { Some(color) } else { None })
+        .collect::<Vec<_>>();
+
+    assert!(fill_colors.len() == 1);
+    assert!((fill_colors[0].to_rgba_components().3 - 0.3).abs() < 0.001);
+}
@@ -11,6 +11,7 @@ fn create_path(id_num: usize) -> AnimationPath {
     AnimationPath {
         appearance_time:    Duration::from_millis(0),
         attributes:         AnimationPathAttribute::Fill(BlendMode::SourceOver, Color::Rgba(0.0, 0.0, 0.0, 1.0), WindingRule::EvenOdd),
+        opacity:            None,
         path:               Arc::new(vec![(Coord2(id_num as _, id_num as _), vec![])])
     }
 }
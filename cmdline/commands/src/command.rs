@@ -63,5 +63,20 @@ pub enum FloCommand {
     ListElements,
 
     /// Writes out debugging SVG files for raycasting a particular element
-    RayCastToSvg(ElementId)
+    RayCastToSvg(ElementId),
+
+    /// Renders all of the keyframes of a layer into a grid of SVG images, labelled with the time of each frame
+    ContactSheet { layer: u64, cols: usize, cell_size: f64, output: String },
+
+    /// Writes the input animation as a JSON scene that can be played back by the web front-end
+    ExportScene(String),
+
+    /// Writes out the number of layers, keyframes, elements and edits in the input animation, along with the size of its file
+    Stats,
+
+    /// Renders every frame of the input animation, and fails if any frame panics or draws nothing when it has content
+    ValidateRender,
+
+    /// Rebuilds the input animation by replaying its edit log into the output animation, and reports any edits that refer to missing layers or elements
+    Repair
 }
@@ -70,6 +70,11 @@ fn run_command<'a>(command: FloCommand, output: &'a mut Publisher<FloCommandOutp
             FloCommand::SelectFrame(layer, when)        => { select_frame(output, state, layer, when).await; }
             FloCommand::ListElements                    => { list_elements(output, state).await; }
             FloCommand::RayCastToSvg(element_id)        => { raycast_to_svg(output, state, element_id).await?; }
+            FloCommand::ContactSheet { layer, cols, cell_size, output: ref output_name } => { contact_sheet(output, state, layer, cols, cell_size, output_name.clone()).await?; }
+            FloCommand::ExportScene(ref output_name)    => { export_scene(output, state, output_name.clone()).await; }
+            FloCommand::Stats                           => { stats(output, state).await; }
+            FloCommand::ValidateRender                  => { validate_render(output, state).await?; }
+            FloCommand::Repair                          => { repair(output, state).await?; }
         }
 
         // Finish the command
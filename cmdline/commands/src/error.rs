@@ -1,4 +1,5 @@
 use flo_animation::*;
+use flo_animation::serializer::{EDIT_FORMAT_VERSION};
 
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -17,11 +18,26 @@ pub enum CommandError {
     /// An edit on the specified line number could not be parsed
     CannotParseEdit(usize, String),
 
+    /// The edits were written using a newer version of the serialization format (line number, version)
+    UnsupportedEditFormat(usize, u64),
+
     /// The operation requires a frame to be selected
     NoFrameSelected,
 
     /// The element ID was not found
-    ElementNotFound(ElementId)
+    ElementNotFound(ElementId),
+
+    /// The layer ID was not found
+    LayerNotFound(u64),
+
+    /// The frames with the specified indexes could not be rendered
+    RenderFailed(Vec<usize>),
+
+    /// The edits with the specified indexes refer to layers or elements that don't exist
+    MissingReferences(Vec<usize>),
+
+    /// The edits with the specified indexes cannot be applied to the output animation
+    InvalidEdits(Vec<usize>)
 }
 
 impl Display for CommandError {
@@ -32,8 +48,13 @@ impl Display for CommandError {
             CouldNotOpenAnimation(name)     => write!(fmt, "Could not open animation '{}'", name),
             CouldNotCreateAnimation(name)   => write!(fmt, "Coult not create animation '{}'", name),
             CannotParseEdit(line, edit)     => write!(fmt, "{}: cannot parse edit '{}'", line, edit),
+            UnsupportedEditFormat(line, v)  => write!(fmt, "{}: edits are in format version {}, but only versions up to {} can be read", line, v, EDIT_FORMAT_VERSION),
             NoFrameSelected                 => write!(fmt, "A frame must be selected for this operation"),
-            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string()))
+            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string())),
+            LayerNotFound(id)               => write!(fmt, "Layer {} was not found", id),
+            RenderFailed(frames)            => write!(fmt, "{} frame(s) failed to render: {}", frames.len(), frames.iter().map(|frame| frame.to_string()).collect::<Vec<_>>().join(", ")),
+            MissingReferences(edits)        => write!(fmt, "{} edit(s) refer to missing layers or elements: {}", edits.len(), edits.iter().map(|edit| edit.to_string()).collect::<Vec<_>>().join(", ")),
+            InvalidEdits(edits)             => write!(fmt, "{} edit(s) cannot be applied to the output animation: {}", edits.len(), edits.iter().map(|edit| edit.to_string()).collect::<Vec<_>>().join(", "))
         }
     }
 }
@@ -89,6 +89,13 @@ impl CommandState {
         Arc::clone(&self.0.input_animation.1)
     }
 
+    ///
+    /// Retrieves the descriptor for where the input animation is stored
+    ///
+    pub fn input_descriptor(&self) -> StorageDescriptor {
+        self.0.input_animation.0.clone()
+    }
+
     ///
     /// Retrieves the current output animation for this state
     ///
@@ -48,33 +48,34 @@ impl StorageDescriptor {
     pub fn open_animation(&self, file_manager: &Arc<dyn FileManager>) -> Option<Arc<impl EditableAnimation>> {
         let storage = match self {
             StorageDescriptor::InMemory                 => SqliteAnimationStorage::new_in_memory().ok(),
-            StorageDescriptor::File(filename)           => SqliteAnimationStorage::open_file(&PathBuf::from(filename)).ok(),
+            _                                           => SqliteAnimationStorage::open_file(self.file_path(file_manager)?.as_path()).ok()
+        };
+
+        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
+        animation
+    }
+
+    ///
+    /// Returns the path of the file that this storage descriptor references (None for in-memory animations, or if the file is not in the catalog)
+    ///
+    pub fn file_path(&self, file_manager: &Arc<dyn FileManager>) -> Option<PathBuf> {
+        match self {
+            StorageDescriptor::InMemory                 => None,
+            StorageDescriptor::File(filename)           => Some(PathBuf::from(filename)),
 
             StorageDescriptor::CatalogNumber(num)       => {
                 let all_files       = file_manager.get_all_files();
-                let requested_file  = all_files.into_iter().nth(*num)?;
-                SqliteAnimationStorage::open_file(requested_file.as_path()).ok()
+                all_files.into_iter().nth(*num)
             }
 
             StorageDescriptor::CatalogName(filename)    => {
                 let all_files       = file_manager.get_all_files();
                 let filename        = filename.to_lowercase();
-                let mut result      = None;
-
-                for file in all_files {
-                    let full_name = file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string());
-                    if full_name.to_lowercase() == filename {
-                        result = SqliteAnimationStorage::open_file(file.as_path()).ok();
-                        break;
-                    }
-                }
 
-                result
+                all_files.into_iter()
+                    .find(|file| file_manager.display_name_for_path(file.as_path()).unwrap_or("<untitled>".to_string()).to_lowercase() == filename)
             }
-        };
-
-        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
-        animation
+        }
     }
 
     ///
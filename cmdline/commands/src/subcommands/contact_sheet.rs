@@ -0,0 +1,160 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+/// The maximum number of rows of keyframes to write to a single contact sheet
+const MAX_ROWS_PER_SHEET: usize = 8;
+
+/// The height of the label underneath each cell
+const LABEL_HEIGHT: f64 = 20.0;
+
+///
+/// Generates the SVG path data for a path
+///
+fn svg_path_data(path: &Path) -> String {
+    let mut data = String::new();
+
+    for component in path.elements() {
+        match component {
+            PathComponent::Move(point)              => { data.push_str(&format!("M {} {} ", point.position.0, point.position.1)); }
+            PathComponent::Line(point)              => { data.push_str(&format!("L {} {} ", point.position.0, point.position.1)); }
+            PathComponent::Bezier(target, cp1, cp2) => { data.push_str(&format!("C {} {} {} {} {} {} ", cp1.position.0, cp1.position.1, cp2.position.0, cp2.position.1, target.position.0, target.position.1)); }
+            PathComponent::Close                    => { data.push_str("Z "); }
+        }
+    }
+
+    data
+}
+
+///
+/// Generates the SVG for a single cell of a contact sheet
+///
+fn contact_sheet_cell(frame: &Arc<dyn Frame>, x: f64, y: f64, cell_size: f64, scale: f64) -> String {
+    let mut svg         = String::new();
+    let when            = frame.time_index();
+
+    // Frames are drawn scaled down into their cell (flipped as FlowBetween's y axis points upwards)
+    svg.push_str(&format!("  <g transform=\"translate({} {}) scale({} {})\">\n", x, y+cell_size, scale, -scale));
+
+    let mut properties  = Arc::new(VectorProperties::default());
+    for element in frame.vector_elements().into_iter().flatten() {
+        properties      = frame.apply_properties_for_element(&element, properties);
+
+        let (r, g, b, a)    = properties.brush_properties.color.to_rgba_components();
+        let fill            = format!("rgb({}, {}, {})", (r*255.0) as u8, (g*255.0) as u8, (b*255.0) as u8);
+
+        for path in element.to_path(&properties, PathConversion::Fastest).into_iter().flatten() {
+            svg.push_str(&format!("    <path d=\"{}\" fill=\"{}\" fill-opacity=\"{}\" />\n", svg_path_data(&path), fill, a * properties.brush_properties.opacity));
+        }
+    }
+
+    svg.push_str("  </g>\n");
+
+    // Border and label for the cell
+    svg.push_str(&format!("  <rect class=\"cell\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\" />\n", x, y, cell_size, cell_size));
+    svg.push_str(&format!("  <text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"12\" text-anchor=\"middle\">{}ms</text>\n", x + cell_size/2.0, y + cell_size + LABEL_HEIGHT*0.75, when.as_millis()));
+
+    svg
+}
+
+///
+/// Renders all of the keyframes in a layer of the input animation into a grid of SVG images
+///
+/// Each sheet has `cols` columns and at most `MAX_ROWS_PER_SHEET` rows: layers with more keyframes than will fit
+/// generate several files, named `<output>-1.svg`, `<output>-2.svg` etc. If everything fits in one sheet, the file
+/// is just called `<output>.svg`. Nothing is written for a layer with no keyframes.
+///
+pub fn contact_sheet<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, layer_id: u64, cols: usize, cell_size: f64, output_name: String) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    async move {
+        let animation       = state.input_animation();
+        let layer           = match animation.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { return Err(CommandError::LayerNotFound(layer_id)); }
+        };
+
+        // Fetch the frames to render
+        let key_frames      = layer.get_key_frames().collect::<Vec<Duration>>();
+
+        if key_frames.len() == 0 {
+            output.publish(FloCommandOutput::Message(format!("Layer {} has no keyframes", layer_id))).await;
+            return Ok(());
+        }
+
+        // Work out the layout of the sheets
+        let cols            = cols.max(1);
+        let per_sheet       = cols * MAX_ROWS_PER_SHEET;
+        let num_sheets      = (key_frames.len() + per_sheet - 1) / per_sheet;
+        let (width, height) = animation.size();
+        let scale           = cell_size / width.max(height).max(1.0);
+
+        output.publish(FloCommandOutput::StartTask(format!("Rendering {} keyframes", key_frames.len()))).await;
+
+        for (sheet_num, sheet_frames) in key_frames.chunks(per_sheet).enumerate() {
+            let filename    = if num_sheets == 1 { format!("{}.svg", output_name) } else { format!("{}-{}.svg", output_name, sheet_num+1) };
+            let rows        = (sheet_frames.len() + cols - 1) / cols;
+            let sheet_cols  = cols.min(sheet_frames.len());
+            let svg_width   = (sheet_cols as f64) * cell_size;
+            let svg_height  = (rows as f64) * (cell_size + LABEL_HEIGHT);
+
+            output.publish(FloCommandOutput::Message(format!("  Writing {}", filename))).await;
+            output.publish(FloCommandOutput::BeginOutput(filename)).await;
+            output.publish(FloCommandOutput::Output(format!("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>
+<svg width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" version=\"1.1\" xmlns=\"http://www.w3.org/2000/svg\">\n", svg_width, svg_height, svg_width, svg_height))).await;
+
+            for (index, when) in sheet_frames.iter().enumerate() {
+                let frame   = layer.get_frame_at_time(*when);
+                let x       = ((index % cols) as f64) * cell_size;
+                let y       = ((index / cols) as f64) * (cell_size + LABEL_HEIGHT);
+
+                output.publish(FloCommandOutput::Output(contact_sheet_cell(&frame, x, y, cell_size, scale))).await;
+                output.publish(FloCommandOutput::TaskProgress((sheet_num * per_sheet + index + 1) as f64, key_frames.len() as f64)).await;
+            }
+
+            output.publish(FloCommandOutput::Output("</svg>\n".to_string())).await;
+        }
+
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_harness::*;
+
+    use futures::executor;
+
+    #[test]
+    fn contact_sheet_for_three_keyframes() {
+        let mut state = CommandState::new();
+
+        state.output_animation().perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(100))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(200)))
+        ]);
+        state = state.read_from_write_side();
+
+        let (result, results)   = run_command_collecting_output(|publisher| executor::block_on(contact_sheet(publisher, &mut state, 1, 4, 64.0, "sheet".to_string())));
+        result.unwrap();
+
+        let filenames       = results.iter().filter_map(|output| if let FloCommandOutput::BeginOutput(name) = output { Some(name.clone()) } else { None }).collect::<Vec<_>>();
+        let svg             = results.iter().filter_map(|output| if let FloCommandOutput::Output(svg) = output { Some(svg.clone()) } else { None }).collect::<String>();
+
+        assert!(filenames == vec!["sheet.svg".to_string()]);
+        assert!(svg.matches("<rect class=\"cell\"").count() == 3);
+        assert!(svg.contains(">100ms</text>"));
+        assert!(svg.contains("width=\"192\""));
+    }
+}
@@ -4,6 +4,8 @@ use crate::output::*;
 
 use flo_stream::*;
 use flo_animation::*;
+use flo_animation::editor::*;
+use flo_animation::serializer::*;
 
 use futures::prelude::*;
 use std::marker::{Unpin};
@@ -17,6 +19,8 @@ where SourceStream: 'a+Unpin+Send+Stream<Item=char> {
         let mut source      = source.fuse();
         let mut line_number = 1;
         let mut edits       = state.edit_buffer().clone();
+        let first_new_edit  = edits.len();
+        let mut edit_lines  = vec![];
 
         // Read from the stream until it is exhausted
         output.publish(FloCommandOutput::StartTask("Deserialize edits".to_string())).await;
@@ -55,15 +59,28 @@ where SourceStream: 'a+Unpin+Send+Stream<Item=char> {
                         }
                     }
 
-                    // Attempt to deserialize the edit
-                    let animation_edit = AnimationEdit::deserialize(&mut edit.chars());
-                    let animation_edit = match animation_edit {
-                        Some(animation_edit)    => Ok(animation_edit),
-                        None                    => Err(CommandError::CannotParseEdit(line_number, edit))
-                    }?;
+                    match deserialize_edit_format_version(&mut edit.chars()) {
+                        // Files from newer versions of FlowBetween may encode their edits differently
+                        Some(Err(EditFormatError::UnsupportedVersion(version))) => {
+                            return Err(CommandError::UnsupportedEditFormat(line_number, version));
+                        }
+
+                        // All of the versions that can be read use the same encoding for their edits
+                        Some(Ok(_version))  => { }
+
+                        None                => {
+                            // Attempt to deserialize the edit
+                            let animation_edit = AnimationEdit::deserialize(&mut edit.chars());
+                            let animation_edit = match animation_edit {
+                                Some(animation_edit)    => Ok(animation_edit),
+                                None                    => Err(CommandError::CannotParseEdit(line_number, edit))
+                            }?;
 
-                    // Add to the edit buffer
-                    edits.push(animation_edit);
+                            // Add to the edit buffer
+                            edits.push(animation_edit);
+                            edit_lines.push(line_number);
+                        }
+                    }
 
                     // Edits are ended by a newline, so the line number must increase
                     line_number += 1;
@@ -71,6 +88,24 @@ where SourceStream: 'a+Unpin+Send+Stream<Item=char> {
             }
         }
 
+        // Check that the edits can be written to the output animation before adding them to the buffer
+        if let Err(errors) = validate_edits(&*state.output_animation(), &edits) {
+            for error in errors.iter() {
+                let message = if error.index >= first_new_edit {
+                    format!("{}: {:?}", edit_lines[error.index - first_new_edit], error.reason)
+                } else {
+                    format!("Edit {}: {:?}", error.index, error.reason)
+                };
+
+                output.publish(FloCommandOutput::Error(message)).await;
+            }
+
+            let mut edit_indexes = errors.iter().map(|error| error.index).collect::<Vec<_>>();
+            edit_indexes.dedup();
+
+            return Err(CommandError::InvalidEdits(edit_indexes));
+        }
+
         // Update the edit buffer with the values we just read
         *state = state.set_edit_buffer(edits);
 
@@ -0,0 +1,52 @@
+use crate::state::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+///
+/// Writes the input animation to a JSON scene file, which can be played back in a browser using `flo_canvas.play_scene()`
+///
+pub fn export_scene<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, output_name: String) -> impl 'a+Future<Output=()>+Send {
+    async move {
+        let animation   = state.input_animation();
+        let filename    = format!("{}.json", output_name);
+
+        output.publish(FloCommandOutput::Message(format!("Writing {}", filename))).await;
+        output.publish(FloCommandOutput::BeginOutput(filename)).await;
+        output.publish(FloCommandOutput::Output(export_json_scene(&*animation))).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_harness::*;
+
+    use futures::executor;
+
+    use std::time::Duration;
+
+    #[test]
+    fn export_scene_with_two_keyframes() {
+        let mut state = CommandState::new();
+
+        state.output_animation().perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(100)))
+        ]);
+        state = state.read_from_write_side();
+
+        let (_, results)    = run_command_collecting_output(|publisher| executor::block_on(export_scene(publisher, &mut state, "scene".to_string())));
+
+        let filenames       = results.iter().filter_map(|output| if let FloCommandOutput::BeginOutput(name) = output { Some(name.clone()) } else { None }).collect::<Vec<_>>();
+        let json            = results.iter().filter_map(|output| if let FloCommandOutput::Output(json) = output { Some(json.clone()) } else { None }).collect::<String>();
+
+        assert!(filenames == vec!["scene.json".to_string()]);
+        assert!(json.contains("\"start_ms\":0.0"));
+        assert!(json.contains("\"start_ms\":100.0"));
+    }
+}
@@ -4,14 +4,27 @@ mod elements;
 mod read_from;
 mod dump_catalog;
 mod select_frame;
+mod stats;
+mod validate_render;
+mod repair;
+mod contact_sheet;
+mod export_scene;
 mod write_to_catalog;
 mod set_catalog_folder;
 
+#[cfg(test)]
+mod test_harness;
+
 pub (super) use self::list::*;
 pub (super) use self::edits::*;
 pub (super) use self::elements::*;
 pub (super) use self::read_from::*;
 pub (super) use self::dump_catalog::*;
 pub (super) use self::select_frame::*;
+pub (super) use self::stats::*;
+pub (super) use self::validate_render::*;
+pub (super) use self::repair::*;
+pub (super) use self::contact_sheet::*;
+pub (super) use self::export_scene::*;
 pub (super) use self::write_to_catalog::*;
 pub (super) use self::set_catalog_folder::*;
@@ -0,0 +1,287 @@
+use super::edits::*;
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+use std::collections::{HashSet};
+
+///
+/// Finds the edits in an edit log that refer to a layer or an element that does not exist at the point where the edit is made
+///
+/// The result is a list of edit indexes along with a description of what's missing
+///
+fn find_missing_references(edits: &[AnimationEdit]) -> Vec<(usize, String)> {
+    let mut layers      = HashSet::new();
+    let mut elements    = HashSet::new();
+    let mut missing     = vec![];
+
+    for (index, edit) in edits.iter().enumerate() {
+        match edit {
+            AnimationEdit::AddNewLayer(layer_id)                => { layers.insert(*layer_id); }
+
+            AnimationEdit::RemoveLayer(layer_id)                => {
+                if !layers.remove(layer_id) {
+                    missing.push((index, format!("layer {}", layer_id)));
+                }
+            }
+
+            AnimationEdit::Layer(layer_id, layer_edit)          => {
+                if !layers.contains(layer_id) {
+                    missing.push((index, format!("layer {}", layer_id)));
+                }
+
+                // Layer edits create the elements that they use
+                elements.extend(layer_edit.used_element_ids().into_iter().filter_map(|element_id| element_id.id()));
+            }
+
+            AnimationEdit::Element(element_ids, element_edit)   => {
+                // The elements being edited must already exist
+                for element_id in element_ids.iter().filter_map(|element_id| element_id.id()) {
+                    if !elements.contains(&element_id) {
+                        missing.push((index, format!("element {}", element_id)));
+                    }
+                }
+
+                match element_edit {
+                    // Grouping creates a new element
+                    ElementEdit::Group(group_id, _) => {
+                        if let Some(group_id) = group_id.id() {
+                            elements.insert(group_id);
+                        }
+                    }
+
+                    // Deleted elements can't be referred to by later edits
+                    ElementEdit::Delete             => {
+                        for element_id in element_ids.iter().filter_map(|element_id| element_id.id()) {
+                            elements.remove(&element_id);
+                        }
+                    }
+
+                    // Other element edits refer to existing elements
+                    other                           => {
+                        for element_id in other.used_element_ids().into_iter().filter_map(|element_id| element_id.id()) {
+                            if !elements.contains(&element_id) {
+                                missing.push((index, format!("element {}", element_id)));
+                            }
+                        }
+                    }
+                }
+            }
+
+            AnimationEdit::Motion(motion_id, motion_edit)       => {
+                if let Some(motion_id) = motion_id.id() {
+                    match motion_edit {
+                        // Motions are elements of their own, which are created by the motion edits
+                        MotionEdit::Create  => { elements.insert(motion_id); }
+
+                        MotionEdit::Delete  => {
+                            if !elements.remove(&motion_id) {
+                                missing.push((index, format!("motion {}", motion_id)));
+                            }
+                        }
+
+                        _                   => {
+                            if !elements.contains(&motion_id) {
+                                missing.push((index, format!("motion {}", motion_id)));
+                            }
+                        }
+                    }
+                }
+            }
+
+            _                                                   => { }
+        }
+    }
+
+    missing
+}
+
+///
+/// Rebuilds the input animation by replaying its edit log into the output animation
+///
+/// This can be used to recover an animation whose element or layer data has become inconsistent with its edit log. The
+/// output animation should be a new animation: if it already contains edits, a new in-memory animation is created to
+/// write to instead. Any edits in the log that refer to missing layers or elements are reported, and generate an error
+/// once the repaired animation has been written.
+///
+pub fn repair<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use self::FloCommandOutput::*;
+
+        // Replay into a fresh animation
+        if state.output_animation().get_num_edits() != 0 {
+            let storage     = InMemoryStorage::new();
+            let animation   = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+
+            *state = state.set_output_animation(StorageDescriptor::InMemory, Arc::new(animation));
+            output.publish(Message("Output animation already contains edits: repairing to a new in-memory animation".to_string())).await;
+        }
+
+        // Read the edit log from the input animation
+        *state = state.clear_edit_buffer();
+        read_all_edits(output, state).await?;
+
+        // Report any edits that can't be replayed correctly
+        let missing = find_missing_references(state.edit_buffer());
+        for (index, description) in missing.iter() {
+            output.publish(Error(format!("Edit {}: refers to missing {}", index, description))).await;
+        }
+
+        // Replay the edits into the output animation
+        write_all_edits(output, state).await?;
+
+        if missing.len() == 0 {
+            output.publish(Message("Repaired animation".to_string())).await;
+            Ok(())
+        } else {
+            let mut edit_indexes = missing.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+            edit_indexes.dedup();
+
+            Err(CommandError::MissingReferences(edit_indexes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_harness::*;
+
+    use flo_canvas::*;
+    use futures::stream;
+    use futures::executor;
+
+    use std::time::{Duration};
+
+    fn animation_with_square(storage: &Arc<InMemoryStorage>) -> impl EditableAnimation {
+        let editor_storage  = Arc::clone(storage);
+        let animation       = create_animation_editor(move |commands| editor_storage.get_responses(commands).boxed());
+        let path            = Arc::new(vec![PathComponent::Move(PathPoint::new(0.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 10.0)), PathComponent::Close]);
+
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Assigned(98), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Assigned(99), BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), path)))
+        ]);
+
+        animation
+    }
+
+    fn run_repair(state: &mut CommandState) -> (Result<(), CommandError>, Vec<String>) {
+        let (result, results)   = run_command_collecting_output(|publisher| executor::block_on(repair(publisher, state)));
+
+        let errors              = results.into_iter()
+            .filter_map(|output| if let FloCommandOutput::Error(msg) = output { Some(msg) } else { None })
+            .collect();
+
+        (result, errors)
+    }
+
+    fn draws_something(animation: &dyn Animation) -> bool {
+        let frame       = animation.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+        let mut drawing = vec![];
+        frame.render_to(&mut drawing);
+
+        drawing.iter().any(|draw| match draw { Draw::Fill | Draw::Stroke => true, _ => false })
+    }
+
+    #[test]
+    fn repair_rebuilds_corrupted_element_table() {
+        let storage     = Arc::new(InMemoryStorage::new());
+        let animation   = animation_with_square(&storage);
+        drop(animation);
+
+        // Corrupt the element table without changing the edit log
+        executor::block_on(async {
+            storage.get_responses(stream::iter(vec![vec![StorageCommand::DetachElementFromLayer(100), StorageCommand::DeleteElement(100)]])).next().await
+        });
+
+        let editor_storage  = Arc::clone(&storage);
+        let corrupted       = create_animation_editor(move |commands| editor_storage.get_responses(commands).boxed());
+        assert!(!draws_something(&corrupted));
+
+        // Repairing should replay the edit log to restore the square (into a new animation, as the output animation has edits)
+        let mut state           = CommandState::new().set_output_animation(StorageDescriptor::InMemory, Arc::new(corrupted)).read_from_write_side();
+        let (result, errors)    = run_repair(&mut state);
+
+        assert!(result == Ok(()));
+        assert!(errors.len() == 0);
+        assert!(draws_something(&*state.read_from_write_side().input_animation()));
+    }
+
+    #[test]
+    fn repair_reports_missing_elements() {
+        let storage     = Arc::new(InMemoryStorage::new());
+        let animation   = animation_with_square(&storage);
+
+        // Edit an element that was never created
+        animation.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::Delete)]);
+
+        let mut state           = CommandState::new().set_output_animation(StorageDescriptor::InMemory, Arc::new(animation)).read_from_write_side();
+        let (result, errors)    = run_repair(&mut state);
+
+        assert!(result == Err(CommandError::MissingReferences(vec![5])));
+        assert!(errors == vec!["Edit 5: refers to missing element 200".to_string()]);
+
+        // The rest of the animation is still repaired
+        assert!(draws_something(&*state.read_from_write_side().input_animation()));
+    }
+
+    #[test]
+    fn repair_reports_edits_to_deleted_elements() {
+        let storage     = Arc::new(InMemoryStorage::new());
+        let animation   = animation_with_square(&storage);
+
+        // Delete the square, then try to delete it again
+        animation.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)]);
+        animation.perform_edits(vec![AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)]);
+
+        let mut state           = CommandState::new().set_output_animation(StorageDescriptor::InMemory, Arc::new(animation)).read_from_write_side();
+        let (result, errors)    = run_repair(&mut state);
+
+        assert!(result == Err(CommandError::MissingReferences(vec![6])));
+        assert!(errors == vec!["Edit 6: refers to missing element 100".to_string()]);
+    }
+
+    #[test]
+    fn motion_edits_refer_to_created_motions() {
+        let edits = vec![
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::SetOrigin(10.0, 20.0)),
+            AnimationEdit::Motion(ElementId::Assigned(2), MotionEdit::SetOrigin(10.0, 20.0)),
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::Delete),
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::SetType(MotionType::Reverse)),
+            AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::Delete)
+        ];
+
+        assert!(find_missing_references(&edits) == vec![
+            (3, "motion 2".to_string()),
+            (5, "motion 1".to_string()),
+            (6, "motion 1".to_string())
+        ]);
+    }
+
+    #[test]
+    fn deleted_elements_are_missing_from_later_edits() {
+        let edits = vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![])))),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.5)),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetOpacity(Duration::from_millis(0), 0.5))
+        ];
+
+        assert!(find_missing_references(&edits) == vec![(4, "element 100".to_string())]);
+    }
+}
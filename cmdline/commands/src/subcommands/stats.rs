@@ -0,0 +1,141 @@
+use crate::state::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+use std::fs;
+
+///
+/// Counts of the things that make up an animation
+///
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+struct AnimationStats {
+    layers:     usize,
+    key_frames: usize,
+    elements:   usize,
+    edits:      usize
+}
+
+///
+/// True if an element is something that's drawn (as opposed to something like a brush definition that affects how other elements are drawn)
+///
+pub (crate) fn is_drawn_element(element: &Vector) -> bool {
+    match element {
+        Vector::BrushDefinition(_) | Vector::BrushProperties(_) | Vector::Motion(_) | Vector::Transformation(_) | Vector::Opacity(_)  => false,
+        _                                                                                                                           => true
+    }
+}
+
+impl AnimationStats {
+    ///
+    /// Gathers the statistics for an animation
+    ///
+    /// Elements are counted from storage without loading the keyframes, so this is fast even for large animations
+    ///
+    fn for_animation(animation: &dyn Animation) -> AnimationStats {
+        let mut stats   = AnimationStats::default();
+
+        stats.edits     = animation.get_num_edits();
+
+        for layer_id in animation.get_layer_ids() {
+            let layer = match animation.get_layer_with_id(layer_id) {
+                Some(layer) => layer,
+                None        => { continue; }
+            };
+
+            stats.layers += 1;
+
+            for when in layer.get_key_frames() {
+                stats.key_frames    += 1;
+                stats.elements      += layer.get_num_elements_in_key_frame(when);
+            }
+        }
+
+        stats
+    }
+}
+
+///
+/// Writes out some statistics about the input animation
+///
+pub fn stats<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl 'a+Future<Output=()>+Send {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let animation   = state.input_animation();
+        let stats       = AnimationStats::for_animation(&*animation);
+
+        // Animations that are not stored in a file have no size
+        let file_size   = state.input_descriptor().file_path(&state.file_manager())
+            .and_then(|path| fs::metadata(path).ok())
+            .map(|metadata| format!("{} bytes", metadata.len()))
+            .unwrap_or("-".to_string());
+
+        output.publish(Message("Animation statistics:".to_string())).await;
+        output.publish(Message(format!("  Layers:    {}", stats.layers))).await;
+        output.publish(Message(format!("  Keyframes: {}", stats.key_frames))).await;
+        output.publish(Message(format!("  Elements:  {}", stats.elements))).await;
+        output.publish(Message(format!("  Edits:     {}", stats.edits))).await;
+        output.publish(Message(format!("  File size: {}", file_size))).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_harness::*;
+
+    use futures::executor;
+
+    use std::sync::*;
+    use std::time::{Duration};
+
+    fn stats_messages(state: &mut CommandState) -> Vec<String> {
+        let (_, results) = run_command_collecting_output(|publisher| executor::block_on(stats(publisher, state)));
+
+        results.into_iter()
+            .filter_map(|output| if let FloCommandOutput::Message(msg) = output { Some(msg) } else { None })
+            .collect()
+    }
+
+    #[test]
+    fn empty_animation_has_no_stats() {
+        let mut state   = CommandState::new();
+        let messages    = stats_messages(&mut state);
+
+        assert!(messages.contains(&"  Layers:    0".to_string()));
+        assert!(messages.contains(&"  Keyframes: 0".to_string()));
+        assert!(messages.contains(&"  Elements:  0".to_string()));
+        assert!(messages.contains(&"  Edits:     0".to_string()));
+        assert!(messages.contains(&"  File size: -".to_string()));
+    }
+
+    #[test]
+    fn stats_for_small_animation() {
+        let mut state   = CommandState::new();
+        let path        = Arc::new(vec![PathComponent::Move(PathPoint::new(0.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 10.0)), PathComponent::Close]);
+
+        state.output_animation().perform_edits(vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(100))),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), Arc::clone(&path)))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(100), PathEdit::CreatePath(ElementId::Assigned(101), Arc::clone(&path))))
+        ]);
+        let mut state   = state.read_from_write_side();
+
+        let messages    = stats_messages(&mut state);
+
+        assert!(messages.contains(&"  Layers:    2".to_string()));
+        assert!(messages.contains(&"  Keyframes: 3".to_string()));
+        assert!(messages.contains(&"  Elements:  2".to_string()));
+        assert!(messages.contains(&"  Edits:     9".to_string()));
+    }
+}
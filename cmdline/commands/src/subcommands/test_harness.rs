@@ -0,0 +1,21 @@
+use crate::output::*;
+
+use flo_stream::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+///
+/// Runs a command against a new publisher, returning its result along with everything it wrote to the output
+///
+pub fn run_command_collecting_output<TResult>(command: impl FnOnce(&mut Publisher<FloCommandOutput>) -> TResult) -> (TResult, Vec<FloCommandOutput>) {
+    let mut publisher   = Publisher::new(1000);
+    let results         = publisher.subscribe();
+
+    let result          = command(&mut publisher);
+    drop(publisher);
+
+    let output          = executor::block_on(async { results.collect::<Vec<_>>().await });
+
+    (result, output)
+}
@@ -0,0 +1,213 @@
+use super::stats::{is_drawn_element};
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_canvas::*;
+use flo_animation::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+use std::panic;
+use std::time::{Duration};
+
+///
+/// Why a frame failed to render
+///
+#[derive(Clone, PartialEq, Debug)]
+enum RenderFailureReason {
+    /// Rendering the frame panicked (with the specified message)
+    Panicked(String),
+
+    /// The frame contains elements but nothing was drawn
+    Empty
+}
+
+///
+/// Describes a layer that failed to render in a particular frame
+///
+#[derive(Clone, PartialEq, Debug)]
+struct RenderFailure {
+    frame:      usize,
+    when:       Duration,
+    layer_id:   u64,
+    reason:     RenderFailureReason
+}
+
+///
+/// Renders a frame to a list of drawing instructions
+///
+fn render_frame(frame: &Arc<dyn Frame>) -> Vec<Draw> {
+    let mut drawing = vec![];
+    frame.render_to(&mut drawing);
+    drawing
+}
+
+///
+/// Returns the message from a panic payload
+///
+fn panic_message(payload: Box<dyn std::any::Any+Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "<no message>".to_string()
+    }
+}
+
+///
+/// Renders every frame of every layer in an animation, returning the frames that failed to render
+///
+/// A frame with no elements that draw anything is expected to render nothing, so an empty rendering is only
+/// a failure if the frame has something to draw.
+///
+fn find_render_failures(animation: &dyn Animation, render: impl Fn(usize, &Arc<dyn Frame>) -> Vec<Draw>) -> Vec<RenderFailure> {
+    let frame_length    = animation.frame_length();
+    let num_frames      = if frame_length.as_nanos() == 0 { 0 } else { ((animation.duration().as_nanos() + frame_length.as_nanos() - 1) / frame_length.as_nanos()) as usize };
+    let layers          = animation.get_layer_ids().into_iter()
+        .filter_map(|layer_id| animation.get_layer_with_id(layer_id))
+        .collect::<Vec<_>>();
+
+    let mut failures    = vec![];
+
+    for frame_num in 0..num_frames {
+        let when = frame_length * (frame_num as u32);
+
+        for layer in layers.iter() {
+            let frame   = layer.get_frame_at_time(when);
+            let result  = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                let has_content = frame.vector_elements()
+                    .map(|mut elements| elements.any(|element| is_drawn_element(&element)))
+                    .unwrap_or(false);
+                let drawing     = render(frame_num, &frame);
+                let drew_shapes = drawing.iter().any(|draw| match draw { Draw::Fill | Draw::Stroke => true, _ => false });
+
+                has_content && !drew_shapes
+            }));
+
+            let reason  = match result {
+                Ok(false)       => { continue; }
+                Ok(true)        => RenderFailureReason::Empty,
+                Err(payload)    => RenderFailureReason::Panicked(panic_message(payload))
+            };
+
+            failures.push(RenderFailure { frame: frame_num, when: when, layer_id: layer.id(), reason: reason });
+        }
+    }
+
+    failures
+}
+
+///
+/// Renders every frame of the input animation and reports any that fail to render
+///
+/// This generates an error if any frame fails, so it can be used to detect rendering regressions.
+///
+pub fn validate_render<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    validate_render_with(output, state, |_frame_num, frame| render_frame(frame))
+}
+
+///
+/// Performs the validate_render command with a particular rendering function
+///
+fn validate_render_with<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, render: impl 'a+Send+Fn(usize, &Arc<dyn Frame>) -> Vec<Draw>) -> impl 'a+Future<Output=Result<(), CommandError>>+Send {
+    async move {
+        use self::FloCommandOutput::*;
+
+        let animation   = state.input_animation();
+
+        output.publish(StartTask("Rendering frames".to_string())).await;
+        let failures    = find_render_failures(&*animation, render);
+        output.publish(FinishTask).await;
+
+        if failures.len() == 0 {
+            output.publish(Message("All frames rendered successfully".to_string())).await;
+            return Ok(());
+        }
+
+        for failure in failures.iter() {
+            let reason = match &failure.reason {
+                RenderFailureReason::Panicked(msg)  => format!("panicked: {}", msg),
+                RenderFailureReason::Empty          => "nothing was drawn".to_string()
+            };
+
+            output.publish(Error(format!("Frame {} ({}ms), layer {}: {}", failure.frame, failure.when.as_millis(), failure.layer_id, reason))).await;
+        }
+
+        let mut failed_frames = failures.iter().map(|failure| failure.frame).collect::<Vec<_>>();
+        failed_frames.dedup();
+
+        Err(CommandError::RenderFailed(failed_frames))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::test_harness::*;
+
+    use futures::executor;
+
+    fn small_animation() -> CommandState {
+        let state   = CommandState::new();
+        let path    = Arc::new(vec![PathComponent::Move(PathPoint::new(0.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 0.0)), PathComponent::Line(PathPoint::new(10.0, 10.0)), PathComponent::Close]);
+
+        // Three frames: the last one is deliberately empty
+        state.output_animation().perform_edits(vec![
+            AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+            AnimationEdit::SetLength(Duration::from_millis(300)),
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(200))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), path)))
+        ]);
+
+        state.read_from_write_side()
+    }
+
+    fn run_validation(state: &mut CommandState, render: impl Send+Fn(usize, &Arc<dyn Frame>) -> Vec<Draw>) -> (Result<(), CommandError>, Vec<String>) {
+        let (result, results)   = run_command_collecting_output(|publisher| executor::block_on(validate_render_with(publisher, state, render)));
+
+        let errors              = results.into_iter()
+            .filter_map(|output| if let FloCommandOutput::Error(msg) = output { Some(msg) } else { None })
+            .collect();
+
+        (result, errors)
+    }
+
+    #[test]
+    fn small_animation_renders_successfully() {
+        let mut state           = small_animation();
+        let (result, errors)    = run_validation(&mut state, |_frame_num, frame| render_frame(frame));
+
+        assert!(result == Ok(()));
+        assert!(errors.len() == 0);
+    }
+
+    #[test]
+    fn reports_failing_frame() {
+        let mut state           = small_animation();
+        let (result, errors)    = run_validation(&mut state, |frame_num, frame| {
+            if frame_num == 1 { panic!("Injected failure"); }
+            render_frame(frame)
+        });
+
+        assert!(result == Err(CommandError::RenderFailed(vec![1])));
+        assert!(errors == vec!["Frame 1 (100ms), layer 1: panicked: Injected failure".to_string()]);
+    }
+
+    #[test]
+    fn reports_frame_that_draws_nothing() {
+        let mut state           = small_animation();
+        let (result, _errors)   = run_validation(&mut state, |frame_num, frame| {
+            if frame_num == 0 { vec![] } else { render_frame(frame) }
+        });
+
+        assert!(result == Err(CommandError::RenderFailed(vec![0])));
+    }
+}
@@ -60,6 +60,12 @@ async fn main() {
             .about("Lists the layers defined in the input animation"))
         .subcommand(SubCommand::with_name("ls-elements")
             .about("Lists all of the elements in the selected frame"))
+        .subcommand(SubCommand::with_name("stats")
+            .about("Shows the number of layers, keyframes, elements and edits in the input animation"))
+        .subcommand(SubCommand::with_name("validate-render")
+            .about("Renders every frame of the input animation and reports any frames that fail to render"))
+        .subcommand(SubCommand::with_name("repair")
+            .about("Rebuilds the input animation from its edit log, writing the result to the output animation"))
         .subcommand(SubCommand::with_name("summarize-edits")
             .about("Reads all of the edits in the input animation and shows a summary of them"))
         .subcommand(SubCommand::with_name("rewrite-edits")
@@ -80,6 +86,32 @@ async fn main() {
                 .help("The element ID in the selected frame to raycast")
                 .required(true)
                 .index(1)))
+        .subcommand(SubCommand::with_name("contact-sheet")
+            .about("Writes out SVG files containing a grid of all of the keyframes in a layer")
+            .arg(Arg::with_name("LAYER")
+                .help("The ID of the layer to render")
+                .required(true)
+                .index(1))
+            .arg(Arg::with_name("OUTPUT")
+                .help("The name of the file to write (without the .svg extension)")
+                .default_value("contact-sheet")
+                .index(2))
+            .arg(Arg::with_name("columns")
+                .long("columns")
+                .takes_value(true)
+                .default_value("8")
+                .help("The number of columns in the grid"))
+            .arg(Arg::with_name("cell-size")
+                .long("cell-size")
+                .takes_value(true)
+                .default_value("128")
+                .help("The size of each cell in the grid")))
+        .subcommand(SubCommand::with_name("export-scene")
+            .about("Writes the input animation as a JSON scene that can be played back in a web browser")
+            .arg(Arg::with_name("OUTPUT")
+                .help("The name of the file to write (without the .json extension)")
+                .default_value("scene")
+                .index(1)))
         .get_matches();
 
     tokio::spawn(async move {
@@ -146,6 +178,21 @@ async fn main() {
             input.push(FloCommand::ListElements);
         }
 
+        // Stats command
+        if let Some(_) = params.subcommand_matches("stats") {
+            input.push(FloCommand::Stats);
+        }
+
+        // Validate render command
+        if let Some(_) = params.subcommand_matches("validate-render") {
+            input.push(FloCommand::ValidateRender);
+        }
+
+        // Repair command
+        if let Some(_) = params.subcommand_matches("repair") {
+            input.push(FloCommand::Repair);
+        }
+
         // Summarize edits command
         if let Some(_) = params.subcommand_matches("summarize-edits") {
             input.push(FloCommand::ReadAllEdits);
@@ -208,6 +255,28 @@ async fn main() {
             // Add a raycast command
             input.push(FloCommand::RayCastToSvg(element_id));
         }
+
+        // Contact sheet command
+        if let Some(contact_sheet) = params.subcommand_matches("contact-sheet") {
+            let layer       = contact_sheet.value_of("LAYER").and_then(|layer| u64::from_str(layer).ok());
+            let cols        = contact_sheet.value_of("columns").and_then(|cols| usize::from_str(cols).ok());
+            let cell_size   = contact_sheet.value_of("cell-size").and_then(|cell_size| f64::from_str(cell_size).ok());
+            let output      = contact_sheet.value_of("OUTPUT").unwrap_or("contact-sheet").to_string();
+
+            match (layer, cols, cell_size) {
+                (Some(layer), Some(cols), Some(cell_size))  => { input.push(FloCommand::ContactSheet { layer, cols, cell_size, output }); }
+                _                                           => {
+                    stderr().write("Invalid parameters for the contact sheet command\n\n".as_bytes()).await.unwrap();
+                    return;
+                }
+            }
+        }
+
+        // Export scene command
+        if let Some(export_scene) = params.subcommand_matches("export-scene") {
+            let output = export_scene.value_of("OUTPUT").unwrap_or("scene").to_string();
+            input.push(FloCommand::ExportScene(output));
+        }
         
         // Prepare as a stream as input to the command line
         let input       = stream::iter(input);
@@ -22,12 +22,21 @@ struct FrameLayer {
     /// The frame data for this layer
     layer_frame:        Arc<dyn Frame>,
 
+    /// The cached rendering of the frame, if there is one (this is drawn in preference to the frame data)
+    rendered_frame:     Option<Arc<Vec<Draw>>>,
+
     /// The alpha value for this layer
     alpha:              BindRef<f64>,
 
     /// The alpha used for rendering this layer last time through
     render_alpha:       f64,
 
+    /// How this layer is composited with the layers underneath it
+    blend_mode:         BindRef<LayerBlendMode>,
+
+    /// The blend mode used for rendering this layer last time through
+    render_blend_mode:  LayerBlendMode,
+
     /// The brush that was last used for this layer
     active_brush:       Option<(BrushDefinition, BrushDrawingStyle)>,
 
@@ -35,6 +44,18 @@ struct FrameLayer {
     active_properties:  Option<BrushProperties>
 }
 
+impl FrameLayer {
+    ///
+    /// Draws the content of this layer, using the cached rendering if one is available
+    ///
+    fn render_to(&self, gc: &mut (dyn GraphicsContext+Send)) {
+        match &self.rendered_frame {
+            Some(rendered_frame)    => gc.draw_list(Box::new(rendered_frame.iter().cloned())),
+            None                    => self.layer_frame.render_to(gc)
+        }
+    }
+}
+
 ///
 /// Represents a layer containing an overlay
 ///
@@ -203,7 +224,8 @@ impl CanvasRenderer {
     ///
     pub fn load_frame(&mut self, model: &FrameLayerModel, layer_model: &LayerModel) {
         // Load the frame data (we don't necessarily form a binding here)
-        let frame = model.frame.get();
+        let frame           = model.frame.get();
+        let rendered_frame  = model.rendered_frame.get();
 
         if let Some(frame) = frame {
             // If there are any overlays, they get invalidated when we add this frame
@@ -213,9 +235,11 @@ impl CanvasRenderer {
             if let Some(existing_layer) = existing_layer {
                 // Update the model of the existing layer
                 existing_layer.alpha                = BindRef::from(&layer_model.alpha);
+                existing_layer.blend_mode           = BindRef::from(&layer_model.blend_mode);
                 existing_layer.active_brush         = None;
                 existing_layer.active_properties    = None;
                 existing_layer.layer_frame          = frame;
+                existing_layer.rendered_frame       = rendered_frame;
             } else {
                 // The layer ID comes from the number of layers we've currently got loaded (this layer will be rendered on top of all others)
                 let animation_layer_id      = model.layer_id;
@@ -228,8 +252,11 @@ impl CanvasRenderer {
                 self.frame_layers.insert(animation_layer_id, FrameLayer {
                     layer_id:           canvas_layer_id,
                     layer_frame:        layer_frame,
+                    rendered_frame:     rendered_frame,
                     alpha:              BindRef::from(&layer_model.alpha),
                     render_alpha:       1.0,
+                    blend_mode:         BindRef::from(&layer_model.blend_mode),
+                    render_blend_mode:  LayerBlendMode::SourceOver,
                     active_brush:       None,
                     active_properties:  None,
                 });
@@ -292,13 +319,16 @@ impl CanvasRenderer {
         canvas.draw(move |gc| {
             // Draw the layers
             for layer in self.frame_layers.values_mut() {
-                let alpha           = layer.alpha.get();
-                layer.render_alpha  = alpha;
+                let alpha                   = layer.alpha.get();
+                let blend_mode              = layer.blend_mode.get();
+                layer.render_alpha          = alpha;
+                layer.render_blend_mode     = blend_mode;
 
                 gc.layer(layer.layer_id);
                 gc.clear_layer();
                 gc.layer_alpha(layer.layer_id, alpha);
-                layer.layer_frame.render_to(gc);
+                gc.layer_blend(layer.layer_id, blend_mode.into());
+                layer.render_to(gc);
             }
         });
     }
@@ -312,16 +342,19 @@ impl CanvasRenderer {
         let layer       = if let Some(layer) = layer { layer } else { return; };
 
         canvas.draw(|gc| {
-            let alpha           = layer.alpha.get();
-            layer.render_alpha  = alpha;
+            let alpha                   = layer.alpha.get();
+            let blend_mode              = layer.blend_mode.get();
+            layer.render_alpha          = alpha;
+            layer.render_blend_mode     = blend_mode;
 
             gc.push_state();
 
             gc.layer(layer.layer_id);
             gc.clear_layer();
             gc.layer_alpha(layer.layer_id, alpha);
+            gc.layer_blend(layer.layer_id, blend_mode.into());
 
-            layer.layer_frame.render_to(gc);
+            layer.render_to(gc);
 
             gc.layer(LayerId(0));
 
@@ -510,16 +543,22 @@ impl CanvasRenderer {
     }
 
     ///
-    /// Updates the layer alphas to the latest versions, if they're different from what's set
+    /// Updates the layer alphas and blend modes to the latest versions, if they're different from what's set
     ///
     pub fn update_layer_alphas(&mut self, canvas: &BindingCanvas) {
         for layer in self.frame_layers.values_mut() {
-            let new_alpha = layer.alpha.get();
+            let new_alpha       = layer.alpha.get();
+            let new_blend_mode  = layer.blend_mode.get();
 
             if new_alpha != layer.render_alpha {
                 layer.render_alpha = new_alpha;
                 canvas.draw(|gc| gc.layer_alpha(layer.layer_id, new_alpha));
             }
+
+            if new_blend_mode != layer.render_blend_mode {
+                layer.render_blend_mode = new_blend_mode;
+                canvas.draw(|gc| gc.layer_blend(layer.layer_id, new_blend_mode.into()));
+            }
         }
     }
 }
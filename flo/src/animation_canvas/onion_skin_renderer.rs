@@ -4,9 +4,11 @@ use super::super::model::*;
 
 use flo_ui::*;
 use flo_canvas::*;
+use flo_animation::*;
 
 use std::iter;
 use std::sync::*;
+use std::time::{Duration};
 
 ///
 /// The onion skin renderer deals with rendering the onion skin overlay layers
@@ -28,43 +30,28 @@ impl OnionSkinRenderer {
     ///
     /// Performs onion skin rendering on a canvas
     ///
-    pub fn render(&self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer, onion_skins: Vec<(OnionSkinTime, Arc<Vec<Draw>>)>, past_color: Color, future_color: Color) {
-        if onion_skins.len() == 0 {
+    /// The onion skins should be ordered from nearest to the current frame to farthest from it, as for `OnionSkinModel::onion_skins`. The
+    /// offset of each onion skin is worked out from its time, so skins that haven't loaded yet don't change how the others are tinted.
+    ///
+    pub fn render(&self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer, onion_skins: Vec<(OnionSkinTime, Arc<Vec<Draw>>)>, options: OnionSkinOptions, current_time: Duration, frame_length: Duration) {
+        if onion_skins.len() == 0 || frame_length == Duration::from_millis(0) {
             renderer.overlay(canvas, OVERLAY_ONIONSKINS, vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0))]);
         } else {
-            // Onion skins further away in time are less opaque
-            let min_opacity     = 0.1;
-            let max_opacity     = 0.5;
-            let opacity_step    = (max_opacity - min_opacity)/(onion_skins.len() as f64);
-
-            // Generate drawing instructions for each set of onion skins, in reverse order (from least opaque to most opaque)
-            let draw_onion_skins = iter::once(Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0)))
-                .chain(onion_skins.into_iter()
-                    .rev()
-                    .enumerate()
-                    .flat_map(|(index, (time, drawing))| {
-                        // Work out the colour and opacity of this item
-                        let opacity = (index as f64)*opacity_step + min_opacity;
-                        let color   = match time {
-                            OnionSkinTime::BeforeFrame(_)   => past_color,
-                            OnionSkinTime::AfterFrame(_)    => future_color
-                        };
-                        let color   = color.with_alpha(opacity as f32);
-
-                        iter::once(Draw::Path(PathOp::NewPath))
-                            .chain(drawing.iter().cloned())
-                            .chain(vec![
-                                Draw::FillColor(color),
-                                Draw::Fill,
-                                Draw::LineWidthPixels(2.0),
-                                Draw::StrokeColor(Color::Rgba(1.0, 1.0, 1.0, opacity as f32)),
-                                Draw::Stroke,
-                                Draw::LineWidthPixels(0.5),
-                                Draw::StrokeColor(color.with_alpha((opacity+0.1) as f32)),
-                                Draw::Stroke
-                            ])
-                            .collect::<Vec<_>>()
-                    }))
+            // Work out the frame offset of each onion skin from its time
+            let current_nanos   = current_time.as_nanos() as f64;
+            let frame_nanos     = frame_length.as_nanos() as f64;
+            let onion_skins     = onion_skins.into_iter()
+                .map(|(time, drawing)| {
+                    let when: Duration  = time.into();
+                    let offset          = ((when.as_nanos() as f64 - current_nanos) / frame_nanos).round() as i64;
+
+                    (offset, drawing)
+                })
+                .collect::<Vec<_>>();
+
+            // Generate drawing instructions for each set of onion skins (from least opaque to most opaque)
+            let draw_onion_skins    = iter::once(Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0)))
+                .chain(options.draw_onion_skins(&onion_skins))
                 .collect();
             renderer.overlay(canvas, OVERLAY_ONIONSKINS, draw_onion_skins);
         }
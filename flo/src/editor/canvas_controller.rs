@@ -19,6 +19,9 @@ use std::collections::{HashSet};
 const MAIN_CANVAS: &str     = "main";
 const PAINT_ACTION: &str    = "Paint";
 
+/// The parameters passed to the onion skin renderer: the options, the current time, the frame length and the onion skins to draw
+type OnionSkinRenderParameters = (OnionSkinOptions, Duration, Duration, Vec<(OnionSkinTime, Arc<Vec<Draw>>)>);
+
 ///
 /// The core of the canvas
 ///
@@ -56,7 +59,7 @@ pub struct CanvasController<Anim: Animation+EditableAnimation> {
     canvases:           Arc<ResourceManager<BindingCanvas>>,
     anim_model:         Arc<FloModel<Anim>>,
     tool_changed:       Arc<Mutex<bool>>,
-    _onion_skin_model:  BindRef<(Color, Color, Vec<(OnionSkinTime, Arc<Vec<Draw>>)>)>,
+    _onion_skin_model:  BindRef<OnionSkinRenderParameters>,
 
     core:               Arc<Desync<CanvasCore<Anim>>>
 }
@@ -124,26 +127,27 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
     ///
     /// Creates a binding from a model to the parameters of the onion skin renderer function
     ///
-    fn onion_skin_binding(view_model: &FloModel<Anim>) -> BindRef<(Color, Color, Vec<(OnionSkinTime, Arc<Vec<Draw>>)>)> {
+    fn onion_skin_binding(view_model: &FloModel<Anim>) -> BindRef<OnionSkinRenderParameters> {
         let onion_skin_model    = view_model.onion_skin();
-        let past_color          = onion_skin_model.past_color.clone();
-        let future_color        = onion_skin_model.future_color.clone();
+        let options             = onion_skin_model.options.clone();
         let onion_skins         = onion_skin_model.onion_skins.clone();
+        let current_time        = view_model.timeline().current_time.clone();
+        let frame_duration      = view_model.timeline().frame_duration.clone();
 
         BindRef::from(computed(move || {
-            (past_color.get(), future_color.get(), onion_skins.get())
+            (options.get(), current_time.get(), frame_duration.get(), onion_skins.get())
         }))
     }
 
     ///
     /// Updates the rendering in the core whenever the onion skins change
     ///
-    fn pipe_onion_skin_renders(canvas: Resource<BindingCanvas>, binding: BindRef<(Color, Color, Vec<(OnionSkinTime, Arc<Vec<Draw>>)>)>, core: Arc<Desync<CanvasCore<Anim>>>) {
+    fn pipe_onion_skin_renders(canvas: Resource<BindingCanvas>, binding: BindRef<OnionSkinRenderParameters>, core: Arc<Desync<CanvasCore<Anim>>>) {
         let onion_skin_stream   = follow(binding);
         let renderer            = OnionSkinRenderer::new();
 
-        pipe_in(core, onion_skin_stream, move |core, (past_color, future_color, onion_skins)| {
-            renderer.render(&*canvas, &mut core.renderer, onion_skins, past_color, future_color);
+        pipe_in(core, onion_skin_stream, move |core, (options, current_time, frame_duration, onion_skins)| {
+            renderer.render(&*canvas, &mut core.renderer, onion_skins, options, current_time, frame_duration);
             Box::pin(future::ready(()))
         })
     }
@@ -508,6 +512,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasCore<Anim> {
                         LayerEdit::SetName(_)                                   => { false },
                         LayerEdit::SetOrdering(_)                               => { self.model.timeline().invalidate_canvas(); false /* ... but whole canvas update */ },
                         LayerEdit::SetAlpha(_)                                  => { true },
+                        LayerEdit::SetBlendMode(_)                              => { true },
+                        LayerEdit::SetLocked(_)                                 => { false },
                     };
 
                     // Force the layer to update if necessary
@@ -535,6 +541,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasCore<Anim> {
                         ElementEdit::SetAnimationBaseType(_)        => true,
                         ElementEdit::AddAnimationEffect(_)          => true,
                         ElementEdit::ReplaceAnimationEffect(_, _)   => true,
+                        ElementEdit::SetOpacity(_, _)               => true,
+                        ElementEdit::SetVisibility(_, _)            => true,
                     };
 
                     // Update all of the layers if needed
@@ -181,6 +181,22 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                     advance_edit_counter = true;
                 },
 
+                Layer(layer_id, SetBlendMode(new_blend_mode)) => {
+                    // Update the blend mode in the model
+                    timeline.layers.get()
+                        .into_iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.blend_mode.set(*new_blend_mode); });
+
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetLocked(locked)) => {
+                    // Update the locked state in the model
+                    timeline.layers.get()
+                        .into_iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.locked.set(*locked); });
+                },
+
                 Layer(layer_id, SetOrdering(at_index)) => {
                     unimplemented!("Cannot update model with layer ordering yet")
                 },
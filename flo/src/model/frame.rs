@@ -1,6 +1,7 @@
 use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_canvas::{Draw};
 
 use futures::*;
 use futures::future;
@@ -43,6 +44,9 @@ pub struct FrameLayerModel {
 
     /// The current frame for this layer
     pub frame: BindRef<Option<Arc<dyn Frame>>>,
+
+    /// The pre-rendered drawing for the current frame, if it's in the layer's cache
+    pub rendered_frame: BindRef<Option<Arc<Vec<Draw>>>>,
 }
 
 ///
@@ -124,6 +128,9 @@ impl FrameModel {
                         let when                = BindRef::clone(&when);
                         let frame_animation     = Arc::clone(&animation);
                         let animation_update    = animation_update.clone();
+                        let rendered_when       = when.clone();
+                        let rendered_animation  = Arc::clone(&animation);
+                        let rendered_update     = animation_update.clone();
 
                         let frame_binding       = ComputedBinding::new_in_context(move || {
                             // Binds to the animation update...
@@ -137,12 +144,23 @@ impl FrameModel {
                                 .map(|layer| layer.get_frame_at_time(when))
                         });
 
+                        // The rendered frame is read from the cache (edits to the layer remove it from there)
+                        let rendered_binding    = ComputedBinding::new_in_context(move || {
+                            rendered_update.get();
+                            let when = rendered_when.get();
+
+                            rendered_animation.get_layer_with_id(layer_id)
+                                .and_then(|layer| layer.get_canvas_cache_at_time(when).retrieve(CacheType::RenderedFrame))
+                        });
+
                         // Add a frame layer model for this frame
                         let frame           = BindRef::new(&frame_binding);
+                        let rendered_frame  = BindRef::new(&rendered_binding);
 
                         vacant.insert(FrameLayerModel {
                             layer_id:       layer_id,
                             frame:          frame,
+                            rendered_frame: rendered_frame,
                         });
                     }
                 }
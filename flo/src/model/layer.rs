@@ -14,6 +14,12 @@ pub struct LayerModel {
 
     /// The alpha value for this layer
     pub alpha: Binding<f64>,
+
+    /// How this layer is composited with the layers underneath it
+    pub blend_mode: Binding<LayerBlendMode>,
+
+    /// True if this layer is locked against editing
+    pub locked: Binding<bool>,
 }
 
 impl PartialEq for LayerModel {
@@ -25,9 +31,11 @@ impl PartialEq for LayerModel {
 impl LayerModel {
     pub fn new<'a>(layer: &'a dyn Layer) -> LayerModel {
         LayerModel {
-            id:     layer.id(),
-            name:   bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id()))),
-            alpha:  bind(layer.alpha()),
+            id:         layer.id(),
+            name:       bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id()))),
+            alpha:      bind(layer.alpha()),
+            blend_mode: bind(layer.blend_mode()),
+            locked:     bind(layer.is_locked()),
         }
     }
 }
@@ -44,6 +44,9 @@ pub struct OnionSkinModel<Anim: Animation> {
     /// The colour of the past onion skins
     pub past_color: Binding<Color>,
 
+    /// How the opacity of the onion skins falls off as they get further from the current frame
+    pub falloff: Binding<OnionSkinFalloff>,
+
     /// Whether or not the onion skins should be displayed
     pub show_onion_skins: Binding<bool>,
 
@@ -59,6 +62,9 @@ pub struct OnionSkinModel<Anim: Animation> {
     /// The number of frames to show after the current frame
     pub frames_after: Binding<usize>,
 
+    /// The options for generating the onion skins, made up from the other settings in this model
+    pub options: BindRef<OnionSkinOptions>,
+
     anim: PhantomData<Anim>
 }
 
@@ -70,30 +76,52 @@ impl<Anim: 'static+Animation> OnionSkinModel<Anim> {
         // Create the basic bindings
         let future_color        = Binding::new(ONIONSKIN_FUTURE);
         let past_color          = Binding::new(ONIONSKIN_PAST);
+        let falloff             = Binding::new(OnionSkinFalloff::Linear);
         let show_onion_skins    = Binding::new(false);
         let frames_before       = Binding::new(3);
         let frames_after        = Binding::new(3);
 
         // Create the derived bindings
-        let onion_skin_times    = Self::onion_skin_times(timeline, BindRef::from(&show_onion_skins), BindRef::from(&frames_before), BindRef::from(&frames_after));
+        let options             = Self::options(BindRef::from(&past_color), BindRef::from(&future_color), BindRef::from(&falloff), BindRef::from(&frames_before), BindRef::from(&frames_after));
+        let onion_skin_times    = Self::onion_skin_times(timeline, BindRef::from(&show_onion_skins), BindRef::clone(&options));
         let onion_skins         = Self::onion_skins(Arc::clone(&animation), BindRef::from(&timeline.selected_layer), BindRef::clone(&onion_skin_times));
 
         OnionSkinModel {
             future_color:       future_color,
             past_color:         past_color,
+            falloff:            falloff,
             show_onion_skins:   show_onion_skins,
             frames_before:      frames_before,
             frames_after:       frames_after,
+            options:            options,
             onion_skin_times:   onion_skin_times,
             onion_skins:        onion_skins,
             anim:               PhantomData
         }
     }
 
+    ///
+    /// Returns a binding for the onion skin options set by this model
+    ///
+    fn options(past_color: BindRef<Color>, future_color: BindRef<Color>, falloff: BindRef<OnionSkinFalloff>, frames_before: BindRef<usize>, frames_after: BindRef<usize>) -> BindRef<OnionSkinOptions> {
+        BindRef::from(computed(move || {
+            OnionSkinOptions {
+                frames_before:  frames_before.get(),
+                frames_after:   frames_after.get(),
+                tint:           OnionSkinTint {
+                    before_color:   past_color.get(),
+                    after_color:    future_color.get(),
+                    falloff:        falloff.get(),
+                    ..OnionSkinTint::default()
+                }
+            }
+        }))
+    }
+
     ///
     /// Returns the current set of times to display onion skins for
     ///
-    fn onion_skin_times(timeline: &TimelineModel<Anim>, show_onion_skins: BindRef<bool>, frames_before: BindRef<usize>, frames_after: BindRef<usize>) -> BindRef<Vec<OnionSkinTime>> {
+    fn onion_skin_times(timeline: &TimelineModel<Anim>, show_onion_skins: BindRef<bool>, options: BindRef<OnionSkinOptions>) -> BindRef<Vec<OnionSkinTime>> {
         // Fetch the timeline properties
         let current_time        = timeline.current_time.clone();
         let frame_duration      = timeline.frame_duration.clone();
@@ -104,34 +132,11 @@ impl<Anim: 'static+Animation> OnionSkinModel<Anim> {
             let show_onion_skins = show_onion_skins.get();
 
             if show_onion_skins {
-                // Displaying the onion skins, so calculate the times we need to fetch
-                let current_time        = current_time.get();
-                let frame_duration      = frame_duration.get();
-                let timeline_duration   = timeline_duration.get();
-                let frames_before       = frames_before.get();
-                let frames_after        = frames_after.get();
-
-                // The maximum number of frames we want to calculate
-                let max_frames          = frames_before.max(frames_after);
-
-                // Compute frames starting from the current time in both directions (so frames are ordered in terms of distance from the current time, with most recent frames first)
-                let mut onion_skin_times = vec![];
-                for frame_num in 1..=max_frames {
-                    // Time offset for this frame
-                    let offset = frame_duration * (frame_num as u32);
-
-                    // Past frame
-                    if frame_num <= frames_before && offset <= current_time {
-                        onion_skin_times.push(OnionSkinTime::BeforeFrame(current_time - offset));
-                    }
-
-                    // Future frame
-                    if frame_num <= frames_after && current_time + offset <= timeline_duration {
-                        onion_skin_times.push(OnionSkinTime::AfterFrame(current_time + offset));
-                    }
-                }
-
-                onion_skin_times
+                // Displaying the onion skins, so ask the options which frames to display (ordered from the nearest to the farthest)
+                options.get().onion_skin_times(current_time.get(), frame_duration.get(), timeline_duration.get())
+                    .into_iter()
+                    .map(|(offset, when)| if offset < 0 { OnionSkinTime::BeforeFrame(when) } else { OnionSkinTime::AfterFrame(when) })
+                    .collect()
             } else {
                 // Not showing any onion skins, so there are no times to display
                 vec![]
@@ -239,9 +244,11 @@ impl<Anim: Animation> Clone for OnionSkinModel<Anim> {
         OnionSkinModel {
             future_color:       self.future_color.clone(),
             past_color:         self.past_color.clone(),
+            falloff:            self.falloff.clone(),
             show_onion_skins:   self.show_onion_skins.clone(),
             frames_before:      self.frames_before.clone(),
             frames_after:       self.frames_after.clone(),
+            options:            self.options.clone(),
             onion_skin_times:   self.onion_skin_times.clone(),
             onion_skins:        self.onion_skins.clone(),
             anim:               PhantomData
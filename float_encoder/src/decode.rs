@@ -1,4 +1,4 @@
-use std::io::{Read, Error};
+use std::io::{Read, Error, ErrorKind};
 use std::mem;
 
 ///
@@ -33,3 +33,68 @@ pub fn unsquish_float<Source: Read>(src: &mut Source, last: f64) -> Result<f64,
         Ok(last + diff)
     }
 }
+
+///
+/// Reads a sequence of `count` floats written by `squish_float_seq` from a source stream
+///
+/// If the source runs out of data before all of the values have been read, this returns an `UnexpectedEof` error
+/// describing how many values could be read.
+///
+pub fn unsquish_float_seq<Source: Read>(src: &mut Source, count: usize) -> Result<Vec<f64>, Error> {
+    let mut values  = Vec::with_capacity(count);
+    let mut last    = 0.0;
+
+    for _ in 0..count {
+        last = unsquish_float(src, last)
+            .map_err(|err| {
+                if err.kind() == ErrorKind::UnexpectedEof {
+                    Error::new(ErrorKind::UnexpectedEof, format!("Ran out of data after reading {} of {} floats", values.len(), count))
+                } else {
+                    err
+                }
+            })?;
+
+        values.push(last);
+    }
+
+    Ok(values)
+}
+
+///
+/// Reads a float written by `squish_float_quantized` from a source stream
+///
+/// The `last` value and `quantum` should be the same as the ones used when the value was squished (though the last
+/// value can also be the previous value returned by this function, as it's rounded to the same multiple of the quantum)
+///
+pub fn unsquish_float_quantized<Source: Read>(src: &mut Source, last: f64, quantum: f64) -> Result<f64, Error> {
+    let last = if last.is_infinite() || last.is_nan() { 0.0 } else { last };
+
+    // Read the variable-length difference
+    let mut encoded: u64    = 0;
+    let mut shift           = 0;
+    loop {
+        let mut byte = [0];
+        src.read_exact(&mut byte)?;
+
+        if shift < 64 {
+            encoded |= ((byte[0] & 0x7f) as u64) << shift;
+        }
+        shift += 7;
+
+        if byte[0] & 0x80 == 0 { break; }
+    }
+
+    if encoded == 0 {
+        // Value stored at full precision
+        let mut value_bytes = [0; 8];
+        src.read_exact(&mut value_bytes)?;
+
+        Ok(f64::from_bits(u64::from_le_bytes(value_bytes)))
+    } else {
+        // Undo the zig-zag encoding to get the difference in quanta
+        let zig_zag = encoded - 1;
+        let diff    = ((zig_zag >> 1) as i64) ^ -((zig_zag & 1) as i64);
+
+        Ok(((last / quantum).round() + (diff as f64)) * quantum)
+    }
+}
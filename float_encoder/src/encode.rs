@@ -1,3 +1,5 @@
+use super::decode::*;
+
 use std::io::{Write, Error};
 use std::mem;
 
@@ -35,3 +37,85 @@ pub fn squish_float<Target: Write>(target: &mut Target, last: f64, next: f64) ->
         target.write(&[(fixed_point&0xff) as u8, (fixed_point>>8) as u8])
     }
 }
+
+///
+/// Writes a sequence of squished floats to the specified vector, returning the number of bytes written
+///
+/// The first value is stored relative to 0.0. Each following value is stored relative to the value that will be read back
+/// for the value before it, so small rounding errors don't build up along the sequence.
+///
+pub fn squish_float_seq(target: &mut Vec<u8>, values: &[f64]) -> usize {
+    let initial_len = target.len();
+    let mut last    = 0.0;
+
+    for value in values.iter() {
+        let start = target.len();
+        squish_float(target, last, *value).expect("Writing to a vec can't fail");
+
+        // Continue from the value that unsquish_float will decode
+        let mut written: &[u8] = &target[start..];
+        last = unsquish_float(&mut written, last).expect("Squished value should decode");
+    }
+
+    target.len() - initial_len
+}
+
+/// Largest difference (in quanta) that can be stored as an integer (beyond this, f64 can't represent every integer)
+const MAX_QUANTIZED_DIFF: f64 = 9_007_199_254_740_992.0;
+
+///
+/// Writes a float rounded to a multiple of `quantum` to the specified stream
+///
+/// Like `squish_float`, the value is stored relative to the previous value. The difference is stored as a whole number
+/// of quanta using as few bytes as possible (differences of up to 63 quanta take a single byte), so a coarser quantum
+/// produces smaller output. NaN, infinite values and very large differences are stored at full precision, as are all
+/// values if `quantum` is not a positive number.
+///
+pub fn squish_float_quantized<Target: Write>(target: &mut Target, last: f64, value: f64, quantum: f64) -> Result<usize, Error> {
+    let last = if last.is_infinite() || last.is_nan() { 0.0 } else { last };
+
+    if !quantum.is_finite() || quantum <= 0.0 || !value.is_finite() {
+        return write_full_precision(target, value);
+    }
+
+    // What we encode is the difference between the values as a number of quanta
+    let diff = (value / quantum).round() - (last / quantum).round();
+
+    if !diff.is_finite() || diff.abs() > MAX_QUANTIZED_DIFF {
+        return write_full_precision(target, (value / quantum).round() * quantum);
+    }
+
+    // Zig-zag encode the difference, leaving 0 free to indicate a full-precision value
+    let diff        = diff as i64;
+    let zig_zag     = ((diff << 1) ^ (diff >> 63)) as u64;
+    let mut encoded = zig_zag + 1;
+
+    // Write 7 bits at a time, with the top bit set if there are more bytes to follow
+    let mut bytes = vec![];
+    loop {
+        let byte = (encoded & 0x7f) as u8;
+        encoded >>= 7;
+
+        if encoded == 0 {
+            bytes.push(byte);
+            break;
+        } else {
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    target.write_all(&bytes)?;
+    Ok(bytes.len())
+}
+
+///
+/// Writes a quantized value that can't be stored as a difference (a 0 byte followed by the f64 value)
+///
+fn write_full_precision<Target: Write>(target: &mut Target, value: f64) -> Result<usize, Error> {
+    let bit_pattern = value.to_bits();
+
+    target.write_all(&[0])?;
+    target.write_all(&bit_pattern.to_le_bytes())?;
+
+    Ok(9)
+}
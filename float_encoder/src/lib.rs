@@ -222,4 +222,160 @@ mod test {
         squish_float(&mut target, 0.0, -1.0).unwrap();
         assert!(target.len() == 2);
     }
+
+    #[test]
+    pub fn can_decode_quantized() {
+        let mut target = vec![];
+
+        squish_float_quantized(&mut target, 0.0, 1.234, 0.01).unwrap();
+
+        let mut src: &[u8] = &target;
+        let res = unsquish_float_quantized(&mut src, 0.0, 0.01).unwrap();
+
+        assert!((res-1.23).abs() < 0.0001);
+    }
+
+    #[test]
+    pub fn quantized_small_difference_is_one_byte() {
+        let mut target = vec![];
+
+        assert!(squish_float_quantized(&mut target, 1.0, 2.0, 0.01).unwrap() == 2);
+        assert!(squish_float_quantized(&mut target, 1.0, 1.25, 0.01).unwrap() == 1);
+        assert!(squish_float_quantized(&mut target, 1.0, 1.0, 0.01).unwrap() == 1);
+        assert!(target.len() == 4);
+    }
+
+    #[test]
+    pub fn coarser_quantum_is_smaller() {
+        let mut fine    = vec![];
+        let mut coarse  = vec![];
+
+        squish_float_quantized(&mut fine, 0.0, 100.0, 0.001).unwrap();
+        squish_float_quantized(&mut coarse, 0.0, 100.0, 1.0).unwrap();
+
+        assert!(coarse.len() < fine.len());
+    }
+
+    #[test]
+    pub fn can_decode_quantized_sequence() {
+        let mut target  = vec![];
+        let values      = [1.5, 0.3, 4093.2, 4085.25, -700_000.257, 0.004, 0.006];
+
+        let mut last = 0.0;
+        for value in values.iter() {
+            squish_float_quantized(&mut target, last, *value, 0.01).unwrap();
+            last = *value;
+        }
+
+        // Each value is decoded relative to the previously decoded value
+        let mut src: &[u8]  = &target;
+        let mut last        = 0.0;
+        for value in values.iter() {
+            let res = unsquish_float_quantized(&mut src, last, 0.01).unwrap();
+            assert!((res-value).abs() <= 0.005 + 1e-9);
+
+            last = res;
+        }
+
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    pub fn can_decode_quantized_nan() {
+        let mut target = vec![];
+
+        squish_float_quantized(&mut target, 0.0, f64::NAN, 0.01).unwrap();
+
+        let mut src: &[u8] = &target;
+        let res = unsquish_float_quantized(&mut src, 0.0, 0.01).unwrap();
+
+        assert!(res.is_nan());
+    }
+
+    #[test]
+    pub fn can_decode_quantized_infinity() {
+        let mut target = vec![];
+
+        squish_float_quantized(&mut target, 0.0, f64::INFINITY, 0.01).unwrap();
+        squish_float_quantized(&mut target, 0.0, f64::NEG_INFINITY, 0.01).unwrap();
+
+        let mut src: &[u8] = &target;
+        let res1 = unsquish_float_quantized(&mut src, 0.0, 0.01).unwrap();
+        let res2 = unsquish_float_quantized(&mut src, 0.0, 0.01).unwrap();
+
+        assert!(res1.is_infinite() && res1 > 0.0);
+        assert!(res2.is_infinite() && res2 < 0.0);
+    }
+
+    #[test]
+    pub fn can_recover_quantized_from_nan() {
+        let mut target = vec![];
+
+        squish_float_quantized(&mut target, f64::NAN, 42.0, 0.01).unwrap();
+
+        let mut src: &[u8] = &target;
+        let res = unsquish_float_quantized(&mut src, f64::NAN, 0.01).unwrap();
+
+        assert!((res-42.0).abs() < 0.0001);
+    }
+
+    #[test]
+    pub fn zero_quantum_keeps_full_precision() {
+        let mut target = vec![];
+
+        squish_float_quantized(&mut target, 0.0, 1.23456789, 0.0).unwrap();
+
+        let mut src: &[u8] = &target;
+        let res = unsquish_float_quantized(&mut src, 0.0, 0.0).unwrap();
+
+        assert!(res == 1.23456789);
+    }
+
+    #[test]
+    pub fn can_decode_float_seq() {
+        let mut target  = vec![];
+        let values      = [1.5, 0.3, 4093.2, 4085.25, -700_000.25, f64::NAN, 42.0];
+
+        let len         = squish_float_seq(&mut target, &values);
+        assert!(len == target.len());
+
+        let mut src: &[u8]  = &target;
+        let res             = unsquish_float_seq(&mut src, values.len()).unwrap();
+
+        assert!(res.len() == values.len());
+        for (res, value) in res.iter().zip(values.iter()) {
+            if value.is_nan() {
+                assert!(res.is_nan());
+            } else {
+                assert!((res-value).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    pub fn float_seq_does_not_accumulate_errors() {
+        let mut target  = vec![];
+        let values      = (0..1000).map(|x| (x as f64) * 0.1234567).collect::<Vec<_>>();
+
+        squish_float_seq(&mut target, &values);
+
+        let mut src: &[u8]  = &target;
+        let res             = unsquish_float_seq(&mut src, values.len()).unwrap();
+
+        assert!((res[999]-values[999]).abs() < 0.01);
+    }
+
+    #[test]
+    pub fn float_seq_reports_missing_data() {
+        let mut target = vec![];
+
+        squish_float_seq(&mut target, &[1.0, 2.0, 700_000.25]);
+        target.truncate(target.len()-1);
+
+        let mut src: &[u8]  = &target;
+        let err             = unsquish_float_seq(&mut src, 3).unwrap_err();
+
+        assert!(err.kind() == std::io::ErrorKind::UnexpectedEof);
+        assert!(err.to_string() == "Ran out of data after reading 2 of 3 floats");
+    }
 }
@@ -106,6 +106,7 @@ impl SqliteCore {
             ReadElementsForKeyFrame(layer_id, when)             => { self.read_elements_for_key_frame(layer_id, when) },
             WriteLayerCache(layer_id, when, cache_type, value)  => { self.write_layer_cache(layer_id, when, cache_type, value) },
             DeleteLayerCache(layer_id, when, cache_type)        => { self.delete_layer_cache(layer_id, when, cache_type) },
+            DeleteLayerCacheType(layer_id, cache_type)          => { self.delete_layer_cache_type(layer_id, cache_type) },
             ReadLayerCache(layer_id, when, cache_type)          => { self.read_layer_cache(layer_id, when, cache_type) },
         };
 
@@ -558,6 +559,16 @@ impl SqliteCore {
         Ok(vec![StorageResponse::Updated])
     }
 
+    ///
+    /// Removes every cached value of a particular type from a layer
+    ///
+    fn delete_layer_cache_type(&mut self, layer_id: u64, cache_type: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("DELETE FROM LayerCache WHERE LayerId = ? AND CacheType = ?;")?;
+        write.execute(params![layer_id as i64, cache_type])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
     ///
     /// Reads the value contained in the specified location of the layer cache
     ///
@@ -645,3 +645,27 @@ fn delete_from_layer_cache() {
     assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(500), "Type".to_string())]) ==
         vec![StorageResponse::LayerCache("Cache2".to_string())]);
 }
+
+#[test]
+fn delete_cache_type_from_layer_cache() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::WriteLayerCache(1, Duration::from_millis(400), "Type".to_string(), "Cache1".to_string()),
+            StorageCommand::WriteLayerCache(1, Duration::from_millis(500), "Type".to_string(), "Cache2".to_string()),
+            StorageCommand::WriteLayerCache(1, Duration::from_millis(400), "Other".to_string(), "Cache3".to_string())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::DeleteLayerCacheType(1, "Type".to_string()),
+        ]) == vec![StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(400), "Type".to_string())]) ==
+        vec![StorageResponse::NotFound]);
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(500), "Type".to_string())]) ==
+        vec![StorageResponse::NotFound]);
+    assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(400), "Other".to_string())]) ==
+        vec![StorageResponse::LayerCache("Cache3".to_string())]);
+}
@@ -1475,6 +1475,77 @@ let flo_canvas = (function() {
         };
     }
 
+    ///
+    /// Draws an exported scene (as generated by `export_json_scene()`) to a canvas element at a particular time
+    ///
+    /// Scenes use the same coordinates as the animation, so the y axis points upwards.
+    ///
+    function draw_scene(canvas, scene, time_ms) {
+        let context         = canvas.getContext('2d');
+        let width           = canvas.width;
+        let height          = canvas.height;
+
+        // Each layer is drawn to its own canvas, so erasing elements only affect the layer they're in
+        let layer_canvas    = document.createElement('canvas');
+        layer_canvas.width  = width;
+        layer_canvas.height = height;
+        let layer_context   = layer_canvas.getContext('2d');
+
+        context.clearRect(0, 0, width, height);
+
+        scene.layers.forEach(layer => {
+            // Find the keyframe that's displayed at this time
+            let keyframe = layer.keyframes.filter(keyframe => keyframe.start_ms <= time_ms && time_ms < keyframe.end_ms)[0];
+            if (!keyframe) {
+                return;
+            }
+
+            layer_context.setTransform(1, 0, 0, 1, 0, 0);
+            layer_context.clearRect(0, 0, width, height);
+            layer_context.setTransform(width/scene.size[0], 0, 0, -height/scene.size[1], 0, height);
+
+            keyframe.elements.forEach(element => {
+                let color = element.color;
+
+                layer_context.globalCompositeOperation  = element.erase ? 'destination-out' : 'source-over';
+                layer_context.fillStyle                 = 'rgba(' + (color[0]*255) + ',' + (color[1]*255) + ',' + (color[2]*255) + ',' + color[3] + ')';
+                layer_context.fill(new Path2D(element.path));
+            });
+
+            context.globalAlpha = layer.alpha;
+            context.drawImage(layer_canvas, 0, 0);
+            context.globalAlpha = 1.0;
+        });
+    }
+
+    ///
+    /// Plays an exported scene in a loop on a canvas element, returning a function that stops the playback
+    ///
+    function play_scene(canvas, scene) {
+        let start_time  = null;
+        let playing     = true;
+
+        let next_frame  = timestamp => {
+            if (!playing) {
+                return;
+            }
+
+            if (start_time === null) {
+                start_time = timestamp;
+            }
+
+            // Scenes with no length just show their first frame
+            let time_ms = scene.duration_ms > 0 ? (timestamp - start_time) % scene.duration_ms : 0;
+            draw_scene(canvas, scene, time_ms);
+
+            requestAnimationFrame(next_frame);
+        };
+
+        requestAnimationFrame(next_frame);
+
+        return () => { playing = false; };
+    }
+
     // The final flo_canvas object
     return {
         start:                      start,
@@ -1482,6 +1553,8 @@ let flo_canvas = (function() {
         resize_canvases:            resize_active_canvases,
         update_canvas:              update_canvas,
         remove_inactive_canvases:   remove_inactive_canvases,
-        update_canvas_map:          update_canvas_map
+        update_canvas_map:          update_canvas_map,
+        draw_scene:                 draw_scene,
+        play_scene:                 play_scene
     };
 })();
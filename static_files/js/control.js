@@ -25,6 +25,27 @@ let flo_control = (function () {
         input_element.min = 0.0;
         input_element.max = 1000.0;
 
+        // Sliders with a step snap their values to multiples of the step from the start of the range
+        let flo_step        = parseFloat(element.getAttribute('flo-range-step') || '0') || 0.0;
+
+        ///
+        /// Converts the position of the input element to a value in the slider's range
+        ///
+        function input_value(flo_min, flo_max) {
+            // We get a number 0-1000, change to fit in the range
+            let value = (input_element.value/1000.0)*(flo_max-flo_min) + flo_min;
+
+            if (flo_step > 0.0) {
+                value = flo_min + Math.round((value-flo_min)/flo_step)*flo_step;
+                value = Math.min(Math.max(value, Math.min(flo_min, flo_max)), Math.max(flo_min, flo_max));
+
+                // Move the slider to the snapped position
+                input_element.value = ((value-flo_min)/(flo_max-flo_min))*1000.0;
+            }
+
+            return value;
+        }
+
         ///
         /// The 'focus' event is fired when the slider has focus
         ///
@@ -44,8 +65,7 @@ let flo_control = (function () {
             let flo_min = flo_min_value['Float'] || 0.0;
             let flo_max = flo_max_value['Float'] || 100.0;
 
-            // We get a number 0-1000, change to fit in the range
-            let value = (input_element.value/1000.0)*(flo_max-flo_min) + flo_min;
+            let value = input_value(flo_min, flo_max);
 
             // This is the editing event: if the node has an edit_value handler, this is where we send it
             let input_handler = element.flo_edit_value || (() => {});
@@ -62,8 +82,7 @@ let flo_control = (function () {
             let flo_min = flo_min_value['Float'] || 0.0;
             let flo_max = flo_max_value['Float'] || 100.0;
 
-            // We get a number 0-1000, change to fit in the range
-            let value = (input_element.value/1000.0)*(flo_max-flo_min) + flo_min;
+            let value = input_value(flo_min, flo_max);
 
             // This is the set event: if the node has an set_value handler, this is where we send it
             let input_handler = element.flo_set_value || (() => {});
@@ -695,12 +714,35 @@ let flo_control = (function () {
     ///
     /// Sets up a control as a textbox
     ///
+    ///
+    /// Reads a value with a unit (eg, '50%') from some text, returning null if the value isn't a number or uses a unit that's not allowed
+    ///
+    /// Numbers with no unit use the first allowed unit. This matches UnitValue::parse in flo_ui.
+    ///
+    let parse_unit_value = (text, allowed_units) => {
+        let unit_names  = { 'px': 'Pixels', '%': 'Percent', 'em': 'Em' };
+        let match       = /^\s*([-+]?[0-9]*\.?[0-9]*)\s*(\S*)\s*$/.exec(text);
+        if (!match) {
+            return null;
+        }
+
+        let value       = parseFloat(match[1]);
+        let unit        = match[2].toLowerCase() || allowed_units[0];
+
+        if (isNaN(value) || allowed_units.indexOf(unit) < 0 || !unit_names[unit]) {
+            return null;
+        }
+
+        return { 'value': value, 'unit': unit_names[unit] };
+    };
+
     let load_textbox = (node, add_action_event, on_property_change) => {
         // Fetch the values of the attributes that can be set for the text box
         let flo_text        = node.flo_text || { 'String': '' };
         let font_size       = node.getAttribute('flo-text-size') || null;
         let font_weight     = node.getAttribute('flo-text-weight') || null;
         let align           = node.getAttribute('flo-text-align') || null;
+        let units           = (node.getAttribute('flo-units') || '').split(' ').filter(unit => unit !== '');
 
         // Set the initial text value
         let input           = node.getElementsByTagName('input')[0];
@@ -729,6 +771,23 @@ let flo_control = (function () {
 
         input.style         = style;
 
+        // Text boxes with units send the value along with its unit, and don't send anything if the text is not a valid value
+        let send_value      = (send_text, send_unit_value) => {
+            if (units.length === 0) {
+                if (send_text) {
+                    send_text({ 'String': input.value || '' });
+                }
+                return;
+            }
+
+            let unit_value = parse_unit_value(input.value || '', units);
+            node.classList.toggle('invalid', unit_value === null);
+
+            if (unit_value !== null && send_unit_value) {
+                send_unit_value(unit_value);
+            }
+        };
+
         // Bind the events for this node
         let has_focus = false;
         add_action_event(node, 'focus', event => {
@@ -741,26 +800,49 @@ let flo_control = (function () {
         add_action_event(node, 'blur', event => {
             if (has_focus) {
                 has_focus = false;
-
-                if (node.flo_set_value) {
-                    node.flo_set_value({ 'String': input.value || '' });
-                }
+                send_value(node.flo_set_value, node.flo_set_unit_value);
             }
         });
 
         add_action_event(node, 'input', event => {
-            if (node.flo_edit_value) {
-                node.flo_edit_value({ 'String': input.value || '' });
+            // Partially composed text isn't part of the value until the composition is committed
+            if (!event.isComposing) {
+                send_value(node.flo_edit_value, node.flo_edit_unit_value);
+            }
+        });
+
+        // Input method composition events
+        add_action_event(node, 'compositionstart', event => {
+            if (node.flo_compose) {
+                node.flo_compose('Start');
+            }
+        });
+
+        add_action_event(node, 'compositionupdate', event => {
+            if (node.flo_compose) {
+                node.flo_compose({ 'Update': event.data || '' });
+            }
+        });
+
+        add_action_event(node, 'compositionend', event => {
+            // A composition that ends with no text has been cancelled
+            let committed = event.data || '';
+
+            if (node.flo_compose) {
+                node.flo_compose(committed !== '' ? { 'Commit': committed } : 'Cancel');
+            }
+
+            if (committed !== '') {
+                send_value(node.flo_edit_value, node.flo_edit_unit_value);
             }
         });
 
         add_action_event(node, 'keydown', event => {
-            if (!event.ctrlKey && !event.altKey && !event.shiftKey && !event.metaKey) {
+            // Enter and escape are used by input methods to commit or cancel a composition
+            if (!event.ctrlKey && !event.altKey && !event.shiftKey && !event.metaKey && !event.isComposing) {
                 if (event.key === 'Enter') {
                     event.preventDefault();
-                    if (node.flo_set_value) {
-                        node.flo_set_value({ 'String': input.value || '' });
-                    }
+                    send_value(node.flo_set_value, node.flo_set_unit_value);
                 } else if (event.key === 'Escape') {
                     event.preventDefault();
                     if (node.flo_cancel_edit) {
@@ -813,6 +895,455 @@ let flo_control = (function () {
         });
     };
 
+    ///
+    /// Sets up a control as a tree view
+    ///
+    let load_tree = (node, add_action_event) => {
+        // The value of a tree is the ID of the selected node
+        let flo_value       = node.flo_value || { 'String': '' };
+        let tree_nodes      = () => [].slice.apply(node.getElementsByTagName('flo-tree-node'));
+        let node_id         = tree_node => tree_node.getAttribute('flo-node-id');
+
+        let update_value = (new_property_value) => {
+            tree_nodes().forEach(tree_node => {
+                if (node_id(tree_node) === new_property_value['String']) {
+                    tree_node.classList.add('selected');
+                } else {
+                    tree_node.classList.remove('selected');
+                }
+            });
+        };
+
+        update_value(flo_value);
+
+        Object.defineProperty(node, 'flo_value', {
+            get: () => flo_value,
+            set: new_value => {
+                if (new_value !== flo_value) {
+                    flo_value = new_value;
+                    update_value(new_value);
+                }
+            }
+        });
+
+        // Trees bound to a property are rendered here rather than by the server (only the visible nodes are generated)
+        let tree_node_element = tree_node => {
+            let children    = tree_node['children'];
+            let state       = children === 'Leaf' ? 'leaf' : children === 'NotLoaded' ? 'lazy' : tree_node['expanded'] ? 'expanded' : 'collapsed';
+            let element     = document.createElement('flo-tree-node');
+            let label       = document.createElement('div');
+
+            element.className   = state;
+            element.setAttribute('flo-node-id', tree_node['id']);
+            label.className     = 'label';
+            label.textContent   = tree_node['label'];
+            element.appendChild(label);
+
+            if (state === 'expanded') {
+                children['Loaded'].forEach(child => element.appendChild(tree_node_element(child)));
+            }
+
+            return element;
+        };
+
+        Object.defineProperty(node, 'flo_tree_nodes', {
+            set: new_nodes => {
+                [].slice.apply(node.children).filter(child => child.tagName.toLowerCase() === 'flo-tree-node').forEach(child => node.removeChild(child));
+                new_nodes.forEach(tree_node => node.appendChild(tree_node_element(tree_node)));
+                update_value(flo_value);
+            }
+        });
+
+        // Selecting, expanding and collapsing nodes generate actions
+        let select      = tree_node => { if (tree_node && node.flo_set_value)       { node.flo_set_value({ 'String': node_id(tree_node) }); } };
+        let expand      = tree_node => { if (tree_node && node.flo_expand_node)     { node.flo_expand_node({ 'String': node_id(tree_node) }); } };
+        let collapse    = tree_node => { if (tree_node && node.flo_collapse_node)   { node.flo_collapse_node({ 'String': node_id(tree_node) }); } };
+        let is_leaf     = tree_node => tree_node.classList.contains('leaf');
+        let is_expanded = tree_node => tree_node.classList.contains('expanded');
+
+        add_action_event(node, 'click', event => {
+            let label = event.target.closest('.label');
+            if (label) {
+                let tree_node = label.parentElement;
+                select(tree_node);
+                node.focus();
+            }
+        });
+
+        add_action_event(node, 'dblclick', event => {
+            let label = event.target.closest('.label');
+            if (label) {
+                let tree_node = label.parentElement;
+                if (!is_leaf(tree_node)) {
+                    is_expanded(tree_node) ? collapse(tree_node) : expand(tree_node);
+                }
+            }
+        });
+
+        // Arrow keys are sent to the session, which works out how they change the selection
+        let tree_keys = { 'ArrowUp': 'KeyUp', 'ArrowDown': 'KeyDown', 'ArrowLeft': 'KeyLeft', 'ArrowRight': 'KeyRight' };
+
+        add_action_event(node, 'keydown', event => {
+            let tree_key = tree_keys[event.key];
+
+            if (tree_key && node.flo_navigate_tree) {
+                event.preventDefault();
+                node.flo_navigate_tree(tree_key);
+            }
+        });
+
+        node.setAttribute('tabindex', '0');
+        node.flo_make_focused = () => { node.focus(); }
+    };
+
+    ///
+    /// Sets up a control as an audio or video player
+    ///
+    let load_media = (node, add_action_event) => {
+        // The value of a media control is the playback position in seconds
+        let flo_value       = node.flo_value || { 'Float': 0.0 };
+        let flo_playing     = node.flo_playing || { 'Bool': false };
+        let media_type      = node.getAttribute('flo-media-type') || 'audio';
+        let media           = document.createElement(media_type === 'video' ? 'video' : 'audio');
+
+        media.setAttribute('controls', '');
+        media.setAttribute('preload', 'auto');
+        media.src           = node.getAttribute('flo-media-src') || '';
+        node.appendChild(media);
+
+        // Only seek if the position is noticeably different from where the media is (so playback isn't interrupted while the controller is following along)
+        let update_position = new_value => {
+            let position = new_value['Float'] || new_value['Int'] || 0.0;
+            if (Math.abs(media.currentTime - position) > 0.1) {
+                media.currentTime = position;
+            }
+        };
+
+        let update_playing = new_value => {
+            if (new_value['Bool'] && media.paused) {
+                media.play().catch(() => {});
+            } else if (!new_value['Bool'] && !media.paused) {
+                media.pause();
+            }
+        };
+
+        update_position(flo_value);
+        update_playing(flo_playing);
+
+        Object.defineProperty(node, 'flo_value', {
+            get: () => flo_value,
+            set: new_value => {
+                flo_value = new_value;
+                update_position(new_value);
+            }
+        });
+
+        Object.defineProperty(node, 'flo_playing', {
+            get: () => flo_playing,
+            set: new_value => {
+                flo_playing = new_value;
+                update_playing(new_value);
+            }
+        });
+
+        // Seeking generates EditValue actions while the user is dragging and a SetValue action when they're done (this works while playing too)
+        add_action_event(media, 'seeking', event => {
+            if (node.flo_edit_value) {
+                node.flo_edit_value({ 'Float': media.currentTime });
+            }
+        });
+
+        add_action_event(media, 'seeked', event => {
+            if (node.flo_set_value) {
+                node.flo_set_value({ 'Float': media.currentTime });
+            }
+        });
+
+        // Starting and stopping playback generate SetPlaying actions
+        add_action_event(media, 'play', event => {
+            if (node.flo_set_playing) {
+                node.flo_set_playing({ 'Bool': true });
+            }
+        });
+
+        add_action_event(media, 'pause', event => {
+            // The media is also paused when it reaches the end: this is reported as a MediaEnded action instead
+            if (node.flo_set_playing && !media.ended) {
+                node.flo_set_playing({ 'Bool': false });
+            }
+        });
+
+        add_action_event(media, 'ended', event => {
+            if (node.flo_media_ended) {
+                node.flo_media_ended();
+            }
+        });
+    };
+
+    ///
+    /// Returns the crop rectangle that results from dragging a handle by an offset (in image pixels)
+    ///
+    /// This matches CropBounds::drag_handle in flo_ui
+    ///
+    let drag_crop_handle = (bounds, handle, dx, dy, image_width, image_height, aspect_ratio) => {
+        if (handle === 'Move') {
+            let width   = Math.min(bounds.width, image_width);
+            let height  = Math.min(bounds.height, image_height);
+            let x       = Math.max(0, Math.min(bounds.x + dx, image_width - width));
+            let y       = Math.max(0, Math.min(bounds.y + dy, image_height - height));
+
+            return { x: x, y: y, width: width, height: height };
+        }
+
+        let move_left   = handle.indexOf('Left') >= 0;
+        let move_right  = handle.indexOf('Right') >= 0;
+        let move_top    = handle.indexOf('Top') >= 0;
+        let move_bottom = handle.indexOf('Bottom') >= 0;
+
+        let left        = Math.max(bounds.x, 0);
+        let top         = Math.max(bounds.y, 0);
+        let right       = Math.min(bounds.x + bounds.width, image_width);
+        let bottom      = Math.min(bounds.y + bounds.height, image_height);
+
+        if (move_left)      { left      = Math.min(Math.max(left + dx, 0), right); }
+        if (move_right)     { right     = Math.max(Math.min(right + dx, image_width), left); }
+        if (move_top)       { top       = Math.min(Math.max(top + dy, 0), bottom); }
+        if (move_bottom)    { bottom    = Math.max(Math.min(bottom + dy, image_height), top); }
+
+        if (!(aspect_ratio > 0)) {
+            return { x: left, y: top, width: right-left, height: bottom-top };
+        }
+
+        // Resize around the opposite corner or edge to keep the aspect ratio, then shrink to fit the image
+        let width       = right-left;
+        let height      = bottom-top;
+
+        if (!move_left && !move_right) {
+            width   = height * aspect_ratio;
+        } else {
+            height  = width / aspect_ratio;
+        }
+
+        let anchor_x    = move_left ? right : move_right ? left : (left+right)/2;
+        let anchor_y    = move_top ? bottom : move_bottom ? top : (top+bottom)/2;
+        let max_width   = move_left ? anchor_x : move_right ? image_width - anchor_x : 2*Math.min(anchor_x, image_width - anchor_x);
+        let max_height  = move_top ? anchor_y : move_bottom ? image_height - anchor_y : 2*Math.min(anchor_y, image_height - anchor_y);
+
+        if (width > max_width)      { width = max_width; height = width / aspect_ratio; }
+        if (height > max_height)    { height = max_height; width = height * aspect_ratio; }
+
+        return {
+            x:      move_left ? anchor_x - width : move_right ? anchor_x : anchor_x - width/2,
+            y:      move_top ? anchor_y - height : move_bottom ? anchor_y : anchor_y - height/2,
+            width:  width,
+            height: height
+        };
+    };
+
+    ///
+    /// Loads a crop control
+    ///
+    let load_crop_image = (node, add_action_event) => {
+        let aspect_ratio    = parseFloat(node.getAttribute('flo-crop-aspect-ratio')) || null;
+        let initial_bounds  = (node.getAttribute('flo-crop-bounds') || '').split(',').map(parseFloat);
+        let image_width     = 0;
+        let image_height    = 0;
+        let bounds          = null;
+
+        // The crop rectangle, with a handle at each corner and edge
+        let rect            = document.createElement('flo-crop-rect');
+        let handles         = ['TopLeft', 'Top', 'TopRight', 'Right', 'BottomRight', 'Bottom', 'BottomLeft', 'Left'];
+
+        handles.forEach(handle_name => {
+            let handle = document.createElement('flo-crop-handle');
+            handle.className        = handle_name;
+            handle.flo_crop_handle  = handle_name;
+            rect.appendChild(handle);
+        });
+        rect.flo_crop_handle = 'Move';
+        node.appendChild(rect);
+
+        // The image is displayed as the background (centered and scaled to fit the control)
+        let image_scale     = () => Math.min(node.clientWidth / image_width, node.clientHeight / image_height);
+        let image_origin    = () => {
+            let scale = image_scale();
+            return { x: (node.clientWidth - image_width*scale)/2, y: (node.clientHeight - image_height*scale)/2 };
+        };
+
+        let position_rect   = () => {
+            if (!bounds || !image_width || !image_height) {
+                rect.style.display = 'none';
+                return;
+            }
+
+            let scale   = image_scale();
+            let origin  = image_origin();
+
+            rect.style.display  = '';
+            rect.style.left     = (origin.x + bounds.x*scale) + 'px';
+            rect.style.top      = (origin.y + bounds.y*scale) + 'px';
+            rect.style.width    = (bounds.width*scale) + 'px';
+            rect.style.height   = (bounds.height*scale) + 'px';
+        };
+
+        // Need the natural size of the image to map between the control and image pixels
+        let image_url       = /url\(["']?([^"')]*)["']?\)/.exec(node.style.backgroundImage || '');
+        if (image_url) {
+            let image   = new Image();
+            image.onload = () => {
+                image_width     = image.naturalWidth;
+                image_height    = image.naturalHeight;
+
+                if (initial_bounds.length === 4 && initial_bounds.every(val => !isNaN(val))) {
+                    bounds = drag_crop_handle({ x: initial_bounds[0], y: initial_bounds[1], width: initial_bounds[2], height: initial_bounds[3] }, 'Move', 0, 0, image_width, image_height, null);
+                } else {
+                    bounds = { x: 0, y: 0, width: image_width, height: image_height };
+                }
+
+                position_rect();
+            };
+            image.src = image_url[1];
+        }
+
+        // Dragging a handle resizes the rectangle, and the drag is sent to the session as a Crop action when it finishes (the
+        // session works out the final bounds, so the rectangle displayed here is just a preview)
+        add_action_event(rect, 'mousedown', event => {
+            if (event.button !== 0 || !bounds) {
+                return;
+            }
+
+            event.preventDefault();
+            event.stopPropagation();
+
+            let handle          = event.target.flo_crop_handle || 'Move';
+            let start_bounds    = bounds;
+            let start_x         = event.clientX;
+            let start_y         = event.clientY;
+            let scale           = image_scale();
+            let dx              = 0;
+            let dy              = 0;
+
+            let mouse_move = event => {
+                event.preventDefault();
+
+                dx      = (event.clientX - start_x) / scale;
+                dy      = (event.clientY - start_y) / scale;
+
+                bounds  = drag_crop_handle(start_bounds, handle, dx, dy, image_width, image_height, aspect_ratio);
+                position_rect();
+            };
+
+            let mouse_up = event => {
+                event.preventDefault();
+
+                document.removeEventListener('mousemove', mouse_move, true);
+                document.removeEventListener('mouseup', mouse_up, true);
+
+                if (node.flo_crop_drag) {
+                    node.flo_crop_drag(start_bounds, handle, [dx, dy], [image_width, image_height]);
+                }
+            };
+
+            document.addEventListener('mousemove', mouse_move, true);
+            document.addEventListener('mouseup', mouse_up, true);
+        });
+    };
+
+    ///
+    /// Returns the proportion of a gauge that's filled in for a value (this matches GaugeArc::proportion in flo_ui)
+    ///
+    let gauge_proportion = (value, min, max) => {
+        if (!(max > min) || isNaN(value)) {
+            return 0.0;
+        }
+
+        return Math.min(1.0, Math.max(0.0, (value-min)/(max-min)));
+    };
+
+    ///
+    /// Returns the SVG path data for an arc (angles are in degrees clockwise from the 3 o'clock position)
+    ///
+    let svg_arc = (cx, cy, radius, start_angle, end_angle) => {
+        // SVG can't draw an arc whose start and end points are the same, so full circles are drawn very slightly short
+        let sweep       = Math.min(Math.max(end_angle - start_angle, 0.0), 359.99);
+        let start       = start_angle / 180.0 * Math.PI;
+        let end         = (start_angle + sweep) / 180.0 * Math.PI;
+        let large_arc   = sweep > 180.0 ? 1 : 0;
+
+        return 'M' + (cx + radius*Math.cos(start)) + ' ' + (cy + radius*Math.sin(start))
+            + ' A' + radius + ' ' + radius + ' 0 ' + large_arc + ' 1 ' + (cx + radius*Math.cos(end)) + ' ' + (cy + radius*Math.sin(end));
+    };
+
+    ///
+    /// Sets up a control as a gauge
+    ///
+    let load_gauge = (node) => {
+        let flo_min_value   = node.flo_min_value || { 'Float': 0.0 };
+        let flo_max_value   = node.flo_max_value || { 'Float': 100.0 };
+        let flo_value       = node.flo_value || { 'Float': 0.0 };
+        let start_angle     = parseFloat(node.getAttribute('flo-gauge-start-angle'));
+        let sweep_angle     = parseFloat(node.getAttribute('flo-gauge-sweep-angle'));
+
+        if (isNaN(start_angle)) { start_angle = 135.0; }
+        if (isNaN(sweep_angle)) { sweep_angle = 270.0; }
+
+        // The gauge is drawn as a track with the value arc on top of it
+        let svg_namespace   = 'http://www.w3.org/2000/svg';
+        let svg             = document.createElementNS(svg_namespace, 'svg');
+        let track           = document.createElementNS(svg_namespace, 'path');
+        let value_arc       = document.createElementNS(svg_namespace, 'path');
+
+        svg.setAttribute('viewBox', '0 0 100 100');
+        track.setAttribute('class', 'flo-gauge-track');
+        value_arc.setAttribute('class', 'flo-gauge-value');
+        track.setAttribute('d', svg_arc(50, 50, 40, start_angle, start_angle + sweep_angle));
+
+        svg.appendChild(track);
+        svg.appendChild(value_arc);
+        node.appendChild(svg);
+
+        ///
+        /// Updates the arc to show the current value
+        ///
+        let update_value = () => {
+            let as_float    = property => property['Float'] !== undefined ? property['Float'] : (property['Int'] || 0);
+            let proportion  = gauge_proportion(as_float(flo_value), as_float(flo_min_value), as_float(flo_max_value));
+
+            if (proportion > 0) {
+                value_arc.setAttribute('d', svg_arc(50, 50, 40, start_angle, start_angle + sweep_angle*proportion));
+            } else {
+                value_arc.removeAttribute('d');
+            }
+        };
+
+        update_value();
+
+        // The value and range are updated by the controller
+        Object.defineProperty(node, 'flo_value', {
+            get: () => flo_value,
+            set: new_value => {
+                flo_value = new_value;
+                update_value();
+            }
+        });
+
+        Object.defineProperty(node, 'flo_min_value', {
+            get: () => flo_min_value,
+            set: new_value => {
+                flo_min_value = new_value;
+                update_value();
+            }
+        });
+
+        Object.defineProperty(node, 'flo_max_value', {
+            get: () => flo_max_value,
+            set: new_value => {
+                flo_max_value = new_value;
+                update_value();
+            }
+        });
+    };
+
     ///
     /// Declare custom elements
     ///
@@ -929,6 +1460,10 @@ let flo_control = (function () {
         load_popup:             load_popup,
         load_textbox:           load_textbox,
         load_checkbox:          load_checkbox,
+        load_tree:              load_tree,
+        load_media:             load_media,
+        load_crop_image:        load_crop_image,
+        load_gauge:             load_gauge,
         layout_popup:           layout_popup,
         on_drag:                on_drag,
         fix_scroll_positions:   fix_scroll_positions
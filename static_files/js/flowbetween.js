@@ -580,6 +580,11 @@ function flowbetween(root_node) {
             return Object.assign.apply(null, [{}].concat(popups));
         };
 
+        // classes returns the list of style classes attached to this control
+        let classes = () => {
+            return [].concat.apply([], get_attrs('Classes') || []);
+        };
+
         // Return an object that can be used to get information about these attributes
         return {
             all:            all,
@@ -590,7 +595,8 @@ function flowbetween(root_node) {
             bounding_box:   bounding_box,
             padding:        padding,
             popup:          popup,
-            scrolls:        scrolls
+            scrolls:        scrolls,
+            classes:        classes
         };
     };
 
@@ -1147,6 +1153,34 @@ function flowbetween(root_node) {
         flo_control.on_drag(node, add_action_event, start_drag, continue_drag, finish_drag, cancel_drag);
     };
 
+    ///
+    /// Wires up a secondary click (context menu) action to a node
+    ///
+    let wire_secondary_click = (action_name, node, controller_path) => {
+        add_action_event(node, 'contextmenu', event => {
+            // The control is handling the context menu, so the browser shouldn't display its own
+            event.preventDefault();
+            event.stopPropagation();
+
+            // Position is relative to the node, or in canvas coordinates if this node is a canvas
+            let client_rect = node.getBoundingClientRect();
+            let x           = event.clientX - client_rect.left;
+            let y           = event.clientY - client_rect.top;
+
+            if (node.flo_map_coords) {
+                let coords  = node.flo_map_coords(x, y);
+                x           = coords[0];
+                y           = coords[1];
+            }
+
+            // Context menus opened with ctrl+click or from the keyboard report button 0, so those are treated as right-clicks too
+            let button      = event.button === 1 ? 'Middle' : event.button > 2 ? { 'Other': event.button } : 'Right';
+
+            note('Secondary click ' + action_name + ' --> ' + controller_path);
+            perform_action(controller_path, action_name, { 'SecondaryClick': [ button, [x, y] ] });
+        });
+    };
+
     ///
     /// Rewires any intrinsic events that might have been removed by a
     /// call to remove_action_events_from_node
@@ -1175,15 +1209,30 @@ function flowbetween(root_node) {
         if (action_type === 'Click') {
             wire_click(action_name, node, controller_path);
 
+        } else if (action_type === 'SecondaryClick') {
+            wire_secondary_click(action_name, node, controller_path);
+
         } else if (action_type['VirtualScroll']) {
             wire_virtual_scroll(action_name, node, controller_path, action_type['VirtualScroll'][0], action_type['VirtualScroll'][1]);
 
+        } else if (action_type === 'Scroll') {
+            wire_scroll(action_name, node, controller_path);
+
         } else if (action_type['Paint']) {
             flo_paint.wire_paint(action_type['Paint'], action_name, node, controller_path);
 
         } else if (action_type === 'Drag') {
             wire_drag(action_name, node, controller_path);
 
+        } else if (action_type === 'DragStart') {
+            node.flo_drag_start = payload => perform_action(controller_path, action_name, { 'DragPayload': payload });
+
+        } else if (action_type === 'DragOver') {
+            node.flo_drag_over = payload => perform_action(controller_path, action_name, { 'DragPayload': payload });
+
+        } else if (action_type === 'Drop') {
+            node.flo_drop = payload => perform_action(controller_path, action_name, { 'DragPayload': payload });
+
         } else if (action_type === 'Resize') {
             node.flo_resize = (width, height) => {
                 if (width !== node.flo_last_width || height !== node.flo_last_height) {
@@ -1198,14 +1247,35 @@ function flowbetween(root_node) {
             node.flo_was_focused = new_property_value => perform_action(controller_path, action_name, null);
 
         } else if (action_type === 'EditValue') {
-            node.flo_edit_value = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+            node.flo_edit_value         = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+            node.flo_edit_unit_value    = new_unit_value => perform_action(controller_path, action_name, { 'UnitValue': new_unit_value });
 
         } else if (action_type === 'SetValue') {
-            node.flo_set_value = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+            node.flo_set_value          = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+            node.flo_set_unit_value     = new_unit_value => perform_action(controller_path, action_name, { 'UnitValue': new_unit_value });
+            node.flo_navigate_tree      = tree_key => perform_action(controller_path, action_name, { 'TreeKey': tree_key });
+
+        } else if (action_type === 'ExpandNode') {
+            node.flo_expand_node = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+
+        } else if (action_type === 'CollapseNode') {
+            node.flo_collapse_node = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+
+        } else if (action_type === 'SetPlaying') {
+            node.flo_set_playing = new_property_value => perform_action(controller_path, action_name, { 'Value': new_property_value });
+
+        } else if (action_type === 'MediaEnded') {
+            node.flo_media_ended = () => perform_action(controller_path, action_name, null);
+
+        } else if (action_type === 'Crop') {
+            node.flo_crop_drag = (start_bounds, handle, offset, image_size) => perform_action(controller_path, action_name, { 'CropDrag': [start_bounds, handle, offset, image_size] });
 
         } else if (action_type === 'CancelEdit') {
             node.flo_cancel_edit = new_property_value => perform_action(controller_path, action_name, null);
 
+        } else if (action_type === 'Compose') {
+            node.flo_compose = composition => perform_action(controller_path, action_name, { 'Composition': composition });
+
         } else if (action_type === 'Dismiss') {
             node.flo_dismiss = () => perform_action(controller_path, action_name, null);
 
@@ -1331,6 +1401,55 @@ function flowbetween(root_node) {
         on_scroll();
     };
 
+    ///
+    /// Wires a node to send its scroll position and size whenever it's scrolled or resized
+    ///
+    let wire_scroll = (action_name, node, controller_path) => {
+        let will_scroll = false;
+        let scroll_now  = () => {
+            let position        = [node.scrollLeft, node.scrollTop];
+            let content_size    = [node.scrollWidth, node.scrollHeight];
+            let viewport_size   = [node.clientWidth, node.clientHeight];
+            let state           = { position, content_size, viewport_size };
+
+            // Only send the event if something has changed
+            let last_state      = node.flo_scroll_state;
+            let changed         = !last_state
+                || last_state.position[0] !== position[0] || last_state.position[1] !== position[1]
+                || last_state.content_size[0] !== content_size[0] || last_state.content_size[1] !== content_size[1]
+                || last_state.viewport_size[0] !== viewport_size[0] || last_state.viewport_size[1] !== viewport_size[1];
+
+            if (changed) {
+                node.flo_scroll_state = state;
+                perform_action(controller_path, action_name, { 'Scroll': state });
+            }
+        };
+
+        let on_scroll   = () => {
+            if (!will_scroll) {
+                will_scroll = true;
+                requestAnimationFrame(() => {
+                    will_scroll = false;
+                    scroll_now();
+                });
+            }
+        };
+
+        // Send the position whenever the node scrolls or changes size
+        add_action_event(node, 'scroll', () => on_scroll());
+
+        let more_resize = node.flo_resize;
+        node.flo_resize = (width, height, element) => {
+            on_scroll();
+            if (more_resize) {
+                more_resize(width, height, element);
+            }
+        };
+
+        // Send the initial position
+        on_scroll();
+    };
+
     ///
     /// Binds a single attribute to a node
     ///
@@ -1367,6 +1486,18 @@ function flowbetween(root_node) {
                 return true;
             });
 
+        } else if (attribute['SubtreeEnabled']) {
+            // Disabling a subtree blocks interaction with everything inside this node
+            remove_action = on_property_change(controller_path, attribute['SubtreeEnabled'], is_enabled => {
+                if (is_enabled['Bool'] === false) {
+                    add_class(node, 'subtree-disabled');
+                } else {
+                    remove_class(node, 'subtree-disabled');
+                }
+
+                return true;
+            });
+
         } else if (attribute['Badged']) {
             // The badged property updates the node class
             remove_action = on_property_change(controller_path, attribute['Badged'], is_badged => {
@@ -1386,6 +1517,27 @@ function flowbetween(root_node) {
                 return true;
             });
 
+        } else if (attribute['Tree'] && attribute['Tree']['Bound']) {
+            // Bound trees are rendered from the JSON in the property
+            remove_action = on_property_change(controller_path, attribute['Tree']['Bound'], new_value => {
+                node.flo_tree_nodes = new_value['String'] ? JSON.parse(new_value['String']) : [];
+                return true;
+            });
+
+        } else if (attribute['BoundClasses']) {
+            // Bound classes replace the classes from the previous value of the property
+            let bound_classes = [];
+
+            remove_action = on_property_change(controller_path, attribute['BoundClasses'], new_value => {
+                let new_classes = (new_value['String'] || '').split(' ').filter(class_name => class_name.length > 0);
+
+                bound_classes.filter(class_name => new_classes.indexOf(class_name) < 0).forEach(class_name => remove_class(node, class_name));
+                new_classes.forEach(class_name => add_class(node, class_name));
+                bound_classes = new_classes;
+
+                return true;
+            });
+
         } else if (attribute['Text']) {
             // Value just updates the flo_text property
             remove_action = on_property_change(controller_path, attribute['Text'], new_value => {
@@ -1416,6 +1568,13 @@ function flowbetween(root_node) {
                 return true;
             });
 
+        } else if (attribute['Media'] && attribute['Media']['Playing']) {
+            // Starts or stops a media control
+            remove_action = on_property_change(controller_path, attribute['Media']['Playing'], new_value => {
+                node.flo_playing = new_value;
+                return true;
+            });
+
         } else if (attribute['Scroll']) {
             let scroll = attribute['Scroll'];
 
@@ -1425,6 +1584,16 @@ function flowbetween(root_node) {
                 canvas_deco.style.height    = scroll['MinimumContentSize'][1] + 'px';
             }
 
+            if (scroll['ScrollTo']) {
+                // Scroll once the content has been laid out (the browser stops us from scrolling past the end of the content)
+                let [scroll_x, scroll_y] = scroll['ScrollTo'];
+
+                requestAnimationFrame(() => {
+                    if (node.scrollLeft !== scroll_x)   { node.scrollLeft = scroll_x; }
+                    if (node.scrollTop !== scroll_y)    { node.scrollTop = scroll_y; }
+                });
+            }
+
         } else if (attribute['FocusPriority']) {
             // Updates the focus priority for this node
             remove_action = on_property_change(controller_path, attribute['FocusPriority'], focus_priority => {
@@ -1552,6 +1721,113 @@ function flowbetween(root_node) {
         });
     };
 
+    ///
+    /// ===== DRAG AND DROP
+    ///
+
+    // The payload that's currently being dragged, and the drop target it's over
+    let current_drag_payload    = null;
+    let current_drop_node       = null;
+
+    ///
+    /// Returns true if a drop target node accepts the specified payload
+    ///
+    let accepts_payload = (drop_node, payload) => {
+        let drop_types = (drop_node.getAttribute('flo-drop-types') || '').split(' ');
+        return drop_types.indexOf(payload.payload_type) >= 0;
+    };
+
+    ///
+    /// Removes the drop target highlighting from the node that the payload is currently over
+    ///
+    let leave_drop_node = () => {
+        if (current_drop_node) {
+            remove_class(current_drop_node, 'flo-drop-accepted');
+            remove_class(current_drop_node, 'flo-drop-rejected');
+            current_drop_node = null;
+        }
+    };
+
+    ///
+    /// The user has started dragging a node
+    ///
+    let drag_payload_start = (event) => {
+        let drag_node = event.target.closest ? event.target.closest('[flo-drag-payload]') : null;
+        if (!drag_node) {
+            return;
+        }
+
+        current_drag_payload = JSON.parse(drag_node.getAttribute('flo-drag-payload'));
+
+        // Browsers only allow the drag to start if there's some data attached to it
+        event.dataTransfer.setData('application/x-flo-drag-payload', JSON.stringify(current_drag_payload));
+        event.dataTransfer.effectAllowed = 'move';
+
+        if (drag_node.flo_drag_start) {
+            drag_node.flo_drag_start(current_drag_payload);
+        }
+    };
+
+    ///
+    /// The payload has been dragged over a node
+    ///
+    let drag_payload_over = (event) => {
+        if (!current_drag_payload) {
+            return;
+        }
+
+        let drop_node = event.target.closest ? event.target.closest('[flo-drop-types]') : null;
+        let accepted  = drop_node && accepts_payload(drop_node, current_drag_payload);
+
+        // Highlight the drop target (incompatible targets are highlighted as rejected)
+        if (drop_node !== current_drop_node) {
+            leave_drop_node();
+
+            if (drop_node) {
+                current_drop_node = drop_node;
+                add_class(drop_node, accepted ? 'flo-drop-accepted' : 'flo-drop-rejected');
+
+                if (accepted && drop_node.flo_drag_over) {
+                    drop_node.flo_drag_over(current_drag_payload);
+                }
+            }
+        }
+
+        // The drop is only allowed if the default action is prevented
+        if (accepted) {
+            event.preventDefault();
+            event.dataTransfer.dropEffect = 'move';
+        } else {
+            event.dataTransfer.dropEffect = 'none';
+        }
+    };
+
+    ///
+    /// The payload has been dropped on a node
+    ///
+    let drop_payload = (event) => {
+        let drop_node = current_drop_node;
+        let payload   = current_drag_payload;
+
+        leave_drop_node();
+
+        if (drop_node && payload && accepts_payload(drop_node, payload)) {
+            event.preventDefault();
+
+            if (drop_node.flo_drop) {
+                drop_node.flo_drop(payload);
+            }
+        }
+    };
+
+    ///
+    /// The drag operation has finished (whether or not it was dropped anywhere)
+    ///
+    let drag_payload_end = () => {
+        leave_drop_node();
+        current_drag_payload = null;
+    };
+
     ///
     /// ===== VIEWMODEL
     ///
@@ -1796,19 +2072,30 @@ function flowbetween(root_node) {
                 // Decode the updates from the message
                 let updates = JSON.parse(event.data);
 
-                // Dispatch them
+                // Dispatch them, then tell the server we're ready for more (it holds back and merges updates until we do)
+                let acknowledge = () => {
+                    if (websocket.readyState === WebSocket.OPEN) {
+                        websocket.send('ack');
+                    }
+                };
+
                 current_update_promise = current_update_promise.then(() => dispatch_updates(updates))
                     .then(() => {
                         current_update_promise = Promise.resolve();
+                        acknowledge();
                     })
                     .catch((err) => {
                         current_update_promise = Promise.resolve();
+                        acknowledge();
                         error('Request failed.', err);
                     });
             });
 
+            let is_open = false;
+
             websocket.addEventListener('open', () => {
                 note('Websocket for session ' + session_id + ' is connected');
+                is_open = true;
 
                 // Register this as the socket for this session
                 websocket_for_session[session_id] = websocket;
@@ -1822,6 +2109,15 @@ function flowbetween(root_node) {
                 error('Session ' + session_id + ' suffered a websocket error: ', event);
                 websocket_for_session[session_id] = null;
             });
+
+            websocket.addEventListener('close', (event) => {
+                // The server keeps the session for a while, so reconnect if the connection drops (the server closes the socket normally if another socket replaces it)
+                if (is_open && event.code !== 1000) {
+                    note('Websocket for session ' + session_id + ' closed: reconnecting');
+                    websocket_for_session[session_id] = null;
+                    setTimeout(() => connect_websocket(websocket_port, session_id), 1000);
+                }
+            });
         });
 
         return connect;
@@ -1927,6 +2223,181 @@ function flowbetween(root_node) {
         });
     };
 
+    ///
+    /// Creates a DOM element from the HTML sent by the server
+    ///
+    let element_from_html = (html) => {
+        let template        = document.createElement('template');
+        template.innerHTML  = html;
+
+        return template.content.childNodes[0];
+    };
+
+    ///
+    /// Replaces the controls in the HTML tree described by a list of HTML diffs
+    ///
+    let replace_html = (updates) => {
+        // Find the original nodes for each update
+        updates.forEach(update => {
+            update.original_node    = node_at_address(update.address);
+            update.original_data    = data_at_address(update.address);
+        });
+
+        // Unwire the original DOM
+        updates.forEach(update => {
+            unwire_node(update.original_node);
+        });
+
+        // Replace the data for each element involved in the update
+        updates.forEach(update => {
+            let address = update.address;
+
+            if (address.length === 0) {
+                // 0-length addresses replace the root node
+                root_control_data = update.ui_tree;
+            } else {
+                // Other attribute replace the subcomponents
+                let node_index  = address[address.length-1];
+                let parent_node = update.original_data.parent;
+                let attributes  = get_attributes(parent_node);
+
+                attributes.subcomponents()[node_index] = update.ui_tree;
+            }
+        });
+
+        // Replace the HTML for each element involved in the update
+        updates.forEach(update => {
+            // Generate the replacement HTML element
+            let new_element     = element_from_html(update.new_html);
+
+            // Replace the original node
+            let parent_node     = update.original_node.parentNode;
+            if (parent_node) {
+                parent_node.replaceChild(new_element, update.original_node);
+            }
+
+            update.new_element  = new_element;
+        });
+
+        // Reformat/bind/wire the new HTML
+        updates.forEach(update => {
+            set_tree_attributes(update.new_element, update.ui_tree, update.original_data.controller_path);
+            apply_templates_to_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
+            bind_viewmodel_to_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
+            wire_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
+        });
+    };
+
+    ///
+    /// Inserts a new control into the HTML tree
+    ///
+    let insert_html = (insert) => {
+        // Find the parent node and the controller path for the new node
+        let node_address    = insert.address.concat([insert.index]);
+        let parent_node     = node_at_address(insert.address);
+        let node_data       = data_at_address(node_address);
+        let attributes      = get_attributes(node_data.parent);
+
+        // Add to the control data
+        let subcomponents   = attributes.subcomponents();
+        if (subcomponents === null) {
+            subcomponents = [];
+            attributes.all().push({ 'SubComponents': subcomponents });
+        }
+
+        subcomponents.splice(insert.index, 0, insert.ui_tree);
+
+        // Add to the DOM, after the node that will now precede it
+        let new_element     = element_from_html(insert.new_html);
+        let subnodes        = get_flo_subnodes(parent_node);
+
+        if (insert.index < subnodes.length) {
+            parent_node.insertBefore(new_element, subnodes[insert.index]);
+        } else if (subnodes.length > 0) {
+            parent_node.insertBefore(new_element, subnodes[subnodes.length-1].nextSibling);
+        } else {
+            parent_node.appendChild(new_element);
+        }
+
+        // Format/bind/wire the new node
+        set_tree_attributes(new_element, insert.ui_tree, node_data.controller_path);
+        apply_templates_to_tree(new_element, insert.ui_tree, node_data.controller_path);
+        bind_viewmodel_to_tree(new_element, insert.ui_tree, node_data.controller_path);
+        wire_tree(new_element, insert.ui_tree, node_data.controller_path);
+    };
+
+    ///
+    /// Changes the classes of a control without replacing it
+    ///
+    let set_classes_html = (set_classes) => {
+        let node            = node_at_address(set_classes.address);
+        let control_data    = get_attributes(data_at_address(set_classes.address).data);
+        let attributes      = control_data.all();
+        let old_classes     = control_data.classes();
+        let new_classes     = set_classes.classes;
+
+        // Update the control data
+        let other_attributes = attributes.filter(attr => Object.keys(attr)[0] !== 'Classes');
+        attributes.splice(0, attributes.length, ...other_attributes.concat([{ 'Classes': new_classes }]));
+
+        // Update the DOM node
+        old_classes.filter(class_name => new_classes.indexOf(class_name) < 0).forEach(class_name => remove_class(node, class_name));
+        new_classes.forEach(class_name => add_class(node, class_name));
+    };
+
+    ///
+    /// Removes a control from the HTML tree
+    ///
+    let delete_html = (deletion) => {
+        let parent_node     = node_at_address(deletion.address);
+        let parent_data     = data_at_address(deletion.address);
+        let node            = get_flo_subnodes(parent_node)[deletion.index];
+
+        // Remove from the control data
+        get_attributes(parent_data.data).subcomponents().splice(deletion.index, 1);
+
+        // Remove from the DOM
+        unwire_node(node);
+        parent_node.removeChild(node);
+    };
+
+    ///
+    /// Moves a control to a new position amongst its siblings
+    ///
+    let move_html = (move) => {
+        let parent_node     = node_at_address(move.address);
+        let parent_data     = data_at_address(move.address);
+
+        // Move the control data
+        let subcomponents   = get_attributes(parent_data.data).subcomponents();
+        let moved_data      = subcomponents.splice(move.from, 1)[0];
+        subcomponents.splice(move.to, 0, moved_data);
+
+        // Move the DOM node: the node stays wired up as its controller path is unchanged
+        let subnodes        = get_flo_subnodes(parent_node);
+        let node            = subnodes[move.from];
+        subnodes.splice(move.from, 1);
+
+        if (move.to < subnodes.length) {
+            parent_node.insertBefore(node, subnodes[move.to]);
+        } else {
+            parent_node.insertBefore(node, subnodes[subnodes.length-1].nextSibling);
+        }
+    };
+
+    ///
+    /// Lays out the UI and tidies up the canvases after the HTML tree has changed
+    ///
+    let finish_html_update = () => {
+        // Update the layout of everything once we're done
+        if (root_control_data) {
+            layout_tree(get_flo_subnodes(get_root())[0], root_control_data);
+        }
+
+        // Tidy canvases if necessary
+        flo_canvas.update_canvas_map();
+    };
+
     ///
     /// A portion of the HTML tree has been updated
     ///
@@ -1935,65 +2406,42 @@ function flowbetween(root_node) {
         note('Updating HTML');
 
         return new Promise((resolve) => {
-            // Find the original nodes for each update
-            updates.forEach(update => {
-                update.original_node    = node_at_address(update.address);
-                update.original_data    = data_at_address(update.address);
-            });
+            replace_html(updates);
+            finish_html_update();
 
-            // Unwire the original DOM
-            updates.forEach(update => {
-                unwire_node(update.original_node);
-            });
+            resolve();
+        });
 
-            // Replace the data for each element involved in the update
-            updates.forEach(update => {
-                let address = update.address;
+    };
 
-                if (address.length === 0) {
-                    // 0-length addresses replace the root node
-                    root_control_data = update.ui_tree;
-                } else {
-                    // Other attribute replace the subcomponents
-                    let node_index  = address[address.length-1];
-                    let parent_node = update.original_data.parent;
-                    let attributes  = get_attributes(parent_node);
+    ///
+    /// Controls in the HTML tree have been moved, inserted or removed
+    ///
+    /// Edits are applied in order, as the addresses in each edit refer to the tree with the previous edits applied
+    ///
+    let on_edit_html = (edits) => {
 
-                    attributes.subcomponents()[node_index] = update.ui_tree;
-                }
-            });
+        note('Editing HTML');
 
-            // Replace the HTML for each element involved in the update
-            updates.forEach(update => {
-                // Generate the replacement HTML element
-                let template        = document.createElement('template');
-                template.innerHTML  = update.new_html;
-                let new_element     = template.content.childNodes[0];
-
-                // Replace the original node
-                let parent_node     = update.original_node.parentNode;
-                if (parent_node) {
-                    parent_node.replaceChild(new_element, update.original_node);
+        return new Promise((resolve) => {
+            edits.forEach(edit => {
+                // serde encodes enums as objects, so we can tell what is what by looking at the first key
+                if (edit['Replace']) {
+                    replace_html([edit['Replace']]);
+                } else if (edit['SetClasses']) {
+                    set_classes_html(edit['SetClasses']);
+                } else if (edit['Insert']) {
+                    insert_html(edit['Insert']);
+                } else if (edit['Delete']) {
+                    delete_html(edit['Delete']);
+                } else if (edit['Move']) {
+                    move_html(edit['Move']);
+                } else {
+                    warn('Unknown edit type', Object.keys(edit)[0], edit);
                 }
-
-                update.new_element  = new_element;
-            });
-
-            // Reformat/bind/wire the new HTML
-            updates.forEach(update => {
-                set_tree_attributes(update.new_element, update.ui_tree, update.original_data.controller_path);
-                apply_templates_to_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
-                bind_viewmodel_to_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
-                wire_tree(update.new_element, update.ui_tree, update.original_data.controller_path);
             });
 
-            // Update the layout of everything once we're done
-            if (root_control_data) {
-                layout_tree(get_flo_subnodes(get_root())[0], root_control_data);
-            }
-
-            // Tidy canvases if necessary
-            flo_canvas.update_canvas_map();
+            finish_html_update();
 
             resolve();
         });
@@ -2168,6 +2616,13 @@ function flowbetween(root_node) {
                     current_promise = current_promise
                         .then(() => on_update_html(updates));
 
+                } else if (update['EditHtml']) {
+
+                    let edits = update['EditHtml'];
+
+                    current_promise = current_promise
+                        .then(() => on_edit_html(edits));
+
                 } else if (update['UpdateCommands']) {
 
                     let updates = update['UpdateCommands'];
@@ -2374,6 +2829,12 @@ function flowbetween(root_node) {
         }
     });
 
+    // Typed payloads can be dragged between controls
+    root_node.addEventListener('dragstart', drag_payload_start);
+    root_node.addEventListener('dragover', drag_payload_over);
+    root_node.addEventListener('drop', drop_payload);
+    root_node.addEventListener('dragend', drag_payload_end);
+
     // Interacting outside 'dismiss' nodes should fire the 'dismiss' event
     root_node.addEventListener('pointerdown', ev => {
         dismiss_others(ev.target);
@@ -4,6 +4,7 @@
 #![warn(bare_trait_objects)]
 
 extern crate sha2;
+extern crate flate2;
 
 pub mod static_file;
 pub mod static_service;
@@ -13,24 +14,24 @@ pub use static_service::*;
 
 pub fn flowbetween_static_files() -> StaticService {
     StaticService::new(vec![
-        StaticFile::new("/index.html",                      include_bytes!("../html/index.html")),
+        StaticFile::new("/index.html",                      include_bytes!("../html/index.html")).with_compression(),
 
-        StaticFile::new("/css/flowbetween.css",             include_bytes!("../css/flowbetween.css")),
+        StaticFile::new("/css/flowbetween.css",             include_bytes!("../css/flowbetween.css")).with_compression(),
 
-        StaticFile::new("/js/flowbetween.js",               include_bytes!("../js/flowbetween.js")),
-        StaticFile::new("/js/canvas.js",                    include_bytes!("../js/canvas.js")),
-        StaticFile::new("/js/matrix.js",                    include_bytes!("../js/matrix.js")),
-        StaticFile::new("/js/paint.js",                     include_bytes!("../js/paint.js")),
-        StaticFile::new("/js/control.js",                   include_bytes!("../js/control.js")),
-        StaticFile::new("/js/keyboard.js",                  include_bytes!("../js/keyboard.js")),
+        StaticFile::new("/js/flowbetween.js",               include_bytes!("../js/flowbetween.js")).with_compression(),
+        StaticFile::new("/js/canvas.js",                    include_bytes!("../js/canvas.js")).with_compression(),
+        StaticFile::new("/js/matrix.js",                    include_bytes!("../js/matrix.js")).with_compression(),
+        StaticFile::new("/js/paint.js",                     include_bytes!("../js/paint.js")).with_compression(),
+        StaticFile::new("/js/control.js",                   include_bytes!("../js/control.js")).with_compression(),
+        StaticFile::new("/js/keyboard.js",                  include_bytes!("../js/keyboard.js")).with_compression(),
 
-        StaticFile::new("/svg/controls/button.svg",         include_bytes!("../svg/controls/button.svg")),
+        StaticFile::new("/svg/controls/button.svg",         include_bytes!("../svg/controls/button.svg")).with_compression(),
         StaticFile::new("/png/Flo-Orb-small.png",           include_bytes!("../png/Flo-Orb-small.png")),
 
         StaticFile::new("/fonts/lato/Lato-Bold.woff2",      include_bytes!("../fonts/lato/Lato-Bold.woff2")),
         StaticFile::new("/fonts/lato/Lato-Regular.woff2",   include_bytes!("../fonts/lato/Lato-Regular.woff2")),
         StaticFile::new("/fonts/lato/Lato-Thin.woff2",      include_bytes!("../fonts/lato/Lato-Thin.woff2")),
-        StaticFile::new("/fonts/lato/OFL.txt",              include_bytes!("../fonts/lato/OFL.txt")),
-        StaticFile::new("/fonts/lato/README-WEB.txt",       include_bytes!("../fonts/lato/README-WEB.txt"))
+        StaticFile::new("/fonts/lato/OFL.txt",              include_bytes!("../fonts/lato/OFL.txt")).with_compression(),
+        StaticFile::new("/fonts/lato/README-WEB.txt",       include_bytes!("../fonts/lato/README-WEB.txt")).with_compression()
     ])
 }
@@ -1,4 +1,32 @@
 use sha2::{Sha256, Digest};
+use flate2::{Compression};
+use flate2::write::{GzEncoder};
+
+use std::io::{Write};
+
+///
+/// The encodings that the content of a static file can be supplied in
+///
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ContentEncoding {
+    /// The raw content of the file
+    Identity,
+
+    /// The content of the file compressed with gzip
+    Gzip
+}
+
+impl ContentEncoding {
+    ///
+    /// Returns the value to use for the `Content-Encoding` header when sending content in this encoding (or None if the header should be left out)
+    ///
+    pub fn content_encoding_header(&self) -> Option<&'static str> {
+        match self {
+            ContentEncoding::Identity   => None,
+            ContentEncoding::Gzip       => Some("gzip")
+        }
+    }
+}
 
 ///
 /// Represents a static file
@@ -7,42 +35,46 @@ pub struct StaticFile {
     mime_type: String,
     path: String,
     etag: String,
-    content: Vec<u8>
+    content: Vec<u8>,
+    gzip_content: Option<Vec<u8>>
 }
 
 impl StaticFile {
     ///
-    /// Returns the MIME type for a particular path
+    /// Returns the MIME type for a particular path, based on its extension
     ///
     fn infer_mime_type<'a>(path: &str) -> &'a str {
-        if path.ends_with("html") || path.ends_with("htm") {
-            "text/html; charset=utf-8"
-        } else if path.ends_with("css") {
-            "text/css; charset=utf-8"
-        } else if path.ends_with("js") {
-            "text/javascript; charset=utf-8"
-        } else if path.ends_with("json") {
-            "application/json; charset=utf-8"
-        } else if path.ends_with("png") {
-            "image/png"
-        } else if path.ends_with("gif") {
-            "image/gif"
-        } else if path.ends_with("jpg") || path.ends_with("jpeg") {
-            "image/jpeg"
-        } else if path.ends_with("svg") {
-            "image/svg+xml; charset=utf-8"
-        } else if path.ends_with("txt") {
-            "text/plain; charset=utf-8"
-        } else if path.ends_with("woff2") {
-            "font/woff2"
-        } else {
-            "application/octet-stream"
+        // The extension is whatever follows the last '.' in the filename
+        let filename    = path.rsplit('/').next().unwrap_or(path);
+        let extension   = filename.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("html") | Some("htm")  => "text/html; charset=utf-8",
+            Some("css")                 => "text/css; charset=utf-8",
+            Some("js")                  => "text/javascript; charset=utf-8",
+            Some("json") | Some("map")  => "application/json; charset=utf-8",
+            Some("txt")                 => "text/plain; charset=utf-8",
+            Some("xml")                 => "application/xml; charset=utf-8",
+            Some("svg")                 => "image/svg+xml; charset=utf-8",
+            Some("png")                 => "image/png",
+            Some("gif")                 => "image/gif",
+            Some("jpg") | Some("jpeg")  => "image/jpeg",
+            Some("webp")                => "image/webp",
+            Some("ico")                 => "image/x-icon",
+            Some("woff2")               => "font/woff2",
+            Some("woff")                => "font/woff",
+            Some("ttf")                 => "font/ttf",
+            Some("wasm")                => "application/wasm",
+            _                           => "application/octet-stream"
         }
     }
 
     ///
     /// Creates an etag from the content for an item
     ///
+    /// The etag is quoted as required by the HTTP spec, and depends only on the content, so it's the same every time the
+    /// same file is loaded.
+    ///
     fn etag_from_content(content: &[u8]) -> String {
         // Hash the content using SHA-256
         let mut hasher = Sha256::default();
@@ -50,25 +82,49 @@ impl StaticFile {
         let output = hasher.finalize();
 
         // Use first few bytes to build a string
-        output.iter()
+        let hash = output.iter()
             .take(8)
-            .fold(String::new(), |so_far, next_byte| so_far + &format!("{:02x}", next_byte))
+            .fold(String::new(), |so_far, next_byte| so_far + &format!("{:02x}", next_byte));
+
+        format!("\"{}\"", hash)
     }
 
     ///
-    /// Creates a new static file with an inferred MIME type
+    /// Creates a new static file with an explicit MIME type
     ///
     pub fn new_with_type(mime_type: &str, path: &str, content: &[u8]) -> StaticFile {
         StaticFile {
-            mime_type:  String::from(mime_type),
-            path:       String::from(path),
-            etag:       StaticFile::etag_from_content(content),
-            content:    Vec::from(content)
+            mime_type:      String::from(mime_type),
+            path:           String::from(path),
+            etag:           StaticFile::etag_from_content(content),
+            content:        Vec::from(content),
+            gzip_content:   None
         }
     }
 
     ///
-    /// Creates a new static file with an explicit MIME type
+    /// Returns a modified version of this file that can also be supplied compressed with gzip
+    ///
+    /// The compressed content is generated immediately. The etag is unchanged, as it's always computed from the raw content.
+    ///
+    pub fn with_compression(mut self) -> StaticFile {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&self.content).unwrap();
+
+        self.gzip_content = Some(encoder.finish().unwrap());
+        self
+    }
+
+    ///
+    /// Returns a modified version of this file with an explicit MIME type, replacing the one inferred from its path
+    ///
+    pub fn with_content_type(mut self, content_type: &str) -> StaticFile {
+        self.mime_type = String::from(content_type);
+        self
+    }
+
+    ///
+    /// Creates a new static file with a MIME type inferred from its extension
     ///
     pub fn new(path: &str, content: &[u8]) -> StaticFile {
         StaticFile::new_with_type(
@@ -78,6 +134,13 @@ impl StaticFile {
         )
     }
 
+    ///
+    /// Returns the path of this file
+    ///
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
     ///
     /// Returns the list of paths where this string can be
     /// accessed
@@ -100,21 +163,65 @@ impl StaticFile {
     ///
     /// Retrieves the bytes that make up this static file
     ///
-    pub fn content<'a>(&'a self) -> &'a [u8] {
+    pub fn content(&self) -> &[u8] {
         &self.content
     }
 
+    ///
+    /// Retrieves the gzip-compressed bytes for this file, if it was created with compression
+    ///
+    pub fn gzip_content(&self) -> Option<&[u8]> {
+        self.gzip_content.as_ref().map(|content| &content[..])
+    }
+
+    ///
+    /// Retrieves the content of this file in the requested encoding, falling back to the raw content if that encoding is not available
+    ///
+    /// The encoding that the content is actually in is returned alongside it.
+    ///
+    pub fn content_with_encoding(&self, encoding: ContentEncoding) -> (ContentEncoding, &[u8]) {
+        match (encoding, &self.gzip_content) {
+            (ContentEncoding::Gzip, Some(gzip_content)) => (ContentEncoding::Gzip, gzip_content),
+            _                                           => (ContentEncoding::Identity, &self.content)
+        }
+    }
+
     ///
     /// Retrieves the MIME type of this static file
     ///
-    pub fn mime_type<'a>(&'a self) -> &'a str {
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    ///
+    /// Retrieves the value to use for the `Content-Type` header when sending this file
+    ///
+    /// This is the type passed to `new_with_type()` if the file was created that way, or the type inferred from the extension
+    /// of its path otherwise (`application/octet-stream` if the extension isn't recognised)
+    ///
+    pub fn content_type(&self) -> &str {
         &self.mime_type
     }
 
     ///
     /// Computes the etag for this file
     ///
+    /// This is a quoted string that can be used directly as the value of an `ETag` header
+    ///
     pub fn etag(&self) -> String {
         self.etag.clone()
     }
+
+    ///
+    /// Returns true if the value of an `If-None-Match` header matches the etag for this file
+    ///
+    /// The header can be a list of etags or `*`. Weak etags (`W/"..."`) are compared with the weak comparison the
+    /// HTTP spec requires for `If-None-Match`, so they match if their value is the same as this file's etag.
+    ///
+    pub fn matches_etag(&self, if_none_match: &str) -> bool {
+        if_none_match.trim() == "*" || if_none_match.split(',')
+            .map(|etag| etag.trim())
+            .map(|etag| etag.strip_prefix("W/").unwrap_or(etag))
+            .any(|etag| etag == self.etag)
+    }
 }
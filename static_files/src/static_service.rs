@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::iter::FromIterator;
 
 use super::static_file::*;
@@ -8,8 +8,8 @@ use super::static_file::*;
 /// Service that supplies static files
 ///
 pub struct StaticService {
-    /// File found in a particular path
-    file_for_path: HashMap<String, Arc<StaticFile>>
+    /// File found in a particular path (files can be added while the service is in use, so this is behind a lock)
+    file_for_path: RwLock<HashMap<String, Arc<StaticFile>>>
 }
 
 impl StaticService {
@@ -19,26 +19,139 @@ impl StaticService {
     pub fn new(files: Vec<StaticFile>) -> StaticService {
         let paths_and_files = files
                 .into_iter()
-                .map(|file| Arc::new(file))
+                .map(Arc::new)
                 .flat_map(|file| file.valid_paths().into_iter().map(move |path| (path, file.clone())));
 
         StaticService {
-            file_for_path: HashMap::from_iter(paths_and_files)
+            file_for_path: RwLock::new(HashMap::from_iter(paths_and_files))
         }
     }
 
+    ///
+    /// Adds a new file to this service, returning the file that was previously at the same path (if there was one)
+    ///
+    /// This can be called while the service is in use: requests that are being handled at the same time will see either
+    /// the old set of files or the new one. A file that is replaced is removed from all of the paths it could be
+    /// found at.
+    ///
+    pub fn add_file(&self, file: StaticFile) -> Option<Arc<StaticFile>> {
+        let file                = Arc::new(file);
+        let mut file_for_path   = self.file_for_path.write().unwrap();
+
+        // Remove the file that's being replaced
+        let previous = file_for_path.get(file.path()).cloned();
+        if let Some(previous) = &previous {
+            file_for_path.retain(|_, existing| !Arc::ptr_eq(existing, previous));
+        }
+
+        // Add the new file at all of its paths
+        for path in file.valid_paths() {
+            file_for_path.insert(path, file.clone());
+        }
+
+        previous
+    }
+
+    ///
+    /// Removes the file at the specified path from this service, returning the file that was removed
+    ///
+    /// The file is removed from all of the paths it could be found at (so removing `/index.html` also removes `/`)
+    ///
+    pub fn remove_file(&self, path: &str) -> Option<Arc<StaticFile>> {
+        let removed = self.file_for_path(path)?;
+
+        self.file_for_path.write().unwrap()
+            .retain(|_, existing| !Arc::ptr_eq(existing, &removed));
+
+        Some(removed)
+    }
+
     ///
     /// Returns the file located at the specified path
     ///
     pub fn file_for_path(&self, path: &str) -> Option<Arc<StaticFile>> {
-        if path.len() == 0 {
-            self.file_for_path("/index.html")
-        } else if path == "/" {
+        if path.is_empty() || path == "/" {
             self.file_for_path("/index.html")
         } else if path.chars().nth(0) != Some('/') {
             self.file_for_path(&format!("/{}", path))
         } else {
-            self.file_for_path.get(path).cloned()
+            self.file_for_path.read().unwrap().get(path).cloned()
         }
     }
+
+    ///
+    /// Returns the file located at the specified path along with the encoding its content should be sent in
+    ///
+    /// The preferred encoding is used if the file is available in that encoding, otherwise its raw content should be sent.
+    /// Use `StaticFile::content_with_encoding()` to retrieve the content itself.
+    ///
+    pub fn file_for_path_with_encoding(&self, path: &str, preferred_encoding: ContentEncoding) -> Option<(Arc<StaticFile>, ContentEncoding)> {
+        self.file_for_path(path)
+            .map(|file| {
+                let (encoding, _) = file.content_with_encoding(preferred_encoding);
+                (file, encoding)
+            })
+    }
+
+    ///
+    /// Returns the etag for the file located at the specified path, if there is one
+    ///
+    pub fn etag_for_path(&self, path: &str) -> Option<String> {
+        self.file_for_path(path).map(|file| file.etag())
+    }
+
+    ///
+    /// Returns true if the file at the specified path exists and has an etag matching the value of an `If-None-Match` header
+    ///
+    /// When this returns true, the file is unchanged from the version the client already has and a 304 Not Modified
+    /// response can be sent instead of its content.
+    ///
+    pub fn matches_etag(&self, path: &str, if_none_match: &str) -> bool {
+        self.file_for_path(path)
+            .map(|file| file.matches_etag(if_none_match))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use flate2::read::{GzDecoder};
+
+    use std::io::{Read};
+
+    #[test]
+    fn fetch_bundled_file() {
+        let service = flowbetween_static_files();
+        let file    = service.file_for_path("/js/canvas.js").unwrap();
+
+        assert!(file.content_type() == "text/javascript; charset=utf-8");
+        assert!(file.content() == &include_bytes!("../js/canvas.js")[..]);
+    }
+
+    #[test]
+    fn fetch_added_file() {
+        let service = flowbetween_static_files();
+        let content = "function play() { }\n".repeat(100);
+
+        assert!(service.file_for_path("/js/player.js").is_none());
+        service.add_file(StaticFile::new("/js/player.js", content.as_bytes()).with_compression());
+
+        // Raw content is supplied when gzip isn't requested
+        let (file, encoding) = service.file_for_path_with_encoding("js/player.js", ContentEncoding::Identity).unwrap();
+        assert!(file.content_type() == "text/javascript; charset=utf-8");
+        assert!(encoding == ContentEncoding::Identity);
+        assert!(file.content_with_encoding(encoding).1 == content.as_bytes());
+
+        // Compressed content decompresses to the same body
+        let (file, encoding)    = service.file_for_path_with_encoding("/js/player.js", ContentEncoding::Gzip).unwrap();
+        let mut decompressed    = vec![];
+        GzDecoder::new(file.content_with_encoding(encoding).1).read_to_end(&mut decompressed).unwrap();
+
+        assert!(encoding.content_encoding_header() == Some("gzip"));
+        assert!(file.content_with_encoding(encoding).1.len() < content.len());
+        assert!(decompressed == content.as_bytes());
+    }
 }
@@ -1,5 +1,11 @@
 use super::paint::*;
+use super::mouse::*;
 use super::command::*;
+use super::keypress::*;
+use super::crop_attr::*;
+use super::drag_drop_attr::*;
+use super::scroll_attr::*;
+use super::unit_value::*;
 use super::super::property::*;
 
 ///
@@ -10,6 +16,10 @@ pub enum ActionTrigger {
     /// User clicked this item (pressed down and released while over the same item)
     Click,
 
+    /// User right-clicked this item (or used the platform's equivalent, such as ctrl+click). Controls with this
+    /// trigger replace the native context menu: controls without it leave the native menu in place.
+    SecondaryClick,
+
     /// User began an interaction outside of this item (usually means that a popup should be dismissed)
     Dismiss,
 
@@ -28,6 +38,16 @@ pub enum ActionTrigger {
     /// Tracks drag actions for this control
     Drag,
 
+    /// The user has started dragging the payload of a control with a `DragDrop::Source` attribute (the parameter is the payload)
+    DragStart,
+
+    /// A payload that this control accepts has been dragged over it (the parameter is the payload). Payloads that the
+    /// control's `DragDrop::Target` attribute doesn't list are rejected and don't generate this action.
+    DragOver,
+
+    /// A payload that this control accepts has been dropped onto it (the parameter is the payload)
+    Drop,
+
     /// This item has been focused for editing
     Focused,
 
@@ -40,8 +60,31 @@ pub enum ActionTrigger {
     /// An edit (which may have sent one or more EditValue updates) has been cancelled
     CancelEdit,
 
+    /// The user is composing text using an input method or on-screen keyboard. While text is being composed, text
+    /// controls don't send EditValue actions for the partially composed text: the value is updated once the composition
+    /// is committed.
+    Compose,
+
     /// Divides a scrollable region into a grid, and generates an event whenever the region in the top-left corner changes
-    VirtualScroll(f32, f32)
+    VirtualScroll(f32, f32),
+
+    /// A node in a tree view has been expanded (the parameter is the ID of the node as a string value)
+    ExpandNode,
+
+    /// A node in a tree view has been collapsed (the parameter is the ID of the node as a string value)
+    CollapseNode,
+
+    /// The user has started or paused a media control (the parameter is the new playing state as a bool value)
+    SetPlaying,
+
+    /// A media control has played to the end of its media
+    MediaEnded,
+
+    /// The user has finished dragging the rectangle in a crop control (the parameter is the new crop bounds)
+    Crop,
+
+    /// A scrolling container has been scrolled or has changed size (the parameter is the new scroll state)
+    Scroll
 }
 
 ///
@@ -64,6 +107,24 @@ pub enum DragAction {
     Cancel  = 3
 }
 
+///
+/// The stage of a text composition (as generated by an input method or an on-screen keyboard)
+///
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub enum TextComposition {
+    /// The user has started composing some text
+    Start,
+
+    /// The text being composed has changed (the parameter is the text composed so far)
+    Update(String),
+
+    /// The composition has finished and the specified text should be inserted
+    Commit(String),
+
+    /// The composition was cancelled and no text should be inserted
+    Cancel
+}
+
 ///
 /// Data that can be sent alongside an action
 ///
@@ -78,12 +139,41 @@ pub enum ActionParameter {
     /// Item drag action. Coordinates are relative to a fixed point during a drag action
     Drag(DragAction, (f32, f32), (f32, f32)),
 
+    /// The payload being dragged in a drag and drop operation
+    DragPayload(DragPayload),
+
     /// The new size (as width/height) of the control in pixels
     Size(f32, f32),
 
+    /// A secondary click with the specified button. The position is in canvas coordinates for canvas controls, or
+    /// in pixels relative to the top-left of the control otherwise
+    SecondaryClick(MouseButton, (f32, f32)),
+
     /// The new value for an item
     Value(PropertyValue),
 
+    /// The new value for a text box that accepts values with units (see `UnitValue::from_parameter` for reading values from
+    /// back-ends that send the text instead)
+    UnitValue(UnitValue),
+
+    /// The state of a text composition
+    Composition(TextComposition),
+
+    /// The new crop rectangle for a crop control, in image pixels
+    Crop(CropBounds),
+
+    /// A handle of the rectangle in a crop control was dragged: the parameters are the bounds when the drag started,
+    /// the handle, the offset it was dragged by and the size of the image, all in image pixels. This is sent to the
+    /// control's `Crop` action, and the session replaces it with a `Crop` parameter containing the new bounds
+    CropDrag(CropBounds, CropHandle, (f32, f32), (f32, f32)),
+
+    /// An arrow key was pressed in a tree view. This is sent to the tree's `SetValue` action, and the session
+    /// replaces it with the selection, expand or collapse action that the key performs
+    TreeKey(KeyPress),
+
+    /// The position and size of a scrolling container
+    Scroll(ScrollState),
+
     /// The parameters for a command
     CommandParameters(Vec<PropertyValue>),
 
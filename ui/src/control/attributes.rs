@@ -9,6 +9,12 @@ use super::popup_attr::*;
 use super::hover_attr::*;
 use super::keybinding::*;
 use super::scroll_attr::*;
+use super::tree_attr::*;
+use super::media_attr::*;
+use super::crop_attr::*;
+use super::drag_drop_attr::*;
+use super::gauge_attr::*;
+use super::unit_value::*;
 use super::appearance_attr::*;
 
 use crate::image;
@@ -74,7 +80,38 @@ pub enum ControlAttribute {
     Canvas(Resource<BindingCanvas>),
 
     /// Binds a keypress to a command (keybinding commands never have any parameters)
-    BindKey(KeyBinding, Command)
+    BindKey(KeyBinding, Command),
+
+    /// Specifies the nodes displayed by a tree view control
+    TreeAttr(Tree),
+
+    /// Style classes attached to this control (classes in the HTML back-end and style classes in GTK, ignored by Cocoa)
+    Classes(Vec<String>),
+
+    /// Style classes attached to this control that are read from a property (as a string of class names separated by spaces)
+    BoundClasses(Property),
+
+    /// Specifies the media played by a media control
+    MediaAttr(Media),
+
+    /// Specifies the crop rectangle for a crop control
+    CropAttr(Crop),
+
+    /// Specifies how this control can be dragged, or what can be dropped onto it
+    DragDropAttr(DragDrop),
+
+    /// Specifies the arc that a gauge control is drawn along
+    GaugeAttr(Gauge),
+
+    /// The units that a text box accepts numeric values in. The first unit is used for numbers entered without a unit
+    Units(Vec<Unit>),
+
+    /// The step that the value of a slider snaps to, measured from the start of its range (sliders without a step are continuous)
+    RangeStep(f32),
+
+    /// Pressing the keys in this binding performs this control's click action, as if the control had been clicked
+    /// (if several controls use the same keys, the first one in the UI tree is the one that's triggered)
+    KeyShortcut(KeyBinding)
 
     // TODO: content attribute (maybe with text?). Image might be appearance though
 }
@@ -260,6 +297,106 @@ impl ControlAttribute {
         }
     }
 
+    ///
+    /// If this is a tree attribute, returns the tree, otherwise returns nothing
+    ///
+    pub fn tree<'a>(&'a self) -> Option<&'a Tree> {
+        match self {
+            TreeAttr(tree)  => Some(tree),
+            _               => None
+        }
+    }
+
+    ///
+    /// If this is a classes attribute, returns the list of classes, otherwise returns nothing
+    ///
+    pub fn classes<'a>(&'a self) -> Option<&'a Vec<String>> {
+        match self {
+            Classes(classes)    => Some(classes),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a bound classes attribute, returns the property the classes are read from, otherwise returns nothing
+    ///
+    pub fn bound_classes<'a>(&'a self) -> Option<&'a Property> {
+        match self {
+            BoundClasses(classes)   => Some(classes),
+            _                       => None
+        }
+    }
+
+    ///
+    /// If this is a media attribute, returns the media attribute, otherwise returns nothing
+    ///
+    pub fn media<'a>(&'a self) -> Option<&'a Media> {
+        match self {
+            MediaAttr(media)    => Some(media),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a crop attribute, returns the crop attribute, otherwise returns nothing
+    ///
+    pub fn crop<'a>(&'a self) -> Option<&'a Crop> {
+        match self {
+            CropAttr(crop)      => Some(crop),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a drag and drop attribute, returns the drag and drop attribute, otherwise returns nothing
+    ///
+    pub fn drag_drop<'a>(&'a self) -> Option<&'a DragDrop> {
+        match self {
+            DragDropAttr(drag_drop) => Some(drag_drop),
+            _                       => None
+        }
+    }
+
+    ///
+    /// If this is a gauge attribute, returns the gauge attribute, otherwise returns nothing
+    ///
+    pub fn gauge<'a>(&'a self) -> Option<&'a Gauge> {
+        match self {
+            GaugeAttr(gauge)    => Some(gauge),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a units attribute, returns the list of units, otherwise returns nothing
+    ///
+    pub fn units<'a>(&'a self) -> Option<&'a Vec<Unit>> {
+        match self {
+            Units(units)        => Some(units),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a range step attribute, returns the step, otherwise returns nothing
+    ///
+    pub fn range_step(&self) -> Option<f32> {
+        match self {
+            RangeStep(step)     => Some(*step),
+            _                   => None
+        }
+    }
+
+    ///
+    /// If this is a key shortcut attribute, returns the keys that trigger the control, otherwise returns nothing
+    ///
+    pub fn key_shortcut<'a>(&'a self) -> Option<&'a KeyBinding> {
+        match self {
+            KeyShortcut(keys)   => Some(keys),
+            _                   => None
+        }
+    }
+
     ///
     /// Returns true if this attribute is different from another one
     /// (non-recursively, so this won't check subcomoponents)
@@ -282,6 +419,16 @@ impl ControlAttribute {
             &HoverAttr(ref hover)               => Some(hover) != compare_to.hover(),
             &HintAttr(ref hint)                 => Some(hint) != compare_to.hint(),
             BindKey(key, cmd)                   => Some((key, cmd)) != compare_to.key_binding(),
+            TreeAttr(tree)                      => Some(tree) != compare_to.tree(),
+            Classes(classes)                    => Some(classes) != compare_to.classes(),
+            BoundClasses(classes)               => Some(classes) != compare_to.bound_classes(),
+            MediaAttr(media)                    => Some(media) != compare_to.media(),
+            CropAttr(crop)                      => Some(crop) != compare_to.crop(),
+            DragDropAttr(drag_drop)             => Some(drag_drop) != compare_to.drag_drop(),
+            GaugeAttr(gauge)                    => Some(gauge) != compare_to.gauge(),
+            Units(units)                        => Some(units) != compare_to.units(),
+            RangeStep(step)                     => Some(*step) != compare_to.range_step(),
+            KeyShortcut(keys)                   => Some(keys) != compare_to.key_shortcut(),
 
             // For the subcomponents we only care about the number as we don't want to recurse
             &SubComponents(ref components)      => Some(components.len()) != compare_to.subcomponents().map(|components| components.len())
@@ -3,6 +3,10 @@ use super::bounds::*;
 use super::actions::*;
 use super::modifier::*;
 use super::attributes::*;
+use super::tree_attr::*;
+use super::keybinding::*;
+use super::drag_drop_attr::*;
+use super::gauge_attr::{Gauge, GaugeArc};   // Imported explicitly as ControlType::* also has a Gauge variant
 
 use super::super::image;
 use super::super::diff::*;
@@ -96,6 +100,26 @@ impl Control {
         Self::new(ControlType::ComboBox)
     }
 
+    /// Creates a new tree view control
+    pub fn tree() -> Control {
+        Self::new(ControlType::TreeView)
+    }
+
+    /// Creates a new media control
+    pub fn media() -> Control {
+        Self::new(ControlType::Media)
+    }
+
+    /// Creates a new image cropping control
+    pub fn crop_image() -> Control {
+        Self::new(ControlType::CropImage)
+    }
+
+    /// Creates a new gauge control
+    pub fn gauge() -> Control {
+        Self::new(ControlType::Gauge)
+    }
+
     /// Adds an attribute to this control
     pub fn add_attribute(&mut self, attribute: ControlAttribute) {
         self.attributes.push(attribute);
@@ -115,6 +139,30 @@ impl Control {
         self.with(ControlAttribute::Controller(String::from(controller)))
     }
 
+    ///
+    /// Creates a control whose value snaps to multiples of the specified step from the start of its range
+    ///
+    pub fn with_range_step(self, step: f32) -> Control {
+        self.with(ControlAttribute::RangeStep(step))
+    }
+
+    ///
+    /// Creates a text control that displays the text from a viewmodel property and sends its edits to the controller as
+    /// actions with the same name as the property (see `DynamicViewModel::bind_text()`)
+    ///
+    pub fn with_text_binding(self, property_name: &str) -> Control {
+        self.with(ControlAttribute::Text(Property::Bind(String::from(property_name))))
+            .with((ActionTrigger::EditValue, property_name))
+            .with((ActionTrigger::SetValue, property_name))
+    }
+
+    ///
+    /// Creates a control whose click action is also performed when the specified keys are pressed
+    ///
+    pub fn with_key_shortcut(self, keys: KeyBinding) -> Control {
+        self.with(ControlAttribute::KeyShortcut(keys))
+    }
+
     /// Returns an iterator over the attributes for this control
     pub fn attributes<'a>(&'a self) -> Box<dyn Iterator<Item=&'a ControlAttribute>+'a> {
         Box::new(self.attributes.iter())
@@ -174,6 +222,26 @@ impl Control {
             .map(|attr| attr.unwrap())
     }
 
+    ///
+    /// If this control has a tree attribute, finds it
+    ///
+    pub fn tree_nodes<'a>(&'a self) -> Option<&'a Tree> {
+        self.attributes.iter()
+            .map(|attr| attr.tree())
+            .find(|attr| attr.is_some())
+            .map(|attr| attr.unwrap())
+    }
+
+    ///
+    /// Returns the style classes attached to this control
+    ///
+    pub fn classes(&self) -> Vec<String> {
+        self.attributes.iter()
+            .filter_map(|attr| attr.classes())
+            .flat_map(|classes| classes.iter().cloned())
+            .collect()
+    }
+
     ///
     /// If this control has a canvas attribute, finds it
     ///
@@ -237,6 +305,70 @@ impl Control {
             .collect()
     }
 
+    ///
+    /// Finds the control that generates the specified action. Subcomponents that belong to another controller
+    /// are not searched.
+    ///
+    pub fn find_action_control<'a>(&'a self, trigger: &ActionTrigger, action_name: &str) -> Option<&'a Control> {
+        let generates_action = self.attributes.iter().any(|attr| match attr {
+            ControlAttribute::Action(action_trigger, ActionEvent::Named(name))  => action_trigger == trigger && name == action_name,
+            _                                                                   => false
+        });
+
+        if generates_action {
+            Some(self)
+        } else {
+            self.subcomponents()
+                .and_then(|subcomponents| subcomponents.iter()
+                    .filter(|subcomponent| subcomponent.controller().is_none())
+                    .filter_map(|subcomponent| subcomponent.find_action_control(trigger, action_name))
+                    .nth(0))
+        }
+    }
+
+    ///
+    /// Returns the payload that is dragged from this control, if it's a drag source
+    ///
+    pub fn drag_payload<'a>(&'a self) -> Option<&'a DragPayload> {
+        self.attributes.iter()
+            .filter_map(|attr| attr.drag_drop())
+            .filter_map(|drag_drop| match drag_drop {
+                DragDrop::Source(payload)   => Some(payload),
+                DragDrop::Target(_)         => None
+            })
+            .nth(0)
+    }
+
+    ///
+    /// True if this control is a drop target that accepts the specified payload
+    ///
+    pub fn accepts_drop(&self, payload: &DragPayload) -> bool {
+        self.attributes.iter()
+            .filter_map(|attr| attr.drag_drop())
+            .any(|drag_drop| drag_drop.accepts(payload))
+    }
+
+    ///
+    /// Returns the step that the value of this control snaps to, or None if its value is continuous
+    ///
+    pub fn range_step(&self) -> Option<f32> {
+        self.attributes.iter()
+            .filter_map(|attr| attr.range_step())
+            .nth(0)
+    }
+
+    ///
+    /// Returns the arc that this control should be drawn along if it's a gauge
+    ///
+    pub fn gauge_arc(&self) -> GaugeArc {
+        self.attributes.iter()
+            .filter_map(|attr| attr.gauge())
+            .fold(GaugeArc::default(), |arc, gauge| match gauge {
+                Gauge::StartAngle(angle)    => GaugeArc { start_angle: *angle, ..arc },
+                Gauge::SweepAngle(angle)    => GaugeArc { sweep_angle: *angle, ..arc }
+            })
+    }
+
     ///
     /// Visits the control tree and performs a mapping function on each item
     ///
@@ -285,7 +417,32 @@ impl DiffableTree for Control {
     }
 
     fn is_different(&self, compare_to: &Self) -> bool {
+        // The number of subcomponents is checked by the diff (which can also insert or remove keyed subcomponents)
         self.control_type() != compare_to.control_type()
-            || self.attributes.iter().any(|attr| !compare_to.has_attribute_flat(attr))
+            || self.subcomponents().is_some() != compare_to.subcomponents().is_some()
+            || self.attributes.iter()
+                .filter(|attr| attr.subcomponents().is_none())
+                .any(|attr| !compare_to.has_attribute_flat(attr))
+    }
+
+    fn key<'a>(&'a self) -> Option<&'a str> {
+        self.attributes.iter()
+            .filter_map(|attr| attr.id())
+            .map(|id| id.as_str())
+            .nth(0)
+    }
+
+    fn can_update_in_place(&self, compare_to: &Self) -> bool {
+        // Controls can only have their classes changed in place
+        let other_attributes = |control: &Control| control.attributes.iter()
+            .filter(|attr| attr.subcomponents().is_none() && attr.classes().is_none())
+            .count();
+
+        self.control_type() == compare_to.control_type()
+            && self.subcomponents().is_some() == compare_to.subcomponents().is_some()
+            && other_attributes(self) == other_attributes(compare_to)
+            && self.attributes.iter()
+                .filter(|attr| attr.subcomponents().is_none() && attr.classes().is_none())
+                .all(|attr| compare_to.has_attribute_flat(attr))
     }
 }
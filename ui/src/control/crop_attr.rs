@@ -0,0 +1,174 @@
+use super::*;
+
+use ::modifier::*;
+
+///
+/// A rectangle within an image, in image pixels
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CropBounds {
+    pub x:      f32,
+    pub y:      f32,
+    pub width:  f32,
+    pub height: f32
+}
+
+///
+/// The part of a crop rectangle that the user is dragging
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum CropHandle {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+
+    /// The whole rectangle is being moved without changing its size
+    Move
+}
+
+///
+/// Attributes associated with a crop control
+///
+/// The crop control displays the image set with the `Image` appearance attribute, with a rectangle that the
+/// user can drag to choose part of the image. When the user finishes dragging the rectangle, a `Crop` action
+/// is generated with the new bounds.
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Crop {
+    /// The current crop rectangle (the whole image is selected if this is not set)
+    Bounds(CropBounds),
+
+    /// Locks the crop rectangle to a particular aspect ratio (width / height)
+    AspectRatio(f32)
+}
+
+impl CropHandle {
+    ///
+    /// Returns which edges of the crop rectangle are moved by this handle, as (left, top, right, bottom)
+    ///
+    fn edges(&self) -> (bool, bool, bool, bool) {
+        use self::CropHandle::*;
+
+        match self {
+            TopLeft     => (true, true, false, false),
+            Top         => (false, true, false, false),
+            TopRight    => (false, true, true, false),
+            Right       => (false, false, true, false),
+            BottomRight => (false, false, true, true),
+            Bottom      => (false, false, false, true),
+            BottomLeft  => (true, false, false, true),
+            Left        => (true, false, false, false),
+            Move        => (true, true, true, true)
+        }
+    }
+}
+
+impl CropBounds {
+    ///
+    /// Creates a new crop rectangle
+    ///
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> CropBounds {
+        CropBounds { x, y, width, height }
+    }
+
+    ///
+    /// Returns the crop rectangle that results from dragging one of the handles of this rectangle by the specified offset
+    ///
+    /// The result is always clamped so that it's within an image of the specified size. If an aspect ratio is
+    /// supplied, the rectangle is resized around the opposite corner or edge to the handle so that it keeps that
+    /// ratio, shrinking it further if necessary to keep it inside the image.
+    ///
+    pub fn drag_handle(&self, handle: CropHandle, offset: (f32, f32), image_size: (f32, f32), aspect_ratio: Option<f32>) -> CropBounds {
+        let (dx, dy)            = offset;
+        let (img_w, img_h)      = image_size;
+
+        if handle == CropHandle::Move {
+            // Moving keeps the size of the rectangle, but it can't leave the image
+            let width   = self.width.min(img_w);
+            let height  = self.height.min(img_h);
+            let x       = (self.x + dx).min(img_w - width).max(0.0);
+            let y       = (self.y + dy).min(img_h - height).max(0.0);
+
+            return CropBounds { x, y, width, height };
+        }
+
+        // Move the edges covered by the handle (edges can't pass the opposite edge or leave the image)
+        let (move_left, move_top, move_right, move_bottom) = handle.edges();
+
+        let mut left    = self.x.max(0.0);
+        let mut top     = self.y.max(0.0);
+        let mut right   = (self.x + self.width).min(img_w);
+        let mut bottom  = (self.y + self.height).min(img_h);
+
+        if move_left    { left      = (left + dx).max(0.0).min(right); }
+        if move_right   { right     = (right + dx).min(img_w).max(left); }
+        if move_top     { top       = (top + dy).max(0.0).min(bottom); }
+        if move_bottom  { bottom    = (bottom + dy).min(img_h).max(top); }
+
+        let bounds = CropBounds { x: left, y: top, width: right-left, height: bottom-top };
+
+        match aspect_ratio {
+            Some(ratio) if ratio > 0.0  => bounds.lock_aspect_ratio(handle, ratio, image_size),
+            _                           => bounds
+        }
+    }
+
+    ///
+    /// Adjusts the size of a rectangle that has just been resized by a handle so that it has a particular aspect ratio
+    ///
+    fn lock_aspect_ratio(&self, handle: CropHandle, ratio: f32, image_size: (f32, f32)) -> CropBounds {
+        let (img_w, img_h)  = image_size;
+        let (move_left, move_top, move_right, move_bottom) = handle.edges();
+
+        // The top and bottom handles set the height, the other handles set the width
+        let (mut width, mut height) = if !move_left && !move_right {
+            (self.height * ratio, self.height)
+        } else {
+            (self.width, self.width / ratio)
+        };
+
+        // The rectangle grows away from the edge opposite the handle, or from its center if the handle doesn't move that axis
+        let anchor_x    = if move_left { self.x + self.width } else if move_right { self.x } else { self.x + self.width/2.0 };
+        let anchor_y    = if move_top { self.y + self.height } else if move_bottom { self.y } else { self.y + self.height/2.0 };
+
+        // Shrink the rectangle until it fits inside the image
+        let max_width   = if move_left { anchor_x } else if move_right { img_w - anchor_x } else { 2.0 * anchor_x.min(img_w - anchor_x) };
+        let max_height  = if move_top { anchor_y } else if move_bottom { img_h - anchor_y } else { 2.0 * anchor_y.min(img_h - anchor_y) };
+
+        if width > max_width    { width = max_width; height = width / ratio; }
+        if height > max_height  { height = max_height; width = height * ratio; }
+
+        // Position relative to the anchor
+        let x = if move_left { anchor_x - width } else if move_right { anchor_x } else { anchor_x - width/2.0 };
+        let y = if move_top { anchor_y - height } else if move_bottom { anchor_y } else { anchor_y - height/2.0 };
+
+        CropBounds { x, y, width, height }
+    }
+
+    ///
+    /// True if this rectangle is entirely inside an image of the specified size
+    ///
+    pub fn is_within(&self, image_size: (f32, f32)) -> bool {
+        let (img_w, img_h) = image_size;
+
+        self.x >= 0.0 && self.y >= 0.0 && self.width >= 0.0 && self.height >= 0.0
+            && self.x + self.width <= img_w && self.y + self.height <= img_h
+    }
+}
+
+impl Modifier<Control> for Crop {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::CropAttr(self))
+    }
+}
+
+impl Modifier<Control> for CropBounds {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::CropAttr(Crop::Bounds(self)))
+    }
+}
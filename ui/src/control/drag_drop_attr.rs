@@ -0,0 +1,72 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+///
+/// An item that can be dragged from one control and dropped onto another
+///
+/// The payload type is used to decide which controls the item can be dropped on: a drop target only
+/// accepts payloads whose type it lists in its `DragDrop::Target` attribute.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DragPayload {
+    /// The type of item that is being dragged (eg, "layer" or "tool")
+    pub payload_type: String,
+
+    /// The data describing the item being dragged
+    pub data: PropertyValue
+}
+
+///
+/// Attributes that describe how a control takes part in drag and drop
+///
+/// Controls with a `Source` attribute can be dragged by the user, and generate a `DragStart` action when they are.
+/// Controls with a `Target` attribute generate `DragOver` and `Drop` actions when a payload of one of the listed types
+/// is dragged over or dropped onto them. Payloads of any other type are rejected: the back-end indicates that the
+/// drop is not allowed and no action is generated.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DragDrop {
+    /// This control can be dragged, carrying the specified payload
+    Source(DragPayload),
+
+    /// This control accepts dropped payloads with any of the specified types
+    Target(Vec<String>)
+}
+
+impl DragPayload {
+    ///
+    /// Creates a new drag payload
+    ///
+    pub fn new(payload_type: &str, data: PropertyValue) -> DragPayload {
+        DragPayload {
+            payload_type:   payload_type.to_string(),
+            data:           data
+        }
+    }
+}
+
+impl DragDrop {
+    ///
+    /// True if this attribute describes a drop target that will accept the specified payload
+    ///
+    pub fn accepts(&self, payload: &DragPayload) -> bool {
+        match self {
+            DragDrop::Source(_)             => false,
+            DragDrop::Target(payload_types) => payload_types.iter().any(|payload_type| payload_type == &payload.payload_type)
+        }
+    }
+}
+
+impl Modifier<Control> for DragDrop {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::DragDropAttr(self))
+    }
+}
+
+impl Modifier<Control> for DragPayload {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::DragDropAttr(DragDrop::Source(self)))
+    }
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+use ::modifier::*;
+
+///
+/// Attributes associated with a gauge control
+///
+/// A gauge displays the value set by its `State::Value` attribute as an arc, filled in proportion to where the
+/// value lies within its `State::Range`. Angles are in degrees, measured clockwise from the 3 o'clock position.
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Gauge {
+    /// The angle where the arc of the gauge starts (the position of the minimum value)
+    StartAngle(f32),
+
+    /// How far the arc of the gauge extends from its start angle (the position of the maximum value)
+    SweepAngle(f32)
+}
+
+///
+/// The arc that a gauge is drawn along
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GaugeArc {
+    /// The angle where the arc starts, in degrees clockwise from the 3 o'clock position
+    pub start_angle: f32,
+
+    /// The length of the arc, in degrees
+    pub sweep_angle: f32
+}
+
+impl Default for GaugeArc {
+    ///
+    /// By default, gauges are drawn along a 270 degree arc with a gap at the bottom
+    ///
+    fn default() -> GaugeArc {
+        GaugeArc {
+            start_angle: 135.0,
+            sweep_angle: 270.0
+        }
+    }
+}
+
+impl GaugeArc {
+    ///
+    /// Creates a new gauge arc
+    ///
+    pub fn new(start_angle: f32, sweep_angle: f32) -> GaugeArc {
+        GaugeArc { start_angle, sweep_angle }
+    }
+
+    ///
+    /// Returns how much of the arc should be filled in for a value, from 0.0 to 1.0
+    ///
+    /// Values outside of the range are clamped to its ends. An empty range is never filled in.
+    ///
+    pub fn proportion(value: f64, min: f64, max: f64) -> f64 {
+        if max <= min || min.is_nan() || max.is_nan() {
+            0.0
+        } else {
+            // (f64::max returns 0.0 for a NaN value)
+            ((value - min) / (max - min)).max(0.0).min(1.0)
+        }
+    }
+
+    ///
+    /// Returns the angle where the filled-in part of the arc ends for a value
+    ///
+    pub fn end_angle(&self, value: f64, min: f64, max: f64) -> f64 {
+        (self.start_angle as f64) + (self.sweep_angle as f64) * Self::proportion(value, min, max)
+    }
+}
+
+impl Modifier<Control> for Gauge {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::GaugeAttr(self))
+    }
+}
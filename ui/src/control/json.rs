@@ -23,6 +23,7 @@ impl ToJsonValue for ControlAttribute {
             StateAttr(Range((min, max)))                => json!({ "Range": [min, max] }),
             StateAttr(Enabled(property))                => json!({ "Enabled": property }),
             StateAttr(FocusPriority(property))          => json!({ "FocusPriority": property }),
+            StateAttr(SubtreeEnabled(property))         => json!({ "SubtreeEnabled": property }),
             PopupAttr(popup)                            => json!({ "Popup": popup }),
             ScrollAttr(scroll)                          => json!({ "Scroll": scroll }),
             Id(id)                                      => json!({ "Id": id }),
@@ -30,6 +31,16 @@ impl ToJsonValue for ControlAttribute {
             Action(trigger, ActionEvent::Named(action)) => json!({ "Action": (trigger, action) }),
             HoverAttr(hover)                            => json!({ "Hover": hover }),
             HintAttr(hint)                              => json!({ "Hint": hint }),
+            TreeAttr(tree)                              => json!({ "Tree": tree }),
+            Classes(classes)                            => json!({ "Classes": classes }),
+            BoundClasses(classes)                       => json!({ "BoundClasses": classes }),
+            MediaAttr(media)                            => json!({ "Media": media }),
+            CropAttr(crop)                              => json!({ "Crop": crop }),
+            DragDropAttr(drag_drop)                     => json!({ "DragDrop": drag_drop }),
+            GaugeAttr(gauge)                            => json!({ "Gauge": gauge }),
+            Units(units)                                => json!({ "Units": units }),
+            RangeStep(step)                             => json!({ "RangeStep": step }),
+            KeyShortcut(keys)                           => json!({ "KeyShortcut": keys }),
 
             BindKey(key, cmd)                           => {
                 // For the purposes of serialization, the command is hidden if it's a system command (we just specify that a key has been bound)
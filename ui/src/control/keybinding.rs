@@ -11,7 +11,11 @@ use std::collections::{HashSet};
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct KeyBinding {
     /// The set of keys that should be held down to trigger this action
-    pub keys: HashSet<KeyPress>
+    pub keys: HashSet<KeyPress>,
+
+    /// For chords, the set of keys that must be pressed first (the chord is triggered if `keys` are pressed shortly afterwards)
+    #[serde(default)]
+    pub prefix: Option<HashSet<KeyPress>>
 }
 
 impl KeyBinding {
@@ -21,10 +25,36 @@ impl KeyBinding {
     pub fn hold_down_keys<KeyIter: IntoIterator>(keys: KeyIter) -> KeyBinding 
     where KeyIter::Item: Into<KeyPress> {
         KeyBinding { 
-            keys: keys.into_iter().map(|key| key.into()).collect::<HashSet<KeyPress>>() 
+            keys:   keys.into_iter().map(|key| key.into()).collect::<HashSet<KeyPress>>(),
+            prefix: None
+        }
+    }
+
+    ///
+    /// Creates a chord binding, which is triggered by pressing the prefix keys followed by the keys in the second binding
+    /// (eg, Ctrl+K Ctrl+S)
+    ///
+    pub fn chord(prefix: KeyBinding, then: KeyBinding) -> KeyBinding {
+        KeyBinding {
+            keys:   then.keys,
+            prefix: Some(prefix.keys)
         }
     }
 
+    ///
+    /// Returns the binding that must be pressed to start this chord, or None if this is not a chord
+    ///
+    pub fn chord_prefix(&self) -> Option<KeyBinding> {
+        self.prefix.as_ref().map(|prefix| KeyBinding { keys: prefix.clone(), prefix: None })
+    }
+
+    ///
+    /// True if this binding consists only of modifier keys
+    ///
+    pub fn is_modifiers_only(&self) -> bool {
+        self.keys.iter().all(|key| key.is_modifier())
+    }
+
     ///
     /// Creates a single key binding
     ///
@@ -92,11 +122,18 @@ impl KeyBinding {
 
 impl Hash for KeyBinding {
     ///
-    /// Keybindings are hashed by the ordered set of keys
+    /// Keybindings are hashed by the ordered set of keys (followed by the ordered set of prefix keys for chords)
     ///
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.keys.iter()
             .sorted()
             .for_each(|key| key.hash(state));
+
+        if let Some(prefix) = &self.prefix {
+            prefix.len().hash(state);
+            prefix.iter()
+                .sorted()
+                .for_each(|key| key.hash(state));
+        }
     }
 }
\ No newline at end of file
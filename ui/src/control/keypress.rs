@@ -109,3 +109,17 @@ pub enum KeyPress {
     KeyNumpadEnter,
     KeyNumpadDecimal,
 }
+
+impl KeyPress {
+    ///
+    /// True if this is a modifier key (such as shift or ctrl)
+    ///
+    pub fn is_modifier(&self) -> bool {
+        use self::KeyPress::*;
+
+        match self {
+            ModifierShift | ModifierCtrl | ModifierAlt | ModifierMeta | ModifierSuper | ModifierHyper   => true,
+            _                                                                                           => false
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+///
+/// The kind of media displayed by a media control
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum MediaType {
+    /// The media is audio only (the control displays the playback controls)
+    Audio,
+
+    /// The media is a video
+    Video
+}
+
+///
+/// Attributes associated with a media control
+///
+/// The playback position (in seconds) is bound using `State::Value`. When the user seeks, the control generates
+/// `EditValue` and `SetValue` actions with the new position. The position is sent back to the control when it
+/// changes, so controllers can keep the media synchronised with the timeline by updating the bound value while
+/// it is playing.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Media {
+    /// The URL of the media to play
+    Source(String),
+
+    /// The type of media to play (audio by default)
+    Type(MediaType),
+
+    /// Whether or not the media is playing (media is paused by default)
+    Playing(Property)
+}
+
+impl Modifier<Control> for Media {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::MediaAttr(self))
+    }
+}
+
+impl Modifier<Control> for MediaType {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::MediaAttr(Media::Type(self)))
+    }
+}
@@ -13,10 +13,16 @@ mod font_attr;
 mod hint_attr;
 mod state_attr;
 mod popup_attr;
+mod tree_attr;
+mod media_attr;
+mod crop_attr;
+mod gauge_attr;
+mod unit_value;
 mod attributes;
 mod hover_attr;
 mod keybinding;
 mod scroll_attr;
+mod drag_drop_attr;
 mod modifier_key;
 mod command_binding;
 mod appearance_attr;
@@ -36,10 +42,16 @@ pub use self::font_attr::*;
 pub use self::hint_attr::*;
 pub use self::state_attr::*;
 pub use self::popup_attr::*;
+pub use self::tree_attr::*;
+pub use self::media_attr::*;
+pub use self::crop_attr::*;
+pub use self::gauge_attr::*;
+pub use self::unit_value::*;
 pub use self::attributes::*;
 pub use self::hover_attr::*;
 pub use self::keybinding::*;
 pub use self::scroll_attr::*;
+pub use self::drag_drop_attr::*;
 pub use self::modifier_key::*;
 pub use self::command_binding::*;
 pub use self::appearance_attr::*;
@@ -48,11 +60,13 @@ pub use self::appearance_attr::*;
 mod test {
     use super::*;
     use super::super::diff::*;
+    use super::super::json::*;
     use super::super::image::*;
     use super::super::property::*;
     use super::super::binding_canvas::*;
     use super::super::resource_manager::*;
 
+    use flo_binding::*;
     use bytes::Bytes;
     use std::sync::*;
 
@@ -287,4 +301,337 @@ mod test {
 
         assert!(canvas1.is_different(&canvas2));
     }
+
+    #[test]
+    fn classes_round_trip_through_json() {
+        let classes     = vec!["toolbar".to_string(), "dark".to_string()];
+        let control     = Control::container().with(ControlAttribute::Classes(classes.clone()));
+        let json        = control.to_json();
+        let round_trip  = serde_json::from_value::<Vec<String>>(json["attributes"][0]["Classes"].clone()).unwrap();
+
+        assert!(round_trip == classes);
+    }
+
+    #[test]
+    fn changing_classes_only_changes_one_control() {
+        let control1    = Control::container()
+            .with(ControlAttribute::Classes(vec!["light".to_string()]))
+            .with(vec![Control::label().with("Text")]);
+        let control2    = Control::container()
+            .with(ControlAttribute::Classes(vec!["dark".to_string()]))
+            .with(vec![Control::label().with("Text")]);
+
+        assert!(control1.is_different(&control2));
+        assert!(!control1.subcomponents().unwrap()[0].is_different(&control2.subcomponents().unwrap()[0]));
+
+        // The diff updates the classes of the container without replacing the label inside it
+        assert!(diff_tree_keyed(&control1, &control2) == vec![TreeEdit::Update(Diff::new(&vec![], &control2))]);
+        assert!(control2.classes() == vec!["dark".to_string()]);
+    }
+
+    #[test]
+    fn changing_classes_and_subcomponents_updates_both() {
+        let control1    = Control::container()
+            .with(ControlAttribute::Classes(vec!["light".to_string()]))
+            .with(vec![Control::label().with("Text")]);
+        let control2    = Control::container()
+            .with(ControlAttribute::Classes(vec!["dark".to_string()]))
+            .with(vec![Control::label().with("New text")]);
+
+        assert!(diff_tree_keyed(&control1, &control2) == vec![
+            TreeEdit::Update(Diff::new(&vec![], &control2)),
+            TreeEdit::Replace(Diff::new(&vec![0], &Control::label().with("New text")))
+        ]);
+    }
+
+    #[test]
+    fn changing_other_attributes_replaces_control() {
+        let control1    = Control::container()
+            .with(ControlAttribute::Classes(vec!["light".to_string()]))
+            .with(vec![Control::label().with("Text")]);
+        let control2    = Control::container()
+            .with(ControlAttribute::Classes(vec!["dark".to_string()]))
+            .with(Bounds::fill_all())
+            .with(vec![Control::label().with("Text")]);
+
+        assert!(diff_tree_keyed(&control1, &control2) == vec![TreeEdit::Replace(Diff::new(&vec![], &control2))]);
+    }
+
+    #[test]
+    fn bound_classes_round_trip_through_json() {
+        let control     = Control::container().with(ControlAttribute::BoundClasses(Property::bound("theme")));
+        let json        = control.to_json();
+        let round_trip  = serde_json::from_value::<Property>(json["attributes"][0]["BoundClasses"].clone()).unwrap();
+
+        assert!(round_trip == Property::bound("theme"));
+    }
+
+    #[test]
+    fn media_round_trips_through_json() {
+        let control     = Control::media()
+            .with(Media::Source("https://example.com/soundtrack.ogg".to_string()))
+            .with(MediaType::Video)
+            .with(Media::Playing(Property::Bind("playing".to_string())))
+            .with(State::Value(Property::Bind("position".to_string())));
+        let json        = control.to_json();
+
+        let media       = json["attributes"].as_array().unwrap().iter()
+            .filter(|attribute| !attribute["Media"].is_null())
+            .map(|attribute| serde_json::from_value::<Media>(attribute["Media"].clone()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(json["control_type"] == "Media");
+        assert!(media == vec![
+            Media::Source("https://example.com/soundtrack.ogg".to_string()),
+            Media::Type(MediaType::Video),
+            Media::Playing(Property::Bind("playing".to_string()))
+        ]);
+    }
+
+    #[test]
+    fn gauge_round_trips_through_json() {
+        let control     = Control::gauge()
+            .with(State::Range((Property::Float(0.0), Property::Float(200.0))))
+            .with(State::Value(Property::Bind("progress".to_string())))
+            .with(Gauge::StartAngle(180.0))
+            .with(Gauge::SweepAngle(180.0));
+        let json        = control.to_json();
+
+        let gauge       = json["attributes"].as_array().unwrap().iter()
+            .filter(|attribute| !attribute["Gauge"].is_null())
+            .map(|attribute| serde_json::from_value::<Gauge>(attribute["Gauge"].clone()).unwrap())
+            .collect::<Vec<_>>();
+        let range       = json["attributes"].as_array().unwrap().iter()
+            .filter(|attribute| !attribute["Range"].is_null())
+            .map(|attribute| serde_json::from_value::<(Property, Property)>(attribute["Range"].clone()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(json["control_type"] == "Gauge");
+        assert!(gauge == vec![Gauge::StartAngle(180.0), Gauge::SweepAngle(180.0)]);
+        assert!(range == vec![(Property::Float(0.0), Property::Float(200.0))]);
+    }
+
+    #[test]
+    fn slider_step_is_serialized() {
+        let control     = Control::slider()
+            .with(State::Range((Property::Float(0.0), Property::Float(24.0))))
+            .with_range_step(1.0);
+        let json        = control.to_json();
+
+        let steps       = json["attributes"].as_array().unwrap().iter()
+            .filter(|attribute| !attribute["RangeStep"].is_null())
+            .map(|attribute| attribute["RangeStep"].as_f64().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(control.range_step() == Some(1.0));
+        assert!(steps == vec![1.0]);
+    }
+
+    #[test]
+    fn slider_without_step_is_continuous() {
+        let control     = Control::slider()
+            .with(State::Range((Property::Float(0.0), Property::Float(24.0))));
+        let json        = control.to_json();
+
+        assert!(control.range_step() == None);
+        assert!(json["attributes"].as_array().unwrap().iter().all(|attribute| attribute["RangeStep"].is_null()));
+    }
+
+    #[test]
+    fn key_shortcut_modifiers_are_serialized() {
+        let control     = Control::button()
+            .with((ActionTrigger::Click, "Undo"))
+            .with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyZ));
+        let json        = control.to_json();
+
+        let shortcuts   = json["attributes"].as_array().unwrap().iter()
+            .filter(|attribute| !attribute["KeyShortcut"].is_null())
+            .map(|attribute| serde_json::from_value::<KeyBinding>(attribute["KeyShortcut"].clone()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(shortcuts == vec![KeyBinding::ctrl(KeyPress::KeyZ)]);
+        assert!(shortcuts[0].keys.contains(&KeyPress::ModifierCtrl));
+    }
+
+    #[test]
+    fn reordering_controls_with_ids_moves_them() {
+        let item        = |id: &str| Control::label().with(id).with(ControlAttribute::Id(id.to_string()));
+        let list_a      = Control::container().with(vec![item("One"), item("Two"), item("Three")]);
+        let list_b      = Control::container().with(vec![item("Three"), item("One"), item("Two"), item("Four")]);
+
+        let edits       = diff_tree_keyed(&list_a, &list_b);
+
+        assert!(edits == vec![
+            TreeEdit::Move { address: vec![], from: 2, to: 0 },
+            TreeEdit::Insert { address: vec![], index: 3, node: item("Four") }
+        ]);
+    }
+
+    #[test]
+    fn gauge_arc_is_proportional_to_value() {
+        let control     = Control::gauge()
+            .with(Gauge::StartAngle(180.0))
+            .with(Gauge::SweepAngle(180.0));
+        let arc         = control.gauge_arc();
+
+        assert!(arc == GaugeArc::new(180.0, 180.0));
+        assert!(Control::gauge().gauge_arc() == GaugeArc::default());
+
+        // A quarter of the way through the range fills a quarter of the arc
+        assert!(GaugeArc::proportion(50.0, 0.0, 200.0) == 0.25);
+        assert!(arc.end_angle(50.0, 0.0, 200.0) == 225.0);
+
+        // Values outside the range are clamped
+        assert!(arc.end_angle(500.0, 0.0, 200.0) == 360.0);
+        assert!(arc.end_angle(-10.0, 0.0, 200.0) == 180.0);
+        assert!(arc.end_angle(50.0, 200.0, 200.0) == 180.0);
+    }
+
+    #[test]
+    fn dragging_crop_handles_stays_within_image() {
+        let image_size  = (640.0, 480.0);
+        let crop        = CropBounds::new(100.0, 100.0, 200.0, 100.0);
+
+        // Dragging a corner past the edge of the image stops at the edge
+        let dragged     = crop.drag_handle(CropHandle::BottomRight, (1000.0, 1000.0), image_size, None);
+        assert!(dragged == CropBounds::new(100.0, 100.0, 540.0, 380.0));
+        assert!(dragged.is_within(image_size));
+
+        // Edges can't be dragged past each other
+        let dragged     = crop.drag_handle(CropHandle::Left, (500.0, 0.0), image_size, None);
+        assert!(dragged == CropBounds::new(300.0, 100.0, 0.0, 100.0));
+
+        // Moving keeps the size of the rectangle
+        let dragged     = crop.drag_handle(CropHandle::Move, (-150.0, 500.0), image_size, None);
+        assert!(dragged == CropBounds::new(0.0, 380.0, 200.0, 100.0));
+
+        // Locking the aspect ratio shrinks the rectangle to fit the image
+        let dragged     = crop.drag_handle(CropHandle::BottomRight, (1000.0, 0.0), image_size, Some(2.0));
+        assert!(dragged.is_within(image_size));
+        assert!((dragged.width / dragged.height - 2.0).abs() < 0.001);
+        assert!(dragged.x == 100.0 && dragged.y == 100.0);
+        assert!(dragged.width == 540.0 && dragged.height == 270.0);
+
+        let dragged     = crop.drag_handle(CropHandle::Top, (0.0, -80.0), image_size, Some(2.0));
+        assert!(dragged == CropBounds::new(20.0, 20.0, 360.0, 180.0));
+    }
+
+    #[test]
+    fn percentage_resolves_against_reference_size() {
+        let value = "50%".parse::<UnitValue>().unwrap();
+
+        assert!(value == UnitValue::new(50.0, Unit::Percent));
+        assert!(value.to_pixels(300.0, 16.0) == 150.0);
+        assert!(value.convert_to(Unit::Em, 300.0, 15.0) == UnitValue::new(10.0, Unit::Em));
+        assert!(value.to_string() == "50%");
+    }
+
+    #[test]
+    fn unit_values_use_default_unit() {
+        assert!(UnitValue::parse(" 2.5 em", Unit::Pixels) == Ok(UnitValue::new(2.5, Unit::Em)));
+        assert!(UnitValue::parse("12", Unit::Percent) == Ok(UnitValue::new(12.0, Unit::Percent)));
+        assert!(UnitValue::from_parameter(&ActionParameter::Value(PropertyValue::String("-4px".to_string())), Unit::Em) == Ok(UnitValue::new(-4.0, Unit::Pixels)));
+    }
+
+    #[test]
+    fn unknown_units_are_rejected() {
+        assert!("12pt".parse::<UnitValue>() == Err(UnitError::UnknownUnit("pt".to_string())));
+        assert!("wide".parse::<UnitValue>() == Err(UnitError::NotANumber("wide".to_string())));
+    }
+
+    fn two_level_tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::branch("layers", "Layers", vec![
+                TreeNode::leaf("layer1", "Layer 1"),
+                TreeNode::leaf("layer2", "Layer 2")
+            ]).expanded(true),
+            TreeNode::lazy("frames", "Frames")
+        ]
+    }
+
+    #[test]
+    fn tree_round_trips_through_json() {
+        let tree        = Tree::Nodes(two_level_tree());
+        let json        = serde_json::to_value(&tree).unwrap();
+        let round_trip  = serde_json::from_value::<Tree>(json).unwrap();
+
+        assert!(round_trip == tree);
+
+        let control     = Control::tree().with(two_level_tree());
+        let json        = control.to_json();
+
+        assert!(control.control_type() == ControlType::TreeView);
+        assert!(json["attributes"][0]["Tree"] == serde_json::to_value(&tree).unwrap());
+    }
+
+    #[test]
+    fn tree_nodes_round_trip_through_property_value() {
+        let value       = Tree::property_value(&two_level_tree());
+
+        assert!(Tree::nodes_from_property_value(&value) == Some(two_level_tree()));
+        assert!(Tree::nodes_from_property_value(&PropertyValue::Int(1)) == None);
+    }
+
+    #[test]
+    fn arrow_keys_change_tree_selection() {
+        let nodes       = two_level_tree();
+        let selection   = bind("layers".to_string());
+        let press_key   = |key| {
+            let selected = selection.get();
+            match navigate_tree(&nodes, Some(&selected), key) {
+                TreeNavigation::Select(id)  => selection.set(id),
+                _                           => { }
+            }
+        };
+
+        press_key(KeyPress::KeyRight);
+        assert!(selection.get() == "layer1".to_string());
+
+        press_key(KeyPress::KeyDown);
+        assert!(selection.get() == "layer2".to_string());
+
+        press_key(KeyPress::KeyDown);
+        assert!(selection.get() == "frames".to_string());
+
+        press_key(KeyPress::KeyUp);
+        press_key(KeyPress::KeyLeft);
+        assert!(selection.get() == "layers".to_string());
+    }
+
+    #[test]
+    fn tree_keys_expand_and_collapse_nodes() {
+        let nodes = two_level_tree();
+
+        assert!(navigate_tree(&nodes, Some("frames"), KeyPress::KeyRight) == TreeNavigation::Expand("frames".to_string()));
+        assert!(navigate_tree(&nodes, Some("layers"), KeyPress::KeyLeft) == TreeNavigation::Collapse("layers".to_string()));
+        assert!(navigate_tree(&nodes, Some("layer1"), KeyPress::KeyRight) == TreeNavigation::None);
+    }
+
+    #[test]
+    fn typed_drag_payload_only_dropped_on_compatible_targets() {
+        let source          = Control::label()
+            .with("Layer 1")
+            .with(DragPayload::new("layer", PropertyValue::Int(1)))
+            .with((ActionTrigger::DragStart, "StartDragLayer"));
+        let layer_list      = Control::container()
+            .with(DragDrop::Target(vec!["layer".to_string()]))
+            .with((ActionTrigger::Drop, "DropLayer"));
+        let tool_list       = Control::container()
+            .with(DragDrop::Target(vec!["tool".to_string()]))
+            .with((ActionTrigger::Drop, "DropTool"));
+
+        // Drag the payload from the source
+        let payload         = source.drag_payload().cloned().unwrap();
+        assert!(payload == DragPayload::new("layer", PropertyValue::Int(1)));
+
+        // Only the list of layers should accept it
+        assert!(layer_list.accepts_drop(&payload));
+        assert!(!tool_list.accepts_drop(&payload));
+        assert!(!source.accepts_drop(&payload));
+
+        // The payload is sent to the controller with the action
+        let json            = serde_json::to_value(ActionParameter::DragPayload(payload.clone())).unwrap();
+        let parameter       = serde_json::from_value::<ActionParameter>(json).unwrap();
+        assert!(parameter == ActionParameter::DragPayload(payload));
+    }
 }
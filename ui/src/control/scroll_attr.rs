@@ -50,7 +50,29 @@ pub enum Scroll {
     /// Fixes the position of this element relative to its containing scroll region
     ///
     /// It will be laid out as normal but will not move when the region is scrolled
-    Fix(FixedAxis)
+    Fix(FixedAxis),
+
+    /// Scrolls the content so that the specified position is at the top-left of the control
+    ///
+    /// The position is clamped so that the control doesn't scroll past the end of its content.
+    /// The control is scrolled whenever this value changes: use `ScrollPosition` to keep it
+    /// in sync with the position the user has scrolled to.
+    ScrollTo(f32, f32)
+}
+
+///
+/// Describes where a scrolling container has been scrolled to (sent with the `Scroll` action)
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ScrollState {
+    /// The position of the top-left corner of the visible region within the content
+    pub position:       (f32, f32),
+
+    /// The size of the content of the scrolling container
+    pub content_size:   (f32, f32),
+
+    /// The size of the visible region of the scrolling container
+    pub viewport_size:  (f32, f32)
 }
 
 impl Modifier<Control> for Scroll {
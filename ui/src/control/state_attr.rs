@@ -24,7 +24,17 @@ pub enum State {
     Range((Property, Property)),
 
     /// The priority for focusing this control (if it's created while no control is focused or a control with a lower priority is focused, then this control will be focused instead)
-    FocusPriority(Property)
+    FocusPriority(Property),
+
+    /// Whether or not the controls contained within this control are enabled
+    ///
+    /// A disabled subtree overrides the `Enabled` state of everything inside it: controls (and nested
+    /// subtrees) cannot re-enable themselves while any of their ancestors has a disabled subtree. The
+    /// control with this attribute is not itself affected.
+    ///
+    /// The Cocoa UI doesn't change the appearance of the controls in a disabled subtree (but the session
+    /// still suppresses their actions).
+    SubtreeEnabled(Property)
 }
 
 impl Modifier<Control> for State {
@@ -0,0 +1,329 @@
+use super::control::*;
+use super::keypress::*;
+use super::attributes::*;
+use super::super::property::*;
+
+use ::modifier::*;
+use serde_json;
+
+use std::ptr;
+
+///
+/// Describes the children of a node in a tree view
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TreeChildren {
+    /// This node can never have any children
+    Leaf,
+
+    /// The children of this node are known
+    Loaded(Vec<TreeNode>),
+
+    /// This node has children that have not been loaded yet: expanding it generates an `ExpandNode` action
+    /// so the controller can supply them
+    NotLoaded
+}
+
+///
+/// A single node in a tree view
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TreeNode {
+    /// The identifier for this node (sent as the value when the node is selected, expanded or collapsed)
+    pub id: String,
+
+    /// The text to display for this node
+    pub label: String,
+
+    /// True if the children of this node are visible
+    pub expanded: bool,
+
+    /// The children of this node
+    pub children: TreeChildren
+}
+
+///
+/// Attributes describing the content of a tree view control
+///
+/// The selected node is bound using `State::Value` (with the ID of the node as a string), and is
+/// changed by the `SetValue` action. Front-ends send arrow keys pressed in the tree to the `SetValue`
+/// action as `ActionParameter::TreeKey`: the session turns these into the action that the key
+/// performs (see `navigate_tree`).
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Tree {
+    /// The top-level nodes in this tree
+    Nodes(Vec<TreeNode>),
+
+    /// The top-level nodes in this tree are read from a property (see `Tree::property_value` for the format)
+    Bound(Property)
+}
+
+///
+/// The result of a keyboard navigation action in a tree view
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum TreeNavigation {
+    /// The key press has no effect
+    None,
+
+    /// The node with the specified ID should become selected
+    Select(String),
+
+    /// The node with the specified ID should be expanded
+    Expand(String),
+
+    /// The node with the specified ID should be collapsed
+    Collapse(String)
+}
+
+impl Tree {
+    ///
+    /// Creates the value of a property that can be used with `Tree::Bound` to display a set of nodes
+    ///
+    pub fn property_value(nodes: &Vec<TreeNode>) -> PropertyValue {
+        PropertyValue::String(serde_json::to_string(nodes).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    ///
+    /// Reads the nodes from the value of a property bound using `Tree::Bound`
+    ///
+    pub fn nodes_from_property_value(value: &PropertyValue) -> Option<Vec<TreeNode>> {
+        match value {
+            PropertyValue::String(json) => serde_json::from_str(json).ok(),
+            _                           => None
+        }
+    }
+}
+
+impl TreeNode {
+    ///
+    /// Creates a new leaf node
+    ///
+    pub fn leaf(id: &str, label: &str) -> TreeNode {
+        TreeNode {
+            id:         id.to_string(),
+            label:      label.to_string(),
+            expanded:   false,
+            children:   TreeChildren::Leaf
+        }
+    }
+
+    ///
+    /// Creates a new node with some children
+    ///
+    pub fn branch(id: &str, label: &str, children: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            id:         id.to_string(),
+            label:      label.to_string(),
+            expanded:   false,
+            children:   TreeChildren::Loaded(children)
+        }
+    }
+
+    ///
+    /// Creates a new node whose children will be loaded when it is expanded
+    ///
+    pub fn lazy(id: &str, label: &str) -> TreeNode {
+        TreeNode {
+            id:         id.to_string(),
+            label:      label.to_string(),
+            expanded:   false,
+            children:   TreeChildren::NotLoaded
+        }
+    }
+
+    ///
+    /// Returns a copy of this node with its expanded state changed
+    ///
+    pub fn expanded(mut self, expanded: bool) -> TreeNode {
+        self.expanded = expanded;
+        self
+    }
+
+    ///
+    /// True if this node can ever have children
+    ///
+    pub fn is_leaf(&self) -> bool {
+        match self.children {
+            TreeChildren::Leaf  => true,
+            _                   => false
+        }
+    }
+
+    ///
+    /// The children of this node that are currently visible
+    ///
+    fn visible_children<'a>(&'a self) -> &'a [TreeNode] {
+        match (self.expanded, &self.children) {
+            (true, TreeChildren::Loaded(children))  => children,
+            _                                       => &[]
+        }
+    }
+}
+
+///
+/// Entry in a flattened tree: the node and the index of its parent in the flattened list
+///
+struct FlatTreeNode<'a> {
+    node:   &'a TreeNode,
+    parent: Option<usize>
+}
+
+///
+/// Returns the path of indexes leading to the node with the specified ID, if it exists
+///
+/// The tree is searched iteratively, so deeply nested trees will not overflow the stack.
+///
+fn path_to_node(nodes: &[TreeNode], id: &str) -> Option<Vec<usize>> {
+    let mut stack = vec![(nodes, 0, vec![])];
+
+    while let Some((siblings, index, path)) = stack.pop() {
+        if index >= siblings.len() { continue; }
+
+        // Continue with the next sibling later on
+        stack.push((siblings, index+1, path.clone()));
+
+        let mut node_path = path;
+        node_path.push(index);
+
+        if siblings[index].id == id {
+            return Some(node_path);
+        }
+
+        if let TreeChildren::Loaded(children) = &siblings[index].children {
+            stack.push((children, 0, node_path));
+        }
+    }
+
+    None
+}
+
+///
+/// Flattens the visible nodes of a tree in display order
+///
+fn visible_nodes<'a>(nodes: &'a [TreeNode]) -> Vec<FlatTreeNode<'a>> {
+    let mut result  = vec![];
+    let mut stack   = vec![(nodes, 0, None)];
+
+    while let Some((siblings, index, parent)) = stack.pop() {
+        if index >= siblings.len() { continue; }
+
+        stack.push((siblings, index+1, parent));
+
+        let node        = &siblings[index];
+        let node_index  = result.len();
+        result.push(FlatTreeNode { node: node, parent: parent });
+
+        stack.push((node.visible_children(), 0, Some(node_index)));
+    }
+
+    result
+}
+
+///
+/// Determines what should happen when a key is pressed in a tree view with the specified content and selection
+///
+/// Up and down move between the visible nodes, right expands a node (or moves to its first child if it's
+/// already expanded) and left collapses a node (or moves to its parent if it's already collapsed). If the
+/// selected node is hidden inside a collapsed node, navigation starts from the nearest visible ancestor.
+///
+pub fn navigate_tree(nodes: &[TreeNode], selected: Option<&str>, key: KeyPress) -> TreeNavigation {
+    let visible = visible_nodes(nodes);
+
+    if visible.len() == 0 {
+        return TreeNavigation::None;
+    }
+
+    // Find the visible node corresponding to the selection
+    let selected_index = selected
+        .and_then(|selected| path_to_node(nodes, selected))
+        .and_then(|path| {
+            // Walk down the path until we reach the selected node or a collapsed node
+            let mut siblings    = nodes;
+            let mut node        = None;
+
+            for index in path {
+                let next_node = &siblings[index];
+                node = Some(next_node);
+
+                if !next_node.expanded { break; }
+                siblings = next_node.visible_children();
+            }
+
+            node.and_then(|node| visible.iter().position(|flat| ptr::eq(flat.node, node)))
+        });
+
+    let selected_index = match selected_index {
+        Some(index) => index,
+        None        => {
+            // With no selection, the up and down keys select the first or last node
+            return match key {
+                KeyPress::KeyDown   => TreeNavigation::Select(visible[0].node.id.clone()),
+                KeyPress::KeyUp     => TreeNavigation::Select(visible[visible.len()-1].node.id.clone()),
+                _                   => TreeNavigation::None
+            };
+        }
+    };
+
+    let current = &visible[selected_index];
+
+    match key {
+        KeyPress::KeyUp | KeyPress::KeyDown if Some(current.node.id.as_str()) != selected => {
+            // Selection is hidden in a collapsed node: move to the ancestor that's actually visible
+            TreeNavigation::Select(current.node.id.clone())
+        },
+
+        KeyPress::KeyUp => {
+            if selected_index > 0 {
+                TreeNavigation::Select(visible[selected_index-1].node.id.clone())
+            } else {
+                TreeNavigation::None
+            }
+        },
+
+        KeyPress::KeyDown => {
+            if selected_index+1 < visible.len() {
+                TreeNavigation::Select(visible[selected_index+1].node.id.clone())
+            } else {
+                TreeNavigation::None
+            }
+        },
+
+        KeyPress::KeyRight => {
+            if current.node.is_leaf() {
+                TreeNavigation::None
+            } else if !current.node.expanded {
+                TreeNavigation::Expand(current.node.id.clone())
+            } else if let Some(first_child) = current.node.visible_children().first() {
+                TreeNavigation::Select(first_child.id.clone())
+            } else {
+                TreeNavigation::None
+            }
+        },
+
+        KeyPress::KeyLeft => {
+            if current.node.expanded && !current.node.is_leaf() {
+                TreeNavigation::Collapse(current.node.id.clone())
+            } else if let Some(parent) = current.parent {
+                TreeNavigation::Select(visible[parent].node.id.clone())
+            } else {
+                TreeNavigation::None
+            }
+        },
+
+        _ => TreeNavigation::None
+    }
+}
+
+impl Modifier<Control> for Tree {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::TreeAttr(self))
+    }
+}
+
+impl Modifier<Control> for Vec<TreeNode> {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::TreeAttr(Tree::Nodes(self)))
+    }
+}
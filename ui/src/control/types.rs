@@ -43,5 +43,17 @@ pub enum ControlType {
     /// A combobox that displays some text and makes it possible to choose a subcontrol from a menu
     ///
     /// Subcontrols must be labels 
-    ComboBox
+    ComboBox,
+
+    /// A tree of expandable nodes, where one node can be selected
+    TreeView,
+
+    /// Plays an audio or video file, with a position that can be synchronised with a timeline
+    Media,
+
+    /// Displays an image with a rectangle that the user can drag to choose the part of the image to crop to
+    CropImage,
+
+    /// Displays a value within a range as a partially filled-in arc
+    Gauge
 }
@@ -0,0 +1,167 @@
+use super::*;
+use super::super::property::*;
+
+use ::modifier::*;
+
+use std::fmt;
+use std::str::{FromStr};
+
+///
+/// The units that a numeric value can be specified in
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Unit {
+    /// Value in pixels ('px')
+    Pixels,
+
+    /// Value as a percentage of a reference size ('%')
+    Percent,
+
+    /// Value as a multiple of the font size ('em')
+    Em
+}
+
+///
+/// A numeric value along with the unit it was specified in
+///
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct UnitValue {
+    pub value:  f64,
+    pub unit:   Unit
+}
+
+///
+/// Errors that can occur while reading a value with a unit
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum UnitError {
+    /// The text did not start with a number
+    NotANumber(String),
+
+    /// The value was specified using a unit that isn't supported
+    UnknownUnit(String)
+}
+
+impl Unit {
+    ///
+    /// The suffix used to indicate this unit
+    ///
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Pixels    => "px",
+            Unit::Percent   => "%",
+            Unit::Em        => "em"
+        }
+    }
+
+    ///
+    /// Returns the unit with the specified suffix
+    ///
+    pub fn from_suffix(suffix: &str) -> Option<Unit> {
+        match suffix.to_lowercase().as_str() {
+            "px"    => Some(Unit::Pixels),
+            "%"     => Some(Unit::Percent),
+            "em"    => Some(Unit::Em),
+            _       => None
+        }
+    }
+}
+
+impl UnitValue {
+    ///
+    /// Creates a new value with a unit
+    ///
+    pub fn new(value: f64, unit: Unit) -> UnitValue {
+        UnitValue { value, unit }
+    }
+
+    ///
+    /// Reads a value with a unit from a string (eg, '50%' or '12px'). Values with no unit are assumed to be in the default unit
+    ///
+    pub fn parse(text: &str, default_unit: Unit) -> Result<UnitValue, UnitError> {
+        let text            = text.trim();
+
+        // Split into the number and the unit
+        let number_len      = text.char_indices()
+            .take_while(|(idx, chr)| chr.is_ascii_digit() || *chr == '.' || ((*chr == '-' || *chr == '+') && *idx == 0))
+            .map(|(idx, chr)| idx + chr.len_utf8())
+            .last()
+            .unwrap_or(0);
+        let (number, unit)  = text.split_at(number_len);
+        let unit            = unit.trim();
+
+        let value           = f64::from_str(number).map_err(|_| UnitError::NotANumber(text.to_string()))?;
+        let unit            = if unit.is_empty() { default_unit } else { Unit::from_suffix(unit).ok_or_else(|| UnitError::UnknownUnit(unit.to_string()))? };
+
+        Ok(UnitValue { value, unit })
+    }
+
+    ///
+    /// Reads a value with a unit from the parameter of a `SetValue` or `EditValue` action
+    ///
+    /// Back-ends that understand units send a `UnitValue` parameter: others send the text the user entered, which is
+    /// parsed here. Plain numbers are assumed to be in the default unit.
+    ///
+    pub fn from_parameter(parameter: &ActionParameter, default_unit: Unit) -> Result<UnitValue, UnitError> {
+        match parameter {
+            ActionParameter::UnitValue(value)                           => Ok(*value),
+            ActionParameter::Value(PropertyValue::String(text))         => UnitValue::parse(text, default_unit),
+            ActionParameter::Value(PropertyValue::Float(value))         => Ok(UnitValue::new(*value, default_unit)),
+            ActionParameter::Value(PropertyValue::Int(value))           => Ok(UnitValue::new(*value as f64, default_unit)),
+            _                                                           => Err(UnitError::NotANumber(String::new()))
+        }
+    }
+
+    ///
+    /// Returns the size of this value in pixels
+    ///
+    /// Percentages are relative to the reference size (eg, the width of the container), and ems are relative to the font size
+    ///
+    pub fn to_pixels(&self, reference_size: f64, font_size: f64) -> f64 {
+        match self.unit {
+            Unit::Pixels    => self.value,
+            Unit::Percent   => self.value * reference_size / 100.0,
+            Unit::Em        => self.value * font_size
+        }
+    }
+
+    ///
+    /// Converts this value to a different unit
+    ///
+    /// Converting to percentages or ems isn't possible if the reference or font size is 0: the value is 0 in this case
+    ///
+    pub fn convert_to(&self, unit: Unit, reference_size: f64, font_size: f64) -> UnitValue {
+        let pixels = self.to_pixels(reference_size, font_size);
+
+        let value = match unit {
+            Unit::Pixels    => pixels,
+            Unit::Percent   => if reference_size == 0.0 { 0.0 } else { pixels * 100.0 / reference_size },
+            Unit::Em        => if font_size == 0.0 { 0.0 } else { pixels / font_size }
+        };
+
+        UnitValue { value, unit }
+    }
+}
+
+impl FromStr for UnitValue {
+    type Err = UnitError;
+
+    ///
+    /// Reads a value with a unit from a string, with pixels as the default unit
+    ///
+    fn from_str(text: &str) -> Result<UnitValue, UnitError> {
+        UnitValue::parse(text, Unit::Pixels)
+    }
+}
+
+impl fmt::Display for UnitValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}{}", self.value, self.unit.suffix())
+    }
+}
+
+impl Modifier<Control> for Vec<Unit> {
+    fn modify(self, control: &mut Control) {
+        control.add_attribute(ControlAttribute::Units(self))
+    }
+}
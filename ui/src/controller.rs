@@ -3,6 +3,7 @@ use flo_binding::binding_context::*;
 
 use super::image::*;
 use super::control::*;
+use super::property::*;
 use super::viewmodel::*;
 use super::binding_canvas::*;
 use super::resource_manager::*;
@@ -311,6 +312,228 @@ pub fn keymap_binding(controller: Arc<dyn Controller>) -> BindRef<Arc<HashMap<Ke
     BindRef::from(binding)
 }
 
+///
+/// Describes the action that a key shortcut attached to a control will perform
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ShortcutBinding {
+    /// The path to the controller that owns the control with the shortcut
+    pub path: Vec<String>,
+
+    /// The name of the action that is sent when the control is clicked
+    pub action: ActionEvent
+}
+
+///
+/// Adds the key shortcuts for a control and its subcomponents to a shortcut map, in the order they appear in the UI
+///
+/// Subcontrollers are searched where they appear in the tree, and shortcuts that are already in the map are left
+/// alone, so the first control to use a particular key binding is the one that is triggered.
+///
+fn add_shortcuts(controller: &Arc<dyn Controller>, control: &Control, path: &Vec<String>, shortcuts: &mut HashMap<KeyBinding, ShortcutBinding>) {
+    // A shortcut on this control performs its click action
+    let click_action = control.attributes()
+        .filter_map(|attr| attr.action())
+        .filter(|(trigger, _)| **trigger == ActionTrigger::Click)
+        .map(|(_, action)| action.clone())
+        .nth(0);
+
+    if let Some(click_action) = click_action {
+        for keys in control.attributes().filter_map(|attr| attr.key_shortcut()) {
+            shortcuts.entry(keys.clone()).or_insert_with(|| ShortcutBinding { path: path.clone(), action: click_action.clone() });
+        }
+    }
+
+    // Process the subcomponents in order
+    for attr in control.attributes() {
+        match attr {
+            ControlAttribute::SubComponents(subcomponents)  => {
+                subcomponents.iter().for_each(|subcomponent| add_shortcuts(controller, subcomponent, path, shortcuts));
+            }
+
+            ControlAttribute::Controller(controller_name)   => {
+                if let Some(subcontroller) = controller.get_subcontroller(controller_name) {
+                    let mut subcontroller_path = path.clone();
+                    subcontroller_path.push(controller_name.clone());
+
+                    add_shortcuts(&subcontroller, &subcontroller.ui().get(), &subcontroller_path, shortcuts);
+                }
+            }
+
+            _                                               => { }
+        }
+    }
+}
+
+///
+/// Creates a binding mapping key presses to the controls whose click actions they should perform
+///
+pub fn shortcut_map_binding(controller: Arc<dyn Controller>) -> BindRef<Arc<HashMap<KeyBinding, ShortcutBinding>>> {
+    let controller  = Arc::downgrade(&controller);
+    let binding     = computed(move || {
+        // Fetch the controller if it hasn't been released
+        let controller = controller.upgrade();
+        let controller = if let Some(controller) = controller { controller } else { return Arc::new(HashMap::new()); };
+
+        // Search the whole UI tree for shortcuts
+        let mut shortcuts = HashMap::new();
+        add_shortcuts(&controller, &controller.ui().get(), &vec![], &mut shortcuts);
+
+        Arc::new(shortcuts)
+    });
+
+    BindRef::from(binding)
+}
+
+///
+/// Reads a boolean property using the viewmodel for a controller
+///
+fn property_to_bool(controller: &dyn Controller, property: &Property) -> Option<bool> {
+    match property {
+        Property::Bool(value)   => Some(*value),
+        Property::Bind(name)    => controller.get_viewmodel().and_then(|viewmodel| viewmodel.get_property(name).get().to_bool()),
+        _                       => None
+    }
+}
+
+///
+/// Adds the actions generated by a control and its subcomponents to the sets of enabled and disabled actions
+///
+fn add_action_states(controller: &Arc<dyn Controller>, control: &Control, path: &Vec<String>, subtree_disabled: bool, enabled: &mut HashSet<(Vec<String>, String)>, disabled: &mut HashSet<(Vec<String>, String)>) {
+    // Record the actions generated by this control
+    for attr in control.attributes() {
+        if let ControlAttribute::Action(_, ActionEvent::Named(name)) = attr {
+            let action = (path.clone(), name.clone());
+            if subtree_disabled { disabled.insert(action); } else { enabled.insert(action); }
+        }
+    }
+
+    // Disabling a subtree overrides anything within it
+    let subtree_disabled = subtree_disabled || control.attributes().any(|attr| match attr {
+        ControlAttribute::StateAttr(State::SubtreeEnabled(enabled))    => property_to_bool(&**controller, enabled) == Some(false),
+        _                                                               => false
+    });
+
+    // Search the subcontroller or the subcomponents
+    for attr in control.attributes() {
+        match attr {
+            ControlAttribute::SubComponents(subcomponents)  => {
+                subcomponents.iter().for_each(|subcomponent| add_action_states(controller, subcomponent, path, subtree_disabled, enabled, disabled));
+            }
+
+            ControlAttribute::Controller(controller_name)   => {
+                if let Some(subcontroller) = controller.get_subcontroller(controller_name) {
+                    let mut subcontroller_path = path.clone();
+                    subcontroller_path.push(controller_name.clone());
+
+                    add_action_states(&subcontroller, &subcontroller.ui().get(), &subcontroller_path, subtree_disabled, enabled, disabled);
+                }
+            }
+
+            _                                               => { }
+        }
+    }
+}
+
+///
+/// Creates a binding containing the actions (as controller path and action name) whose controls all belong to disabled subtrees
+///
+/// Actions that aren't attached to any control (for instance, actions generated by commands) are never disabled. The
+/// binding is only recalculated when the UI or the properties it depends on change, so it's cheap to check when
+/// dispatching an action.
+///
+pub fn disabled_actions_binding(controller: Arc<dyn Controller>) -> BindRef<Arc<HashSet<(Vec<String>, String)>>> {
+    let controller  = Arc::downgrade(&controller);
+    let binding     = computed(move || {
+        // Fetch the controller if it hasn't been released
+        let controller = controller.upgrade();
+        let controller = if let Some(controller) = controller { controller } else { return Arc::new(HashSet::new()); };
+
+        // An action is only disabled if none of the controls that generate it are enabled
+        let mut enabled     = HashSet::new();
+        let mut disabled    = HashSet::new();
+        add_action_states(&controller, &controller.ui().get(), &vec![], false, &mut enabled, &mut disabled);
+
+        disabled.retain(|action| !enabled.contains(action));
+
+        Arc::new(disabled)
+    });
+
+    BindRef::from(binding)
+}
+
+///
+/// Reads the value of a property using the viewmodel for a controller
+///
+fn property_to_value(controller: &dyn Controller, property: &Property) -> Option<PropertyValue> {
+    match property {
+        Property::Bind(name)    => controller.get_viewmodel().map(|viewmodel| viewmodel.get_property(name).get()),
+        constant                => constant.clone().into()
+    }
+}
+
+///
+/// Works out the actions that a key pressed in a tree view should perform
+///
+/// `action_name` is the name of the `SetValue` action for the tree view, which must be part of the UI for
+/// the controller. The returned actions are the names and parameters of the actions to send to the controller.
+///
+pub fn tree_key_actions(controller: &dyn Controller, action_name: &str, key: KeyPress) -> Vec<(String, ActionParameter)> {
+    let ui      = controller.ui().get();
+    let tree    = if let Some(tree) = ui.find_action_control(&ActionTrigger::SetValue, action_name) { tree } else { return vec![]; };
+
+    // Read the nodes and the selection from the tree's attributes
+    let nodes       = match tree.tree_nodes() {
+        Some(Tree::Nodes(nodes))        => nodes.clone(),
+        Some(Tree::Bound(property))     => property_to_value(controller, property).and_then(|value| Tree::nodes_from_property_value(&value)).unwrap_or_else(|| vec![]),
+        None                            => vec![]
+    };
+    let selected    = tree.attributes()
+        .filter_map(|attr| match attr {
+            ControlAttribute::StateAttr(State::Value(value))    => property_to_value(controller, value),
+            _                                                   => None
+        })
+        .filter_map(|value| value.string())
+        .nth(0);
+
+    // Finds the name of the action the tree generates for a particular trigger
+    let action_for  = |trigger: ActionTrigger| tree.actions().into_iter()
+        .filter(|(action_trigger, _)| action_trigger == &trigger)
+        .map(|(_, event)| match event { ActionEvent::Named(name) => name })
+        .nth(0);
+
+    let (action_name, node_id) = match navigate_tree(&nodes, selected.as_ref().map(|selected| selected.as_str()), key) {
+        TreeNavigation::None            => (None, None),
+        TreeNavigation::Select(id)      => (Some(action_name.to_string()), Some(id)),
+        TreeNavigation::Expand(id)      => (action_for(ActionTrigger::ExpandNode), Some(id)),
+        TreeNavigation::Collapse(id)    => (action_for(ActionTrigger::CollapseNode), Some(id))
+    };
+
+    match (action_name, node_id) {
+        (Some(action_name), Some(node_id))  => vec![(action_name, ActionParameter::Value(PropertyValue::String(node_id)))],
+        _                                   => vec![]
+    }
+}
+
+///
+/// Works out the new bounds for a crop control after one of its handles has been dragged
+///
+/// `action_name` is the name of the `Crop` action for the control, which is used to find the aspect ratio that
+/// the crop rectangle is locked to, if there is one. The result is always inside the image.
+///
+pub fn crop_drag_bounds(controller: &dyn Controller, action_name: &str, start_bounds: &CropBounds, handle: CropHandle, offset: (f32, f32), image_size: (f32, f32)) -> CropBounds {
+    let ui              = controller.ui().get();
+    let aspect_ratio    = ui.find_action_control(&ActionTrigger::Crop, action_name)
+        .and_then(|crop_control| crop_control.attributes()
+            .filter_map(|attr| match attr.crop() {
+                Some(Crop::AspectRatio(ratio))  => Some(*ratio),
+                _                               => None
+            })
+            .nth(0));
+
+    start_bounds.drag_handle(handle, offset, image_size, aspect_ratio)
+}
+
 ///
 /// A controller that does nothing
 ///
@@ -336,6 +559,7 @@ impl Controller for NullController {
 #[cfg(test)]
 mod test {
     use super::*;
+    use super::super::dynamic_viewmodel::*;
 
     struct TestController {
         pub label_controller: Arc<LabelController>,
@@ -541,4 +765,102 @@ mod test {
         assert!(controller_path_for_address(&control, &vec![1, 2]) == Some(vec!["Test1", "Test2"]));
         assert!(controller_path_for_address(&control, &vec![1, 2, 0]) == Some(vec!["Test1", "Test2", "Test3"]));
     }
+
+    struct PanelController {
+        view_model: Arc<DynamicViewModel>,
+        ui: BindRef<Control>
+    }
+
+    impl Controller for PanelController {
+        fn ui(&self) -> BindRef<Control> {
+            BindRef::clone(&self.ui)
+        }
+
+        fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+            Some(self.view_model.clone())
+        }
+    }
+
+    #[test]
+    fn disabling_container_suppresses_child_actions() {
+        let view_model  = Arc::new(DynamicViewModel::new());
+        view_model.set_property("PanelEnabled", PropertyValue::Bool(true));
+
+        let controller: Arc<dyn Controller> = Arc::new(PanelController {
+            view_model: view_model.clone(),
+            ui:         BindRef::from(bind(Control::container()
+                .with(State::SubtreeEnabled(Property::bound("PanelEnabled")))
+                .with((ActionTrigger::Click, "Container"))
+                .with(vec![
+                    Control::button().with((ActionTrigger::Click, "Button1")),
+                    Control::container()
+                        .with(State::SubtreeEnabled(Property::Bool(true)))
+                        .with(vec![
+                            Control::button().with((ActionTrigger::Click, "Button2"))
+                        ])
+                ])))
+        });
+        let disabled_actions    = disabled_actions_binding(Arc::clone(&controller));
+        let action_is_disabled  = |name: &str| disabled_actions.get().contains(&(vec![], name.to_string()));
+
+        assert!(!action_is_disabled("Button1"));
+        assert!(!action_is_disabled("Button2"));
+
+        view_model.set_property("PanelEnabled", PropertyValue::Bool(false));
+
+        // All the child actions are disabled, including in the nested subtree that tries to re-enable itself
+        assert!(action_is_disabled("Button1"));
+        assert!(action_is_disabled("Button2"));
+
+        // The container itself and actions that aren't attached to any control are unaffected
+        assert!(!action_is_disabled("Container"));
+        assert!(!action_is_disabled("NotAControl"));
+    }
+
+    struct ToolbarController {
+        edit_controller: Arc<PanelController>,
+        ui: BindRef<Control>
+    }
+
+    impl Controller for ToolbarController {
+        fn ui(&self) -> BindRef<Control> {
+            BindRef::clone(&self.ui)
+        }
+
+        fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+            if id == "Edit" { Some(self.edit_controller.clone()) } else { None }
+        }
+    }
+
+    #[test]
+    fn first_control_with_shortcut_is_triggered() {
+        let edit_controller = Arc::new(PanelController {
+            view_model: Arc::new(DynamicViewModel::new()),
+            ui:         BindRef::from(bind(Control::container()
+                .with(vec![
+                    Control::button().with((ActionTrigger::Click, "Undo")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyZ)),
+                    Control::button().with((ActionTrigger::Click, "Redo")).with_key_shortcut(KeyBinding::ctrl_shift(KeyPress::KeyZ))
+                ])))
+        });
+        let toolbar         = Arc::new(ToolbarController {
+            edit_controller:    edit_controller,
+            ui:                 BindRef::from(bind(Control::container()
+                .with(vec![
+                    Control::container().with_controller("Edit"),
+                    Control::button().with((ActionTrigger::Click, "Revert")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyZ)),
+                    Control::button().with((ActionTrigger::Click, "Save")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyS)),
+                    Control::label().with("No action").with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyL))
+                ])))
+        });
+
+        let shortcuts       = shortcut_map_binding(toolbar).get();
+
+        // Ctrl+Z is used by the undo button in the subcontroller as well as the revert button, but undo appears first in the UI
+        assert!(shortcuts.get(&KeyBinding::ctrl(KeyPress::KeyZ)) == Some(&ShortcutBinding { path: vec!["Edit".to_string()], action: ActionEvent::Named("Undo".to_string()) }));
+        assert!(shortcuts.get(&KeyBinding::ctrl_shift(KeyPress::KeyZ)) == Some(&ShortcutBinding { path: vec!["Edit".to_string()], action: ActionEvent::Named("Redo".to_string()) }));
+        assert!(shortcuts.get(&KeyBinding::ctrl(KeyPress::KeyS)) == Some(&ShortcutBinding { path: vec![], action: ActionEvent::Named("Save".to_string()) }));
+
+        // Controls that can't be clicked don't have a shortcut
+        assert!(shortcuts.get(&KeyBinding::ctrl(KeyPress::KeyL)).is_none());
+    }
 }
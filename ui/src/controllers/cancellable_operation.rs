@@ -0,0 +1,308 @@
+use super::super::control::*;
+
+use futures::prelude::*;
+use futures::future;
+use futures::future::{BoxFuture, Either};
+
+use flo_canvas::{Color};
+use flo_binding::*;
+
+use std::sync::*;
+
+/// Action generated when the cancel button for an operation is clicked
+pub const CANCEL_OPERATION: &str = "CancelOperation";
+
+///
+/// Token passed to a cancellable operation, which is triggered when the operation is cancelled
+///
+#[derive(Clone)]
+pub struct CancellationToken {
+    /// Set to true when the operation is cancelled
+    cancelled: Binding<bool>
+}
+
+impl CancellationToken {
+    ///
+    /// Creates a new token that has not been cancelled
+    ///
+    pub fn new() -> CancellationToken {
+        CancellationToken { cancelled: bind(false) }
+    }
+
+    ///
+    /// Triggers this token
+    ///
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    ///
+    /// True if this token has been triggered
+    ///
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+
+    ///
+    /// Returns a future that completes once this token has been triggered
+    ///
+    pub fn cancelled(&self) -> impl 'static+Send+Future<Output=()> {
+        let cancelled = self.cancelled.clone();
+
+        async move {
+            let mut cancelled_state = Box::pin(follow(cancelled));
+
+            while let Some(is_cancelled) = cancelled_state.next().await {
+                if is_cancelled { return; }
+            }
+        }
+    }
+}
+
+///
+/// The operation that is currently in progress
+///
+struct OperationState {
+    /// The ID to assign to the next operation
+    next_id: usize,
+
+    /// The ID and cancellation token of the running operation
+    current: Option<(usize, CancellationToken)>
+}
+
+///
+/// Manages a long-running asynchronous operation started by a controller, providing an inline progress
+/// indicator with a button to cancel it
+///
+/// Operations are started with `start()`, which returns a future that the controller should run (usually as
+/// part of its runtime). The UI is empty while no operation is running. The `CANCEL_OPERATION` action generated
+/// by the cancel button should be passed on to `action()`.
+///
+pub struct CancellableOperation {
+    /// The operation that's currently running
+    state: Arc<Mutex<OperationState>>,
+
+    /// True while an operation is running
+    running: Binding<bool>,
+
+    /// The progress of the current operation, from 0.0 to 1.0
+    progress: Binding<f32>,
+
+    /// The user interface for the operation
+    ui: BindRef<Control>
+}
+
+impl CancellableOperation {
+    ///
+    /// Creates a new cancellable operation helper, with no operation running
+    ///
+    pub fn new() -> CancellableOperation {
+        let running     = bind(false);
+        let progress    = bind(0.0);
+        let ui          = Self::create_ui(&running, &progress);
+
+        CancellableOperation {
+            state:      Arc::new(Mutex::new(OperationState { next_id: 0, current: None })),
+            running:    running,
+            progress:   progress,
+            ui:         ui
+        }
+    }
+
+    ///
+    /// The user interface for this operation: a progress bar with a cancel button while the operation is running
+    ///
+    pub fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    ///
+    /// True while an operation is running
+    ///
+    pub fn is_running(&self) -> BindRef<bool> {
+        BindRef::from(&self.running)
+    }
+
+    ///
+    /// The progress of the current operation, from 0.0 to 1.0
+    ///
+    pub fn progress(&self) -> BindRef<f32> {
+        BindRef::from(&self.progress)
+    }
+
+    ///
+    /// Starts a new operation, cancelling any operation that's already running
+    ///
+    /// The operation function is passed a cancellation token and a binding that it can use to report its progress.
+    /// The returned future must be run for the operation to proceed: it returns the result of the operation, or
+    /// `None` if the operation was cancelled (in which case the operation's future is dropped without being polled
+    /// again).
+    ///
+    pub fn start<TOperation, TFuture>(&self, operation: TOperation) -> BoxFuture<'static, Option<TFuture::Output>>
+    where
+        TOperation:         FnOnce(CancellationToken, Binding<f32>) -> TFuture,
+        TFuture:            'static+Send+Future,
+        TFuture::Output:    Send {
+        // Only one operation can run at once
+        self.cancel();
+
+        // Register the new operation
+        let token       = CancellationToken::new();
+        let operation_id;
+
+        {
+            let mut state   = self.state.lock().unwrap();
+            operation_id    = state.next_id;
+            state.next_id   += 1;
+            state.current   = Some((operation_id, token.clone()));
+        }
+
+        self.progress.set(0.0);
+        self.running.set(true);
+
+        // Run the operation until it completes or the token is triggered
+        let operation   = operation(token.clone(), self.progress.clone());
+        let state       = Arc::clone(&self.state);
+        let running     = self.running.clone();
+
+        async move {
+            let result = match future::select(operation.boxed(), token.cancelled().boxed()).await {
+                Either::Left((result, _))   => Some(result),
+                Either::Right(_)            => None
+            };
+
+            // Clean up if this is still the current operation
+            let mut state = state.lock().unwrap();
+            if state.current.as_ref().map(|(id, _)| *id) == Some(operation_id) {
+                state.current = None;
+                running.set(false);
+            }
+
+            result
+        }.boxed()
+    }
+
+    ///
+    /// Cancels the running operation, returning true if there was an operation to cancel
+    ///
+    /// This does nothing if the operation has already completed.
+    ///
+    pub fn cancel(&self) -> bool {
+        let current = self.state.lock().unwrap().current.take();
+
+        if let Some((_, token)) = current {
+            token.cancel();
+            self.running.set(false);
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Updates the operation from an action, returning true if the action was one generated by its UI
+    ///
+    pub fn action(&self, action_id: &str, _parameter: &ActionParameter) -> bool {
+        match action_id {
+            CANCEL_OPERATION    => { self.cancel(); true }
+            _                   => false
+        }
+    }
+
+    ///
+    /// Creates the UI binding for an operation
+    ///
+    fn create_ui(running: &Binding<bool>, progress: &Binding<f32>) -> BindRef<Control> {
+        let running     = running.clone();
+        let progress    = progress.clone();
+
+        BindRef::from(computed(move || {
+            if !running.get() {
+                return Control::empty();
+            }
+
+            let progress = progress.get().max(0.0).min(1.0);
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(vec![
+                    Control::container()
+                        .with(Bounds::stretch_horiz(1.0))
+                        .with(Appearance::Background(Color::Rgba(0.5, 0.5, 0.5, 0.25)))
+                        .with(vec![
+                            Control::empty()
+                                .with(Bounds::stretch_horiz(progress))
+                                .with(Appearance::Background(Color::Rgba(0.3, 0.5, 0.9, 1.0))),
+                            Control::empty()
+                                .with(Bounds::stretch_horiz(1.0-progress))
+                        ]),
+                    Control::button()
+                        .with("Cancel")
+                        .with(Bounds::next_horiz(64.0))
+                        .with((ActionTrigger::Click, CANCEL_OPERATION))
+                ])
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor;
+    use futures_timer::{Delay};
+
+    use std::time::{Duration};
+
+    #[test]
+    fn cancel_operation_midway() {
+        let operation   = CancellableOperation::new();
+        let task_token  = Arc::new(Mutex::new(None));
+
+        let op_token    = Arc::clone(&task_token);
+        let task        = operation.start(move |token, progress| async move {
+            *op_token.lock().unwrap() = Some(token);
+
+            // Make some progress and then never finish
+            progress.set(0.5);
+            future::pending::<()>().await;
+        });
+
+        assert!(operation.is_running().get());
+        assert!(operation.ui().get() != Control::empty());
+
+        let result = executor::block_on(async {
+            let cancel = async {
+                Delay::new(Duration::from_millis(20)).await;
+                assert!(operation.progress().get() == 0.5);
+                assert!(operation.action(CANCEL_OPERATION, &ActionParameter::None));
+            };
+
+            future::join(task, cancel).await.0
+        });
+
+        // The task's token should be triggered and the operation should be cleaned up
+        assert!(result.is_none());
+        assert!(task_token.lock().unwrap().as_ref().unwrap().is_cancelled());
+        assert!(!operation.is_running().get());
+        assert!(operation.ui().get() == Control::empty());
+    }
+
+    #[test]
+    fn cancel_after_completion_does_nothing() {
+        let operation   = CancellableOperation::new();
+        let task_token  = Arc::new(Mutex::new(None));
+
+        let op_token    = Arc::clone(&task_token);
+        let task        = operation.start(move |token, _progress| async move {
+            *op_token.lock().unwrap() = Some(token);
+            42
+        });
+
+        assert!(executor::block_on(task) == Some(42));
+        assert!(!operation.is_running().get());
+
+        assert!(!operation.cancel());
+        assert!(!task_token.lock().unwrap().as_ref().unwrap().is_cancelled());
+    }
+}
@@ -0,0 +1,451 @@
+use super::super::control::*;
+use super::super::property::*;
+use super::super::controller::*;
+
+use flo_canvas::{Color};
+use flo_binding::*;
+
+use std::sync::*;
+
+/// Action generated when the text in the command palette's search box is edited
+pub const EDIT_COMMAND_PALETTE_QUERY: &str = "EditCommandPaletteQuery";
+
+/// Action generated when the search box is committed (runs the best matching command)
+pub const RUN_COMMAND_PALETTE_QUERY: &str = "RunCommandPaletteQuery";
+
+/// Action generated when the command palette is dismissed
+pub const DISMISS_COMMAND_PALETTE: &str = "DismissCommandPalette";
+
+/// Prefix for the actions generated when one of the commands in the palette is clicked (followed by the index of the command in the list of matches)
+pub const SELECT_PALETTE_COMMAND: &str = "SelectPaletteCommand-";
+
+/// The number of recently used commands that are shown first when the search box is empty
+const NUM_RECENT_COMMANDS: usize = 8;
+
+/// The maximum number of matching commands to display
+const MAX_DISPLAYED_COMMANDS: usize = 12;
+
+/// The height of each row in the palette
+const COMMAND_ROW_HEIGHT: f32 = 24.0;
+
+///
+/// A command that can be run from the command palette
+///
+#[derive(Clone)]
+pub struct PaletteCommand {
+    /// The name of this command, which is what the user searches for
+    pub name: String,
+
+    /// The action to perform when this command is selected
+    action: Arc<dyn Fn()+Send+Sync>
+}
+
+impl PaletteCommand {
+    ///
+    /// Creates a new palette command
+    ///
+    pub fn new<TAction: 'static+Fn()+Send+Sync>(name: &str, action: TAction) -> PaletteCommand {
+        PaletteCommand {
+            name:   name.to_string(),
+            action: Arc::new(action)
+        }
+    }
+
+    ///
+    /// Performs the action for this command
+    ///
+    pub fn dispatch(&self) {
+        (self.action)();
+    }
+}
+
+impl PartialEq for PaletteCommand {
+    fn eq(&self, other: &PaletteCommand) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.action, &other.action)
+    }
+}
+
+///
+/// Manages a command palette: a search box that finds commands by name using a fuzzy match and runs the one the
+/// user picks
+///
+/// The palette's UI is empty while it's closed. It's a controller, so it can be used as a subcontroller: once opened
+/// with `open()`, the actions generated by its UI filter the commands as the user types and dispatch the command
+/// that's chosen. While the search box is empty, the recently used commands are listed first, followed by the rest of
+/// the commands.
+///
+pub struct CommandPalette {
+    /// The commands that can be chosen from this palette
+    commands: Binding<Vec<PaletteCommand>>,
+
+    /// The names of the recently dispatched commands, most recent first
+    recent: Binding<Vec<String>>,
+
+    /// The text that the user has typed into the search box
+    query: Binding<String>,
+
+    /// True if the palette is open
+    is_open: Binding<bool>,
+
+    /// The user interface for the palette
+    ui: BindRef<Control>
+}
+
+impl CommandPalette {
+    ///
+    /// Creates a new command palette with no commands
+    ///
+    pub fn new() -> CommandPalette {
+        let commands    = bind(vec![]);
+        let recent      = bind(vec![]);
+        let query       = bind(String::new());
+        let is_open     = bind(false);
+        let ui          = Self::create_ui(&commands, &recent, &query, &is_open);
+
+        CommandPalette {
+            commands:   commands,
+            recent:     recent,
+            query:      query,
+            is_open:    is_open,
+            ui:         ui
+        }
+    }
+
+    ///
+    /// Adds a command to this palette (replacing any existing command with the same name)
+    ///
+    pub fn add_command<TAction: 'static+Fn()+Send+Sync>(&self, name: &str, action: TAction) {
+        let mut commands = self.commands.get();
+
+        commands.retain(|command| command.name != name);
+        commands.push(PaletteCommand::new(name, action));
+
+        self.commands.set(commands);
+    }
+
+    ///
+    /// Removes the command with the specified name from this palette
+    ///
+    pub fn remove_command(&self, name: &str) {
+        let mut commands = self.commands.get();
+        commands.retain(|command| command.name != name);
+        self.commands.set(commands);
+    }
+
+    ///
+    /// Whether or not the palette is open
+    ///
+    pub fn is_open(&self) -> BindRef<bool> {
+        BindRef::from(&self.is_open)
+    }
+
+    ///
+    /// Opens the palette with an empty search box
+    ///
+    pub fn open(&self) {
+        self.query.set(String::new());
+        self.is_open.set(true);
+    }
+
+    ///
+    /// Closes the palette without running a command
+    ///
+    pub fn close(&self) {
+        self.is_open.set(false);
+    }
+
+    ///
+    /// Sets the text in the search box
+    ///
+    pub fn set_query(&self, query: &str) {
+        self.query.set(query.to_string());
+    }
+
+    ///
+    /// Returns the names of the commands that match the search box, best match first
+    ///
+    pub fn matching_commands(&self) -> Vec<String> {
+        Self::matches(&self.commands.get(), &self.recent.get(), &self.query.get()).into_iter()
+            .map(|command| command.name)
+            .collect()
+    }
+
+    ///
+    /// Dispatches the command at the specified index in the list of matches and closes the palette, returning false if there's no such command
+    ///
+    pub fn select(&self, index: usize) -> bool {
+        let matches = Self::matches(&self.commands.get(), &self.recent.get(), &self.query.get());
+
+        if let Some(command) = matches.get(index) {
+            // Update the recent commands
+            let mut recent = self.recent.get();
+            recent.retain(|name| name != &command.name);
+            recent.insert(0, command.name.clone());
+            recent.truncate(NUM_RECENT_COMMANDS);
+            self.recent.set(recent);
+
+            // Close the palette and run the command
+            self.is_open.set(false);
+            command.dispatch();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Updates the palette from an action, returning true if the action was one generated by its UI
+    ///
+    /// Actions are ignored while the palette is closed.
+    ///
+    pub fn handle_action(&self, action_id: &str, parameter: &ActionParameter) -> bool {
+        if !self.is_open.get() {
+            return false;
+        }
+
+        match (action_id, parameter) {
+            (EDIT_COMMAND_PALETTE_QUERY, ActionParameter::Value(PropertyValue::String(query)))  => { self.set_query(query); true }
+            (RUN_COMMAND_PALETTE_QUERY, ActionParameter::Value(PropertyValue::String(query)))   => { self.set_query(query); self.select(0); true }
+            (RUN_COMMAND_PALETTE_QUERY, _)                                                      => { self.select(0); true }
+            (DISMISS_COMMAND_PALETTE, _)                                                        => { self.close(); true }
+
+            _ => {
+                if let Some(index) = action_id.strip_prefix(SELECT_PALETTE_COMMAND) {
+                    index.parse::<usize>().ok().map(|index| self.select(index));
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    ///
+    /// Scores how well a command name matches a query, returning None if it doesn't match at all
+    ///
+    /// Every character in the query must appear in the name in order (ignoring case). Names that contain the query
+    /// as a single run of characters score higher than ones where the characters are spread out, and characters that
+    /// match at the start of a word score higher than ones that match in the middle of one.
+    ///
+    fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+        let query   = query.to_lowercase().chars().filter(|chr| !chr.is_whitespace()).collect::<Vec<_>>();
+        let name    = name.to_lowercase().chars().collect::<Vec<_>>();
+
+        let mut score       = 0;
+        let mut name_pos    = 0;
+        let mut last_match  = None;
+
+        for query_chr in query {
+            // Find the next character in the name that matches this character
+            let match_pos = (name_pos..name.len()).find(|pos| name[*pos] == query_chr)?;
+
+            // Consecutive matches and matches at the start of words are better
+            if last_match.is_some() && last_match == match_pos.checked_sub(1) {
+                score += 8;
+            } else if match_pos == 0 || !name[match_pos-1].is_alphanumeric() {
+                score += 6;
+            } else {
+                score += 1;
+            }
+
+            // Skipped characters make the match worse
+            score       -= (match_pos - name_pos) as i64;
+            last_match  = Some(match_pos);
+            name_pos    = match_pos + 1;
+        }
+
+        Some(score)
+    }
+
+    ///
+    /// Returns the commands matching a query, best match first
+    ///
+    fn matches(commands: &Vec<PaletteCommand>, recent: &Vec<String>, query: &str) -> Vec<PaletteCommand> {
+        if query.trim().is_empty() {
+            // Recently used commands first, then the remaining commands in the order they were added
+            let recent_commands = recent.iter()
+                .filter_map(|name| commands.iter().find(|command| &command.name == name));
+            let other_commands  = commands.iter()
+                .filter(|command| !recent.contains(&command.name));
+
+            recent_commands.chain(other_commands).cloned().collect()
+        } else {
+            // Sort by score (this is a stable sort, so commands with the same score stay in the order they were added)
+            let mut matches = commands.iter()
+                .filter_map(|command| Self::fuzzy_score(query, &command.name).map(|score| (score, command)))
+                .collect::<Vec<_>>();
+            matches.sort_by(|(score_a, _), (score_b, _)| score_b.cmp(score_a));
+
+            matches.into_iter().map(|(_, command)| command.clone()).collect()
+        }
+    }
+
+    ///
+    /// Creates the UI binding for the palette
+    ///
+    fn create_ui(commands: &Binding<Vec<PaletteCommand>>, recent: &Binding<Vec<String>>, query: &Binding<String>, is_open: &Binding<bool>) -> BindRef<Control> {
+        let commands    = commands.clone();
+        let recent      = recent.clone();
+        let query       = query.clone();
+        let is_open     = is_open.clone();
+
+        BindRef::from(computed(move || {
+            if !is_open.get() {
+                return Control::empty();
+            }
+
+            let query       = query.get();
+            let matches     = Self::matches(&commands.get(), &recent.get(), &query);
+
+            // The list of matching commands (or a message if there are none)
+            let command_list = if matches.is_empty() {
+                vec![
+                    Control::label()
+                        .with("No matching commands")
+                        .with(TextAlign::Center)
+                        .with(Bounds::next_vert(COMMAND_ROW_HEIGHT))
+                ]
+            } else {
+                matches.into_iter()
+                    .take(MAX_DISPLAYED_COMMANDS)
+                    .enumerate()
+                    .map(|(index, command)| {
+                        Control::label()
+                            .with(command.name)
+                            .with(Bounds::next_vert(COMMAND_ROW_HEIGHT))
+                            .with(State::Selected(Property::Bool(index == 0)))
+                            .with((ActionTrigger::Click, format!("{}{}", SELECT_PALETTE_COMMAND, index)))
+                    })
+                    .collect()
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Appearance::Background(Color::Rgba(0.2, 0.2, 0.2, 0.95)))
+                .with((ActionTrigger::Dismiss, DISMISS_COMMAND_PALETTE))
+                .with(vec![
+                    Control::text_box()
+                        .with(query)
+                        .with(Bounds::next_vert(COMMAND_ROW_HEIGHT))
+                        .with(State::FocusPriority(Property::from(128.0)))
+                        .with((ActionTrigger::EditValue, EDIT_COMMAND_PALETTE_QUERY))
+                        .with((ActionTrigger::SetValue, RUN_COMMAND_PALETTE_QUERY))
+                        .with((ActionTrigger::CancelEdit, DISMISS_COMMAND_PALETTE)),
+                    Control::container()
+                        .with(Bounds::next_vert(COMMAND_ROW_HEIGHT * (MAX_DISPLAYED_COMMANDS as f32)))
+                        .with(command_list)
+                ])
+        }))
+    }
+}
+
+impl Controller for CommandPalette {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        self.handle_action(action_id, action_data);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn palette() -> (CommandPalette, Vec<Arc<AtomicUsize>>) {
+        let palette     = CommandPalette::new();
+        let counters    = (0..3).map(|_| Arc::new(AtomicUsize::new(0))).collect::<Vec<_>>();
+
+        for (name, counter) in ["New Layer", "Delete Layer", "Export Movie"].iter().zip(counters.iter()) {
+            let counter = Arc::clone(counter);
+            palette.add_command(name, move || { counter.fetch_add(1, Ordering::SeqCst); });
+        }
+
+        (palette, counters)
+    }
+
+    #[test]
+    fn filter_and_dispatch_command() {
+        let (palette, counters) = palette();
+
+        palette.open();
+        assert!(palette.handle_action(EDIT_COMMAND_PALETTE_QUERY, &ActionParameter::Value(PropertyValue::String("layer".to_string()))));
+        assert!(palette.matching_commands() == vec!["New Layer".to_string(), "Delete Layer".to_string()]);
+
+        assert!(palette.handle_action(EDIT_COMMAND_PALETTE_QUERY, &ActionParameter::Value(PropertyValue::String("del".to_string()))));
+        assert!(palette.matching_commands() == vec!["Delete Layer".to_string()]);
+
+        // Picking the match runs its action and closes the palette
+        assert!(palette.handle_action(&format!("{}0", SELECT_PALETTE_COMMAND), &ActionParameter::None));
+
+        assert!(counters[0].load(Ordering::SeqCst) == 0);
+        assert!(counters[1].load(Ordering::SeqCst) == 1);
+        assert!(counters[2].load(Ordering::SeqCst) == 0);
+        assert!(!palette.is_open().get());
+    }
+
+    #[test]
+    fn closed_palette_ignores_actions() {
+        let (palette, counters) = palette();
+
+        assert!(!palette.handle_action(RUN_COMMAND_PALETTE_QUERY, &ActionParameter::Value(PropertyValue::String("new".to_string()))));
+        assert!(!palette.handle_action(&format!("{}0", SELECT_PALETTE_COMMAND), &ActionParameter::None));
+
+        assert!(counters.iter().all(|counter| counter.load(Ordering::SeqCst) == 0));
+        assert!(!palette.is_open().get());
+    }
+
+    #[test]
+    fn actions_are_received_as_a_controller() {
+        let (palette, counters) = palette();
+        let controller: &dyn Controller = &palette;
+
+        palette.open();
+        controller.action(RUN_COMMAND_PALETTE_QUERY, &ActionParameter::Value(PropertyValue::String("export".to_string())));
+
+        assert!(counters[2].load(Ordering::SeqCst) == 1);
+        assert!(!palette.is_open().get());
+    }
+
+    #[test]
+    fn fuzzy_match_spread_out_characters() {
+        let (palette, counters) = palette();
+
+        palette.open();
+        palette.handle_action(RUN_COMMAND_PALETTE_QUERY, &ActionParameter::Value(PropertyValue::String("expmov".to_string())));
+
+        assert!(counters[2].load(Ordering::SeqCst) == 1);
+    }
+
+    #[test]
+    fn empty_query_shows_recent_commands_first() {
+        let (palette, _counters) = palette();
+
+        palette.open();
+        palette.set_query("export");
+        palette.select(0);
+
+        palette.open();
+        assert!(palette.matching_commands() == vec!["Export Movie".to_string(), "New Layer".to_string(), "Delete Layer".to_string()]);
+    }
+
+    #[test]
+    fn no_matching_commands() {
+        let (palette, counters) = palette();
+
+        palette.open();
+        palette.set_query("zzz");
+
+        assert!(palette.matching_commands().is_empty());
+        assert!(!palette.select(0));
+        assert!(counters.iter().all(|counter| counter.load(Ordering::SeqCst) == 0));
+
+        // The palette stays open and shows a message instead of the list of commands
+        assert!(palette.is_open().get());
+        assert!(palette.ui().get() != Control::empty());
+    }
+}
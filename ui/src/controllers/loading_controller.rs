@@ -0,0 +1,290 @@
+use super::super::*;
+
+use futures::prelude::*;
+use futures::future;
+use futures::future::{BoxFuture, Either};
+use futures_timer::{Delay};
+
+use flo_canvas::{Color};
+use flo_binding::*;
+
+use std::sync::*;
+use std::time::{Duration};
+
+///
+/// Controller that displays a skeleton placeholder in place of its content while the content is loading.
+/// Supply this as a controller for a control whose content is generated asynchronously to avoid the
+/// content flashing up empty before it has loaded.
+///
+/// The content controller's UI replaces the skeleton as soon as the loading flag is set to false. If
+/// a timeout is set and the content is still loading when it expires, an error UI is displayed instead
+/// (until the loading flag changes again).
+///
+pub struct LoadingController<ContentController: Controller> {
+    /// Controller that provides the content once it is loaded
+    content_controller: ContentController,
+
+    /// Binding that is true while the content is being loaded
+    loading: BindRef<bool>,
+
+    /// The UI to display while the content is loading
+    skeleton: BindRef<Control>,
+
+    /// The UI to display if the content takes too long to load
+    error: BindRef<Control>,
+
+    /// How long to wait for the content to load before displaying the error UI (or None to wait forever)
+    timeout: Option<Duration>,
+
+    /// Set to true when the content has taken longer than the timeout to load
+    timed_out: Binding<bool>,
+
+    /// User interface for this controller
+    ui: BindRef<Control>
+}
+
+impl<ContentController: Controller> LoadingController<ContentController> {
+    ///
+    /// Creates a new loading controller
+    ///
+    /// By default, the skeleton is a shaded panel filling the control, and the controller will wait forever for the
+    /// content to load.
+    ///
+    pub fn new<T: Into<BindRef<bool>>>(controller: ContentController, loading: T) -> LoadingController<ContentController> {
+        let content_controller  = controller;
+        let loading             = loading.into();
+        let skeleton            = BindRef::from(&Self::default_skeleton());
+        let error               = BindRef::from(&Self::default_error());
+        let timed_out           = bind(false);
+
+        let ui                  = Self::create_ui(&content_controller.ui(), &loading, &skeleton, &error, &timed_out);
+
+        LoadingController {
+            content_controller: content_controller,
+            loading:            loading,
+            skeleton:           skeleton,
+            error:              error,
+            timeout:            None,
+            timed_out:          timed_out,
+            ui:                 ui
+        }
+    }
+
+    ///
+    /// The skeleton UI that's displayed if no other skeleton is specified
+    ///
+    pub fn default_skeleton() -> Control {
+        Control::container()
+            .with(Bounds::fill_all())
+            .with(Appearance::Background(Color::Rgba(0.5, 0.5, 0.5, 0.25)))
+    }
+
+    ///
+    /// The error UI that's displayed if no other error UI is specified
+    ///
+    pub fn default_error() -> Control {
+        Control::label()
+            .with(Bounds::fill_all())
+            .with(TextAlign::Center)
+            .with("Could not load")
+    }
+
+    ///
+    /// Returns a modified controller with a different skeleton UI
+    ///
+    pub fn with_skeleton<T: Into<BindRef<Control>>>(mut self, skeleton: T) -> LoadingController<ContentController> {
+        self.skeleton = skeleton.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Returns a modified controller that displays an error UI if the content takes longer than the specified time to load
+    ///
+    pub fn with_timeout<T: Into<BindRef<Control>>>(mut self, timeout: Duration, error: T) -> LoadingController<ContentController> {
+        self.timeout    = Some(timeout);
+        self.error      = error.into();
+        self.regenerate_ui()
+    }
+
+    ///
+    /// Regenerates the UI field from the current bindings
+    ///
+    fn regenerate_ui(mut self) -> LoadingController<ContentController> {
+        self.ui = Self::create_ui(&self.content_controller.ui(), &self.loading, &self.skeleton, &self.error, &self.timed_out);
+        self
+    }
+
+    ///
+    /// Creates the UI binding for this controller
+    ///
+    fn create_ui(content: &BindRef<Control>, loading: &BindRef<bool>, skeleton: &BindRef<Control>, error: &BindRef<Control>, timed_out: &Binding<bool>) -> BindRef<Control> {
+        let content     = content.clone();
+        let loading     = loading.clone();
+        let skeleton    = skeleton.clone();
+        let error       = error.clone();
+        let timed_out   = timed_out.clone();
+
+        BindRef::from(computed(move || {
+            // The content UI is only bound once it has loaded, so it can't update while the skeleton is displayed
+            if !loading.get() {
+                content.get()
+            } else if timed_out.get() {
+                error.get()
+            } else {
+                skeleton.get()
+            }
+        }))
+    }
+
+    ///
+    /// Creates the future that marks the content as timed out if the loading flag stays set for too long
+    ///
+    fn timeout_runtime(loading: BindRef<bool>, timed_out: Binding<bool>, timeout: Duration) -> BoxFuture<'static, ()> {
+        async move {
+            let mut loading_state   = Box::pin(follow(loading));
+            let mut next_state      = loading_state.next().await;
+
+            while let Some(is_loading) = next_state {
+                // Changing the loading state resets the timeout
+                timed_out.set(false);
+
+                if is_loading {
+                    // Wait for the loading state to change or the timeout to expire
+                    match future::select(Delay::new(timeout), loading_state.next()).await {
+                        Either::Left((_, next_loading_state))   => {
+                            timed_out.set(true);
+                            next_state = next_loading_state.await;
+                        }
+
+                        Either::Right((next_loading_state, _))  => {
+                            next_state = next_loading_state;
+                        }
+                    }
+                } else {
+                    next_state = loading_state.next().await;
+                }
+            }
+        }.boxed()
+    }
+}
+
+impl<ContentController: Controller> Controller for LoadingController<ContentController> {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        self.content_controller.get_viewmodel()
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        self.content_controller.get_subcontroller(id)
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        self.content_controller.action(action_id, action_data);
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        self.content_controller.get_image_resources()
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        self.content_controller.get_canvas_resources()
+    }
+
+    fn runtime(&self) -> Option<BoxFuture<'static, ()>> {
+        let content_runtime = self.content_controller.runtime();
+        let timeout_runtime = self.timeout.map(|timeout| Self::timeout_runtime(self.loading.clone(), self.timed_out.clone(), timeout));
+
+        match (content_runtime, timeout_runtime) {
+            (Some(content_runtime), Some(timeout_runtime))  => Some(future::join(content_runtime, timeout_runtime).map(|_| ()).boxed()),
+            (Some(runtime), None) | (None, Some(runtime))   => Some(runtime),
+            (None, None)                                    => None
+        }
+    }
+
+    fn tick(&self) { self.content_controller.tick() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor;
+    use ::desync::*;
+
+    struct ContentController {
+        ui: BindRef<Control>
+    }
+
+    impl Controller for ContentController {
+        fn ui(&self) -> BindRef<Control> {
+            self.ui.clone()
+        }
+
+        fn get_subcontroller(&self, _id: &str) -> Option<Arc<dyn Controller>> {
+            None
+        }
+    }
+
+    fn content() -> ContentController {
+        ContentController { ui: BindRef::from(bind(Control::label().with("Loaded"))) }
+    }
+
+    #[test]
+    fn skeleton_is_replaced_when_loaded() {
+        let loading     = bind(true);
+        let controller  = LoadingController::new(content(), &loading);
+
+        assert!(controller.ui().get() == LoadingController::<ContentController>::default_skeleton());
+
+        loading.set(false);
+        assert!(controller.ui().get() == Control::label().with("Loaded"));
+
+        loading.set(true);
+        assert!(controller.ui().get() == LoadingController::<ContentController>::default_skeleton());
+    }
+
+    #[test]
+    fn custom_skeleton_is_displayed_while_loading() {
+        let loading     = bind(true);
+        let controller  = LoadingController::new(content(), &loading)
+            .with_skeleton(&Control::label().with("Loading..."));
+
+        assert!(controller.ui().get() == Control::label().with("Loading..."));
+    }
+
+    #[test]
+    fn skeleton_stays_without_timeout() {
+        let loading     = bind(true);
+        let controller  = LoadingController::new(content(), &loading);
+
+        assert!(controller.runtime().is_none());
+
+        executor::block_on(async { Delay::new(Duration::from_millis(50)).await; });
+        assert!(controller.ui().get() == LoadingController::<ContentController>::default_skeleton());
+    }
+
+    #[test]
+    fn error_displayed_after_timeout() {
+        let loading     = bind(true);
+        let error       = Control::label().with("Error");
+        let controller  = LoadingController::new(content(), &loading)
+            .with_timeout(Duration::from_millis(20), &error);
+
+        // Run the runtime in the background
+        let runtime     = controller.runtime().expect("Runtime");
+        let runner      = Desync::new(());
+        runner.future_desync(move |_| runtime).detach();
+
+        assert!(controller.ui().get() == LoadingController::<ContentController>::default_skeleton());
+
+        executor::block_on(async { Delay::new(Duration::from_millis(200)).await; });
+        assert!(controller.ui().get() == error);
+
+        // Loading the content replaces the error
+        loading.set(false);
+        assert!(controller.ui().get() == Control::label().with("Loaded"));
+    }
+}
@@ -1,5 +1,11 @@
 mod virtual_canvas;
+mod virtual_list;
+mod scroll_position;
 mod popup_controller;
+mod overflow_toolbar;
+mod cancellable_operation;
+mod command_palette;
+mod loading_controller;
 mod controller_event;
 mod controller_action;
 mod controller_resources;
@@ -7,7 +13,13 @@ mod immediate_controller;
 mod createable_controller;
 
 pub use self::virtual_canvas::*;
+pub use self::virtual_list::*;
+pub use self::scroll_position::*;
 pub use self::popup_controller::*;
+pub use self::overflow_toolbar::*;
+pub use self::cancellable_operation::*;
+pub use self::command_palette::*;
+pub use self::loading_controller::*;
 pub use self::controller_event::*;
 pub use self::controller_action::*;
 pub use self::controller_resources::*;
@@ -0,0 +1,268 @@
+use super::super::control::*;
+use super::super::property::*;
+
+use flo_binding::*;
+
+/// Action generated by the toolbar container when it changes size
+pub const RESIZE_TOOLBAR: &str = "ResizeToolbar";
+
+/// Action generated when the overflow button is clicked
+pub const TOGGLE_TOOLBAR_OVERFLOW: &str = "ToggleToolbarOverflow";
+
+/// Action generated when the overflow menu is dismissed
+pub const DISMISS_TOOLBAR_OVERFLOW: &str = "DismissToolbarOverflow";
+
+/// The width of the button that opens the overflow menu
+const OVERFLOW_BUTTON_WIDTH: f32 = 24.0;
+
+/// The height of each item in the overflow menu
+const OVERFLOW_ITEM_HEIGHT: f32 = 28.0;
+
+///
+/// An item in a toolbar, along with the width it occupies
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ToolbarItem {
+    pub control:    Control,
+    pub width:      f32
+}
+
+impl ToolbarItem {
+    ///
+    /// Creates a new toolbar item
+    ///
+    pub fn new(control: Control, width: f32) -> ToolbarItem {
+        ToolbarItem { control, width }
+    }
+}
+
+///
+/// A toolbar that moves the items that don't fit into its width into an overflow menu
+///
+/// The UI generated by `ui()` is a container that generates a `RESIZE_TOOLBAR` action whenever it changes size. This
+/// action and the actions generated by the overflow button and its menu should be passed on to `action()`, which
+/// re-flows the toolbar as needed. Items are kept in order: once an item doesn't fit, it and all the items after it
+/// are moved into the overflow menu.
+///
+pub struct OverflowToolbar {
+    /// The items in this toolbar
+    items: Binding<Vec<ToolbarItem>>,
+
+    /// The width available for the toolbar
+    available_width: Binding<f32>,
+
+    /// True if the overflow menu is open
+    overflow_open: Binding<bool>,
+
+    /// The user interface for the toolbar
+    ui: BindRef<Control>
+}
+
+impl OverflowToolbar {
+    ///
+    /// Creates a new toolbar containing the specified items
+    ///
+    /// The toolbar initially assumes it has enough space for all of its items (until it receives a resize action)
+    ///
+    pub fn new(items: Vec<ToolbarItem>) -> OverflowToolbar {
+        let available_width = bind(items.iter().map(|item| item.width).sum::<f32>());
+        let items           = bind(items);
+        let overflow_open   = bind(false);
+        let ui              = Self::create_ui(&items, &available_width, &overflow_open);
+
+        OverflowToolbar {
+            items:              items,
+            available_width:    available_width,
+            overflow_open:      overflow_open,
+            ui:                 ui
+        }
+    }
+
+    ///
+    /// The user interface for this toolbar
+    ///
+    pub fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    ///
+    /// Replaces the items in this toolbar
+    ///
+    pub fn set_items(&self, items: Vec<ToolbarItem>) {
+        self.items.set(items);
+    }
+
+    ///
+    /// Sets the width that's available for the toolbar
+    ///
+    pub fn set_available_width(&self, width: f32) {
+        self.available_width.set(width);
+
+        // Close the overflow menu if it's no longer needed
+        if self.overflow_items().is_empty() {
+            self.overflow_open.set(false);
+        }
+    }
+
+    ///
+    /// Whether or not the overflow menu is open
+    ///
+    pub fn overflow_open(&self) -> BindRef<bool> {
+        BindRef::from(&self.overflow_open)
+    }
+
+    ///
+    /// Returns the items that are displayed in the toolbar itself
+    ///
+    pub fn visible_items(&self) -> Vec<ToolbarItem> {
+        Self::split_items(&self.items.get(), self.available_width.get()).0
+    }
+
+    ///
+    /// Returns the items that have been moved to the overflow menu
+    ///
+    pub fn overflow_items(&self) -> Vec<ToolbarItem> {
+        Self::split_items(&self.items.get(), self.available_width.get()).1
+    }
+
+    ///
+    /// Updates the toolbar from an action, returning true if the action was one generated by the toolbar
+    ///
+    pub fn action(&self, action_id: &str, parameter: &ActionParameter) -> bool {
+        match (action_id, parameter) {
+            (RESIZE_TOOLBAR, ActionParameter::Size(width, _height)) => { self.set_available_width(*width); true }
+            (TOGGLE_TOOLBAR_OVERFLOW, _)                            => { self.overflow_open.set(!self.overflow_open.get()); true }
+            (DISMISS_TOOLBAR_OVERFLOW, _)                           => { self.overflow_open.set(false); true }
+            _                                                       => false
+        }
+    }
+
+    ///
+    /// Splits a list of items into the items that fit into the available width and the items that should be in the overflow menu
+    ///
+    fn split_items(items: &[ToolbarItem], available_width: f32) -> (Vec<ToolbarItem>, Vec<ToolbarItem>) {
+        // Everything is visible if all of the items fit
+        let total_width = items.iter().map(|item| item.width).sum::<f32>();
+        if total_width <= available_width {
+            return (items.to_vec(), vec![]);
+        }
+
+        // Otherwise, some space is needed for the overflow button
+        let mut remaining_width = available_width - OVERFLOW_BUTTON_WIDTH;
+        let num_visible         = items.iter()
+            .take_while(|item| {
+                remaining_width -= item.width;
+                remaining_width >= 0.0
+            })
+            .count();
+
+        (items[0..num_visible].to_vec(), items[num_visible..].to_vec())
+    }
+
+    ///
+    /// Creates the UI binding for the toolbar
+    ///
+    fn create_ui(items: &Binding<Vec<ToolbarItem>>, available_width: &Binding<f32>, overflow_open: &Binding<bool>) -> BindRef<Control> {
+        let items           = items.clone();
+        let available_width = available_width.clone();
+        let overflow_open   = overflow_open.clone();
+
+        BindRef::from(computed(move || {
+            let (visible, overflow) = Self::split_items(&items.get(), available_width.get());
+
+            let mut toolbar_items = visible.into_iter()
+                .map(|item| item.control.with(Bounds::next_horiz(item.width)))
+                .collect::<Vec<_>>();
+
+            // The overflow button is only displayed if there are items that don't fit
+            if !overflow.is_empty() {
+                let is_open         = overflow_open.get();
+                let menu_width      = overflow.iter().map(|item| item.width).fold(0.0, f32::max);
+                let menu_height     = OVERFLOW_ITEM_HEIGHT * (overflow.len() as f32);
+
+                let mut menu        = Control::popup()
+                    .with(Popup::IsOpen(Property::Bool(is_open)))
+                    .with(Popup::Direction(PopupDirection::Below))
+                    .with(Popup::Size(menu_width.ceil() as u32, menu_height.ceil() as u32))
+                    .with(ControlAttribute::ZIndex(1000));
+
+                // Only generate the menu content while it's open
+                if is_open {
+                    menu = menu
+                        .with((ActionTrigger::Dismiss, DISMISS_TOOLBAR_OVERFLOW))
+                        .with(overflow.into_iter()
+                            .map(|item| item.control.with(Bounds::next_vert(OVERFLOW_ITEM_HEIGHT)))
+                            .collect::<Vec<_>>());
+                }
+
+                toolbar_items.push(Control::button()
+                    .with("\u{00bb}")
+                    .with(Bounds::next_horiz(OVERFLOW_BUTTON_WIDTH))
+                    .with((ActionTrigger::Click, TOGGLE_TOOLBAR_OVERFLOW))
+                    .with(vec![menu]));
+            }
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with((ActionTrigger::Resize, RESIZE_TOOLBAR))
+                .with(toolbar_items)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn toolbar() -> OverflowToolbar {
+        OverflowToolbar::new(vec![
+            ToolbarItem::new(Control::button().with("Pencil"), 40.0),
+            ToolbarItem::new(Control::button().with("Brush"), 40.0),
+            ToolbarItem::new(Control::button().with("Eraser"), 40.0),
+            ToolbarItem::new(Control::button().with("Fill"), 40.0)
+        ])
+    }
+
+    #[test]
+    fn narrow_toolbar_overflows() {
+        let toolbar = toolbar();
+
+        // Space for the overflow button and two items
+        assert!(toolbar.action(RESIZE_TOOLBAR, &ActionParameter::Size(110.0, 32.0)));
+
+        assert!(toolbar.visible_items().len() == 2);
+        assert!(toolbar.overflow_items().len() == 2);
+        assert!(toolbar.overflow_items()[0].control.text() == Some(&"Eraser".to_property()));
+
+        // The UI should have the two visible items and the overflow button
+        let ui = toolbar.ui().get();
+        assert!(ui.subcomponents().unwrap().len() == 3);
+    }
+
+    #[test]
+    fn wide_toolbar_has_no_overflow() {
+        let toolbar = toolbar();
+
+        // Exactly enough space for all of the items, so no overflow button is needed
+        assert!(toolbar.action(RESIZE_TOOLBAR, &ActionParameter::Size(160.0, 32.0)));
+
+        assert!(toolbar.visible_items().len() == 4);
+        assert!(toolbar.overflow_items().len() == 0);
+
+        let ui = toolbar.ui().get();
+        assert!(ui.subcomponents().unwrap().len() == 4);
+        assert!(ui.subcomponents().unwrap().iter().all(|item| item.actions().iter().all(|(trigger, _)| *trigger != ActionTrigger::Click)));
+    }
+
+    #[test]
+    fn widening_toolbar_closes_overflow_menu() {
+        let toolbar = toolbar();
+
+        toolbar.action(RESIZE_TOOLBAR, &ActionParameter::Size(110.0, 32.0));
+        toolbar.action(TOGGLE_TOOLBAR_OVERFLOW, &ActionParameter::None);
+        assert!(toolbar.overflow_open().get());
+
+        toolbar.action(RESIZE_TOOLBAR, &ActionParameter::Size(400.0, 32.0));
+        assert!(!toolbar.overflow_open().get());
+    }
+}
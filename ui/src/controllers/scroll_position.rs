@@ -0,0 +1,149 @@
+use super::super::control::*;
+
+use flo_binding::*;
+
+///
+/// Tracks the position of a scrolling container, and provides a way to scroll it to a new position
+///
+/// The scrolling container should have the `Scroll` action trigger (which should be passed on to
+/// `update_from_action`) and the attribute returned by `scroll_attr`, which will scroll the control
+/// whenever `scroll_to` is called.
+///
+pub struct ScrollPosition {
+    /// The position of the top-left corner of the visible region
+    position: Binding<(f32, f32)>,
+
+    /// The size of the content of the scrolling container
+    content_size: Binding<(f32, f32)>,
+
+    /// The size of the visible part of the scrolling container
+    viewport_size: Binding<(f32, f32)>
+}
+
+impl ScrollPosition {
+    ///
+    /// Creates a new scroll position for a container whose content is the specified size
+    ///
+    pub fn new(content_size: (f32, f32)) -> ScrollPosition {
+        ScrollPosition {
+            position:       bind((0.0, 0.0)),
+            content_size:   bind(content_size),
+            viewport_size:  bind((0.0, 0.0))
+        }
+    }
+
+    ///
+    /// The position of the top-left corner of the visible region of the container
+    ///
+    pub fn position(&self) -> BindRef<(f32, f32)> {
+        BindRef::from(&self.position)
+    }
+
+    ///
+    /// The size of the content of the container
+    ///
+    pub fn content_size(&self) -> BindRef<(f32, f32)> {
+        BindRef::from(&self.content_size)
+    }
+
+    ///
+    /// The size of the visible region of the container
+    ///
+    pub fn viewport_size(&self) -> BindRef<(f32, f32)> {
+        BindRef::from(&self.viewport_size)
+    }
+
+    ///
+    /// Updates the size of the content (eg, when the controller changes the minimum content size of the container)
+    ///
+    pub fn set_content_size(&self, content_size: (f32, f32)) {
+        self.content_size.set(content_size);
+        self.position.set(self.clamp(self.position.get()));
+    }
+
+    ///
+    /// Scrolls the container to a new position
+    ///
+    /// The position is clamped so that the container does not scroll beyond the end of its content
+    ///
+    pub fn scroll_to(&self, offset: (f32, f32)) {
+        self.position.set(self.clamp(offset));
+    }
+
+    ///
+    /// Updates the scroll position from the parameter of a `Scroll` action, returning true if the parameter was a scroll state
+    ///
+    pub fn update_from_action(&self, parameter: &ActionParameter) -> bool {
+        match parameter {
+            ActionParameter::Scroll(state)  => {
+                self.content_size.set(state.content_size);
+                self.viewport_size.set(state.viewport_size);
+                self.position.set(self.clamp(state.position));
+
+                true
+            }
+
+            _                               => false
+        }
+    }
+
+    ///
+    /// Returns the attribute that scrolls the container to the current position
+    ///
+    pub fn scroll_attr(&self) -> Scroll {
+        let (x, y) = self.position.get();
+        Scroll::ScrollTo(x, y)
+    }
+
+    ///
+    /// Clamps a position so that it's within the content of the container
+    ///
+    fn clamp(&self, position: (f32, f32)) -> (f32, f32) {
+        let (x, y)                  = position;
+        let (width, height)         = self.content_size.get();
+        let (view_w, view_h)        = self.viewport_size.get();
+
+        let max_x                   = (width - view_w).max(0.0);
+        let max_y                   = (height - view_h).max(0.0);
+
+        (x.min(max_x).max(0.0), y.min(max_y).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scroll_to_updates_position() {
+        let scroll      = ScrollPosition::new((1000.0, 2000.0));
+        let position    = scroll.position();
+
+        scroll.scroll_to((100.0, 200.0));
+
+        assert!(position.get() == (100.0, 200.0));
+        assert!(scroll.scroll_attr() == Scroll::ScrollTo(100.0, 200.0));
+    }
+
+    #[test]
+    fn scroll_to_beyond_content_clamps() {
+        let scroll      = ScrollPosition::new((1000.0, 2000.0));
+        let position    = scroll.position();
+
+        scroll.update_from_action(&ActionParameter::Scroll(ScrollState { position: (0.0, 0.0), content_size: (1000.0, 2000.0), viewport_size: (200.0, 300.0) }));
+        scroll.scroll_to((5000.0, -100.0));
+
+        assert!(position.get() == (800.0, 0.0));
+    }
+
+    #[test]
+    fn user_scrolling_updates_position() {
+        let scroll      = ScrollPosition::new((1000.0, 2000.0));
+        let position    = scroll.position();
+
+        assert!(scroll.update_from_action(&ActionParameter::Scroll(ScrollState { position: (50.0, 60.0), content_size: (1000.0, 2500.0), viewport_size: (200.0, 300.0) })));
+
+        assert!(position.get() == (50.0, 60.0));
+        assert!(scroll.content_size().get() == (1000.0, 2500.0));
+    }
+}
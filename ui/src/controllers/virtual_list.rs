@@ -0,0 +1,280 @@
+use super::super::control::*;
+
+use flo_binding::*;
+
+use std::sync::*;
+use std::collections::{HashMap};
+
+/// The number of rows that are created above and below the visible part of the list by default
+const DEFAULT_OVERSCAN: usize = 4;
+
+///
+/// Provides a scrolling list of fixed-height rows that only creates the controls for the rows that are
+/// visible (plus a few rows either side so they're ready when the user scrolls)
+///
+/// The rows are placed in a fixed number of slots, so when the list scrolls, the rows that stay on screen
+/// keep their place in the list of subcomponents. This means that the diff for a scroll only contains the
+/// slots for the rows that have become visible, and the rows that have scrolled off are removed as their
+/// slots are reused. The `Scroll` action for the list should be passed on to `update_from_action()`.
+///
+pub struct VirtualList {
+    /// The number of items in the list
+    item_count: Binding<usize>,
+
+    /// The height of each row
+    row_height: f32,
+
+    /// The number of rows to create above and below the visible part of the list
+    overscan: usize,
+
+    /// The first visible row and the number of visible rows
+    visible_rows: Binding<(usize, usize)>,
+
+    /// The slots containing the rows that are currently created, with the index of the item in each slot
+    slots: Binding<Vec<Option<(usize, Control)>>>,
+
+    /// Creates the control for a particular row
+    make_row: Arc<dyn Fn(usize) -> Control+Send+Sync>,
+
+    /// Binding for the control
+    control: BindRef<Control>
+}
+
+impl VirtualList {
+    ///
+    /// Creates a new virtual list with the specified number of rows. The list sends the `Scroll` action with the specified name
+    /// when it's scrolled.
+    ///
+    pub fn new<MakeRow: 'static+Fn(usize) -> Control+Send+Sync>(scroll_action: &str, item_count: usize, row_height: f32, make_row: MakeRow) -> VirtualList {
+        let item_count  = bind(item_count);
+        let slots       = bind(vec![]);
+        let control     = Self::make_control(scroll_action, &item_count, row_height, &slots);
+
+        let list        = VirtualList {
+            item_count:     item_count,
+            row_height:     row_height,
+            overscan:       DEFAULT_OVERSCAN,
+            visible_rows:   bind((0, 0)),
+            slots:          slots,
+            make_row:       Arc::new(make_row),
+            control:        control
+        };
+
+        list.update_slots(false);
+        list
+    }
+
+    ///
+    /// Changes the number of rows that are created above and below the visible part of the list
+    ///
+    pub fn with_overscan(mut self, overscan: usize) -> VirtualList {
+        self.overscan = overscan;
+        self.update_slots(false);
+        self
+    }
+
+    ///
+    /// Retrieves the control that displays this list
+    ///
+    pub fn control(&self) -> BindRef<Control> {
+        BindRef::clone(&self.control)
+    }
+
+    ///
+    /// The number of items in this list
+    ///
+    pub fn item_count(&self) -> BindRef<usize> {
+        BindRef::from(&self.item_count)
+    }
+
+    ///
+    /// Changes the number of items in this list
+    ///
+    pub fn set_item_count(&self, item_count: usize) {
+        self.item_count.set(item_count);
+        self.update_slots(false);
+    }
+
+    ///
+    /// Creates the rows again (eg, when the items they are displaying have changed)
+    ///
+    pub fn refresh(&self) {
+        self.update_slots(true);
+    }
+
+    ///
+    /// Returns the indexes of the items that currently have a control in this list
+    ///
+    pub fn created_rows(&self) -> Vec<usize> {
+        let mut rows = self.slots.get().into_iter()
+            .filter_map(|slot| slot.map(|(index, _)| index))
+            .collect::<Vec<_>>();
+        rows.sort();
+
+        rows
+    }
+
+    ///
+    /// Updates the visible rows from the parameter of a `Scroll` action, returning true if the parameter was a scroll state
+    ///
+    pub fn update_from_action(&self, parameter: &ActionParameter) -> bool {
+        match parameter {
+            ActionParameter::Scroll(state)  => {
+                let (_, top)            = state.position;
+                let (_, height)         = state.viewport_size;
+                let row_height          = self.row_height;
+
+                // The number of visible rows depends only on the height of the viewport: a viewport that starts part-way
+                // through a row can show part of one extra row, so that's always allowed for
+                let first_visible       = (top.max(0.0) / row_height).floor() as usize;
+                let num_visible         = (height.max(0.0) / row_height).ceil() as usize + 1;
+
+                self.visible_rows.set((first_visible, num_visible));
+                self.update_slots(false);
+
+                true
+            }
+
+            _                               => false
+        }
+    }
+
+    ///
+    /// Creates the rows for the items that are visible, reusing existing rows unless `recreate` is set
+    ///
+    fn update_slots(&self, recreate: bool) {
+        let (first_visible, num_visible)    = self.visible_rows.get();
+        let item_count                      = self.item_count.get();
+
+        // The number of slots only changes when the size of the viewport changes, so scrolling doesn't change the number of subcomponents
+        let num_slots                       = num_visible + self.overscan*2;
+        let first_row                       = first_visible.saturating_sub(self.overscan);
+        let last_row                        = (first_visible + num_visible + self.overscan).min(item_count);
+
+        // Find the rows that already exist
+        let mut existing_rows               = if recreate { HashMap::new() } else {
+            self.slots.get().into_iter()
+                .filter_map(|slot| slot)
+                .collect::<HashMap<_, _>>()
+        };
+
+        // Each row always goes in the same slot, so it doesn't move when the list scrolls
+        let mut slots                       = vec![None; num_slots];
+
+        for index in first_row..last_row {
+            let row             = existing_rows.remove(&index).unwrap_or_else(|| (self.make_row)(index));
+            slots[index % num_slots] = Some((index, row));
+        }
+
+        self.slots.set(slots);
+    }
+
+    ///
+    /// Creates the control binding for this virtual list
+    ///
+    fn make_control(scroll_action: &str, item_count: &Binding<usize>, row_height: f32, slots: &Binding<Vec<Option<(usize, Control)>>>) -> BindRef<Control> {
+        let scroll_action   = String::from(scroll_action);
+        let item_count      = item_count.clone();
+        let slots           = slots.clone();
+
+        BindRef::from(computed(move || {
+            let item_count  = item_count.get();
+            let slots       = slots.get();
+
+            // Each row is positioned where it appears in the whole list
+            let rows        = slots.into_iter()
+                .map(|slot| match slot {
+                    Some((index, row))  => row.with(Bounds { x1: Position::Start, y1: Position::At((index as f32) * row_height), x2: Position::End, y2: Position::At(((index+1) as f32) * row_height) }),
+                    None                => Control::empty()
+                })
+                .collect::<Vec<_>>();
+
+            Control::scrolling_container()
+                .with(Scroll::MinimumContentSize(0.0, (item_count as f32) * row_height))
+                .with(Scroll::HorizontalScrollBar(ScrollBarVisibility::Never))
+                .with(Scroll::VerticalScrollBar(ScrollBarVisibility::OnlyIfNeeded))
+                .with((ActionTrigger::Scroll, scroll_action.clone()))
+                .with(rows)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::diff::*;
+
+    fn scroll_to(list: &VirtualList, top: f32) {
+        list.update_from_action(&ActionParameter::Scroll(ScrollState { position: (0.0, top), content_size: (100.0, 10000.0), viewport_size: (100.0, 100.0) }));
+    }
+
+    #[test]
+    fn only_visible_rows_are_created() {
+        let list = VirtualList::new("Scroll", 10000, 10.0, |index| Control::label().with(format!("Row {}", index)));
+
+        scroll_to(&list, 500.0);
+
+        assert!(list.created_rows() == (46..65).collect::<Vec<_>>());
+        assert!(list.control().get().subcomponents().unwrap().len() == 19);
+    }
+
+    #[test]
+    fn scrolling_creates_only_new_rows() {
+        let created = Arc::new(Mutex::new(vec![]));
+        let rows    = Arc::clone(&created);
+        let list    = VirtualList::new("Scroll", 10000, 10.0, move |index| { rows.lock().unwrap().push(index); Control::label().with(format!("Row {}", index)) })
+            .with_overscan(2);
+
+        scroll_to(&list, 500.0);
+        let before = list.control().get();
+        created.lock().unwrap().clear();
+
+        // Scrolling by a single row creates a single row, and the diff only replaces the slot that the new row goes in
+        scroll_to(&list, 510.0);
+        let after = list.control().get();
+
+        assert!(*created.lock().unwrap() == vec![63]);
+        assert!(list.created_rows() == (49..64).collect::<Vec<_>>());
+
+        let differences = diff_tree(&before, &after);
+        assert!(differences.len() == 1);
+        assert!(differences[0].address() == &vec![(63 % 15) as u32]);
+    }
+
+    #[test]
+    fn scrolling_part_of_a_row_keeps_the_slots() {
+        let list    = VirtualList::new("Scroll", 10000, 10.0, |index| Control::label().with(format!("Row {}", index)))
+            .with_overscan(2);
+
+        scroll_to(&list, 500.0);
+        let at_row = list.control().get();
+
+        // Scrolling part-way through a row shows part of an extra row, but that's already allowed for
+        scroll_to(&list, 505.0);
+        let part_way = list.control().get();
+
+        assert!(part_way.subcomponents().unwrap().len() == at_row.subcomponents().unwrap().len());
+        assert!(diff_tree(&at_row, &part_way).len() == 0);
+
+        // Scrolling on to the next row only changes the slot for the new row
+        scroll_to(&list, 515.0);
+        let next_row = list.control().get();
+
+        assert!(next_row.subcomponents().unwrap().len() == at_row.subcomponents().unwrap().len());
+
+        let differences = diff_tree(&part_way, &next_row);
+        assert!(differences.len() == 1);
+        assert!(differences[0].address() == &vec![(63 % 15) as u32]);
+    }
+
+    #[test]
+    fn rows_past_the_end_are_not_created() {
+        let list = VirtualList::new("Scroll", 50, 10.0, |index| Control::label().with(format!("Row {}", index)));
+
+        scroll_to(&list, 450.0);
+        assert!(list.created_rows() == (41..50).collect::<Vec<_>>());
+
+        list.set_item_count(45);
+        assert!(list.created_rows() == (41..45).collect::<Vec<_>>());
+    }
+}
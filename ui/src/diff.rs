@@ -1,3 +1,5 @@
+use std::collections::{HashSet, HashMap};
+
 ///
 /// Represents a difference between two trees
 ///
@@ -45,6 +47,46 @@ pub trait DiffableTree where Self: Clone {
     /// (excluding child nodes)
     ///
     fn is_different(&self, compare_to: &Self) -> bool;
+
+    ///
+    /// Returns a key that identifies this node amongst its siblings, if it has one
+    ///
+    /// Keyed nodes can be moved around by `diff_tree_keyed()` instead of being replaced.
+    ///
+    fn key<'a>(&'a self) -> Option<&'a str> { None }
+
+    ///
+    /// Returns true if the differences between this node and the specified node (excluding child nodes) can be
+    /// applied without replacing the node
+    ///
+    /// `diff_tree_keyed()` generates an `Update` edit for these nodes and then carries on comparing their
+    /// children, rather than replacing the whole subtree.
+    ///
+    fn can_update_in_place(&self, _compare_to: &Self) -> bool { false }
+}
+
+///
+/// An edit that changes one tree into another, as generated by `diff_tree_keyed()`
+///
+/// Edits are applied in order, so the indices and addresses in each edit are for the tree with the
+/// edits before it already applied.
+///
+#[derive(Clone, PartialEq, Serialize)]
+pub enum TreeEdit<TNode: Clone> {
+    /// Replaces the node at an address
+    Replace(Diff<TNode>),
+
+    /// Updates the node at an address without replacing its child nodes (any changes to the children are in separate edits)
+    Update(Diff<TNode>),
+
+    /// Inserts a new child node into the node at the specified address
+    Insert { address: Vec<u32>, index: u32, node: TNode },
+
+    /// Removes a child node from the node at the specified address
+    Delete { address: Vec<u32>, index: u32 },
+
+    /// Removes a child node from the `from` index of the node at the specified address and inserts it again at the `to` index
+    Move { address: Vec<u32>, from: u32, to: u32 }
 }
 
 ///
@@ -96,6 +138,119 @@ fn diff_tree_run<TNode: DiffableTree>(address: &Vec<u32>, source: &TNode, target
     }
 }
 
+///
+/// Computes the difference between two trees, moving child nodes with matching keys instead of replacing them
+///
+/// When all of the children of a node have a key, this generates insert, delete and move edits for the
+/// children, so reordering a list of keyed nodes produces much smaller updates than `diff_tree()`. Nodes
+/// with unkeyed children are compared by position, and so are nodes whose children have duplicate keys
+/// (a warning is logged in this case).
+///
+pub fn diff_tree_keyed<TNode: DiffableTree>(source: &TNode, target: &TNode) -> Vec<TreeEdit<TNode>> {
+    let mut edits = vec![];
+    diff_tree_keyed_run(&vec![], source, target, &mut edits);
+
+    edits
+}
+
+///
+/// Returns the keys of a list of child nodes, or None if they can't be diffed by key
+///
+fn child_keys<'a, TNode: DiffableTree>(children: &Vec<&'a TNode>) -> Option<Vec<&'a str>> {
+    // Every child must have a key
+    let keys = children.iter()
+        .map(|child| child.key())
+        .collect::<Option<Vec<_>>>()?;
+
+    // Duplicate keys would make moves ambiguous
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = keys.iter().find(|key| !seen.insert(**key)) {
+        warn!("Found duplicate key {:?} while diffing the UI: falling back to positional diff", duplicate);
+        return None;
+    }
+
+    Some(keys)
+}
+
+///
+/// Computes the keyed difference between two trees (where we know the address)
+///
+fn diff_tree_keyed_run<TNode: DiffableTree>(address: &Vec<u32>, source: &TNode, target: &TNode, edits: &mut Vec<TreeEdit<TNode>>) {
+    if source.is_different(target) {
+        if source.can_update_in_place(target) {
+            // Update the node and carry on comparing its children
+            edits.push(TreeEdit::Update(Diff::new(address, target)));
+        } else {
+            // Different nodes replace the source with the target
+            edits.push(TreeEdit::Replace(Diff::new(address, target)));
+            return;
+        }
+    }
+
+    let source_children = source.child_nodes();
+    let target_children = target.child_nodes();
+
+    if source_children.len() == 0 && target_children.len() == 0 {
+        // Nothing to compare
+        return;
+    }
+
+    let keys = child_keys(&source_children).and_then(|source_keys| child_keys(&target_children).map(|target_keys| (source_keys, target_keys)));
+
+    if let Some((source_keys, target_keys)) = keys {
+        let source_for_key  = source_keys.iter().cloned().zip(source_children.iter().cloned()).collect::<HashMap<_, _>>();
+        let target_key_set  = target_keys.iter().cloned().collect::<HashSet<_>>();
+        let mut current     = source_keys;
+
+        // Delete the nodes that aren't in the target (from the end, so the indices stay valid)
+        for index in (0..current.len()).rev() {
+            if !target_key_set.contains(current[index]) {
+                edits.push(TreeEdit::Delete { address: address.clone(), index: index as u32 });
+                current.remove(index);
+            }
+        }
+
+        // Move or insert nodes so that they're in the same order as the target
+        for (index, key) in target_keys.iter().enumerate() {
+            if current.get(index) == Some(key) {
+                continue;
+            }
+
+            if let Some(from) = current.iter().position(|existing_key| existing_key == key) {
+                edits.push(TreeEdit::Move { address: address.clone(), from: from as u32, to: index as u32 });
+                current.remove(from);
+            } else {
+                edits.push(TreeEdit::Insert { address: address.clone(), index: index as u32, node: target_children[index].clone() });
+            }
+
+            current.insert(index, key);
+        }
+
+        // Compare the nodes that were in the source tree against their new versions
+        let mut node_address = address.clone();
+
+        for (index, key) in target_keys.iter().enumerate() {
+            if let Some(source_child) = source_for_key.get(key) {
+                node_address.push(index as u32);
+                diff_tree_keyed_run(&node_address, *source_child, target_children[index], edits);
+                node_address.pop();
+            }
+        }
+    } else if source_children.len() != target_children.len() {
+        // Positional diff: if the child node counts are different, the nodes are different
+        edits.push(TreeEdit::Replace(Diff::new(address, target)));
+    } else {
+        // Positional diff: check for differences in the child nodes
+        let mut node_address = address.clone();
+
+        for node_index in 0..source_children.len() {
+            node_address.push(node_index as u32);
+            diff_tree_keyed_run(&node_address, source_children[node_index], target_children[node_index], edits);
+            node_address.pop();
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -312,4 +467,117 @@ mod test {
         assert!(differences.len() == 1);
         assert!(differences[0].address() == &vec![1]);
     }
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct KeyedTree {
+        key: String,
+        value: u32,
+        child_nodes: Vec<KeyedTree>
+    }
+
+    impl DiffableTree for KeyedTree {
+        fn child_nodes<'a>(&'a self) -> Vec<&'a KeyedTree> {
+            self.child_nodes.iter().collect()
+        }
+
+        fn is_different(&self, compare_to: &Self) -> bool {
+            self.value != compare_to.value
+        }
+
+        fn key<'a>(&'a self) -> Option<&'a str> {
+            Some(&self.key)
+        }
+    }
+
+    fn keyed(key: &str, value: u32) -> KeyedTree {
+        KeyedTree { key: key.to_string(), value: value, child_nodes: vec![] }
+    }
+
+    fn keyed_list(children: Vec<KeyedTree>) -> KeyedTree {
+        KeyedTree { key: "list".to_string(), value: 0, child_nodes: children }
+    }
+
+    ///
+    /// Applies a set of keyed edits to a tree (so we can check that they produce the target)
+    ///
+    fn apply_edits(tree: &KeyedTree, edits: &Vec<TreeEdit<KeyedTree>>) -> KeyedTree {
+        fn node_at<'a>(tree: &'a mut KeyedTree, address: &Vec<u32>) -> &'a mut KeyedTree {
+            address.iter().fold(tree, |node, index| &mut node.child_nodes[*index as usize])
+        }
+
+        let mut tree = tree.clone();
+
+        for edit in edits.iter() {
+            match edit {
+                TreeEdit::Replace(diff)                     => { *node_at(&mut tree, diff.address()) = diff.replacement().clone(); }
+                TreeEdit::Update(diff)                      => { node_at(&mut tree, diff.address()).value = diff.replacement().value; }
+                TreeEdit::Insert { address, index, node }   => { node_at(&mut tree, address).child_nodes.insert(*index as usize, node.clone()); }
+                TreeEdit::Delete { address, index }         => { node_at(&mut tree, address).child_nodes.remove(*index as usize); }
+                TreeEdit::Move { address, from, to }        => {
+                    let node    = node_at(&mut tree, address);
+                    let child   = node.child_nodes.remove(*from as usize);
+                    node.child_nodes.insert(*to as usize, child);
+                }
+            }
+        }
+
+        tree
+    }
+
+    #[test]
+    fn reordering_keyed_children_only_moves_them() {
+        let tree_a  = keyed_list(vec![keyed("a", 1), keyed("b", 2), keyed("c", 3), keyed("d", 4)]);
+        let tree_b  = keyed_list(vec![keyed("d", 4), keyed("a", 1), keyed("b", 2), keyed("c", 3)]);
+        let edits   = diff_tree_keyed(&tree_a, &tree_b);
+
+        assert!(edits == vec![TreeEdit::Move { address: vec![], from: 3, to: 0 }]);
+        assert!(apply_edits(&tree_a, &edits) == tree_b);
+    }
+
+    #[test]
+    fn keyed_children_are_inserted_and_deleted() {
+        let tree_a  = keyed_list(vec![keyed("a", 1), keyed("b", 2), keyed("c", 3)]);
+        let tree_b  = keyed_list(vec![keyed("c", 3), keyed("x", 5), keyed("a", 1)]);
+        let edits   = diff_tree_keyed(&tree_a, &tree_b);
+
+        assert!(edits.iter().all(|edit| match edit { TreeEdit::Replace(_) => false, _ => true }));
+        assert!(edits.iter().any(|edit| edit == &TreeEdit::Delete { address: vec![], index: 1 }));
+        assert!(edits.iter().any(|edit| edit == &TreeEdit::Insert { address: vec![], index: 1, node: keyed("x", 5) }));
+        assert!(apply_edits(&tree_a, &edits) == tree_b);
+    }
+
+    #[test]
+    fn moved_keyed_child_is_compared_at_new_address() {
+        let tree_a  = keyed_list(vec![keyed("a", 1), keyed("b", 2)]);
+        let tree_b  = keyed_list(vec![keyed("b", 2), keyed("a", 10)]);
+        let edits   = diff_tree_keyed(&tree_a, &tree_b);
+
+        assert!(edits == vec![
+            TreeEdit::Move { address: vec![], from: 1, to: 0 },
+            TreeEdit::Replace(Diff::new(&vec![1], &keyed("a", 10)))
+        ]);
+        assert!(apply_edits(&tree_a, &edits) == tree_b);
+    }
+
+    #[test]
+    fn duplicate_keys_use_positional_diff() {
+        let tree_a  = keyed_list(vec![keyed("a", 1), keyed("a", 2)]);
+        let tree_b  = keyed_list(vec![keyed("a", 2), keyed("a", 1)]);
+        let edits   = diff_tree_keyed(&tree_a, &tree_b);
+
+        assert!(edits == vec![
+            TreeEdit::Replace(Diff::new(&vec![0], &keyed("a", 2))),
+            TreeEdit::Replace(Diff::new(&vec![1], &keyed("a", 1)))
+        ]);
+    }
+
+    #[test]
+    fn keyed_diff_matches_positional_diff_without_keys() {
+        let tree_a      = simple_tree();
+        let tree_b      = tree_with_multiple_differences();
+        let differences = diff_tree(&tree_a, &tree_b);
+        let edits       = diff_tree_keyed(&tree_a, &tree_b);
+
+        assert!(edits == differences.into_iter().map(|diff| TreeEdit::Replace(diff)).collect::<Vec<_>>());
+    }
 }
@@ -1,5 +1,6 @@
 use super::property::*;
 use super::viewmodel::*;
+use super::control::*;
 
 use desync::{Desync};
 use flo_stream::*;
@@ -31,10 +32,24 @@ pub struct DynamicViewModel {
     /// Maps computed bindings to their values (we ignore these when setting)
     computed: Mutex<HashMap<String, BindRef<PropertyValue>>>,
 
+    /// Text bindings that are kept in sync with properties in this viewmodel
+    text_bindings: Mutex<HashMap<String, TextBinding>>,
+
     /// Used for properties that don't exist in this model
     nothing: BindRef<PropertyValue>
 }
 
+///
+/// A text binding that is bound in both directions to a property
+///
+struct TextBinding {
+    /// The text binding
+    text: Arc<Binding<String>>,
+
+    /// Updates the property when the text binding changes (released when this is dropped)
+    _when_changed: Box<dyn Releasable>
+}
+
 ///
 /// Notifier for a dynamic stream
 ///
@@ -193,6 +208,7 @@ impl DynamicViewModel {
             new_properties:     Desync::new(Publisher::new(100)),
             bindings:           Mutex::new(HashMap::new()),
             computed:           Mutex::new(HashMap::new()),
+            text_bindings:      Mutex::new(HashMap::new()),
             nothing:            BindRef::from(bind(PropertyValue::Nothing)) }
     }
 
@@ -230,6 +246,60 @@ impl DynamicViewModel {
         });
     }
 
+    ///
+    /// Binds a string in both directions to the property with the same name as a text control
+    ///
+    /// The property is updated whenever the binding changes, and setting the property (for instance, by
+    /// passing the edits from the control to `edit_text()`) updates the binding. Values are only passed on
+    /// when they're different from the value already on the other side, so an edit doesn't bounce back to
+    /// the control that made it, and edits and changes to the binding in the same update settle on one value.
+    /// Use `Control::with_text_binding()` to attach the property and edit actions to the control.
+    ///
+    pub fn bind_text(&self, control_id: &str, text: Binding<String>) {
+        // If this is done while computing the UI, we don't want to attach to the current context
+        BindingContext::out_of_context(move || {
+            // Replace any existing text binding for this control
+            self.text_bindings.lock().unwrap().remove(control_id);
+
+            // The property starts with the current value of the text
+            self.set_property(control_id, PropertyValue::String(text.get()));
+            let property        = self.get_binding(control_id).unwrap();
+
+            // Changes to the text update the property if it doesn't already have the same value
+            let text            = Arc::new(text);
+            let weak_text       = Arc::downgrade(&text);
+            let when_changed    = text.when_changed(notify(move || {
+                if let Some(text) = weak_text.upgrade() {
+                    let new_value = PropertyValue::String(text.get());
+
+                    if property.get() != new_value {
+                        property.set(new_value);
+                    }
+                }
+            }));
+
+            self.text_bindings.lock().unwrap().insert(String::from(control_id), TextBinding { text: text, _when_changed: when_changed });
+        });
+    }
+
+    ///
+    /// Updates the text binding for a control from an `EditValue` or `SetValue` action generated by that control
+    ///
+    /// Returns true if the action was for a control with a bound text property (so controllers can pass their actions
+    /// here first and ignore any that are handled).
+    ///
+    pub fn edit_text(&self, control_id: &str, action_parameter: &ActionParameter) -> bool {
+        if !self.text_bindings.lock().unwrap().contains_key(control_id) {
+            return false;
+        }
+
+        if let ActionParameter::Value(PropertyValue::String(new_text)) = action_parameter {
+            self.set_property(control_id, PropertyValue::String(new_text.clone()));
+        }
+
+        true
+    }
+
     ///
     /// Returns true if the specified binding exists in this viewmodel
     ///
@@ -256,6 +326,26 @@ impl DynamicViewModel {
         // Synchronise with where the future completes, so the property is ready by the time this call returns
         self.new_properties.sync(|_| { });
     }
+
+    ///
+    /// Sets the value of a 'set' binding, creating it if it doesn't already exist
+    ///
+    fn set_binding(&self, property_name: &str, new_value: PropertyValue) {
+        let mut bindings = self.bindings.lock().unwrap();
+
+        if let Some(value) = bindings.get(&String::from(property_name)) {
+            // Update the binding
+            (**value).set(new_value);
+
+            // Awkward return because rust keeps the borrow in the else clause even though nothing can reference it
+            return;
+        }
+
+        // Property does not exist in this viewmodel: create a new one
+        let new_binding = bind(new_value);
+        bindings.insert(String::from(property_name), Arc::new(new_binding.clone()));
+        self.follow_binding(property_name, new_binding);
+    }
 }
 
 impl ViewModel for DynamicViewModel {
@@ -273,20 +363,15 @@ impl ViewModel for DynamicViewModel {
     }
 
     fn set_property(&self, property_name: &str, new_value: PropertyValue) {
-        let mut bindings = self.bindings.lock().unwrap();
+        // Bound text is updated after the property, so the text binding sees that the property already has its new value
+        let new_text = if let PropertyValue::String(new_text) = &new_value { Some(new_text.clone()) } else { None };
 
-        if let Some(value) = bindings.get(&String::from(property_name)) {
-            // Update the binding
-            (**value).set(new_value);
+        self.set_binding(property_name, new_value);
 
-            // Awkward return because rust keeps the borrow in the else clause even though nothing can reference it
-            return;
+        if let Some(new_text) = new_text {
+            let text = self.text_bindings.lock().unwrap().get(property_name).map(|text_binding| Arc::clone(&text_binding.text));
+            text.map(|text| text.set(new_text));
         }
-
-        // Property does not exist in this viewmodel: create a new one
-        let new_binding = bind(new_value);
-        bindings.insert(String::from(property_name), Arc::new(new_binding.clone()));
-        self.follow_binding(property_name, new_binding);
     }
 
     fn get_property_names(&self) -> Vec<String> {
@@ -680,4 +765,55 @@ mod test {
         assert!(test_value.get() == PropertyValue::Int(2));
         assert!((*notified.lock().unwrap()) == true);
     }
+
+    #[test]
+    fn text_binding_updates_property() {
+        let viewmodel   = DynamicViewModel::new();
+        let text        = bind("Test".to_string());
+
+        viewmodel.bind_text("Name", text.clone());
+        assert!(viewmodel.get_property("Name").get() == PropertyValue::String("Test".to_string()));
+
+        text.set("Changed".to_string());
+        assert!(viewmodel.get_property("Name").get() == PropertyValue::String("Changed".to_string()));
+    }
+
+    #[test]
+    fn text_edits_update_binding() {
+        let viewmodel   = DynamicViewModel::new();
+        let text        = bind("Test".to_string());
+
+        viewmodel.bind_text("Name", text.clone());
+
+        assert!(viewmodel.edit_text("Name", &ActionParameter::Value(PropertyValue::String("Edited".to_string()))));
+        assert!(!viewmodel.edit_text("NotBound", &ActionParameter::Value(PropertyValue::String("Edited".to_string()))));
+
+        assert!(text.get() == "Edited".to_string());
+        assert!(viewmodel.get_property("Name").get() == PropertyValue::String("Edited".to_string()));
+    }
+
+    #[test]
+    fn text_edits_are_not_sent_back_to_control() {
+        let viewmodel   = DynamicViewModel::new();
+        let text        = bind("Test".to_string());
+        let changes     = Arc::new(Mutex::new(0));
+
+        viewmodel.bind_text("Name", text.clone());
+
+        let property_changes = changes.clone();
+        viewmodel.get_property("Name").when_changed(notify(move || (*property_changes.lock().unwrap()) += 1)).keep_alive();
+
+        // Editing the control changes the property once, and doesn't change it again when the text binding is updated
+        viewmodel.edit_text("Name", &ActionParameter::Value(PropertyValue::String("Edited".to_string())));
+        viewmodel.get_property("Name").get();
+        assert!((*changes.lock().unwrap()) == 1);
+        assert!(text.get() == "Edited".to_string());
+
+        // Changing the binding and then the control in the same update settles on the last value
+        text.set("Changed".to_string());
+        viewmodel.edit_text("Name", &ActionParameter::Value(PropertyValue::String("Edited again".to_string())));
+
+        assert!(text.get() == "Edited again".to_string());
+        assert!(viewmodel.get_property("Name").get() == PropertyValue::String("Edited again".to_string()));
+    }
 }
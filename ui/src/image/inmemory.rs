@@ -60,6 +60,10 @@ impl ImageData for InMemoryImageData {
     fn read_future(&self) -> BoxStream<'static, Bytes> {
         Box::pin(ImageStreamIterator::from(&self.bytes))
     }
+
+    fn size(&self) -> usize {
+        self.bytes.len()
+    }
 }
 
 #[cfg(test)]
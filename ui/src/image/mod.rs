@@ -1,3 +1,5 @@
+use crate::resource_manager::*;
+
 use futures::stream::{BoxStream};
 use bytes::Bytes;
 
@@ -25,6 +27,18 @@ pub trait ImageData : Send+Sync {
 
     /// Reads the raw data for this image
     fn read_future(&self) -> BoxStream<'static, Bytes>;
+
+    /// The number of bytes of memory used to store this image
+    fn size(&self) -> usize { 0 }
+}
+
+impl ResourceSize for Image {
+    fn resource_size(&self) -> usize {
+        match self {
+            Image::Png(data)    => data.size(),
+            Image::Svg(data)    => data.size()
+        }
+    }
 }
 
 impl Image {
@@ -40,6 +54,7 @@ mod inmemory;
 mod static_data;
 mod shortcuts;
 mod png;
+mod thumbnail;
 mod bytes_iterator;
 pub use self::inmemory::*;
 pub use self::static_data::*;
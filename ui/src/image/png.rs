@@ -25,3 +25,32 @@ pub fn png_data_for_rgba(rgba: &[u8], width: u32, height: u32) -> InMemoryImageD
     // Generate the image data object for the final PNG
     InMemoryImageData::from(png_data)
 }
+
+///
+/// Decodes PNG image data into an RGBA buffer, returning the buffer along with the width and height of the image
+///
+/// Returns None if the data is not a valid PNG image.
+///
+pub fn rgba_data_for_png(data: &dyn ImageData) -> Option<(Vec<u8>, u32, u32)> {
+    // Expand paletted and low bit-depth images, and reduce 16-bit images, so every image ends up with 8 bits per channel
+    let mut png_decoder         = png::Decoder::new(data.read());
+    png_decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+    // Read the image data
+    let (info, mut png_reader)  = png_decoder.read_info().ok()?;
+    let mut pixels              = vec![0; info.buffer_size()];
+    png_reader.next_frame(&mut pixels).ok()?;
+
+    // Convert to RGBA
+    let rgba = match info.color_type {
+        png::ColorType::RGBA            => pixels,
+        png::ColorType::RGB             => pixels.chunks(3).flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha  => pixels.chunks(2).flat_map(|ga| vec![ga[0], ga[0], ga[0], ga[1]]).collect(),
+        png::ColorType::Grayscale       => pixels.iter().flat_map(|g| vec![*g, *g, *g, 255]).collect(),
+
+        // Paletted images are expanded to RGB by the decoder
+        png::ColorType::Indexed         => { return None; }
+    };
+
+    Some((rgba, info.width, info.height))
+}
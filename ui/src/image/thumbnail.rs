@@ -0,0 +1,165 @@
+use super::*;
+use super::png::*;
+
+use lazy_static::lazy_static;
+
+use std::collections::{HashMap};
+
+lazy_static! {
+    /// Thumbnails that have already been generated, indexed by the address of the image data and the size of the thumbnail
+    static ref THUMBNAIL_CACHE: Mutex<HashMap<(usize, u32, u32), (Weak<dyn ImageData>, Image)>> = Mutex::new(HashMap::new());
+}
+
+impl Image {
+    ///
+    /// Returns a version of this image scaled down to fit within the specified size
+    ///
+    /// The aspect ratio of the image is preserved, and images that already fit are returned unchanged (they're never
+    /// scaled up). SVG images are returned unchanged as they can be drawn at any size, as are PNG images that can't
+    /// be decoded. Thumbnails are cached, so requesting the same thumbnail of an image again won't decode the image.
+    ///
+    pub fn thumbnail(&self, max_width: u32, max_height: u32) -> Image {
+        let data = match self {
+            Image::Png(data)    => data,
+            Image::Svg(_)       => { return self.clone(); }
+        };
+
+        // Use the cached thumbnail if there is one (the weak reference checks that the image data hasn't been freed and its address reused)
+        let cache_key = (Arc::as_ptr(data) as *const () as usize, max_width, max_height);
+
+        if let Some((cached_data, thumbnail)) = THUMBNAIL_CACHE.lock().unwrap().get(&cache_key) {
+            if cached_data.upgrade().map(|cached_data| Arc::ptr_eq(&cached_data, data)).unwrap_or(false) {
+                return thumbnail.clone();
+            }
+        }
+
+        // Generate a new thumbnail (images that aren't scaled are returned as they are: caching these would keep the image alive forever)
+        let thumbnail = match Self::scale_png(&**data, max_width, max_height) {
+            Some(thumbnail) => thumbnail,
+            None            => { return self.clone(); }
+        };
+
+        // Cache it, removing any thumbnails for images that no longer exist
+        let mut cache = THUMBNAIL_CACHE.lock().unwrap();
+        cache.retain(|_, (cached_data, _)| cached_data.strong_count() > 0);
+        cache.insert(cache_key, (Arc::downgrade(data), thumbnail.clone()));
+
+        thumbnail
+    }
+
+    ///
+    /// Decodes and scales down a PNG image, returning None if the image can't be decoded or is already small enough
+    ///
+    fn scale_png(data: &dyn ImageData, max_width: u32, max_height: u32) -> Option<Image> {
+        let (rgba, width, height) = rgba_data_for_png(data)?;
+
+        // Work out the size of the thumbnail
+        if width <= max_width && height <= max_height {
+            return None;
+        }
+
+        let scale           = f64::min((max_width as f64) / (width as f64), (max_height as f64) / (height as f64));
+        let new_width       = (((width as f64) * scale).round() as u32).max(1);
+        let new_height      = (((height as f64) * scale).round() as u32).max(1);
+
+        // Generate the thumbnail by averaging the pixels that each target pixel covers
+        let mut thumbnail   = vec![0u8; (new_width * new_height * 4) as usize];
+
+        for y in 0..new_height {
+            let start_y = (y as u64 * height as u64 / new_height as u64) as u32;
+            let end_y   = (((y+1) as u64 * height as u64 / new_height as u64) as u32).max(start_y+1);
+
+            for x in 0..new_width {
+                let start_x = (x as u64 * width as u64 / new_width as u64) as u32;
+                let end_x   = (((x+1) as u64 * width as u64 / new_width as u64) as u32).max(start_x+1);
+
+                // Colours are weighted by their alpha so transparent pixels don't darken the edges of shapes
+                let mut total       = [0u64; 4];
+                let mut num_pixels  = 0u64;
+
+                for src_y in start_y..end_y {
+                    for src_x in start_x..end_x {
+                        let pos     = ((src_y * width + src_x) * 4) as usize;
+                        let alpha   = rgba[pos+3] as u64;
+
+                        total[0]    += rgba[pos+0] as u64 * alpha;
+                        total[1]    += rgba[pos+1] as u64 * alpha;
+                        total[2]    += rgba[pos+2] as u64 * alpha;
+                        total[3]    += alpha;
+                        num_pixels  += 1;
+                    }
+                }
+
+                let pos = ((y * new_width + x) * 4) as usize;
+                if total[3] > 0 {
+                    thumbnail[pos+0] = (total[0] / total[3]) as u8;
+                    thumbnail[pos+1] = (total[1] / total[3]) as u8;
+                    thumbnail[pos+2] = (total[2] / total[3]) as u8;
+                }
+                thumbnail[pos+3] = (total[3] / num_pixels) as u8;
+            }
+        }
+
+        Some(Image::png_from_rgba_data(&thumbnail, new_width, new_height))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solid_image(width: u32, height: u32) -> Image {
+        let rgba = (0..(width*height)).flat_map(|_| vec![255, 0, 0, 255]).collect::<Vec<u8>>();
+        Image::png_from_rgba_data(&rgba, width, height)
+    }
+
+    fn image_data(image: &Image) -> Arc<dyn ImageData> {
+        match image {
+            Image::Png(data) => Arc::clone(data),
+            Image::Svg(data) => Arc::clone(data)
+        }
+    }
+
+    #[test]
+    fn thumbnail_preserves_aspect_ratio() {
+        let image           = solid_image(100, 50);
+        let thumbnail       = image.thumbnail(20, 20);
+
+        let (rgba, w, h)    = rgba_data_for_png(&*image_data(&thumbnail)).unwrap();
+
+        assert!((w, h) == (20, 10));
+        assert!(rgba[0..4] == [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn small_images_are_not_scaled_up() {
+        let image       = solid_image(10, 10);
+        let thumbnail   = image.thumbnail(20, 20);
+
+        assert!(Arc::ptr_eq(&image_data(&image), &image_data(&thumbnail)));
+    }
+
+    #[test]
+    fn unscaled_images_are_not_kept_alive() {
+        let image       = solid_image(10, 10);
+        let data        = image_data(&image);
+        let thumbnail   = image.thumbnail(20, 20);
+
+        drop(thumbnail);
+        drop(image);
+
+        // Only our reference to the data should be left (the thumbnail cache shouldn't be holding on to it)
+        assert!(Arc::strong_count(&data) == 1);
+    }
+
+    #[test]
+    fn thumbnails_are_cached() {
+        let image       = solid_image(64, 64);
+        let thumbnail_1 = image.thumbnail(16, 16);
+        let thumbnail_2 = image.thumbnail(16, 16);
+        let thumbnail_3 = image.thumbnail(8, 8);
+
+        assert!(Arc::ptr_eq(&image_data(&thumbnail_1), &image_data(&thumbnail_2)));
+        assert!(!Arc::ptr_eq(&image_data(&thumbnail_1), &image_data(&thumbnail_3)));
+    }
+}
@@ -6,6 +6,8 @@ extern crate flo_stream;
 
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
+extern crate log;
 extern crate serde_json;
 extern crate lazy_static;
 extern crate serde;
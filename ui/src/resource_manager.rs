@@ -4,6 +4,7 @@ use std::fmt;
 use std::ops::*;
 use std::sync::*;
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 use std::collections::*;
 
 ///
@@ -23,7 +24,38 @@ struct ResourceManagerCore<T: Send+Sync> {
     clean_size: usize,
 
     /// Resources that have been assigned a name
-    named_resources: HashMap<String, Resource<T>>
+    named_resources: HashMap<String, Resource<T>>,
+
+    /// How long a named resource can go unused before it's evicted (None to keep named resources until they're replaced)
+    eviction_timeout: Option<Duration>,
+
+    /// When named resources that are not being used were first found to be unused
+    unused_since: HashMap<String, Instant>
+}
+
+///
+/// Trait implemented by resources that can report how much memory they are using
+///
+pub trait ResourceSize {
+    ///
+    /// The approximate number of bytes used by this resource
+    ///
+    fn resource_size(&self) -> usize;
+}
+
+///
+/// Describes the resources being tracked by a resource manager
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ResourceUsage {
+    /// The number of resources that are still in memory
+    pub count: usize,
+
+    /// The number of those resources that have a name
+    pub named_count: usize,
+
+    /// The approximate number of bytes used by the resources
+    pub bytes: usize
 }
 
 ///
@@ -196,6 +228,47 @@ impl<T: Send+Sync> ResourceManagerCore<T> {
         // Clean again after adding 16 new resources
         self.clean_size = self.resources.len() - self.free_slots.len() + 16;
     }
+
+    ///
+    /// Removes named resources that are not used outside of the resource manager, and have been unused for the specified time
+    ///
+    /// Resources are used if there is any other reference to them, so any resource that's in a control (including controls that
+    /// are in a diff that has not been sent yet) will not be evicted. Returns the number of resources that were removed.
+    ///
+    fn evict_unused(&mut self, unused_for: Duration, now: Instant) -> usize {
+        // The name map holds one reference to each named resource, so any other reference means that the resource is in use
+        let unused = self.named_resources.iter()
+            .filter(|(_, resource)| Arc::strong_count(&resource.resource) <= 1)
+            .map(|(name, _)| name.clone())
+            .collect::<HashSet<_>>();
+
+        // Forget about resources that are being used again
+        self.unused_since.retain(|name, _| unused.contains(name));
+
+        // Evict resources that have been unused for long enough
+        let mut evicted = 0;
+
+        for name in unused {
+            let unused_since = *self.unused_since.entry(name.clone()).or_insert(now);
+
+            if now.duration_since(unused_since) >= unused_for {
+                self.named_resources.remove(&name);
+                self.unused_since.remove(&name);
+                evicted += 1;
+            }
+        }
+
+        evicted
+    }
+
+    ///
+    /// Returns the resources that are still in memory
+    ///
+    fn live_resources(&self) -> Vec<Arc<T>> {
+        self.resources.iter()
+            .filter_map(|weak| weak.resource.upgrade())
+            .collect()
+    }
 }
 
 impl<T: 'static+Send+Sync> ResourceManager<T> {
@@ -209,11 +282,47 @@ impl<T: 'static+Send+Sync> ResourceManager<T> {
                 named_resources:    HashMap::new(),
                 name_for_id:        vec![],
                 free_slots:         vec![],
-                clean_size:         1
+                clean_size:         1,
+                eviction_timeout:   None,
+                unused_since:       HashMap::new()
             })
         }
     }
 
+    ///
+    /// Sets how long a named resource can go unused before it's removed from this manager
+    ///
+    /// Unnamed resources are always removed as soon as nothing refers to them, but named resources are normally kept until
+    /// their name is reused. With a timeout, named resources that are not used by anything outside of the manager (such as
+    /// a control) are removed once they've been unused for the timeout. Unused resources are checked for when new
+    /// resources are registered.
+    ///
+    pub fn set_eviction_timeout(&self, timeout: Option<Duration>) {
+        self.core.desync(move |core| {
+            core.eviction_timeout = timeout;
+            core.unused_since.clear();
+        });
+    }
+
+    ///
+    /// Immediately removes any named resources that are not being used outside of this manager, returning the number that were removed
+    ///
+    pub fn purge_unused(&self) -> usize {
+        self.core.sync(|core| {
+            let evicted = core.evict_unused(Duration::from_millis(0), Instant::now());
+            core.clean_resources();
+
+            evicted
+        })
+    }
+
+    ///
+    /// Returns the number of resources that are still in memory
+    ///
+    pub fn resource_count(&self) -> usize {
+        self.core.sync(|core| core.live_resources().len())
+    }
+
     ///
     /// Registers a resource and assigns it a name
     ///
@@ -237,6 +346,11 @@ impl<T: 'static+Send+Sync> ResourceManager<T> {
                 self.core.desync(|core| core.clean_resources());
             }
 
+            // Remove any named resources that have been unused for long enough
+            if let Some(timeout) = core.eviction_timeout {
+                self.core.desync(move |core| { core.evict_unused(timeout, Instant::now()); });
+            }
+
             resource
         })
     }
@@ -324,6 +438,23 @@ impl<T: 'static+Send+Sync> ResourceManager<T> {
     }
 }
 
+impl<T: 'static+Send+Sync+ResourceSize> ResourceManager<T> {
+    ///
+    /// Returns the number of resources in this manager, how many of them are named, and how much memory they are using
+    ///
+    pub fn usage(&self) -> ResourceUsage {
+        self.core.sync(|core| {
+            let live_resources = core.live_resources();
+
+            ResourceUsage {
+                count:          live_resources.len(),
+                named_count:    core.named_resources.len(),
+                bytes:          live_resources.iter().map(|resource| resource.resource_size()).sum()
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -458,4 +589,74 @@ mod test {
             assert!(resource_manager.get_resource_with_id(resource[i].id()).map(|x| *x) == Some(i));
         }
     }
+
+    #[test]
+    fn purge_removes_unused_named_resources() {
+        let resource_manager    = ResourceManager::new();
+
+        let unused              = resource_manager.register(2);
+        let used                = resource_manager.register(3);
+        resource_manager.assign_name(&unused, "Unused");
+        resource_manager.assign_name(&used, "Used");
+        mem::drop(unused);
+
+        assert!(resource_manager.purge_unused() == 1);
+
+        assert!(resource_manager.get_named_resource("Unused").is_none());
+        assert!(resource_manager.get_named_resource("Used").map(|x| *x) == Some(3));
+        assert!(resource_manager.resource_count() == 1);
+    }
+
+    #[test]
+    fn named_resources_are_evicted_after_timeout() {
+        let resource_manager    = ResourceManager::new();
+        resource_manager.set_eviction_timeout(Some(Duration::from_millis(0)));
+
+        let resource            = resource_manager.register(2);
+        resource_manager.assign_name(&resource, "Mr Resource");
+        mem::drop(resource);
+
+        // Eviction happens when a new resource is registered
+        let _other              = resource_manager.register(3);
+
+        assert!(resource_manager.get_named_resource("Mr Resource").is_none());
+    }
+
+    #[test]
+    fn resources_in_use_are_not_evicted() {
+        let resource_manager    = ResourceManager::new();
+        resource_manager.set_eviction_timeout(Some(Duration::from_millis(0)));
+
+        // Another reference to the resource (eg, in a control that's waiting to be sent as part of a diff) keeps it alive
+        let resource            = resource_manager.register(2);
+        resource_manager.assign_name(&resource, "Mr Resource");
+        let pending             = resource.clone();
+        mem::drop(resource);
+
+        let _other              = resource_manager.register(3);
+
+        assert!(resource_manager.get_named_resource("Mr Resource").map(|x| *x) == Some(2));
+        assert!(resource_manager.purge_unused() == 0);
+        assert!(*pending == 2);
+    }
+
+    struct SizedResource(usize);
+
+    impl ResourceSize for SizedResource {
+        fn resource_size(&self) -> usize { self.0 }
+    }
+
+    #[test]
+    fn usage_counts_live_resources() {
+        let resource_manager    = ResourceManager::new();
+
+        let small               = resource_manager.register(SizedResource(10));
+        let large               = resource_manager.register(SizedResource(1000));
+        let released            = resource_manager.register(SizedResource(50));
+        resource_manager.assign_name(&large, "Large");
+        mem::drop(released);
+
+        assert!(resource_manager.usage() == ResourceUsage { count: 2, named_count: 1, bytes: 1010 });
+        assert!(small.0 == 10);
+    }
 }
@@ -1,4 +1,5 @@
 use super::event::*;
+use super::key_chords::*;
 use crate::control::*;
 use crate::property::*;
 use crate::controller::*;
@@ -12,6 +13,7 @@ use futures::future::{BoxFuture};
 
 use std::mem;
 use std::sync::*;
+use std::time::{Instant};
 use std::collections::{HashSet, HashMap};
 
 ///
@@ -42,6 +44,18 @@ pub struct UiSessionCore {
     /// Maps key bindings to the commands they're attached to
     key_map: BindRef<Arc<HashMap<KeyBinding, HashSet<Command>>>>,
 
+    /// Maps key bindings to the controls whose click actions they perform
+    shortcut_map: BindRef<Arc<HashMap<KeyBinding, ShortcutBinding>>>,
+
+    /// All of the key bindings in the key map and the shortcut map (used to track chords made from either kind of binding)
+    all_key_bindings: BindRef<Arc<HashMap<KeyBinding, ()>>>,
+
+    /// The actions that are suppressed because their controls are in disabled subtrees
+    disabled_actions: BindRef<Arc<HashSet<(Vec<String>, String)>>>,
+
+    /// Tracks the progress of any key chord the user is pressing
+    key_chords: KeyChordTracker,
+
     /// Functions to be called next time the core is updated
     update_callbacks: Vec<Box<dyn FnMut(&mut UiSessionCore) -> ()+Send>>
 }
@@ -55,12 +69,19 @@ impl UiSessionCore {
         let ui_tree     = assemble_ui(Arc::clone(&controller));
         let command_map = command_map_binding(Arc::clone(&controller));
         let key_map     = keymap_binding(Arc::clone(&controller));
+        let shortcuts   = shortcut_map_binding(Arc::clone(&controller));
+        let all_keys    = Self::all_key_bindings(BindRef::clone(&key_map), BindRef::clone(&shortcuts));
+        let disabled    = disabled_actions_binding(Arc::clone(&controller));
 
         UiSessionCore {
             last_update_id:     0,
             ui_tree:            ui_tree,
             command_map:        command_map,
             key_map:            key_map,
+            shortcut_map:       shortcuts,
+            all_key_bindings:   all_keys,
+            disabled_actions:   disabled,
+            key_chords:         KeyChordTracker::new(CHORD_TIMEOUT),
             tick:               ExpiringPublisher::new(1),
             suspend_updates:    ExpiringPublisher::new(1),
             suspension_count:   0,
@@ -69,6 +90,20 @@ impl UiSessionCore {
         }
     }
 
+    ///
+    /// Creates a binding containing every key binding that's used by a command or a control shortcut
+    ///
+    fn all_key_bindings(key_map: BindRef<Arc<HashMap<KeyBinding, HashSet<Command>>>>, shortcut_map: BindRef<Arc<HashMap<KeyBinding, ShortcutBinding>>>) -> BindRef<Arc<HashMap<KeyBinding, ()>>> {
+        BindRef::from(computed(move || {
+            let key_map         = key_map.get();
+            let shortcut_map    = shortcut_map.get();
+
+            Arc::new(key_map.keys().chain(shortcut_map.keys())
+                .map(|binding| (binding.clone(), ()))
+                .collect())
+        }))
+    }
+
     ///
     /// Retrieves the ID of the last update that was dispatched for this core
     ///
@@ -157,14 +192,21 @@ impl UiSessionCore {
                 },
 
                 UiEvent::KeyPress(key_binding) => {
-                    // Turn into a set of command events
-                    let key_map     = self.key_map.get();
-                    let commands    = key_map.get(&key_binding);
+                    // Turn into a set of command events (key presses that are part of a chord only generate commands when the chord is completed)
+                    let all_bindings    = self.all_key_bindings.get();
+                    let key_map         = self.key_map.get();
+                    let shortcut_map    = self.shortcut_map.get();
+                    let key_binding     = self.key_chords.key_pressed(&*all_bindings, &key_binding, Instant::now());
+                    let commands        = key_binding.as_ref().and_then(|key_binding| key_map.get(key_binding));
+                    let shortcut        = key_binding.as_ref().and_then(|key_binding| shortcut_map.get(key_binding));
 
-                    // Dispatch the commands
                     if let Some(commands) = commands {
+                        // Dispatch the commands (commands bound to a key take priority over control shortcuts)
                         commands.into_iter()
                             .for_each(|cmd| self.dispatch_command(&cmd, vec![]));
+                    } else if let Some(ShortcutBinding { path, action: ActionEvent::Named(action_name) }) = shortcut {
+                        // Perform the click action for the control with this shortcut
+                        self.dispatch_action_to_path(controller, path, action_name.clone(), ActionParameter::None);
                     }
                 }
 
@@ -254,7 +296,22 @@ impl UiSessionCore {
     /// Dispatches an action to a controller
     ///
     fn dispatch_action(&mut self, controller: &dyn Controller, event_name: String, action_parameter: ActionParameter) {
-        controller.action(&event_name, &action_parameter);
+        match action_parameter {
+            // Keys pressed in a tree view are replaced by the actions they perform
+            ActionParameter::TreeKey(key) => {
+                for (action_name, action_parameter) in tree_key_actions(controller, &event_name, key) {
+                    controller.action(&action_name, &action_parameter);
+                }
+            }
+
+            // Dragging a crop handle is performed here so the controller always sees bounds that are inside the image
+            ActionParameter::CropDrag(start_bounds, handle, offset, image_size) => {
+                let bounds = crop_drag_bounds(controller, &event_name, &start_bounds, handle, offset, image_size);
+                controller.action(&event_name, &ActionParameter::Crop(bounds));
+            }
+
+            action_parameter => controller.action(&event_name, &action_parameter)
+        }
     }
 
     ///
@@ -270,6 +327,11 @@ impl UiSessionCore {
     /// Dispatches an action to a controller given by a path
     ///
     pub fn dispatch_action_to_path(&mut self, controller: &dyn Controller, controller_path: &Vec<String>, event_name: String, action_parameter: ActionParameter) {
+        // Actions from controls inside a disabled subtree are suppressed
+        if self.disabled_actions.get().contains(&(controller_path.clone(), event_name.clone())) {
+            return;
+        }
+
         // Find the controller along this path
         if controller_path.len() == 0 {
             // Straight to the root controller
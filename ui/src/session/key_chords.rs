@@ -0,0 +1,127 @@
+use crate::control::*;
+
+use std::time::{Duration, Instant};
+use std::collections::{HashSet, HashMap};
+
+/// How long to wait for the second part of a chord after its prefix has been pressed
+pub const CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+///
+/// Tracks the prefixes of key chords (such as the Ctrl+K of Ctrl+K Ctrl+S) as keys are pressed
+///
+pub struct KeyChordTracker {
+    /// The prefix keys that have been pressed and when they were pressed, if a chord is in progress
+    armed: Option<(HashSet<KeyPress>, Instant)>,
+
+    /// How long to wait for the second part of a chord
+    timeout: Duration
+}
+
+impl KeyChordTracker {
+    ///
+    /// Creates a new chord tracker that waits for the specified time for the second part of a chord
+    ///
+    pub fn new(timeout: Duration) -> KeyChordTracker {
+        KeyChordTracker {
+            armed:      None,
+            timeout:    timeout
+        }
+    }
+
+    ///
+    /// Updates the chord state when a key binding is pressed, and returns the binding whose commands should be performed
+    ///
+    /// Pressing the prefix of a chord in the key map starts the chord and returns None. If the keys pressed within the
+    /// timeout complete the chord, then the chord binding is returned, otherwise the chord is cancelled and None is
+    /// returned. Pressing just modifier keys doesn't affect a chord that's in progress (so the user can release and
+    /// press ctrl again for the second part of a chord). Once the timeout has expired, key presses are treated as if
+    /// the prefix was never pressed.
+    ///
+    pub fn key_pressed<Commands>(&mut self, key_map: &HashMap<KeyBinding, Commands>, key_binding: &KeyBinding, now: Instant) -> Option<KeyBinding> {
+        // Modifier keys are pressed on the way to the next key so they don't complete or cancel a chord
+        if key_binding.is_modifiers_only() {
+            return Some(key_binding.clone());
+        }
+
+        // If a chord is in progress, this key either completes it or cancels it
+        if let Some((prefix, armed_at)) = self.armed.take() {
+            if now.duration_since(armed_at) <= self.timeout {
+                let chord = KeyBinding { keys: key_binding.keys.clone(), prefix: Some(prefix) };
+
+                return if key_map.contains_key(&chord) { Some(chord) } else { None };
+            }
+        }
+
+        // Start a new chord if these keys are the prefix of one
+        let is_prefix = key_map.keys().any(|binding| binding.prefix.as_ref() == Some(&key_binding.keys));
+
+        if is_prefix {
+            self.armed = Some((key_binding.keys.clone(), now));
+            None
+        } else {
+            Some(key_binding.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn save_chord_map() -> HashMap<KeyBinding, &'static str> {
+        let mut key_map = HashMap::new();
+
+        key_map.insert(KeyBinding::chord(KeyBinding::ctrl(KeyPress::KeyK), KeyBinding::ctrl(KeyPress::KeyS)), "save_all");
+        key_map.insert(KeyBinding::ctrl(KeyPress::KeyS), "save");
+
+        key_map
+    }
+
+    #[test]
+    fn completing_chord_within_timeout_performs_action() {
+        let key_map     = save_chord_map();
+        let mut chords  = KeyChordTracker::new(CHORD_TIMEOUT);
+        let start       = Instant::now();
+
+        // The prefix doesn't perform an action itself
+        assert!(chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyK), start) == None);
+
+        // Releasing and pressing ctrl again doesn't cancel the chord
+        assert!(chords.key_pressed(&key_map, &KeyBinding::key(KeyPress::ModifierCtrl), start + Duration::from_millis(100)) == Some(KeyBinding::key(KeyPress::ModifierCtrl)));
+
+        // The second key completes the chord
+        let action = chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyS), start + Duration::from_millis(500));
+        assert!(action.and_then(|binding| key_map.get(&binding).cloned()) == Some("save_all"));
+
+        // Once the chord is finished, the second key has its usual meaning again
+        let action = chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyS), start + Duration::from_millis(600));
+        assert!(action.and_then(|binding| key_map.get(&binding).cloned()) == Some("save"));
+    }
+
+    #[test]
+    fn timeout_resets_chord() {
+        let key_map     = save_chord_map();
+        let mut chords  = KeyChordTracker::new(CHORD_TIMEOUT);
+        let start       = Instant::now();
+
+        assert!(chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyK), start) == None);
+
+        // Pressing the second key after the timeout is just a normal key press
+        let action = chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyS), start + CHORD_TIMEOUT + Duration::from_millis(1));
+        assert!(action.and_then(|binding| key_map.get(&binding).cloned()) == Some("save"));
+    }
+
+    #[test]
+    fn unmatched_second_key_cancels_chord() {
+        let key_map     = save_chord_map();
+        let mut chords  = KeyChordTracker::new(CHORD_TIMEOUT);
+        let start       = Instant::now();
+
+        assert!(chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyK), start) == None);
+        assert!(chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyX), start + Duration::from_millis(100)) == None);
+
+        // The chord is no longer in progress
+        let action = chords.key_pressed(&key_map, &KeyBinding::ctrl(KeyPress::KeyS), start + Duration::from_millis(200));
+        assert!(action.and_then(|binding| key_map.get(&binding).cloned()) == Some("save"));
+    }
+}
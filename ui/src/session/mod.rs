@@ -4,12 +4,14 @@ mod core;
 mod event_publisher;
 mod update_stream;
 mod command_tracker;
+mod key_chords;
 pub mod state;
 pub mod session;
 mod canvas_stream;
 mod viewmodel_stream;
 mod controller_runtime;
 mod priority_future;
+mod recording_session;
 
 pub use self::event::*;
 pub use self::update::*;
@@ -18,6 +20,7 @@ pub use self::event_publisher::*;
 pub use self::canvas_stream::*;
 pub use self::viewmodel_stream::*;
 pub use self::update_stream::*;
+pub use self::recording_session::*;
 
 #[cfg(test)] mod tests;
 
@@ -0,0 +1,179 @@
+use super::*;
+use super::event::*;
+use super::update::*;
+use super::update_stream::*;
+use crate::control::*;
+use crate::controller::*;
+use crate::user_interface::*;
+
+use flo_binding::*;
+use flo_stream::*;
+use futures::prelude::*;
+use futures::future;
+use futures::task::{Poll, Waker};
+
+use std::sync::*;
+
+///
+/// The updates that have been recorded by a recording session
+///
+struct RecordedUpdates {
+    /// The updates in the order they were received
+    updates: Vec<UiUpdate>,
+
+    /// The control tree after applying all of the updates
+    tree: Control,
+
+    /// Tasks waiting for more updates to arrive
+    wakers: Vec<Waker>
+}
+
+///
+/// A UI session that records the updates generated by its controller instead of displaying them
+///
+/// This can be used to test controllers without needing a real front-end: the recorded updates can be retrieved with
+/// `updates()` and `current_tree()` returns the control tree built by applying the UI diffs in the order they were
+/// generated. Events can be sent to the controller using the input sink.
+///
+pub struct RecordingSession<CoreController: Controller> {
+    /// The session that generates the updates
+    session: UiSession<CoreController>,
+
+    /// The updates that have been recorded so far
+    recorded: Arc<Mutex<RecordedUpdates>>
+}
+
+impl<CoreController: Controller+'static> RecordingSession<CoreController> {
+    ///
+    /// Creates a new recording session for a controller, and returns a future that will run the session and record its updates
+    ///
+    pub fn new(controller: CoreController) -> (RecordingSession<CoreController>, impl Unpin+Future<Output=()>) {
+        let (session, run_loop) = UiSession::new(controller);
+        let recorded            = Arc::new(Mutex::new(RecordedUpdates { updates: vec![], tree: Control::empty(), wakers: vec![] }));
+
+        // Record the updates from the session until it finishes
+        let updates             = session.get_updates();
+        let record_to           = Arc::clone(&recorded);
+        let record_loop         = updates.for_each(move |updates| {
+            if let Ok(updates) = updates {
+                record_to.lock().unwrap().record(updates);
+            }
+
+            future::ready(())
+        });
+
+        let run_loop            = future::select(run_loop, Box::pin(record_loop)).map(|_| ());
+
+        (RecordingSession { session, recorded }, run_loop)
+    }
+
+    ///
+    /// Returns the updates that have been recorded so far
+    ///
+    pub fn updates(&self) -> Vec<UiUpdate> {
+        self.recorded.lock().unwrap().updates.clone()
+    }
+
+    ///
+    /// Returns the control tree after applying all of the UI updates that have been recorded so far
+    ///
+    pub fn current_tree(&self) -> Control {
+        self.recorded.lock().unwrap().tree.clone()
+    }
+
+    ///
+    /// Returns a future that completes once at least the specified number of updates have been recorded
+    ///
+    pub fn wait_for_updates(&self, num_updates: usize) -> impl Future<Output=()>+Send {
+        let recorded = Arc::clone(&self.recorded);
+
+        future::poll_fn(move |context| {
+            let mut recorded = recorded.lock().unwrap();
+
+            if recorded.updates.len() >= num_updates {
+                Poll::Ready(())
+            } else {
+                recorded.wakers.push(context.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+impl RecordedUpdates {
+    ///
+    /// Records a set of updates from the session
+    ///
+    fn record(&mut self, updates: Vec<UiUpdate>) {
+        for update in updates {
+            if let UiUpdate::UpdateUi(diffs) = &update {
+                for diff in diffs.iter() {
+                    self.tree = replace_at_address(&self.tree, &diff.address, &diff.new_ui);
+                }
+            }
+
+            self.updates.push(update);
+        }
+
+        self.wakers.drain(..).for_each(|waker| waker.wake());
+    }
+}
+
+///
+/// Returns a copy of a control tree with the control at the specified address replaced
+///
+fn replace_at_address(control: &Control, address: &[u32], new_ui: &Control) -> Control {
+    if address.len() == 0 {
+        return new_ui.clone();
+    }
+
+    // The address is an index into all of the subcomponents of the control
+    let mut index       = address[0] as usize;
+    let attributes      = control.attributes()
+        .map(|attr| match attr {
+            ControlAttribute::SubComponents(components) => {
+                if index < components.len() {
+                    let mut components  = components.clone();
+                    components[index]   = replace_at_address(&components[index], &address[1..], new_ui);
+                    index               = usize::MAX;
+
+                    ControlAttribute::SubComponents(components)
+                } else {
+                    index               = index.saturating_sub(components.len());
+                    attr.clone()
+                }
+            }
+
+            other                                       => other.clone()
+        })
+        .collect::<Vec<_>>();
+
+    Control::new(control.control_type()).with(attributes)
+}
+
+impl<CoreController: 'static+Controller> UserInterface<Vec<UiEvent>, Vec<UiUpdate>, ()> for RecordingSession<CoreController> {
+    /// The type of the update stream for this UI
+    type UpdateStream = UiUpdateStream;
+
+    /// Retrieves an input event sink for this user interface
+    fn get_input_sink(&self) -> WeakPublisher<Vec<UiEvent>> {
+        self.session.get_input_sink()
+    }
+
+    /// Retrieves a view onto the update stream for this user interface
+    fn get_updates(&self) -> UiUpdateStream {
+        self.session.get_updates()
+    }
+}
+
+impl<CoreController: 'static+Controller> CoreUserInterface for RecordingSession<CoreController> {
+    type CoreController = CoreController;
+
+    fn ui_tree(&self) -> BindRef<Control> {
+        self.session.ui_tree()
+    }
+
+    fn controller(&self) -> Arc<CoreController> {
+        self.session.controller()
+    }
+}
@@ -52,6 +52,31 @@ impl Controller for ActionTestController {
     }
 }
 
+struct ParameterTestController {
+    ui:         Binding<Control>,
+    viewmodel:  Arc<DynamicViewModel>,
+    actions:    Arc<Mutex<Vec<(String, ActionParameter)>>>
+}
+
+impl Controller for ParameterTestController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::new(&self.ui)
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.viewmodel.clone())
+    }
+
+    fn action(&self, action_id: &str, action_data: &ActionParameter) {
+        // Selecting a node updates the bound selection
+        if let ("Select", ActionParameter::Value(node_id)) = (action_id, action_data) {
+            self.viewmodel.set_property("Selected", node_id.clone());
+        }
+
+        self.actions.lock().unwrap().push((action_id.to_string(), action_data.clone()));
+    }
+}
+
 /// Creates a timeout future
 fn timeout(ms: u64) -> oneshot::Receiver<()> {
     let (timeout_send, timeout_recv) = oneshot::channel::<()>();
@@ -218,6 +243,49 @@ fn ui_update_triggers_update() {
     });
 }
 
+#[test]
+fn reordering_keyed_controls_generates_edits() {
+    let thread_pool = executor::ThreadPool::new().unwrap();
+
+    // Create a list of controls with IDs that we'll reorder later on
+    let item        = |id: &str| Control::label().with(id).with(ControlAttribute::Id(id.to_string()));
+    let ui          = bind(Control::container().with(vec![item("One"), item("Two"), item("Three")]));
+
+    let controller  = TestController { ui: ui.clone(), viewmodel: None };
+
+    // Start a UI session for this controller
+    let (session, run_loop) = UiSession::new(controller);
+    thread_pool.spawn_ok(run_loop);
+
+    // Get an update stream that moves keyed controls and attach a timeout
+    let update_stream   = session.get_updates().with_keyed_edits();
+    let next_or_timeout = stream::select(update_stream.map(|updates| updates.map(|updates| TestItem::Updates(updates))),
+        timeout(1000).into_stream().map(|_| Ok(TestItem::Timeout)));
+
+    let mut next_or_timeout = next_or_timeout;
+
+    executor::block_on(async {
+        // Fetch the first item from the stream
+        let first_item = next_or_timeout.next().await;
+        assert!(first_item != Some(Ok(TestItem::Timeout)));
+
+        // Reorder the list and remove one of the items
+        spawn(move || {
+            sleep(Duration::from_millis(50));
+            ui.set(Control::container().with(vec![item("Three"), item("One")]));
+        });
+
+        // The items should be moved rather than the container being replaced
+        let updated_ui = next_or_timeout.next().await;
+        assert!(updated_ui == Some(Ok(TestItem::Updates(vec![
+            UiUpdate::EditUi(vec![
+                UiEdit::Delete { address: vec![], index: 1 },
+                UiEdit::Move { address: vec![], from: 1, to: 0 }
+            ])
+        ]))));
+    });
+}
+
 #[test]
 fn viewmodel_update_triggers_update() {
     let thread_pool         = executor::ThreadPool::new().unwrap();
@@ -391,4 +459,180 @@ fn send_command_to_root_controller() {
     assert!(&*actions.lock().unwrap() == &vec!["Test".to_string()]);
 }
 
+/// Sends key presses to a session for a controller, returning the actions that the controller received
+fn actions_for_key_presses(ui: Control, key_presses: Vec<KeyBinding>) -> Vec<String> {
+    let thread_pool         = executor::ThreadPool::new().unwrap();
+
+    let controller          = ActionTestController { ui: bind(ui), viewmodel: None, actions: Arc::new(Mutex::new(vec![])) };
+    let actions             = controller.actions.clone();
+
+    // Start a UI session for this controller
+    let (session, run_loop) = UiSession::new(controller);
+    thread_pool.spawn_ok(run_loop);
+
+    let update_stream       = session.get_updates();
+    let mut event_publisher = session.get_input_sink();
+    let mut next_or_timeout = stream::select(update_stream.map(|updates| updates.map(|updates| TestItem::Updates(updates))), timeout(1000).into_stream().map(|_| Ok(TestItem::Timeout)));
+
+    executor::block_on(async {
+        let first_item = next_or_timeout.next().await;
+        assert!(first_item != Some(Ok(TestItem::Timeout)));
+
+        // Press the keys, then wait for a tick so we know they've been processed
+        event_publisher.publish(key_presses.into_iter().map(|key_binding| UiEvent::KeyPress(key_binding)).collect()).await;
+
+        event_publisher.publish(vec![UiEvent::Tick]).await;
+        let tick_update = next_or_timeout.next().await;
+        assert!(tick_update != Some(Ok(TestItem::Timeout)));
+    });
+
+    let actions = actions.lock().unwrap().clone();
+    actions
+}
+
+#[test]
+fn key_shortcut_performs_click_action() {
+    let ui      = Control::container().with(vec![
+        Control::button().with((ActionTrigger::Click, "Undo")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyZ)),
+        Control::button().with((ActionTrigger::Click, "Save")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyS))
+    ]);
+    let actions = actions_for_key_presses(ui, vec![KeyBinding::ctrl(KeyPress::KeyZ)]);
+
+    assert!(actions == vec!["Undo".to_string()]);
+}
+
+#[test]
+fn command_key_binding_takes_priority_over_shortcut() {
+    let ui      = Control::container().with(vec![
+        Control::empty()
+            .with((ActionTrigger::Command(Command::with_id("Test")), "Test"))
+            .with(ControlAttribute::BindKey(KeyBinding::ctrl(KeyPress::KeyZ), Command::with_id("Test"))),
+        Control::button().with((ActionTrigger::Click, "Undo")).with_key_shortcut(KeyBinding::ctrl(KeyPress::KeyZ))
+    ]);
+    let actions = actions_for_key_presses(ui, vec![KeyBinding::ctrl(KeyPress::KeyZ)]);
+
+    // Only the command is performed: the shortcut on the button is ignored
+    assert!(actions == vec!["Test".to_string()]);
+}
+
+#[test]
+fn tree_keys_are_performed_by_the_session() {
+    let thread_pool         = executor::ThreadPool::new().unwrap();
+
+    // Tree view whose nodes and selection are both bound to the viewmodel
+    let nodes               = vec![
+        TreeNode::leaf("one", "One"),
+        TreeNode::branch("two", "Two", vec![TreeNode::leaf("three", "Three")])
+    ];
+    let viewmodel           = Arc::new(DynamicViewModel::new());
+    viewmodel.set_property("Nodes", Tree::property_value(&nodes));
+    viewmodel.set_property("Selected", PropertyValue::String("one".to_string()));
+
+    let tree                = Control::tree()
+        .with(Tree::Bound(Property::bound("Nodes")))
+        .with(State::Value(Property::bound("Selected")))
+        .with((ActionTrigger::SetValue, "Select"))
+        .with((ActionTrigger::ExpandNode, "Expand"))
+        .with((ActionTrigger::CollapseNode, "Collapse"));
+    let controller          = ParameterTestController { ui: bind(tree), viewmodel: viewmodel.clone(), actions: Arc::new(Mutex::new(vec![])) };
+    let actions             = controller.actions.clone();
+
+    // Start a UI session for this controller
+    let (session, run_loop) = UiSession::new(controller);
+    thread_pool.spawn_ok(run_loop);
+
+    let update_stream       = session.get_updates();
+    let mut event_publisher = session.get_input_sink();
+    let mut next_or_timeout = stream::select(update_stream.map(|updates| updates.map(|updates| TestItem::Updates(updates))), timeout(1000).into_stream().map(|_| Ok(TestItem::Timeout)));
+
+    executor::block_on(async {
+        let first_item = next_or_timeout.next().await;
+        assert!(first_item != Some(Ok(TestItem::Timeout)));
+
+        // Move down to the branch, then try to expand it
+        event_publisher.publish(vec![
+            UiEvent::Action(vec![], "Select".to_string(), ActionParameter::TreeKey(KeyPress::KeyDown)),
+            UiEvent::Action(vec![], "Select".to_string(), ActionParameter::TreeKey(KeyPress::KeyRight))
+        ]).await;
+
+        event_publisher.publish(vec![UiEvent::Tick]).await;
+        let tick_update = next_or_timeout.next().await;
+        assert!(tick_update != Some(Ok(TestItem::Timeout)));
+    });
+
+    // The key presses should have been replaced by the actions they perform
+    assert!(viewmodel.get_property("Selected").get() == PropertyValue::String("two".to_string()));
+    assert!(&*actions.lock().unwrap() == &vec![
+        ("Select".to_string(), ActionParameter::Value(PropertyValue::String("two".to_string()))),
+        ("Expand".to_string(), ActionParameter::Value(PropertyValue::String("two".to_string())))
+    ]);
+}
+
+#[test]
+fn crop_drags_are_clamped_by_the_session() {
+    let thread_pool         = executor::ThreadPool::new().unwrap();
+
+    // Crop control locked to a 2:1 aspect ratio
+    let crop                = Control::crop_image()
+        .with(CropBounds::new(100.0, 100.0, 200.0, 100.0))
+        .with(Crop::AspectRatio(2.0))
+        .with((ActionTrigger::Crop, "Crop"));
+    let controller          = ParameterTestController { ui: bind(crop), viewmodel: Arc::new(DynamicViewModel::new()), actions: Arc::new(Mutex::new(vec![])) };
+    let actions             = controller.actions.clone();
+
+    // Start a UI session for this controller
+    let (session, run_loop) = UiSession::new(controller);
+    thread_pool.spawn_ok(run_loop);
+
+    let update_stream       = session.get_updates();
+    let mut event_publisher = session.get_input_sink();
+    let mut next_or_timeout = stream::select(update_stream.map(|updates| updates.map(|updates| TestItem::Updates(updates))), timeout(1000).into_stream().map(|_| Ok(TestItem::Timeout)));
+
+    executor::block_on(async {
+        let first_item = next_or_timeout.next().await;
+        assert!(first_item != Some(Ok(TestItem::Timeout)));
+
+        // Drag the bottom-right handle well outside of the image
+        event_publisher.publish(vec![
+            UiEvent::Action(vec![], "Crop".to_string(), ActionParameter::CropDrag(CropBounds::new(100.0, 100.0, 200.0, 100.0), CropHandle::BottomRight, (1000.0, 0.0), (640.0, 480.0)))
+        ]).await;
+
+        event_publisher.publish(vec![UiEvent::Tick]).await;
+        let tick_update = next_or_timeout.next().await;
+        assert!(tick_update != Some(Ok(TestItem::Timeout)));
+    });
+
+    // The controller should see the bounds from the end of the drag, inside the image and with the right aspect ratio
+    assert!(&*actions.lock().unwrap() == &vec![
+        ("Crop".to_string(), ActionParameter::Crop(CropBounds::new(100.0, 100.0, 540.0, 270.0)))
+    ]);
+}
+
 // TODO: also check we trigger an update if a canvas that's in the UI changes
+
+#[test]
+fn recording_session_applies_diffs_to_tree() {
+    let thread_pool = executor::ThreadPool::new().unwrap();
+
+    // Controller with a couple of labels
+    let ui          = bind(Control::container().with(vec![Control::label().with("One"), Control::label().with("Two")]));
+    let controller  = TestController { ui: ui.clone(), viewmodel: None };
+
+    // Start recording the session
+    let (session, run_loop) = RecordingSession::new(controller);
+    thread_pool.spawn_ok(run_loop);
+
+    executor::block_on(session.wait_for_updates(2));
+
+    assert!(session.updates()[0] == UiUpdate::Start);
+    assert!(session.current_tree() == Control::container().with(vec![Control::label().with("One"), Control::label().with("Two")]));
+
+    // Changing one of the labels generates a diff for just that label, which is applied to the recorded tree
+    let num_updates = session.updates().len();
+    ui.set(Control::container().with(vec![Control::label().with("One"), Control::label().with("Three")]));
+
+    executor::block_on(session.wait_for_updates(num_updates+1));
+
+    assert!(session.updates()[num_updates] == UiUpdate::UpdateUi(vec![UiDiff { address: vec![1], new_ui: Control::label().with("Three") }]));
+    assert!(session.current_tree() == Control::container().with(vec![Control::label().with("One"), Control::label().with("Three")]));
+}
@@ -16,6 +16,30 @@ pub struct UiDiff {
     pub new_ui: Control
 }
 
+///
+/// Represents an edit to the UI tree, which can move controls around as well as replacing them
+///
+/// Edits are applied in order, so the addresses and indices in each edit are for the tree with the
+/// edits before it already applied.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum UiEdit {
+    /// Replaces the control at an address
+    Replace(UiDiff),
+
+    /// Changes the style classes of the control at an address, leaving the rest of it (including its subcomponents) as it is
+    SetClasses { address: Vec<u32>, classes: Vec<String> },
+
+    /// Inserts a new control into the subcomponents of the control at the specified address
+    Insert { address: Vec<u32>, index: u32, new_ui: Control },
+
+    /// Removes a control from the subcomponents of the control at the specified address
+    Delete { address: Vec<u32>, index: u32 },
+
+    /// Moves one of the subcomponents of the control at the specified address from one index to another
+    Move { address: Vec<u32>, from: u32, to: u32 }
+}
+
 ///
 /// Represents a difference to a canvas
 ///
@@ -42,6 +66,12 @@ pub enum UiUpdate {
     /// Represents a series of updates to the UI tree
     UpdateUi(Vec<UiDiff>),
 
+    /// Represents a series of edits to the UI tree that move, insert or remove keyed controls
+    ///
+    /// These are only generated by update streams that have been created `with_keyed_edits()`: other
+    /// streams replace the parent control instead.
+    EditUi(Vec<UiEdit>),
+
     /// Represents an update to a canvas in a controller
     UpdateCanvas(Vec<CanvasDiff>),
 
@@ -57,7 +57,10 @@ pub struct UiUpdateStream {
     pending: Arc<Mutex<Option<Vec<UiUpdate>>>>,
 
     /// Number of times the update stream has been suspended
-    is_suspended: bool
+    is_suspended: bool,
+
+    /// True if keyed controls should be moved around using `UiUpdate::EditUi` instead of replacing their parent
+    keyed_edits: bool
 }
 
 impl UiUpdateStream {
@@ -93,12 +96,24 @@ impl UiUpdateStream {
             pending_ui:         pending_ui,
             pending:            pending,
             tick:               tick,
-            is_suspended:       false
+            is_suspended:       false,
+            keyed_edits:        false
         };
 
         new_stream
     }
 
+    ///
+    /// Changes this stream so that it generates `UiUpdate::EditUi` updates when keyed controls are moved, inserted or removed
+    ///
+    /// User interfaces that can move their controls around can use this to avoid re-creating whole lists when they're
+    /// reordered.
+    ///
+    pub fn with_keyed_edits(mut self) -> UiUpdateStream {
+        self.keyed_edits = true;
+        self
+    }
+
     ///
     /// Pulls any UI events into the pending stream
     ///
@@ -115,28 +130,23 @@ impl UiUpdateStream {
             // Send any differences in the UI tree
             if let Some(last_ui) = self.last_ui.take() {
                 // Find the differences in the UI
-                let differences = diff_tree(&last_ui, &new_ui);
-
-                if differences.len() != 0 {
-                    // Found some differences: change into a series of UiDiffs
-                    let diffs = differences.into_iter()
-                        .map(|diff| UiDiff {
-                            address:    diff.address().clone(),
-                            new_ui:     diff.replacement().clone()
-                        })
-                        .collect::<Vec<_>>();
+                let differences = if self.keyed_edits {
+                    diff_tree_keyed(&last_ui, &new_ui)
+                } else {
+                    diff_tree(&last_ui, &new_ui).into_iter().map(TreeEdit::Replace).collect()
+                };
 
-                    ui_updates.extend(diffs);
-                }
+                // Change into a series of UiEdits
+                ui_updates.extend(differences.into_iter().map(ui_edit_from_tree_edit));
 
                 // The new UI is now the last UI
                 self.last_ui = Some(new_ui);
             } else {
                 // Create a diff from the entire UI
-                ui_updates.push(UiDiff {
+                ui_updates.push(UiEdit::Replace(UiDiff {
                     address:    vec![],
                     new_ui:     new_ui.clone()
-                });
+                }));
 
                 // This is now the last UI
                 self.last_ui = Some(new_ui);
@@ -144,9 +154,19 @@ impl UiUpdateStream {
         }
 
         if ui_updates.len() > 0 {
+            // Updates that only replace controls are sent as UiDiffs
+            let only_replacements   = ui_updates.iter().all(|edit| match edit { UiEdit::Replace(_) => true, _ => false });
+            let update              = if only_replacements {
+                UiUpdate::UpdateUi(ui_updates.into_iter()
+                    .filter_map(|edit| match edit { UiEdit::Replace(diff) => Some(diff), _ => None })
+                    .collect())
+            } else {
+                UiUpdate::EditUi(ui_updates)
+            };
+
             self.pending_ui.lock().unwrap()
                 .get_or_insert_with(|| vec![])
-                .push(UiUpdate::UpdateUi(ui_updates))
+                .push(update)
         }
 
         if command_updates.len() > 0 {
@@ -248,3 +268,16 @@ impl Stream for UiUpdateStream {
         }
     }
 }
+
+///
+/// Converts an edit generated by `diff_tree_keyed()` into a UI edit
+///
+fn ui_edit_from_tree_edit(edit: TreeEdit<Control>) -> UiEdit {
+    match edit {
+        TreeEdit::Replace(diff)                     => UiEdit::Replace(UiDiff { address: diff.address().clone(), new_ui: diff.replacement().clone() }),
+        TreeEdit::Update(diff)                      => UiEdit::SetClasses { address: diff.address().clone(), classes: diff.replacement().classes() },
+        TreeEdit::Insert { address, index, node }  => UiEdit::Insert { address: address, index: index, new_ui: node },
+        TreeEdit::Delete { address, index }        => UiEdit::Delete { address: address, index: index },
+        TreeEdit::Move { address, from, to }       => UiEdit::Move { address: address, from: from, to: to }
+    }
+}
@@ -48,6 +48,16 @@ impl ActionsFrom<ViewAction> for ControlAttribute {
             Action(trigger, ActionEvent::Named(name))   => event_actions(trigger, name),
 
             BindKey(_key, _cmd)                         => vec![],
+            KeyShortcut(_keys)                          => vec![],
+            Classes(_classes)                           => vec![],
+            BoundClasses(_classes)                      => vec![],
+            MediaAttr(_media)                           => vec![ /* TODO */ ],
+            CropAttr(_crop)                             => vec![ /* TODO */ ],
+            DragDropAttr(_drag_drop)                    => vec![ /* TODO */ ],
+            GaugeAttr(_gauge)                           => vec![ /* TODO */ ],
+            Units(_units)                               => vec![],
+            RangeStep(_step)                            => vec![ /* TODO */ ],
+            TreeAttr(_tree)                             => vec![ /* TODO */ ],
             Canvas(_canvas_resource)                    => vec![],              // Can send the whole canvas here, but more consistent if it's done in the same place it's attached
 
             SubComponents(_components)                  => vec![]               // Handled separately by ViewState
@@ -65,6 +75,7 @@ fn event_actions(trigger: &ActionTrigger, name: &String) -> Vec<ViewAction> {
 
     match trigger {
         Click                           => vec![ViewAction::RequestEvent(ViewEvent::Click, name.clone())],
+        SecondaryClick                  => vec![ /* TODO */ ],
         Dismiss                         => vec![ViewAction::RequestEvent(ViewEvent::Dismiss, name.clone())],
         Resize                          => vec![ViewAction::RequestEvent(ViewEvent::Resize, name.clone())],
 
@@ -81,11 +92,22 @@ fn event_actions(trigger: &ActionTrigger, name: &String) -> Vec<ViewAction> {
         Paint(PaintDevice::Other)       => vec![],
 
         Drag                            => vec![ViewAction::RequestEvent(ViewEvent::Drag, name.clone())],
+        DragStart                       => vec![ /* TODO */ ],
+        DragOver                        => vec![ /* TODO */ ],
+        Drop                            => vec![ /* TODO */ ],
         Focused                         => vec![ViewAction::RequestEvent(ViewEvent::Focused, name.clone())],
         EditValue                       => vec![ViewAction::RequestEvent(ViewEvent::EditValue, name.clone())],
         SetValue                        => vec![ViewAction::RequestEvent(ViewEvent::SetValue, name.clone())],
         CancelEdit                      => vec![ViewAction::RequestEvent(ViewEvent::CancelEdit, name.clone())],
+        Compose                         => vec![ /* TODO */ ],
         VirtualScroll(width, height)    => vec![ViewAction::RequestEvent(ViewEvent::VirtualScroll(*width as f64, *height as f64), name.clone())],
+
+        ExpandNode                      => vec![ /* TODO */ ],
+        CollapseNode                    => vec![ /* TODO */ ],
+        SetPlaying                      => vec![ /* TODO */ ],
+        MediaEnded                      => vec![ /* TODO */ ],
+        Crop                            => vec![ /* TODO */ ],
+        Scroll                          => vec![ /* TODO */ ],
     }
 }
 
@@ -111,7 +133,8 @@ impl ActionsFrom<ViewAction> for State {
             Enabled(property)           => vec![ViewAction::SetState(ViewStateUpdate::Enabled(bind_property(property.clone())))],
             Value(property)             => vec![ViewAction::SetState(ViewStateUpdate::Value(bind_property(property.clone())))],
             Range((lower, upper))       => vec![ViewAction::SetState(ViewStateUpdate::Range(bind_property(lower.clone()), bind_property(upper.clone())))],
-            FocusPriority(property)     => vec![ViewAction::SetState(ViewStateUpdate::FocusPriority(bind_property(property.clone())))]
+            FocusPriority(property)     => vec![ViewAction::SetState(ViewStateUpdate::FocusPriority(bind_property(property.clone())))],
+            SubtreeEnabled(_property)   => vec![ /* Not supported by the Cocoa UI: the controls look enabled, but the session suppresses their actions */ ]
         }
     }
 }
@@ -149,7 +172,8 @@ impl ActionsFrom<ViewAction> for Scroll {
             MinimumContentSize(width, height)   => vec![ViewAction::SetScrollMinimumSize(*width as f64, *height as f64)],
             HorizontalScrollBar(visibility)     => vec![ViewAction::SetHorizontalScrollBar(*visibility)],
             VerticalScrollBar(visibility)       => vec![ViewAction::SetVerticalScrollBar(*visibility)],
-            Fix(axis)                           => vec![ViewAction::SetState(ViewStateUpdate::FixScrollAxis(*axis))]
+            Fix(axis)                           => vec![ViewAction::SetState(ViewStateUpdate::FixScrollAxis(*axis))],
+            ScrollTo(_x, _y)                    => vec![ /* TODO */ ]
         }
     }
 }
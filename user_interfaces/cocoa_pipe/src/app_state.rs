@@ -88,6 +88,7 @@ impl AppState {
             UpdateCanvas(differences)   => { self.update_canvas(differences) }
             UpdateViewModel(updates)    => { self.update_viewmodel(updates) }
             UpdateCommands(_updates)    => { vec![/* TODO */] }
+            EditUi(_edits)              => { vec![/* Not generated: the core update stream isn't created with keyed edits */] }
         }
     }
 
@@ -61,7 +61,11 @@ impl From<ControlType> for ViewType {
             Rotor                   => ViewType::Rotor,
             TextBox                 => ViewType::TextBox,
             CheckBox                => ViewType::CheckBox,
-            ComboBox                => ViewType::ComboBox
+            ComboBox                => ViewType::ComboBox,
+            TreeView                => ViewType::Empty,              // TODO: no native tree view yet
+            Media                   => ViewType::Empty,              // TODO: no native media view yet
+            CropImage               => ViewType::Empty,              // TODO: no native crop view yet
+            Gauge                   => ViewType::Empty               // TODO: no native gauge view yet
         }
     }
 }
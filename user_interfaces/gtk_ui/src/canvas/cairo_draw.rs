@@ -182,7 +182,7 @@ impl CairoDraw {
     ///
     /// Converts a blend mode into an operator
     ///
-    fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
+    pub fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
         match blend {
             flo::BlendMode::SourceOver      => cairo::Operator::Over,
             flo::BlendMode::SourceIn        => cairo::Operator::In,
@@ -15,7 +15,10 @@ struct Layer {
     stored: Option<cairo::ImageSurface>,
 
     /// Context that this surface will be drawn upon
-    context: CairoDraw
+    context: CairoDraw,
+
+    /// How this layer is composited with the layers underneath it
+    blend_mode: BlendMode
 }
 
 ///
@@ -164,6 +167,15 @@ impl PixBufCanvas {
                 self.current_layer = new_layer_id;
             },
 
+            Draw::LayerBlend(layer_id, blend_mode) => {
+                // The blend mode is used when the layers are composited in render_to_context()
+                let viewport        = &self.viewport;
+                let pixel_scale     = self.pixel_scale;
+                let layer           = self.layers.entry(layer_id).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+                layer.blend_mode    = blend_mode;
+            },
+
             Draw::Store             => { let current_layer = self.current_layer; self.save_layer(current_layer); },
             Draw::Restore           => { let current_layer = self.current_layer; self.restore_layer(current_layer); },
             Draw::FreeStoredBuffer  => { let current_layer = self.current_layer; self.clear_storage(current_layer); },
@@ -216,7 +228,7 @@ impl PixBufCanvas {
             let layer_pattern = cairo::SurfacePattern::create(&layer.surface);
             layer_pattern.set_filter(cairo::Filter::Nearest);
 
-            drawable.set_operator(cairo::Operator::Over);
+            drawable.set_operator(CairoDraw::get_operator(layer.blend_mode));
             drawable.set_source(&layer_pattern);
             drawable.paint();
         }
@@ -259,9 +271,62 @@ impl PixBufCanvas {
         let new_layer = Layer {
             surface:    surface,
             context:    draw,
-            stored:     None
+            stored:     None,
+            blend_mode: BlendMode::SourceOver
         };
 
         new_layer
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    ///
+    /// Fills the whole of a layer with a colour
+    ///
+    fn fill_layer(canvas: &mut PixBufCanvas, layer_id: LayerId, color: Color) {
+        canvas.draw(Draw::Layer(layer_id));
+        canvas.draw(Draw::Path(PathOp::NewPath));
+        canvas.draw(Draw::Path(PathOp::Move(-2.0, -2.0)));
+        canvas.draw(Draw::Path(PathOp::Line(2.0, -2.0)));
+        canvas.draw(Draw::Path(PathOp::Line(2.0, 2.0)));
+        canvas.draw(Draw::Path(PathOp::Line(-2.0, 2.0)));
+        canvas.draw(Draw::Path(PathOp::ClosePath));
+        canvas.draw(Draw::FillColor(color));
+        canvas.draw(Draw::Fill);
+    }
+
+    ///
+    /// Renders a canvas and returns the colour of its top-left pixel (as 0xAARRGGBB)
+    ///
+    fn render_pixel(canvas: &PixBufCanvas) -> u32 {
+        let mut target = cairo::ImageSurface::create(cairo::Format::ARgb32, 4, 4).unwrap();
+
+        {
+            let context = cairo::Context::new(&target);
+            canvas.render_to_context(&context);
+        }
+
+        let data = target.get_data().unwrap();
+        u32::from_ne_bytes([data[0], data[1], data[2], data[3]])
+    }
+
+    #[test]
+    fn layers_are_composited_with_their_blend_mode() {
+        let viewport    = CanvasViewport { width: 4, height: 4, viewport_x: 0, viewport_y: 0, viewport_width: 4, viewport_height: 4 };
+        let mut canvas  = PixBufCanvas::new(viewport, 1.0);
+
+        // Yellow layer underneath a cyan layer
+        fill_layer(&mut canvas, LayerId(0), Color::Rgba(1.0, 1.0, 0.0, 1.0));
+        fill_layer(&mut canvas, LayerId(1), Color::Rgba(0.0, 1.0, 1.0, 1.0));
+
+        // Drawn normally, the cyan layer covers the yellow one
+        assert!(render_pixel(&canvas) == 0xff00ffff);
+
+        // Multiplying the two layers together leaves only the green component
+        canvas.draw(Draw::LayerBlend(LayerId(1), BlendMode::Multiply));
+        assert!(render_pixel(&canvas) == 0xff00ff00);
+    }
+}
@@ -53,9 +53,12 @@ pub enum GtkWidgetType {
     Overlay,
 
     Rotor,
+    Gauge,
     CanvasDrawingArea,
     CanvasLayout,
-    CanvasRender
+    CanvasRender,
+
+    TreeView
 }
 
 ///
@@ -99,6 +102,18 @@ pub enum GtkWidgetAction {
     /// Controls the popup attributes of this widget
     Popup(WidgetPopup),
 
+    /// Sets the arc that a gauge widget is drawn along
+    Gauge(ui::Gauge),
+
+    /// Adds a CSS class to the style context of this widget, so it can be themed by a style sheet (the class is kept if the widget is put in an event box)
+    AddStyleClass(String),
+
+    /// Removes a CSS class previously added by `AddStyleClass`
+    RemoveStyleClass(String),
+
+    /// Sets the MIME types of the files that can be dropped onto this widget (types such as 'image/*' match any subtype)
+    AcceptDropTypes(Vec<String>),
+
     /// Deletes this widget (and any child widgets it may contain)
     Delete
 }
@@ -124,7 +139,10 @@ pub enum WidgetContent {
     Tooltip(Option<String>),
 
     /// Specifies a drawing to perform on this widget
-    Draw(Vec<canvas::Draw>)
+    Draw(Vec<canvas::Draw>),
+
+    /// Sets the nodes displayed by a tree view
+    SetTree(Vec<ui::TreeNode>)
 }
 
 impl From<WidgetContent> for GtkWidgetAction {
@@ -168,6 +186,9 @@ pub enum WidgetState {
     /// Sets whether or not this widget shows a badge next to it
     SetBadged(bool),
 
+    /// Sets whether or not the widgets contained by this widget are enabled
+    SetSubtreeEnabled(bool),
+
     /// Sets whether or not this widget is enabled
     SetEnabled(bool),
 
@@ -187,7 +208,10 @@ pub enum WidgetState {
     SetRangeMin(f64),
 
     /// Sets the maximum value for this widget
-    SetRangeMax(f64)
+    SetRangeMax(f64),
+
+    /// Sets the step that values of this widget snap to (0 for continuous values)
+    SetRangeStep(f64)
 }
 
 impl From<WidgetState> for GtkWidgetAction {
@@ -238,6 +262,12 @@ impl From<ui::Scroll> for GtkWidgetAction {
     }
 }
 
+impl From<ui::Gauge> for GtkWidgetAction {
+    fn from(item: ui::Gauge) -> GtkWidgetAction {
+        GtkWidgetAction::Gauge(item)
+    }
+}
+
 ///
 /// GTK actions that can be requested
 ///
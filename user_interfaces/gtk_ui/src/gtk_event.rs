@@ -1,6 +1,8 @@
 use super::gtk_action::*;
 use super::gtk_event_parameter::*;
 
+use std::path::{PathBuf};
+
 ///
 /// User interface events that can be generated by Gtk
 ///
@@ -16,5 +18,20 @@ pub enum GtkEvent {
     CloseWindow(WindowId),
 
     /// Registered event has occurred on a widget
-    Event(WidgetId, String, GtkEventParameter)
+    Event(WidgetId, String, GtkEventParameter),
+
+    /// One or more files were dropped onto a widget that requested the `DropFiles` event (only the files with an accepted MIME type are included)
+    DropFiles(WidgetId, String, Vec<GtkDroppedFile>)
+}
+
+///
+/// Describes a file that was dropped onto a widget
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct GtkDroppedFile {
+    /// The path to the file
+    pub path: PathBuf,
+
+    /// The MIME type of the file
+    pub mime_type: String
 }
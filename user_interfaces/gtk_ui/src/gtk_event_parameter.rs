@@ -46,7 +46,13 @@ pub enum GtkEventParameter {
     DragFinish((f64, f64), (f64, f64)),
 
     /// Virtual scroll region has moved (tuples are the x and y coordinates and the width and height of the grid)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// Scrolling container has moved or changed size
+    Scroll(ScrollState),
+
+    /// An arrow key was pressed in a tree view
+    TreeKey(KeyPress)
 }
 
 ///
@@ -74,7 +80,9 @@ impl From<GtkEventParameter> for ActionParameter {
             GtkEventParameter::DragStart(x, y)                              => ActionParameter::Drag(DragAction::Start, (x as f32, y as f32), (x as f32, y as f32)),
             GtkEventParameter::DragContinue((from_x, from_y), (to_x, to_y)) => ActionParameter::Drag(DragAction::Drag, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
             GtkEventParameter::DragFinish((from_x, from_y), (to_x, to_y))   => ActionParameter::Drag(DragAction::Finish, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
-            GtkEventParameter::VirtualScroll(top_left, size)                => ActionParameter::VirtualScroll(top_left, size)
+            GtkEventParameter::VirtualScroll(top_left, size)                => ActionParameter::VirtualScroll(top_left, size),
+            GtkEventParameter::Scroll(state)                                => ActionParameter::Scroll(state),
+            GtkEventParameter::TreeKey(key)                                 => ActionParameter::TreeKey(key)
         }
     }
 }
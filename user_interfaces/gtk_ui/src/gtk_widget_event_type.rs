@@ -65,8 +65,20 @@ pub enum GtkWidgetEventType {
     /// Performs virtual scrolling using a grid with the specified width and height
     VirtualScroll(f32, f32),
 
+    /// User has scrolled a scrolling container, or its size has changed
+    Scroll,
+
     /// User has interacted outside of this widget
-    Dismiss
+    Dismiss,
+
+    /// User has expanded a node in a tree view
+    ExpandNode,
+
+    /// User has collapsed a node in a tree view
+    CollapseNode,
+
+    /// User has dropped files onto the widget
+    DropFiles
 }
 
 impl From<PaintDevice> for GtkPaintDevice {
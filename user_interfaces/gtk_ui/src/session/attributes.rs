@@ -123,7 +123,11 @@ impl ToGtkActions for Control {
             Rotor               => New(GtkWidgetType::Rotor),
             TextBox             => New(GtkWidgetType::TextBox),
             CheckBox            => New(GtkWidgetType::CheckBox),
-            ComboBox            => New(GtkWidgetType::Generic)
+            ComboBox            => New(GtkWidgetType::Generic),
+            TreeView            => New(GtkWidgetType::TreeView),
+            Media               => New(GtkWidgetType::Generic),     // TODO: no media playback widget yet
+            CropImage           => New(GtkWidgetType::Generic),     // TODO: no crop widget yet
+            Gauge               => New(GtkWidgetType::Gauge)
         };
 
         // The widget class allows the style sheet to specifically target Flo widgets
@@ -141,6 +145,10 @@ impl ToGtkActions for Control {
             TextBox             => "flo-textbox",
             CheckBox            => "flo-checkbox",
             ComboBox            => "flo-combobox",
+            TreeView            => "flo-tree",
+            Media               => "flo-media",
+            CropImage           => "flo-crop-image",
+            Gauge               => "flo-gauge",
         };
 
         // Build into the 'create control' action
@@ -191,6 +199,36 @@ impl ToGtkActions for ControlAttribute {
             &Id(ref id)                             => vec![ WidgetContent::AddClass(id.clone()).into() ].into_actions(),
             &Action(ref _trigger, ref _action_name) => vec![],
             BindKey(_key, _cmd)                     => vec![],
+            KeyShortcut(_keys)                      => vec![],
+            Classes(classes)                        => classes.iter().map(|class_name| GtkWidgetAction::AddStyleClass(class_name.clone())).collect::<Vec<_>>().into_actions(),
+
+            BoundClasses(classes)                   => {
+                // Remember the classes from the last value of the property so they can be removed when it changes
+                let last_classes = Mutex::new(vec![]);
+
+                vec![ PropertyAction::from_property(classes.clone(), move |classes| {
+                    let new_classes         = classes.string().unwrap_or_default().split_whitespace().map(|class_name| class_name.to_string()).collect::<Vec<_>>();
+                    let mut last_classes    = last_classes.lock().unwrap();
+
+                    let actions = last_classes.iter()
+                        .filter(|class_name| !new_classes.contains(class_name))
+                        .map(|class_name| GtkWidgetAction::RemoveStyleClass(class_name.clone()))
+                        .chain(new_classes.iter().map(|class_name| GtkWidgetAction::AddStyleClass(class_name.clone())))
+                        .collect();
+
+                    *last_classes = new_classes;
+                    actions
+                }) ]
+            },
+            MediaAttr(_media)                       => vec![],
+            CropAttr(_crop)                         => vec![],
+            DragDropAttr(DragDrop::Target(types))   => vec![ GtkWidgetAction::AcceptDropTypes(types.clone()) ].into_actions(),
+            DragDropAttr(DragDrop::Source(_))       => vec![],
+            GaugeAttr(gauge)                        => vec![ GtkWidgetAction::Gauge(*gauge).into() ].into_actions(),
+            Units(_units)                           => vec![],
+            RangeStep(step)                         => vec![ WidgetState::SetRangeStep(*step as f64).into() ].into_actions(),
+            TreeAttr(Tree::Nodes(nodes))            => vec![ WidgetContent::SetTree(nodes.clone()).into() ].into_actions(),
+            TreeAttr(Tree::Bound(nodes))            => vec![ PropertyAction::from_property(nodes.clone(), |nodes| vec![ WidgetContent::SetTree(Tree::nodes_from_property_value(&nodes).unwrap_or_else(|| vec![])).into() ]) ],
 
             // TODO: canvas drawing instructions are needed for canvases that have been 'seen' before, but for entirely new canvases
             // there will be an initial update that will duplicate these actions (fortunately starting with a clear so it's not user
@@ -273,6 +311,7 @@ impl ToGtkActions for State {
                 PropertyAction::from_property(max.clone(), |max| vec![ WidgetState::SetRangeMax(max.to_f64().unwrap_or(0.0)).into() ])
             ],
             FocusPriority(ref priority) => vec![], /* TODO */
+            SubtreeEnabled(ref enabled) => vec![ PropertyAction::from_property(enabled.clone(), |value| vec![ WidgetState::SetSubtreeEnabled(value.to_bool().unwrap_or(true)).into() ]) ],
 
             Value(ref value)            => vec![ PropertyAction::from_property(value.clone(), |value| {
                 match value {
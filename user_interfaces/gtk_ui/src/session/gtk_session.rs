@@ -229,6 +229,16 @@ impl GtkSessionCore {
             Tick                                        => vec![ UiEvent::Tick ],
             Event(widget, event_name, parameter)        => self.controller_for_widget.get(&widget)
                 .map(|controller| vec![ UiEvent::Action((**controller).clone(), event_name, parameter.into()) ])
+                .unwrap_or(vec![]),
+
+            // Each dropped file generates its own drop action, with the MIME type of the file as the payload type
+            DropFiles(widget, event_name, files)        => self.controller_for_widget.get(&widget)
+                .map(|controller| files.into_iter()
+                    .map(|file| {
+                        let payload = DragPayload::new(&file.mime_type, PropertyValue::String(file.path.to_string_lossy().to_string()));
+                        UiEvent::Action((**controller).clone(), event_name.clone(), ActionParameter::DragPayload(payload))
+                    })
+                    .collect())
                 .unwrap_or(vec![])
         }
     }
@@ -245,7 +255,8 @@ impl GtkSessionCore {
             UpdateUi(ui_differences)                => self.update_ui(ui_differences),
             UpdateCanvas(canvas_differences)        => self.update_canvases(canvas_differences),
             UpdateViewModel(viewmodel_differences)  => self.update_viewmodel(viewmodel_differences),
-            UpdateCommands(_command_updates)        => vec![/* TODO */]
+            UpdateCommands(_command_updates)        => vec![/* TODO */],
+            EditUi(_ui_edits)                       => vec![/* Not generated: the core update stream isn't created with keyed edits */]
         }
     }
 
@@ -597,15 +608,26 @@ impl GtkSessionCore {
                     ActionEvent::Named(action_name) => {
                         match action {
                             Click                           => vec![ RequestEvent(GtkWidgetEventType::Click, action_name) ],
+                            SecondaryClick                  => vec![ /* TODO */ ],
                             Dismiss                         => vec![ RequestEvent(GtkWidgetEventType::Dismiss, action_name) ],
                             Paint(device)                   => vec![ RequestEvent(GtkWidgetEventType::Paint(device.into()), action_name) ],
                             Drag                            => vec![ RequestEvent(GtkWidgetEventType::Drag, action_name) ],
+                            DragStart                       => vec![ /* TODO */ ],
+                            DragOver                        => vec![ /* TODO */ ],
+                            Drop                            => vec![ RequestEvent(GtkWidgetEventType::DropFiles, action_name) ],
                             Resize                          => vec![ /* TODO */ ],
                             Focused                         => vec![ /* TODO */ ],
                             CancelEdit                      => vec![ /* TODO */ ],
+                            Compose                         => vec![ /* TODO */ ],
                             EditValue                       => vec![ RequestEvent(GtkWidgetEventType::EditValue, action_name) ],
                             SetValue                        => vec![ RequestEvent(GtkWidgetEventType::SetValue, action_name) ],
                             VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ],
+                            ExpandNode                      => vec![ RequestEvent(GtkWidgetEventType::ExpandNode, action_name) ],
+                            CollapseNode                    => vec![ RequestEvent(GtkWidgetEventType::CollapseNode, action_name) ],
+                            SetPlaying                      => vec![ /* TODO */ ],
+                            MediaEnded                      => vec![ /* TODO */ ],
+                            Crop                            => vec![ /* TODO */ ],
+                            Scroll                          => vec![ RequestEvent(GtkWidgetEventType::Scroll, action_name) ],
                             Command(_cmd)                   => vec![ /* TODO */ ]
                         }
                     }
@@ -1,8 +1,10 @@
 use super::drag::*;
+use super::drop_files::*;
 use super::click::*;
 use super::paint::*;
 use super::layout::*;
 use super::widget::*;
+use super::widget_data::*;
 use super::flo_layout::*;
 use super::custom_style::*;
 use super::super::gtk_action::*;
@@ -81,10 +83,14 @@ pub fn process_basic_widget_action<W: GtkUiWidget>(widget: &mut W, flo_gtk: &mut
         &Layout(ref layout)                         => process_basic_widget_layout(widget.id(), widget.get_underlying(), flo_gtk, layout),
         &Content(ref content)                       => process_basic_widget_content(widget, flo_gtk, content),
         &Appearance(ref appearance)                 => process_basic_widget_appearance(widget, flo_gtk, appearance),
-        &State(ref state)                           => process_basic_widget_state(widget, state),
+        &State(ref state)                           => process_basic_widget_state(widget, flo_gtk, state),
         &Font(ref font)                             => process_basic_widget_font(widget, flo_gtk, font),
         &Scroll(ref scroll)                         => process_basic_widget_scroll(widget.get_underlying(), flo_gtk, scroll),
         &Popup(ref _popup)                          => (),
+        &Gauge(_gauge)                              => (),
+        &AddStyleClass(ref class_name)              => { flo_gtk.widget_data().add_style_class(&*widget, class_name); },
+        &RemoveStyleClass(ref class_name)           => { flo_gtk.widget_data().remove_style_class(&*widget, class_name); },
+        &AcceptDropTypes(ref accepted_types)        => { DropFileActions::set_accepted_types(flo_gtk.widget_data(), flo_gtk.get_event_sink(), &*widget, accepted_types); },
 
         &Show                                       => { widget.get_underlying().show() },
         &New(_widget_type)                          => (),
@@ -164,6 +170,7 @@ pub fn process_basic_widget_layout<W: Clone+WidgetExt+IsA<gtk::Widget>>(id: Widg
 pub fn set_widget_parent<W: GtkUiWidget>(widget: &mut W, children: &Vec<WidgetId>, flo_gtk: &mut FloGtk) {
     // Fetch the widget information
     let widget_data     = flo_gtk.widget_data();
+    let child_ids       = children.clone();
     let children        = children.iter()
         .map(|child_id| widget_data.get_widget(*child_id))
         .filter(|child| !child.is_none())
@@ -171,6 +178,17 @@ pub fn set_widget_parent<W: GtkUiWidget>(widget: &mut W, children: &Vec<WidgetId
         .collect();
 
     widget.set_children(children);
+
+    // The children are disabled if this widget's subtree is disabled
+    let subtree_enabled = widget_data.get_widget_data_or_insert(widget.id(), || EnabledState::new())
+        .map(|enabled_state| {
+            let mut enabled_state       = enabled_state.borrow_mut();
+            enabled_state.children      = child_ids.clone();
+            enabled_state.subtree_enabled
+        })
+        .unwrap_or(true);
+
+    child_ids.iter().for_each(|child_id| set_parent_subtree_enabled(&widget_data, *child_id, subtree_enabled));
 }
 
 ///
@@ -183,6 +201,7 @@ pub fn process_basic_widget_content<W: GtkUiWidget>(widget: &mut W, flo_gtk: &mu
         &SetChildren(ref children)      => set_widget_parent(widget, children, flo_gtk),
         &SetText(ref _text)             => () /* Standard gtk widgets can't have text in them */,
         &Draw(ref _drawing)             => () /* Drawing requires support from the widget */,
+        &SetTree(ref _nodes)            => () /* Trees require support from the widget */,
         &Tooltip(ref tooltip)           => {
             let widget = widget.get_underlying();
             widget.set_tooltip_text(tooltip.as_ref().map(|text| text.as_str()));
@@ -223,10 +242,68 @@ pub fn process_basic_widget_appearance<W: GtkUiWidget>(widget: &W, flo_gtk: &mut
     }
 }
 
+///
+/// Tracks whether or not a widget is enabled
+///
+/// Gtk+ only has a single 'sensitive' flag for each widget, which has to combine the widget's own enabled state with
+/// the state of the subtree it's in. (Gtk+ makes the descendants of an insensitive widget insensitive too, so only the
+/// immediate children of a widget with a disabled subtree need to be updated)
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct EnabledState {
+    /// True if the widget itself is enabled
+    pub enabled: bool,
+
+    /// True if the children of this widget are enabled
+    pub subtree_enabled: bool,
+
+    /// False if the parent of this widget has disabled its subtree
+    pub parent_subtree_enabled: bool,
+
+    /// The children of this widget
+    pub children: Vec<WidgetId>
+}
+
+impl EnabledState {
+    ///
+    /// Creates the enabled state for a widget that hasn't been disabled
+    ///
+    pub fn new() -> EnabledState {
+        EnabledState {
+            enabled:                true,
+            subtree_enabled:        true,
+            parent_subtree_enabled: true,
+            children:               vec![]
+        }
+    }
+
+    ///
+    /// True if the widget with this state should be sensitive to input
+    ///
+    pub fn is_sensitive(&self) -> bool {
+        self.enabled && self.parent_subtree_enabled
+    }
+}
+
+///
+/// Updates whether or not the parent of a widget has disabled its subtree
+///
+fn set_parent_subtree_enabled(widget_data: &WidgetData, widget_id: WidgetId, parent_subtree_enabled: bool) {
+    let widget          = widget_data.get_widget(widget_id);
+    let enabled_state   = widget_data.get_widget_data_or_insert(widget_id, || EnabledState::new());
+
+    if let (Some(widget), Some(enabled_state)) = (widget, enabled_state) {
+        let mut enabled_state                   = enabled_state.borrow_mut();
+        enabled_state.parent_subtree_enabled    = parent_subtree_enabled;
+
+        widget.borrow().get_underlying().set_sensitive(enabled_state.is_sensitive());
+    }
+}
+
 ///
 /// Processes a basic state command for a widget being managed by FlowBetween
 ///
-pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetState) {
+pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, flo_gtk: &mut FloGtk, state: &WidgetState) {
     use self::WidgetState::*;
 
     match state {
@@ -244,8 +321,28 @@ pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetStat
             if badged { context.add_class("badged") } else { context.remove_class("badged") }
         },
         &SetEnabled(enabled)        => {
-            widget.get_underlying()
-                .set_sensitive(enabled)
+            // The widget stays insensitive if it's in a disabled subtree
+            let widget_data = flo_gtk.widget_data();
+
+            if let Some(enabled_state) = widget_data.get_widget_data_or_insert(widget.id(), || EnabledState::new()) {
+                let mut enabled_state   = enabled_state.borrow_mut();
+                enabled_state.enabled   = enabled;
+
+                widget.get_underlying().set_sensitive(enabled_state.is_sensitive());
+            }
+        },
+        &SetSubtreeEnabled(enabled) => {
+            // Update the children that have been assigned to this widget (children added later pick up the state in set_widget_parent)
+            let widget_data = flo_gtk.widget_data();
+            let children    = widget_data.get_widget_data_or_insert(widget.id(), || EnabledState::new())
+                .map(|enabled_state| {
+                    let mut enabled_state           = enabled_state.borrow_mut();
+                    enabled_state.subtree_enabled   = enabled;
+                    enabled_state.children.clone()
+                })
+                .unwrap_or_else(|| vec![]);
+
+            children.into_iter().for_each(|child_id| set_parent_subtree_enabled(&widget_data, child_id, enabled));
         },
 
         SetValueBool(_value)        => (),
@@ -253,7 +350,8 @@ pub fn process_basic_widget_state<W: GtkUiWidget>(widget: &W, state: &WidgetStat
         SetValueText(_value)        => (),
         SetValueFloat(_value)       => (),
         SetRangeMin(_from)          => (),
-        SetRangeMax(_to)            => ()
+        SetRangeMax(_to)            => (),
+        SetRangeStep(_step)         => ()
     }
 }
 
@@ -286,7 +384,8 @@ pub fn process_basic_widget_scroll<W: WidgetExt>(_widget: &W, _flo_gtk: &mut Flo
         &MinimumContentSize(_width, _height)    => (),
         &HorizontalScrollBar(ref _visibility)   => (),
         &VerticalScrollBar(ref _visibility)     => (),
-        &Fix(ref _axis)                         => ()
+        &Fix(ref _axis)                         => (),
+        &ScrollTo(_x, _y)                       => ()
     }
 }
 
@@ -312,6 +411,10 @@ pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut Flo
             DragActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
         },
 
-        VirtualScroll(_, _) | EditValue | SetValue | Dismiss => { }
+        DropFiles => {
+            DropFileActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
+        },
+
+        VirtualScroll(_, _) | Scroll | EditValue | SetValue | Dismiss | ExpandNode | CollapseNode => { }
     }
 }
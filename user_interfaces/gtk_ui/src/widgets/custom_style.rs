@@ -138,6 +138,40 @@ impl CustomStyle {
     }
 }
 
+///
+/// The CSS classes that have been added to a widget by `GtkWidgetAction::AddStyleClass`
+///
+/// These are tracked so they can be applied again if the widget is replaced (eg, by being put in an event box)
+///
+pub struct StyleClasses {
+    /// The classes that have been added, in the order they were added
+    classes: Vec<String>
+}
+
+impl StyleClasses {
+    ///
+    /// Creates an empty set of style classes
+    ///
+    pub fn new() -> StyleClasses {
+        StyleClasses {
+            classes: vec![]
+        }
+    }
+
+    ///
+    /// Adds the style classes to a widget
+    ///
+    pub fn apply(&self, widget: &gtk::Widget) {
+        let style_context = widget.get_style_context();
+
+        for class_name in self.classes.iter() {
+            if !style_context.has_class(class_name) {
+                style_context.add_class(class_name);
+            }
+        }
+    }
+}
+
 ///
 /// Trait used to provide a custom style for a particular widget
 ///
@@ -151,6 +185,16 @@ pub trait CustomStyleForWidget {
     /// Causes the custom style for a particular widget to be updated
     ///
     fn update_custom_style(&self, widget: &dyn GtkUiWidget);
+
+    ///
+    /// Adds a CSS class to a widget
+    ///
+    fn add_style_class(&self, widget: &dyn GtkUiWidget, class_name: &str);
+
+    ///
+    /// Removes a CSS class added by `add_style_class` from a widget
+    ///
+    fn remove_style_class(&self, widget: &dyn GtkUiWidget, class_name: &str);
 }
 
 impl CustomStyleForWidget for WidgetData {
@@ -179,5 +223,32 @@ impl CustomStyleForWidget for WidgetData {
         if let Some(existing_style) = self.get_widget_data::<CustomStyle>(widget.id()) {
             existing_style.borrow_mut().reload_if_needed();
         }
+
+        // Make sure that the style classes are still applied (the widget may have been replaced by the actions)
+        if let Some(style_classes) = self.get_widget_data::<StyleClasses>(widget.id()) {
+            style_classes.borrow().apply(widget.get_underlying());
+        }
+    }
+
+    fn add_style_class(&self, widget: &dyn GtkUiWidget, class_name: &str) {
+        let style_classes = self.get_widget_data_or_insert(widget.id(), || StyleClasses::new());
+
+        if let Some(style_classes) = style_classes {
+            let mut style_classes = style_classes.borrow_mut();
+
+            if !style_classes.classes.iter().any(|existing| existing == class_name) {
+                style_classes.classes.push(class_name.to_string());
+            }
+        }
+
+        widget.get_underlying().get_style_context().add_class(class_name);
+    }
+
+    fn remove_style_class(&self, widget: &dyn GtkUiWidget, class_name: &str) {
+        if let Some(style_classes) = self.get_widget_data::<StyleClasses>(widget.id()) {
+            style_classes.borrow_mut().classes.retain(|existing| existing != class_name);
+        }
+
+        widget.get_underlying().get_style_context().remove_class(class_name);
     }
 }
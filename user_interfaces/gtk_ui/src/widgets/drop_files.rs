@@ -0,0 +1,153 @@
+use super::widget::*;
+use super::widget_data::*;
+use super::super::gtk_event::*;
+use super::super::gtk_thread::*;
+
+use gtk;
+use gtk::prelude::*;
+use gdk;
+use gio;
+use glib;
+
+use std::rc::*;
+use std::cell::*;
+use std::path::{Path};
+
+///
+/// Provides the implementation of the 'drop files' action for Flo widgets
+///
+pub struct DropFileActions {
+    /// Where events for these actions should be sent
+    event_sink: GtkEventSink,
+
+    /// Names of the events to generate for this widget
+    event_names: Vec<String>,
+
+    /// The MIME types of the files that can be dropped on this widget
+    accepted_types: Vec<String>
+}
+
+impl DropFileActions {
+    ///
+    /// Creates a new drop file actions object
+    ///
+    fn new(event_sink: GtkEventSink) -> DropFileActions {
+        DropFileActions {
+            event_sink:     event_sink,
+            event_names:    vec![],
+            accepted_types: vec![]
+        }
+    }
+
+    ///
+    /// Wires a widget up to generate an event when files are dropped on it
+    ///
+    pub fn wire_widget<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W, event_name: String) {
+        let drop_actions = Self::for_widget(widget_data, event_sink, widget);
+        drop_actions.borrow_mut().event_names.push(event_name);
+    }
+
+    ///
+    /// Sets the MIME types of the files that can be dropped on a widget
+    ///
+    pub fn set_accepted_types<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W, accepted_types: &Vec<String>) {
+        let drop_actions = Self::for_widget(widget_data, event_sink, widget);
+        drop_actions.borrow_mut().accepted_types = accepted_types.clone();
+    }
+
+    ///
+    /// Retrieves the drop actions for a widget, making it into a drop target if it isn't one already
+    ///
+    fn for_widget<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W) -> Rc<RefCell<DropFileActions>> {
+        let widget_id       = widget.id();
+        let drop_actions    = widget_data.get_widget_data::<DropFileActions>(widget_id);
+
+        match drop_actions {
+            Some(existing_actions) => Rc::clone(&*existing_actions),
+
+            None => {
+                // Create some new wiring
+                widget_data.set_widget_data(widget_id, Self::new(event_sink));
+
+                // Connect events
+                let drop_actions = widget_data.get_widget_data::<DropFileActions>(widget_id).unwrap();
+                let drop_actions = Rc::clone(&*drop_actions);
+                Self::connect_events(widget.get_underlying(), widget_id, Rc::clone(&drop_actions));
+
+                drop_actions
+            }
+        }
+    }
+
+    ///
+    /// Registers a widget as a drop target for files and connects the events for a drop file actions object
+    ///
+    fn connect_events(widget: &gtk::Widget, widget_id: WidgetId, drop_actions: Rc<RefCell<Self>>) {
+        // Files are dragged as a list of URIs
+        let targets = vec![ gtk::TargetEntry::new("text/uri-list", gtk::TargetFlags::OTHER_APP, 0) ];
+        widget.drag_dest_set(gtk::DestDefaults::ALL, &targets, gdk::DragAction::COPY);
+
+        widget.connect_drag_data_received(move |_widget, _context, _x, _y, data, _info, _time| {
+            let drop_actions = drop_actions.borrow();
+
+            // Only local files with an accepted MIME type are sent with the event
+            let files = data.get_uris().into_iter()
+                .filter_map(|uri| glib::filename_from_uri(&uri).ok())
+                .map(|(path, _hostname)| GtkDroppedFile { mime_type: mime_type_for_path(&path), path: path })
+                .filter(|file| drop_actions.accepted_types.iter().any(|accepted_type| mime_type_matches(accepted_type, &file.mime_type)))
+                .collect::<Vec<_>>();
+
+            if files.len() > 0 {
+                drop_actions.event_names.iter().for_each(|name| {
+                    publish_event(&drop_actions.event_sink, GtkEvent::DropFiles(widget_id, name.clone(), files.clone()));
+                });
+            }
+        });
+    }
+}
+
+///
+/// Guesses the MIME type of a file from its name
+///
+fn mime_type_for_path(path: &Path) -> String {
+    let (content_type, _uncertain) = gio::content_type_guess(Some(path.to_string_lossy().as_ref()), &[]);
+
+    gio::content_type_get_mime_type(&content_type)
+        .map(|mime_type| mime_type.to_string())
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+///
+/// True if a MIME type matches an accepted type (which can be a pattern like 'image/*')
+///
+fn mime_type_matches(accepted_type: &str, mime_type: &str) -> bool {
+    if accepted_type == "*/*" {
+        true
+    } else if accepted_type.ends_with("/*") {
+        let prefix = &accepted_type[0..accepted_type.len()-1];
+        mime_type.len() > prefix.len() && mime_type.get(0..prefix.len()).map(|mime_prefix| mime_prefix.eq_ignore_ascii_case(prefix)).unwrap_or(false)
+    } else {
+        accepted_type.eq_ignore_ascii_case(mime_type)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exact_mime_types_match() {
+        assert!(mime_type_matches("image/png", "image/png"));
+        assert!(mime_type_matches("image/PNG", "image/png"));
+        assert!(!mime_type_matches("image/png", "image/jpeg"));
+    }
+
+    #[test]
+    fn wildcard_mime_types_match_subtypes() {
+        assert!(mime_type_matches("image/*", "image/png"));
+        assert!(mime_type_matches("image/*", "image/jpeg"));
+        assert!(!mime_type_matches("image/*", "text/plain"));
+        assert!(!mime_type_matches("image/*", "image/"));
+        assert!(mime_type_matches("*/*", "text/plain"));
+    }
+}
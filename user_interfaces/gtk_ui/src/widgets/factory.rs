@@ -5,11 +5,13 @@ use super::flo_popover_widget::*;
 use super::flo_label_widget::*;
 use super::flo_scale_widget::*;
 use super::flo_rotor_widget::*;
+use super::flo_gauge_widget::*;
 use super::flo_scroll_widget::*;
 use super::flo_canvas_widget::*;
 use super::flo_overlay_widget::*;
 use super::flo_textbox_widget::*;
 use super::flo_checkbox_widget::*;
+use super::flo_tree_view_widget::*;
 use super::flo_render_canvas_widget::*;
 use super::flo_canvas_layout_widget::*;
 use super::widget_data::*;
@@ -38,6 +40,7 @@ pub fn create_widget(id: WidgetId, widget_type: GtkWidgetType, widget_data: Rc<W
         CheckBox            => Box::new(FloCheckBoxWidget::new(id, gtk::CheckButton::new())),
         TextBox             => Box::new(FloTextBoxWidget::new(id, gtk::Entry::new())),
         Label               => Box::new(FloLabelWidget::new(id, gtk::Label::new(None))),
+        TreeView            => Box::new(FloTreeViewWidget::new(id, gtk::TreeView::new())),
         Popover             => Box::new(FloPopoverWidget::new(id, gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None), widget_data)),
 
         Overlay             => Box::new(FloOverlayWidget::new(id, gtk::Overlay::new(), gtk::Layout::new::<gtk::Adjustment, gtk::Adjustment>(None, None), widget_data)),
@@ -47,6 +50,7 @@ pub fn create_widget(id: WidgetId, widget_type: GtkWidgetType, widget_data: Rc<W
             Box::new(FloScrollWidget::new(id, gtk::ScrolledWindow::new(no_adjustment.as_ref(), no_adjustment.as_ref()), widget_data))
         },
         Rotor               => Box::new(FloRotorWidget::new(id, gtk::DrawingArea::new())),
+        Gauge               => Box::new(FloGaugeWidget::new(id, gtk::DrawingArea::new())),
         CanvasDrawingArea   => Box::new(FloDrawingWidget::new(id, gtk::DrawingArea::new(), widget_data)),
         CanvasLayout        => {
             let no_adjustment: Option<gtk::Adjustment> = None;
@@ -5,6 +5,7 @@ use super::basic_widget::*;
 use super::flo_overlay_widget::*;
 use super::super::gtk_action::*;
 use super::super::gtk_thread::*;
+use super::super::gtk_widget_event_type::*;
 
 use flo_ui;
 use flo_ui::*;
@@ -175,6 +176,14 @@ impl GtkUiWidget for FloBinWidget {
             // Deletions remove the bin widget and not the underlying one
             &Delete                 => { process_basic_widget_action(self, flo_gtk, action); },
 
+            // Style classes apply to the bin widget
+            &AddStyleClass(_)       |
+            &RemoveStyleClass(_)    => { process_basic_widget_action(self, flo_gtk, action); },
+
+            // Files are dropped onto the outermost widget
+            &AcceptDropTypes(_)     |
+            &RequestEvent(GtkWidgetEventType::DropFiles, _) => { process_basic_widget_action(self, flo_gtk, action); },
+
             // Everything else is either passed to the fixed widget or processed via the normal steps
             _                       => {
                 if let Some(ref mut fixed) = self.overlay {
@@ -31,7 +31,10 @@ struct DrawingCore {
     draw_pending:   bool,
 
     /// Widget data object
-    widget_data:    Rc<WidgetData>
+    widget_data:    Rc<WidgetData>,
+
+    /// The ID of the widget that this core belongs to
+    widget_id:      WidgetId
 }
 
 ///
@@ -54,22 +57,25 @@ impl FloDrawingWidget {
     ///
     pub fn new<W: Clone+Cast+IsA<gtk::Widget>>(widget_id: WidgetId, drawing_area: W, data: Rc<WidgetData>) -> FloDrawingWidget {
         // Create the data structures
-        let canvas      = Canvas::new();
-        let as_widget   = drawing_area.clone().upcast::<gtk::Widget>();
-        let pixbufs     = PixBufCanvas::new(CanvasViewport::minimal(), as_widget.get_scale_factor() as f64);
+        let canvas          = Canvas::new();
+        let as_widget       = drawing_area.clone().upcast::<gtk::Widget>();
+        let scale_factor    = as_widget.get_scale_factor();
+        let pixbufs         = PixBufCanvas::new(CanvasViewport::minimal(), scale_factor as f64);
 
-        let core        = DrawingCore {
+        let core            = DrawingCore {
             canvas:         canvas,
             pixbufs:        pixbufs,
-            scale_factor:   1,
+            scale_factor:   scale_factor,
             need_redraw:    true,
             draw_pending:   false,
-            widget_data:    data
+            widget_data:    data,
+            widget_id:      widget_id
         };
-        let core        = Rc::new(RefCell::new(core));
+        let core            = Rc::new(RefCell::new(core));
 
         // Wire events
         Self::connect_size_allocate(&as_widget, Rc::clone(&core));
+        Self::connect_scale_factor(&as_widget, Rc::clone(&core));
         Self::connect_draw(&as_widget, Rc::clone(&core));
 
         // Generate the widget
@@ -99,6 +105,20 @@ impl FloDrawingWidget {
         });
     }
 
+    ///
+    /// Deals with the widget moving to a monitor with a different scale factor
+    ///
+    fn connect_scale_factor(drawing_area: &gtk::Widget, core: Rc<RefCell<DrawingCore>>) {
+        drawing_area.connect_property_scale_factor_notify(move |widget| {
+            let mut core = core.borrow_mut();
+
+            // The pixbufs are re-created at the new resolution when the widget is next drawn
+            if widget.get_scale_factor() != core.scale_factor {
+                Self::queue_draw(&mut *core, widget);
+            }
+        });
+    }
+
     ///
     /// Redraws the core from the canvas
     ///
@@ -124,15 +144,15 @@ impl FloDrawingWidget {
         // Get the allocated viewport
         let allocation          = widget.get_allocation();
         let current_viewport    = Self::get_viewport(widget, &allocation);
+        let scale_factor        = widget.get_scale_factor();
 
-        // A redraw is required if the size is different from the last time we drew the pixbufs
-        if existing_viewport != current_viewport {
+        // A redraw is required if the size or the resolution is different from the last time we drew the pixbufs
+        if existing_viewport != current_viewport || scale_factor != core.scale_factor {
             // Update the viewport
             core.pixbufs.set_viewport(current_viewport);
             core.need_redraw = true;
 
             // Store the scaling factor for the widget
-            let scale_factor    = widget.get_scale_factor();
             core.scale_factor   = scale_factor;
             core.pixbufs.set_pixel_scale(scale_factor as f64);
         }
@@ -141,6 +161,9 @@ impl FloDrawingWidget {
         if core.need_redraw {
             Self::redraw(&mut core);
             core.need_redraw = false;
+
+            // Pointer coordinates stay in logical pixels, so the translation matrix changes along with the viewport and the scale factor
+            Self::update_translation_matrix(&*core);
         }
 
         context.save();
@@ -187,7 +210,7 @@ impl FloDrawingWidget {
     ///
     /// (Paint actions in particular should specify canvas coordinates)
     ///
-    fn update_translation_matrix(&self, core: &DrawingCore) {
+    fn update_translation_matrix(core: &DrawingCore) {
         // Get the transformation matrix: we store this as the translation matrix to use for paint events
         let canvas_to_widget    = core.pixbufs.get_matrix();
         let mut widget_to_canvas = canvas_to_widget;
@@ -211,7 +234,7 @@ impl FloDrawingWidget {
         }
 
         // Store the transformation matrix for use with generating coordinates for paint events
-        core.widget_data.set_widget_data(self.widget_id, widget_to_canvas);
+        core.widget_data.set_widget_data(core.widget_id, widget_to_canvas);
     }
 
     ///
@@ -235,7 +258,7 @@ impl FloDrawingWidget {
         core.canvas.write(actions);
 
         // Make sure that the translation matrix is up to date
-        self.update_translation_matrix(&*core);
+        Self::update_translation_matrix(&*core);
 
         // Note that a redraw is needed
         Self::queue_draw(&mut *core, &self.as_widget);
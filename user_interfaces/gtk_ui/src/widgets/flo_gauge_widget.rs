@@ -0,0 +1,146 @@
+use super::widget::*;
+use super::basic_widget::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_action::*;
+
+use flo_ui::*;
+
+use gtk;
+use gtk::prelude::*;
+use cairo;
+
+use std::ops::Range;
+use std::rc::*;
+use std::cell::*;
+use std::f64;
+
+struct GaugeData {
+    /// Value the gauge is displaying
+    value: f64,
+
+    /// Range of values displayed by this gauge
+    range: Range<f64>,
+
+    /// The arc that the gauge is drawn along
+    arc: GaugeArc
+}
+
+///
+/// The gauge widget displays a value as a partially filled-in arc
+///
+pub struct FloGaugeWidget {
+    id: WidgetId,
+
+    /// The widget (a drawing area)
+    widget: gtk::Widget,
+
+    /// Data that's shared with the drawing handler for this widget
+    data: Rc<RefCell<GaugeData>>
+}
+
+impl FloGaugeWidget {
+    ///
+    /// Creates a new gauge control
+    ///
+    pub fn new<W: Clone+Cast+IsA<gtk::Widget>+IsA<gtk::DrawingArea>>(id: WidgetId, widget: W) -> FloGaugeWidget {
+        // Create the data
+        let data = GaugeData {
+            value:  0.0,
+            range:  0.0..1.0,
+            arc:    GaugeArc::default()
+        };
+        let data = Rc::new(RefCell::new(data));
+
+        // Register events
+        Self::connect_drawing(&widget.clone().upcast::<gtk::Widget>(), Rc::clone(&data));
+
+        // Generate the final widget
+        FloGaugeWidget {
+            id:     id,
+            widget: widget.upcast::<gtk::Widget>(),
+            data:   data
+        }
+    }
+
+    ///
+    /// Connects the drawing event for a gauge widget
+    ///
+    fn connect_drawing(widget: &gtk::Widget, data: Rc<RefCell<GaugeData>>) {
+        widget.connect_draw(move |widget, context| {
+            let data        = data.borrow();
+            let allocation  = widget.get_allocation();
+
+            // The gauge is a circle in the center of the widget
+            let width       = allocation.width as f64;
+            let height      = allocation.height as f64;
+            let line_width  = (width.min(height) * 0.08).max(1.0);
+            let radius      = (width.min(height) - line_width) / 2.0;
+            let to_radians  = |angle: f64| angle / 180.0 * f64::consts::PI;
+
+            let start_angle = data.arc.start_angle as f64;
+            let end_angle   = data.arc.end_angle(data.value, data.range.start, data.range.end);
+
+            context.save();
+            context.set_line_width(line_width);
+            context.set_line_cap(cairo::LineCap::Round);
+
+            // Draw the track
+            let style_context   = widget.get_style_context();
+            let color           = style_context.get_color(style_context.get_state());
+
+            context.set_source_rgba(color.red, color.green, color.blue, color.alpha * 0.15);
+            context.new_path();
+            context.arc(width/2.0, height/2.0, radius, to_radians(start_angle), to_radians(start_angle + data.arc.sweep_angle as f64));
+            context.stroke();
+
+            // Draw the value on top of it
+            if end_angle != start_angle {
+                context.set_source_rgba(color.red, color.green, color.blue, color.alpha);
+                context.new_path();
+
+                if end_angle > start_angle {
+                    context.arc(width/2.0, height/2.0, radius, to_radians(start_angle), to_radians(end_angle));
+                } else {
+                    context.arc_negative(width/2.0, height/2.0, radius, to_radians(start_angle), to_radians(end_angle));
+                }
+
+                context.stroke();
+            }
+
+            context.restore();
+
+            Inhibit(true)
+        });
+    }
+}
+
+impl GtkUiWidget for FloGaugeWidget {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use self::GtkWidgetAction::*;
+        use self::WidgetState::*;
+
+        match action {
+            &State(SetValueFloat(value))                => { self.data.borrow_mut().value = value; self.widget.queue_draw(); },
+            &State(SetValueInt(value))                  => { self.data.borrow_mut().value = value as f64; self.widget.queue_draw(); },
+            &State(SetRangeMin(min_value))              => { self.data.borrow_mut().range.start = min_value; self.widget.queue_draw(); },
+            &State(SetRangeMax(max_value))              => { self.data.borrow_mut().range.end = max_value; self.widget.queue_draw(); },
+
+            &Gauge(flo_ui::Gauge::StartAngle(angle))    => { self.data.borrow_mut().arc.start_angle = angle; self.widget.queue_draw(); },
+            &Gauge(flo_ui::Gauge::SweepAngle(angle))    => { self.data.borrow_mut().arc.sweep_angle = angle; self.widget.queue_draw(); },
+
+            other_action                                => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    fn set_children(&mut self, _children: Vec<Rc<RefCell<dyn GtkUiWidget>>>) {
+        // Gauges have no child widgets
+    }
+
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.widget
+    }
+}
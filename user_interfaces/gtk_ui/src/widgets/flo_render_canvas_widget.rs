@@ -82,6 +82,7 @@ impl FloRenderCanvasWidget {
         // Initialise on realize
         Self::on_realize(&mut as_glarea, Rc::clone(&core));
         Self::on_render(&mut as_glarea, Rc::clone(&core));
+        Self::on_scale_factor_changed(&mut as_glarea, Rc::clone(&core));
 
         FloRenderCanvasWidget {
             id:         id,
@@ -107,6 +108,20 @@ impl FloRenderCanvasWidget {
         });
     }
 
+    ///
+    /// Installs the callback that re-renders the GLArea at the new resolution when it moves to a monitor with a different scale factor
+    ///
+    fn on_scale_factor_changed(glarea: &mut gtk::GLArea, core: Rc<RefCell<FloRenderWidgetCore>>) {
+        glarea.connect_property_scale_factor_notify(move |gl_widget| {
+            let core = core.borrow();
+
+            // The scale is read again when rendering, which will also update the transform used for pointer events
+            if gl_widget.get_scale_factor() as f32 != core.scale {
+                gl_widget.queue_render();
+            }
+        });
+    }
+
     ///
     /// Installs the callback that deals with rendering the GLArea
     ///
@@ -256,6 +271,11 @@ impl FloRenderWidgetCore {
         let Transform2D([a, b, _c])     = active_transform;
         let cairo_matrix                = cairo::Matrix::new(a[0] as f64, b[0] as f64, a[1] as f64, b[1] as f64, a[2] as f64, b[2] as f64);
 
+        // The transform is for device pixels but pointer events are in logical pixels, so they need to be multiplied by the scale factor first
+        let mut scale_matrix            = cairo::Matrix::identity();
+        scale_matrix.scale(self.scale as f64, self.scale as f64);
+        let cairo_matrix                = cairo::Matrix::multiply(&scale_matrix, &cairo_matrix);
+
         self.widget_data.set_widget_data(self.widget_id, cairo_matrix);
     }
 }
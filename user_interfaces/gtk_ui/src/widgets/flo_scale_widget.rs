@@ -32,7 +32,10 @@ pub struct FloScaleWidget {
     widget: gtk::Widget,
 
     /// Flag that indicates if the user is pressing a mouse button (ie, dragging the scale)
-    button_pressed: Rc<RefCell<bool>>
+    button_pressed: Rc<RefCell<bool>>,
+
+    /// The step that values are snapped to (0 for continuous values)
+    step: Rc<RefCell<f64>>
 }
 
 impl FloScaleWidget {
@@ -40,9 +43,11 @@ impl FloScaleWidget {
     /// Creates a new scale widget
     ///
     pub fn new(id: WidgetId, scale: gtk::Scale) -> FloScaleWidget {
-        let button_pressed = Rc::new(RefCell::new(false));
+        let button_pressed  = Rc::new(RefCell::new(false));
+        let step            = Rc::new(RefCell::new(0.0));
 
         Self::connect_button_events(&scale, Rc::clone(&button_pressed));
+        Self::connect_step_snapping(&scale, Rc::clone(&step));
 
         FloScaleWidget {
             min:            0.0,
@@ -50,10 +55,31 @@ impl FloScaleWidget {
             id:             id,
             widget:         scale.clone().upcast::<gtk::Widget>(),
             scale:          scale,
-            button_pressed: button_pressed
+            button_pressed: button_pressed,
+            step:           step
         }
     }
 
+    ///
+    /// Snaps the values the user picks to the nearest step from the start of the range
+    ///
+    fn connect_step_snapping(scale: &gtk::Scale, step: Rc<RefCell<f64>>) {
+        scale.connect_change_value(move |scale, _scroll_type, new_value| {
+            let step = *step.borrow();
+
+            if step > 0.0 {
+                let lower   = scale.get_adjustment().get_lower();
+                let snapped = lower + ((new_value - lower) / step).round() * step;
+
+                scale.set_value(snapped);
+                Inhibit(true)
+            } else {
+                // Continuous scales use the value as it is
+                Inhibit(false)
+            }
+        });
+    }
+
     ///
     /// Hooks up the button pressed event
     ///
@@ -99,6 +125,10 @@ impl GtkUiWidget for FloScaleWidget {
                 self.max = max_value as f64;
                 self.scale.set_range(self.min.min(self.max), self.max.max(self.min));
             },
+            &State(SetRangeStep(step))                  => {
+                *self.step.borrow_mut() = step;
+                if step > 0.0 { self.scale.set_increments(step, step); }
+            },
 
             &RequestEvent(SetValue, ref event_name_ref)     => {
                 // Set events are value changes that occur while the mouse button has been released
@@ -243,6 +243,68 @@ impl FloScrollWidget {
         self.connect_virtual_scroll_on_adjust(self.layout.get_hadjustment().unwrap(), Rc::clone(&scroll_state), sink.clone(), action_name.clone(), width, height);
         self.connect_virtual_scroll_on_adjust(self.layout.get_vadjustment().unwrap(), Rc::clone(&scroll_state), sink, action_name, width, height);
     }
+
+    ///
+    /// Scrolls the content so that the specified position is at the top-left of the widget
+    ///
+    fn scroll_to(&self, x: f32, y: f32) {
+        let h_adjust    = self.layout.get_hadjustment().unwrap();
+        let v_adjust    = self.layout.get_vadjustment().unwrap();
+
+        // The adjustments clamp the values so we can't scroll past the end of the content. Nothing happens if the
+        // position is unchanged (which is the case when the position is being set to where the user scrolled to)
+        if h_adjust.get_value() != x as f64 { h_adjust.set_value(x as f64); }
+        if v_adjust.get_value() != y as f64 { v_adjust.set_value(y as f64); }
+    }
+
+    ///
+    /// Sends a scroll event describing the current position and size of the widget
+    ///
+    fn generate_scroll_event(widget_id: WidgetId, sink: &mut GtkEventSink, action_name: &str, layout: &gtk::Layout) {
+        let h_adjust    = layout.get_hadjustment().unwrap();
+        let v_adjust    = layout.get_vadjustment().unwrap();
+
+        let state       = ScrollState {
+            position:       (h_adjust.get_value() as f32, v_adjust.get_value() as f32),
+            content_size:   (h_adjust.get_upper() as f32, v_adjust.get_upper() as f32),
+            viewport_size:  (h_adjust.get_page_size() as f32, v_adjust.get_page_size() as f32)
+        };
+
+        publish_event(sink, GtkEvent::Event(widget_id, action_name.to_string(), GtkEventParameter::Scroll(state)));
+    }
+
+    ///
+    /// Begins sending scroll events whenever the widget is scrolled or resized
+    ///
+    fn start_scroll_events(&self, sink: GtkEventSink, action_name: String) {
+        let mut sink = sink;
+
+        // Generate the initial event
+        Self::generate_scroll_event(self.id, &mut sink, &action_name, &self.layout);
+
+        // The adjustments change their value when the widget is scrolled, and change their bounds when it's resized
+        for adjustment in vec![self.layout.get_hadjustment().unwrap(), self.layout.get_vadjustment().unwrap()] {
+            let widget_id   = self.id;
+
+            let weak_layout = self.layout.clone().downgrade();
+            let value_sink  = RefCell::new(sink.clone());
+            let value_name  = action_name.clone();
+            adjustment.connect_value_changed(move |_| {
+                if let Some(layout) = weak_layout.upgrade() {
+                    Self::generate_scroll_event(widget_id, &mut *value_sink.borrow_mut(), &value_name, &layout);
+                }
+            });
+
+            let weak_layout = self.layout.clone().downgrade();
+            let size_sink   = RefCell::new(sink.clone());
+            let size_name   = action_name.clone();
+            adjustment.connect_changed(move |_| {
+                if let Some(layout) = weak_layout.upgrade() {
+                    Self::generate_scroll_event(widget_id, &mut *size_sink.borrow_mut(), &size_name, &layout);
+                }
+            });
+        }
+    }
 }
 
 impl GtkUiWidget for FloScrollWidget {
@@ -264,6 +326,7 @@ impl GtkUiWidget for FloScrollWidget {
             },
             &Scroll(HorizontalScrollBar(visibility))    => { self.h_policy = Self::policy_for_visibility(visibility); self.update_policy(); },
             &Scroll(VerticalScrollBar(visibility))      => { self.v_policy = Self::policy_for_visibility(visibility); self.update_policy(); },
+            &Scroll(ScrollTo(x, y))                     => { self.scroll_to(x, y); },
 
             // Content actions are handled by the fixed widget
             &Content(SetText(_))                        => { self.fixed_widget.borrow_mut().process(flo_gtk, action); },
@@ -271,6 +334,7 @@ impl GtkUiWidget for FloScrollWidget {
 
             // This can generate virtual scroll events
             &RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), ref name) => self.start_virtual_scrolling(flo_gtk.get_event_sink(), name.clone(), width, height),
+            &RequestEvent(GtkWidgetEventType::Scroll, ref name)                       => self.start_scroll_events(flo_gtk.get_event_sink(), name.clone()),
 
             // All other actions are basic actions
             other_action                                => { process_basic_widget_action(self, flo_gtk, other_action); }
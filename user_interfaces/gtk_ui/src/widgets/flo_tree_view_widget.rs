@@ -0,0 +1,258 @@
+use super::basic_widget::*;
+use super::super::widgets::*;
+use super::super::gtk_event::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_action::*;
+use super::super::gtk_event_parameter::*;
+use super::super::gtk_widget_event_type::*;
+
+use flo_ui::*;
+
+use gtk;
+use gdk;
+use gtk::prelude::*;
+
+use std::rc::*;
+use std::cell::*;
+
+/// Column in the tree store containing the ID of each node
+const ID_COLUMN: u32 = 0;
+
+/// Column in the tree store containing the label of each node
+const LABEL_COLUMN: u32 = 1;
+
+///
+/// Implements behaviour for the tree view widget
+///
+pub struct FloTreeViewWidget {
+    /// The ID of this widget
+    id: WidgetId,
+
+    /// The tree view widget
+    widget: gtk::TreeView,
+
+    /// The store containing the nodes displayed in the tree
+    store: gtk::TreeStore,
+
+    /// The tree view again, but cast to a widget
+    as_widget: gtk::Widget,
+
+    /// The ID of the node that should be selected
+    selected: Option<String>,
+
+    /// Set to true while the tree is being updated from the controller (so we don't generate events for those changes)
+    updating: Rc<Cell<bool>>
+}
+
+impl FloTreeViewWidget {
+    ///
+    /// Creates a new tree view widget
+    ///
+    pub fn new<W: Clone+Cast+IsA<gtk::TreeView>+IsA<gtk::Widget>>(id: WidgetId, tree_view: W) -> FloTreeViewWidget {
+        let tree_view   = tree_view.upcast::<gtk::TreeView>();
+        let store       = gtk::TreeStore::new(&[String::static_type(), String::static_type()]);
+
+        // Single column displaying the label for each node
+        let column      = gtk::TreeViewColumn::new();
+        let cell        = gtk::CellRendererText::new();
+        column.pack_start(&cell, true);
+        column.add_attribute(&cell, "text", LABEL_COLUMN as i32);
+
+        tree_view.append_column(&column);
+        tree_view.set_headers_visible(false);
+        tree_view.set_model(Some(&store));
+        tree_view.set_can_focus(true);
+
+        FloTreeViewWidget {
+            id:             id,
+            widget:         tree_view.clone(),
+            store:          store,
+            as_widget:      tree_view.clone().upcast::<gtk::Widget>(),
+            selected:       None,
+            updating:       Rc::new(Cell::new(false))
+        }
+    }
+
+    ///
+    /// Replaces the content of the tree with the specified set of nodes
+    ///
+    fn set_tree(&mut self, nodes: &Vec<TreeNode>) {
+        self.updating.set(true);
+
+        self.store.clear();
+
+        // Add the nodes to the store (iteratively, to cope with very deep trees)
+        let mut to_add      = nodes.iter().rev().map(|node| (None, node)).collect::<Vec<_>>();
+        let mut to_expand   = vec![];
+
+        while let Some((parent, node)) = to_add.pop() {
+            let iter = self.store.insert_with_values(parent.as_ref(), None, &[ID_COLUMN, LABEL_COLUMN], &[&node.id, &node.label]);
+
+            match &node.children {
+                TreeChildren::Leaf              => { }
+                TreeChildren::Loaded(children)  => { to_add.extend(children.iter().rev().map(|child| (Some(iter.clone()), child))); }
+
+                // Lazy nodes get a placeholder child so Gtk+ will show an expander for them
+                TreeChildren::NotLoaded         => { self.store.insert_with_values(Some(&iter), None, &[ID_COLUMN, LABEL_COLUMN], &[&"", &"..."]); }
+            }
+
+            if node.expanded {
+                to_expand.push(iter);
+            }
+        }
+
+        // Expand the nodes that should be expanded
+        for iter in to_expand {
+            if let Some(path) = self.store.get_path(&iter) {
+                self.widget.expand_row(&path, false);
+            }
+        }
+
+        // Restore the selection
+        self.updating.set(false);
+        let selected = self.selected.clone();
+        self.set_selected(selected);
+    }
+
+    ///
+    /// Changes the node that is selected in the tree view
+    ///
+    fn set_selected(&mut self, selected: Option<String>) {
+        self.updating.set(true);
+
+        let selection = self.widget.get_selection();
+        selection.unselect_all();
+
+        if let Some(selected_id) = &selected {
+            self.store.foreach(|model, path, iter| {
+                let id = model.get_value(iter, ID_COLUMN as i32).get::<String>().ok().and_then(|id| id);
+
+                if id.as_ref() == Some(selected_id) {
+                    selection.select_path(path);
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+
+        self.selected = selected;
+        self.updating.set(false);
+    }
+
+    ///
+    /// Sends an event containing the ID of a node when a row in the tree is expanded or collapsed
+    ///
+    fn wire_row_event(&self, flo_gtk: &mut FloGtk, event_type: GtkWidgetEventType, event_name: &String) {
+        let id          = self.id;
+        let sink        = flo_gtk.get_event_sink();
+        let event_name  = event_name.clone();
+        let updating    = Rc::clone(&self.updating);
+
+        let send_event = move |tree_view: &gtk::TreeView, iter: &gtk::TreeIter, _path: &gtk::TreePath| {
+            if updating.get() { return; }
+
+            let node_id = tree_view.get_model()
+                .and_then(|model| model.get_value(iter, ID_COLUMN as i32).get::<String>().ok())
+                .and_then(|id| id);
+
+            if let Some(node_id) = node_id {
+                publish_event(&sink, GtkEvent::Event(id, event_name.clone(), GtkEventParameter::NewText(node_id)));
+            }
+        };
+
+        match event_type {
+            GtkWidgetEventType::ExpandNode      => { self.widget.connect_row_expanded(send_event); }
+            GtkWidgetEventType::CollapseNode    => { self.widget.connect_row_collapsed(send_event); }
+            _                                   => { }
+        }
+    }
+}
+
+impl GtkUiWidget for FloTreeViewWidget {
+    ///
+    /// Retrieves the ID assigned to this widget
+    ///
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    ///
+    /// Processes an action for this widget
+    ///
+    fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
+        use self::GtkWidgetAction::*;
+
+        match action {
+            Content(WidgetContent::SetTree(nodes))  => { self.set_tree(nodes); }
+
+            // The value of a tree view is the ID of the selected node
+            State(WidgetState::SetValueText(id))    => { self.set_selected(Some(id.clone())); }
+
+            // Changing the selection generates a set value event
+            RequestEvent(GtkWidgetEventType::SetValue, event_name) => {
+                let id          = self.id;
+                let sink        = flo_gtk.get_event_sink();
+                let event_name  = event_name.clone();
+                let updating    = Rc::clone(&self.updating);
+
+                self.widget.get_selection().connect_changed(move |selection| {
+                    if updating.get() { return; }
+
+                    let node_id = selection.get_selected()
+                        .and_then(|(model, iter)| model.get_value(&iter, ID_COLUMN as i32).get::<String>().ok())
+                        .and_then(|id| id);
+
+                    if let Some(node_id) = node_id {
+                        if node_id != "" {
+                            publish_event(&sink, GtkEvent::Event(id, event_name.clone(), GtkEventParameter::NewText(node_id)));
+                        }
+                    }
+                });
+
+                // Arrow keys are sent to the session, which decides how they change the selection
+                let sink        = flo_gtk.get_event_sink();
+                let event_name  = event_name.clone();
+
+                self.widget.connect_key_press_event(move |_, event| {
+                    use gdk::keys::constants as key;
+
+                    let tree_key = match event.get_keyval() {
+                        key::Up     => Some(KeyPress::KeyUp),
+                        key::Down   => Some(KeyPress::KeyDown),
+                        key::Left   => Some(KeyPress::KeyLeft),
+                        key::Right  => Some(KeyPress::KeyRight),
+                        _           => None
+                    };
+
+                    if let Some(tree_key) = tree_key {
+                        publish_event(&sink, GtkEvent::Event(id, event_name.clone(), GtkEventParameter::TreeKey(tree_key)));
+                        Inhibit(true)
+                    } else {
+                        Inhibit(false)
+                    }
+                });
+            },
+
+            RequestEvent(GtkWidgetEventType::ExpandNode, event_name)    => { self.wire_row_event(flo_gtk, GtkWidgetEventType::ExpandNode, event_name); },
+            RequestEvent(GtkWidgetEventType::CollapseNode, event_name)  => { self.wire_row_event(flo_gtk, GtkWidgetEventType::CollapseNode, event_name); },
+
+            // Standard behaviour for all other actions
+            other_action => { process_basic_widget_action(self, flo_gtk, other_action); }
+        }
+    }
+
+    ///
+    /// Sets the children of this widget
+    ///
+    fn set_children(&mut self, _children: Vec<Rc<RefCell<dyn GtkUiWidget>>>) {
+        // Tree views display their nodes rather than child controls
+    }
+
+    ///
+    /// Retrieves the underlying widget for this UI widget
+    ///
+    fn get_underlying<'a>(&'a self) -> &'a gtk::Widget {
+        &self.as_widget
+    }
+}
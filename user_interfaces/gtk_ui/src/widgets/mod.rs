@@ -8,11 +8,13 @@ pub mod flo_fixed_widget;
 pub mod flo_popover_widget;
 pub mod flo_scale_widget;
 pub mod flo_rotor_widget;
+pub mod flo_gauge_widget;
 pub mod flo_scroll_widget;
 pub mod flo_canvas_widget;
 pub mod flo_overlay_widget;
 pub mod flo_textbox_widget;
 pub mod flo_checkbox_widget;
+pub mod flo_tree_view_widget;
 pub mod flo_render_canvas_widget;
 pub mod flo_canvas_layout_widget;
 pub mod proxy_widget;
@@ -25,6 +27,7 @@ mod factory;
 mod image;
 mod click;
 mod drag;
+mod drop_files;
 mod paint;
 mod events;
 mod scroll_size;
@@ -91,6 +91,14 @@ impl<Widget> GtkUiWidget for ProxyWidget<Widget> {
             // Deletions remove the proxy widget and not the underlying one
             &Delete                 => { process_basic_widget_action(self, flo_gtk, action); },
 
+            // Style classes apply to the outermost widget
+            &AddStyleClass(_)       |
+            &RemoveStyleClass(_)    => { process_basic_widget_action(self, flo_gtk, action); },
+
+            // Files are dropped onto the outermost widget
+            &AcceptDropTypes(_)     |
+            &RequestEvent(GtkWidgetEventType::DropFiles, _) => { process_basic_widget_action(self, flo_gtk, action); },
+
             // Everything else is processed like the proxy doesn't exist
             _                       => { self.underlying_widget.borrow_mut().process(flo_gtk, action); }
         }
@@ -27,4 +27,18 @@ impl CanvasUpdate {
             updates:        updates
         }
     }
+
+    ///
+    /// Appends the updates from another canvas update to this one, if it is for the same canvas
+    ///
+    /// The update is returned unchanged if it's for a different canvas.
+    ///
+    pub (crate) fn append(&mut self, update: CanvasUpdate) -> Result<(), CanvasUpdate> {
+        if self.controller == update.controller && self.canvas_name == update.canvas_name {
+            self.updates.push_str(&update.updates);
+            Ok(())
+        } else {
+            Err(update)
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use ui::{ActionParameter, KeyPress};
+use ui::{ActionParameter, DragAction, KeyPress};
+
+use std::collections::{HashSet};
 
 ///
 /// Represents details of an event from the browser side
@@ -45,3 +47,96 @@ pub enum Event {
     ///
     Tick
 }
+
+///
+/// Removes the drag events from a batch of events that are followed by another drag event for the same action
+///
+/// Only the latest position of a drag is processed when several arrive in a single batch. The other events (including
+/// the events that start and finish a drag) are all kept, in the order they were sent.
+///
+pub fn coalesce_events(events: Vec<Event>) -> Vec<Event> {
+    // Work backwards so we know whether or not the next event for each action is a drag
+    let mut next_is_drag    = HashSet::new();
+    let mut result          = Vec::with_capacity(events.len());
+
+    for event in events.into_iter().rev() {
+        match &event {
+            Event::Action(controller_path, action_name, ActionParameter::Drag(DragAction::Drag, _, _)) => {
+                if !next_is_drag.insert((controller_path.clone(), action_name.clone())) {
+                    // A later drag event replaces this one
+                    continue;
+                }
+            }
+
+            Event::Action(controller_path, action_name, _) => {
+                next_is_drag.remove(&(controller_path.clone(), action_name.clone()));
+            }
+
+            _ => { }
+        }
+
+        result.push(event);
+    }
+
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ui::{MouseButton};
+
+    fn drag(action: DragAction, x: f32) -> Event {
+        Event::Action(vec!["canvas".to_string()], "Drag".to_string(), ActionParameter::Drag(action, (0.0, 0.0), (x, 0.0)))
+    }
+
+    fn drag_position(event: &Event) -> Option<(DragAction, f32)> {
+        match event {
+            Event::Action(_, _, ActionParameter::Drag(action, _, (x, _)))   => Some((*action, *x)),
+            _                                                               => None
+        }
+    }
+
+    #[test]
+    fn only_latest_drag_is_kept() {
+        let events = coalesce_events(vec![drag(DragAction::Start, 0.0), drag(DragAction::Drag, 1.0), drag(DragAction::Drag, 2.0), drag(DragAction::Drag, 3.0), Event::Tick]);
+
+        assert!(events.len() == 3);
+        assert!(drag_position(&events[0]) == Some((DragAction::Start, 0.0)));
+        assert!(drag_position(&events[1]) == Some((DragAction::Drag, 3.0)));
+        assert!(drag_position(&events[2]) == None);
+    }
+
+    #[test]
+    fn drags_are_not_merged_across_start_and_finish() {
+        let events = coalesce_events(vec![
+            drag(DragAction::Start, 0.0), drag(DragAction::Drag, 1.0), drag(DragAction::Drag, 2.0), drag(DragAction::Finish, 2.0),
+            drag(DragAction::Start, 5.0), drag(DragAction::Drag, 6.0), drag(DragAction::Finish, 6.0)
+        ]);
+
+        let positions = events.iter().map(|event| drag_position(event).unwrap()).collect::<Vec<_>>();
+        assert!(positions == vec![
+            (DragAction::Start, 0.0), (DragAction::Drag, 2.0), (DragAction::Finish, 2.0),
+            (DragAction::Start, 5.0), (DragAction::Drag, 6.0), (DragAction::Finish, 6.0)
+        ]);
+    }
+
+    #[test]
+    fn right_click_generates_secondary_click_action() {
+        // As sent by the browser when a control with a SecondaryClick trigger gets a 'contextmenu' event
+        let json    = r#"{ "Action": [ ["canvas"], "ShowMenu", { "SecondaryClick": [ "Right", [ 100.5, 42.0 ] ] } ] }"#;
+        let event   = serde_json::from_str::<Event>(json).unwrap();
+
+        match event {
+            Event::Action(path, name, ActionParameter::SecondaryClick(button, position)) => {
+                assert!(path == vec!["canvas".to_string()]);
+                assert!(name == "ShowMenu".to_string());
+                assert!(button == MouseButton::Right);
+                assert!(position == (100.5, 42.0));
+            }
+
+            _ => { assert!(false, "Not a secondary click action") }
+        }
+    }
+}
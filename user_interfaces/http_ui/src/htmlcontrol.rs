@@ -63,7 +63,11 @@ fn control_class(ctrl: &Control) -> &str {
         Rotor               => "flo-rotor",
         TextBox             => "flo-textbox",
         CheckBox            => "flo-checkbox",
-        ComboBox            => "flo-combobox"
+        ComboBox            => "flo-combobox",
+        TreeView            => "flo-tree",
+        Media               => "flo-media",
+        CropImage           => "flo-crop-image",
+        Gauge               => "flo-gauge"
     }
 }
 
@@ -153,6 +157,36 @@ fn add_combobox_subcomponents(ctrl: &Control, dom_element: &mut DomNode, base_pa
     }
 }
 
+///
+/// Converts a node in a tree view to HTML
+///
+fn tree_node_to_html(node: &TreeNode) -> DomNode {
+    let state = match (&node.children, node.expanded) {
+        (TreeChildren::Leaf, _)         => "leaf",
+        (TreeChildren::NotLoaded, _)    => "lazy",
+        (_, true)                       => "expanded",
+        (_, false)                      => "collapsed"
+    };
+
+    let mut result = DomElement::new("flo-tree-node").with(vec![
+        DomAttribute::new("class", state),
+        DomAttribute::new("flo-node-id", &node.id),
+        DomElement::new("div").with(vec![
+            DomAttribute::new("class", "label"),
+            DomText::new(&node.label)
+        ])
+    ]);
+
+    // Children are only generated when they're visible (the control is regenerated when a node is expanded)
+    if let (true, TreeChildren::Loaded(children)) = (node.expanded, &node.children) {
+        for child in children.iter() {
+            result.append_child_node(tree_node_to_html(child));
+        }
+    }
+
+    result
+}
+
 impl ToHtml for Control {
     fn to_html_subcomponent(&self, base_path: &str, controller_path: &str) -> DomNode {
         // Start with the main element
@@ -235,7 +269,33 @@ impl ToHtml for ControlAttribute {
             &HoverAttr(ref hover)           => hover.to_html_subcomponent(base_path, controller_path),
             &HintAttr(ref hint)             => hint.to_html_subcomponent(base_path, controller_path),
 
+            TreeAttr(Tree::Nodes(nodes))    => DomCollection::new(nodes.iter().map(|node| tree_node_to_html(node)).collect()),
+            TreeAttr(Tree::Bound(_))        => DomEmpty::new(),
+            Classes(classes)                => if classes.len() > 0 { DomAttribute::new("class", &classes.join(" ")) } else { DomEmpty::new() },
+            BoundClasses(_)                 => DomEmpty::new(),
+
+            MediaAttr(Media::Source(url))               => DomAttribute::new("flo-media-src", url),
+            MediaAttr(Media::Type(MediaType::Audio))    => DomAttribute::new("flo-media-type", "audio"),
+            MediaAttr(Media::Type(MediaType::Video))    => DomAttribute::new("flo-media-type", "video"),
+            MediaAttr(Media::Playing(_))                => DomEmpty::new(),
+
+            CropAttr(Crop::Bounds(bounds))              => DomAttribute::new("flo-crop-bounds", &format!("{},{},{},{}", bounds.x, bounds.y, bounds.width, bounds.height)),
+            CropAttr(Crop::AspectRatio(ratio))          => DomAttribute::new("flo-crop-aspect-ratio", &ratio.to_string()),
+
+            DragDropAttr(DragDrop::Source(payload))     => DomCollection::new(vec![
+                DomAttribute::new("draggable", "true"),
+                DomAttribute::new("flo-drag-payload", &serde_json::to_string(payload).unwrap_or_default())
+            ]),
+            DragDropAttr(DragDrop::Target(types))       => DomAttribute::new("flo-drop-types", &types.join(" ")),
+
+            GaugeAttr(Gauge::StartAngle(angle))         => DomAttribute::new("flo-gauge-start-angle", &angle.to_string()),
+            GaugeAttr(Gauge::SweepAngle(angle))         => DomAttribute::new("flo-gauge-sweep-angle", &angle.to_string()),
+
+            Units(units)                    => DomAttribute::new("flo-units", &units.iter().map(|unit| unit.suffix()).collect::<Vec<_>>().join(" ")),
+            RangeStep(step)                 => DomAttribute::new("flo-range-step", &step.to_string()),
+
             BindKey(_key, _cmd)             => DomEmpty::new(),
+            KeyShortcut(_keys)              => DomEmpty::new(),
             &BoundingBox(_)                 => DomEmpty::new(),
             &Id(_)                          => DomEmpty::new(),
             &Controller(_)                  => DomEmpty::new(),
@@ -457,4 +517,80 @@ mod test {
 
         assert!(control.to_html("test/base").to_string() == "<flo-empty><flo-empty style=\"background: no-repeat center/contain url(&quot;test/base/i/Test/test_image&quot;);\"></flo-empty></flo-empty>");
     }
+
+    #[test]
+    fn can_convert_classes_to_html() {
+        let control = Control::container()
+            .with(ControlAttribute::Classes(vec!["toolbar".to_string(), "dark".to_string()]))
+            .with(Hint::Class("hinted".to_string()));
+
+        assert!(control.to_html("").to_string() == "<flo-container class=\"toolbar dark hinted\"></flo-container>");
+    }
+
+    #[test]
+    fn can_convert_media_to_html() {
+        let control = Control::media()
+            .with(Media::Source("/audio/soundtrack.ogg".to_string()))
+            .with(MediaType::Video);
+
+        assert!(control.to_html("").to_string() == "<flo-media flo-media-src=\"/audio/soundtrack.ogg\" flo-media-type=\"video\"></flo-media>");
+    }
+
+    #[test]
+    fn can_convert_crop_image_to_html() {
+        let control = Control::crop_image()
+            .with(CropBounds::new(10.0, 20.0, 160.0, 90.0))
+            .with(Crop::AspectRatio(1.5));
+
+        assert!(control.to_html("").to_string() == "<flo-crop-image flo-crop-bounds=\"10,20,160,90\" flo-crop-aspect-ratio=\"1.5\"></flo-crop-image>");
+    }
+
+    #[test]
+    fn can_convert_gauge_to_html() {
+        let control = Control::gauge()
+            .with(State::Range((Property::Float(0.0), Property::Float(100.0))))
+            .with(State::Value(Property::Float(25.0)))
+            .with(Gauge::StartAngle(180.0))
+            .with(Gauge::SweepAngle(180.0));
+
+        assert!(control.to_html("").to_string() == "<flo-gauge flo-gauge-start-angle=\"180\" flo-gauge-sweep-angle=\"180\"></flo-gauge>");
+    }
+
+    #[test]
+    fn can_convert_text_box_with_units_to_html() {
+        let control = Control::text_box()
+            .with(vec![Unit::Percent, Unit::Pixels, Unit::Em]);
+
+        assert!(control.to_html("").to_string() == "<flo-textbox flo-units=\"% px em\"></flo-textbox>");
+    }
+
+    #[test]
+    fn can_convert_drag_and_drop_to_html() {
+        let source = Control::label()
+            .with(DragPayload::new("layer", PropertyValue::Int(1)));
+        let target = Control::container()
+            .with(DragDrop::Target(vec!["layer".to_string(), "group".to_string()]));
+
+        assert!(source.to_html("").to_string() == "<flo-label draggable=\"true\" flo-drag-payload=\"{&quot;payload_type&quot;:&quot;layer&quot;,&quot;data&quot;:{&quot;Int&quot;:1}}\"></flo-label>");
+        assert!(target.to_html("").to_string() == "<flo-container flo-drop-types=\"layer group\"></flo-container>");
+    }
+
+    #[test]
+    fn can_convert_tree_to_html() {
+        let control = Control::tree()
+            .with(vec![
+                TreeNode::branch("layers", "Layers", vec![TreeNode::leaf("layer1", "Layer 1")]).expanded(true),
+                TreeNode::lazy("frames", "Frames")
+            ]);
+
+        assert!(control.to_html("").to_string() == "<flo-tree><flo-tree-node class=\"expanded\" flo-node-id=\"layers\"><div class=\"label\">Layers</div><flo-tree-node class=\"leaf\" flo-node-id=\"layer1\"><div class=\"label\">Layer 1</div></flo-tree-node></flo-tree-node><flo-tree-node class=\"lazy\" flo-node-id=\"frames\"><div class=\"label\">Frames</div></flo-tree-node></flo-tree>");
+    }
+
+    #[test]
+    fn can_convert_slider_with_step_to_html() {
+        let control = Control::slider()
+            .with_range_step(1.0);
+
+        assert!(control.to_html("").to_string() == "<flo-slider flo-range-step=\"1\"></flo-slider>");
+    }
 }
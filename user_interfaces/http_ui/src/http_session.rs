@@ -1,6 +1,7 @@
 use super::event::*;
 use super::update::*;
 use super::lazy_future::*;
+use super::pending_updates::*;
 use super::http_user_interface::*;
 
 use ui::*;
@@ -31,7 +32,10 @@ pub struct HttpSession<CoreUi> {
     input: BoxFuture<'static, WeakPublisher<Vec<Event>>>,
 
     /// The stream of events for the session (or None if it has been reset or not started yet)
-    updates: BoxFuture<'static, HttpUpdateStream>
+    updates: BoxFuture<'static, HttpUpdateStream>,
+
+    /// The maximum number of sets of updates that can be waiting to be sent before they're coalesced
+    max_pending_updates: usize
 }
 
 impl<CoreUi: 'static+CoreUserInterface+Send+Sync> HttpSession<CoreUi> {
@@ -44,13 +48,42 @@ impl<CoreUi: 'static+CoreUserInterface+Send+Sync> HttpSession<CoreUi> {
         let log     = LogPublisher::new(module_path!());
 
         HttpSession {
-            log:        log,
-            http_ui:    http_ui,
-            input:      input,
-            updates:    updates
+            log:                    log,
+            http_ui:                http_ui,
+            input:                  input,
+            updates:                updates,
+            max_pending_updates:    DEFAULT_MAX_PENDING_UPDATES
         }
     }
 
+    ///
+    /// Sets the maximum number of sets of updates that can be waiting to be sent by the streams returned
+    /// by `get_coalesced_updates()` before they're coalesced
+    ///
+    pub fn set_max_pending_updates(&mut self, max_pending_updates: usize) {
+        self.max_pending_updates = max_pending_updates;
+    }
+
+    ///
+    /// Retrieves the maximum number of sets of updates that can be waiting to be sent before they're coalesced
+    ///
+    pub fn max_pending_updates(&self) -> usize {
+        self.max_pending_updates
+    }
+
+    ///
+    /// Retrieves a stream of updates for this session that only returns updates while the client has acknowledged
+    /// enough of the earlier ones, coalescing the updates that are waiting to be sent
+    ///
+    /// This is used when streaming updates to a client (eg, over a websocket): whenever this stream is polled, it
+    /// reads every update that's available from the session. Updates are only returned while there's space in the
+    /// window, and if more than `max_pending_updates()` sets of updates are waiting then they're combined so that
+    /// only the latest update to each control is sent.
+    ///
+    pub fn get_coalesced_updates(&self, window: UpdateWindow) -> HttpUpdateStream {
+        coalesced_update_stream(self.http_ui.get_updates(), self.max_pending_updates, window)
+    }
+
     ///
     /// Retrieves the log for this session
     ///
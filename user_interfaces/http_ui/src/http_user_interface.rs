@@ -14,6 +14,7 @@ use futures::task::{Poll};
 use futures::stream::{BoxStream};
 use itertools::join;
 use percent_encoding::*;
+use serde_json::json;
 
 use std::mem;
 use std::sync::*;
@@ -115,8 +116,8 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
             // Finish the UI loop if there are no more events
             if next_events.is_none() { break; }
 
-            // Process the events into HTTP events
-            let http_events = next_events.unwrap().into_iter()
+            // Process the events into HTTP events (only the most recent position is needed for drags that arrive together)
+            let http_events = coalesce_events(next_events.unwrap()).into_iter()
                 .map(|event| Self::http_event_to_core_event(event))
                 .collect::<Vec<_>>();
 
@@ -249,6 +250,29 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
         HtmlDiff::new(address, &new_ui, html.to_string())
     }
 
+    ///
+    /// Maps a core UI edit into a HTML edit
+    ///
+    fn map_core_ui_edit(ui_edit: UiEdit, ui_tree: &Control, base_path: &str) -> HtmlEdit {
+        match ui_edit {
+            UiEdit::Replace(ui_diff)                    => HtmlEdit::Replace(Self::map_core_ui_diff(ui_diff, ui_tree, base_path)),
+            UiEdit::SetClasses { address, classes }     => HtmlEdit::SetClasses { address: address, classes: classes },
+            UiEdit::Delete { address, index }           => HtmlEdit::Delete { address: address, index: index },
+            UiEdit::Move { address, from, to }          => HtmlEdit::Move { address: address, from: from, to: to },
+
+            UiEdit::Insert { address, index, new_ui }   => {
+                // Inserted nodes end up at their index in the final tree, so the controller path can be found there
+                let mut node_address    = address.clone();
+                node_address.push(index);
+
+                let controller_path     = html_controller_path_for_address(ui_tree, &node_address);
+                let html                = new_ui.to_html_subcomponent(base_path, &controller_path);
+
+                HtmlEdit::Insert { address: address, index: index, ui_tree: new_ui.to_json(), new_html: html.to_string() }
+            }
+        }
+    }
+
     ///
     /// Maps a single core update to a HTTP update
     ///
@@ -266,6 +290,14 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
                 )]
             },
 
+            EditUi(core_edits) => {
+                // Map the UI edits
+                vec![Update::EditHtml(core_edits.into_iter()
+                    .map(|core_ui_edit| Self::map_core_ui_edit(core_ui_edit, &ui_tree, &base_path))
+                    .collect()
+                )]
+            },
+
             UpdateCanvas(canvas_diffs) => vec![Update::UpdateCanvas(canvas_diffs.into_iter().map(|diff| Self::map_canvas_diff(diff)).collect())],
 
             UpdateViewModel(view_model_diffs) => vec![Update::UpdateViewModel(view_model_diffs)],
@@ -302,6 +334,86 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
     }
 }
 
+impl<CoreUi: 'static+CoreUserInterface> HttpUserInterface<CoreUi> {
+    ///
+    /// Retrieves the current state of the whole UI tree as JSON
+    ///
+    /// This is the same as the JSON sent to the client, except that properties that are bound to a viewmodel are replaced
+    /// with the value that they currently have.
+    ///
+    pub fn current_ui_json(&self) -> serde_json::Value {
+        let controller: Arc<dyn Controller> = self.core_ui.controller();
+        let ui_tree                         = self.ui_tree.get();
+
+        resolved_control_json(&ui_tree, Some(&controller))
+    }
+}
+
+///
+/// Converts a control to JSON, replacing any bound properties with their values from the viewmodel of the controller that owns the control
+///
+fn resolved_control_json(control: &Control, controller: Option<&Arc<dyn Controller>>) -> serde_json::Value {
+    let viewmodel       = controller.and_then(|controller| controller.get_viewmodel());
+
+    // The subcomponents of a control with a controller attribute belong to that controller
+    let subcontroller   = match control.controller() {
+        Some(controller_name)   => controller.and_then(|controller| controller.get_subcontroller(controller_name)),
+        None                    => controller.cloned()
+    };
+
+    let attributes      = control.attributes()
+        .map(|attribute| match attribute {
+            ControlAttribute::SubComponents(components) => {
+                let components = components.iter()
+                    .map(|component| resolved_control_json(component, subcontroller.as_ref()))
+                    .collect::<Vec<_>>();
+
+                json!({ "SubComponents": components })
+            }
+
+            attribute                                   => {
+                let mut json = attribute.to_json();
+                if let Some(viewmodel) = &viewmodel {
+                    resolve_bound_properties(&mut json, &**viewmodel);
+                }
+
+                json
+            }
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "attributes":   attributes,
+        "control_type": control.control_type()
+    })
+}
+
+///
+/// Replaces any bound properties (`{ "Bind": "name" }`) in the JSON for an attribute with their values from a viewmodel
+///
+fn resolve_bound_properties(json: &mut serde_json::Value, viewmodel: &dyn ViewModel) {
+    use serde_json::Value;
+
+    match json {
+        Value::Object(map) => {
+            let bound_property = match (map.len(), map.get("Bind")) {
+                (1, Some(Value::String(property_name))) => Some(property_name.clone()),
+                _                                       => None
+            };
+
+            if let Some(property_name) = bound_property {
+                // Property values have the same JSON representation as constant properties
+                *json = serde_json::to_value(viewmodel.get_property(&property_name).get()).unwrap();
+            } else {
+                map.values_mut().for_each(|value| resolve_bound_properties(value, viewmodel));
+            }
+        }
+
+        Value::Array(values)    => values.iter_mut().for_each(|value| resolve_bound_properties(value, viewmodel)),
+        _                       => { }
+    }
+}
+
 pub type HttpUpdateStream   = BoxStream<'static, Result<Vec<Update>, ()>>;
 
 impl<CoreUi: CoreUserInterface> UserInterface<Vec<Event>, Vec<Update>, ()> for HttpUserInterface<CoreUi> {
@@ -312,8 +424,8 @@ impl<CoreUi: CoreUserInterface> UserInterface<Vec<Event>, Vec<Update>, ()> for H
     }
 
     fn get_updates(&self) -> Self::UpdateStream {
-        // Fetch the updates from the core
-        let core_updates = self.core_ui.get_updates();
+        // Fetch the updates from the core (the web front-end can move keyed controls around without re-creating them)
+        let core_updates = self.core_ui.get_updates().with_keyed_edits();
 
         // Fetch the extra components we need to map events from this object
         let ui_tree     = BindRef::clone(&self.ui_tree);
@@ -372,6 +484,41 @@ mod test {
         timeout_recv
     }
 
+    struct BoundController {
+        ui:         Binding<Control>,
+        viewmodel:  Arc<DynamicViewModel>
+    }
+
+    impl Controller for BoundController {
+        fn ui(&self) -> BindRef<Control> {
+            BindRef::new(&self.ui)
+        }
+
+        fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+            Some(self.viewmodel.clone())
+        }
+    }
+
+    #[test]
+    fn current_ui_json_resolves_bound_properties() {
+        let viewmodel                       = Arc::new(DynamicViewModel::new());
+        viewmodel.set_property("Label", PropertyValue::String("Hello".to_string()));
+
+        let controller                      = BoundController {
+            ui:         bind(Control::container().with(vec![Control::label().with(ControlAttribute::Text(Property::bound("Label")))])),
+            viewmodel:  viewmodel.clone()
+        };
+        let (core_session, _core_run_loop)  = UiSession::new(controller);
+        let (http_ui, _http_run_loop)       = HttpUserInterface::new(Arc::new(core_session), "test/session".to_string());
+
+        let label_json                      = |ui_json: serde_json::Value| ui_json["attributes"][0]["SubComponents"][0]["attributes"][0].clone();
+
+        assert!(label_json(http_ui.current_ui_json()) == json![{ "Text": { "String": "Hello" } }]);
+
+        viewmodel.set_property("Label", PropertyValue::String("Goodbye".to_string()));
+        assert!(label_json(http_ui.current_ui_json()) == json![{ "Text": { "String": "Goodbye" } }]);
+    }
+
     #[test]
     fn generates_initial_update() {
         let thread_pool                     = executor::ThreadPool::new().unwrap();
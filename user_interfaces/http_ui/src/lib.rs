@@ -16,6 +16,7 @@ extern crate percent_encoding;
 extern crate itertools;
 extern crate futures;
 extern crate desync;
+extern crate flate2;
 
 extern crate bytes;
 
@@ -31,6 +32,7 @@ mod null_session;
 pub mod minidom;
 mod canvas_update;
 mod lazy_future;
+mod pending_updates;
 
 pub use self::http_user_interface::*;
 pub use self::http_controller::*;
@@ -42,3 +44,4 @@ pub use self::htmlcontrol::*;
 pub use self::ui_handler::*;
 pub use self::null_session::*;
 pub use self::canvas_update::*;
+pub use self::pending_updates::*;
@@ -10,6 +10,7 @@ pub fn quote_text(text: &String) -> String {
             '>' => result.push_str("&gt;"),
             '&' => result.push_str("&amp;"),
             '\'' => result.push_str("&quot;"),
+            '"' => result.push_str("&quot;"),
 
             _ => result.push(c)
         }
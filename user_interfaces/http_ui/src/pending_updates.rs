@@ -0,0 +1,466 @@
+use super::update::*;
+use super::http_user_interface::*;
+
+use ui::*;
+
+use futures::*;
+use futures::stream;
+use futures::task::{Poll, Waker};
+
+use std::sync::*;
+use std::collections::{VecDeque};
+
+/// The number of sets of updates that can be waiting to be sent before they're coalesced by default
+pub const DEFAULT_MAX_PENDING_UPDATES: usize = 16;
+
+/// The number of sets of updates that can be sent to a client before it has to acknowledge them by default
+pub const DEFAULT_MAX_UNACKNOWLEDGED_UPDATES: usize = 4;
+
+/// The message a client sends over a websocket once it has processed a set of updates
+pub const UPDATE_ACKNOWLEDGEMENT: &str = "ack";
+
+///
+/// Tracks how many sets of updates have been sent to a client without being acknowledged
+///
+/// A stream created by `coalesced_update_stream()` stops returning updates once the window is full. The updates
+/// that arrive while it's full are coalesced until the client calls `acknowledge()` to indicate that it has
+/// processed an earlier set of updates.
+///
+#[derive(Clone)]
+pub struct UpdateWindow {
+    core: Arc<Mutex<UpdateWindowCore>>
+}
+
+///
+/// The shared state of an update window
+///
+struct UpdateWindowCore {
+    /// The maximum number of sets of updates that can be unacknowledged
+    max_unacknowledged: usize,
+
+    /// The number of sets of updates that have been sent and not acknowledged yet
+    unacknowledged: usize,
+
+    /// The task waiting for space in the window
+    waker: Option<Waker>
+}
+
+///
+/// Queue of updates that are waiting to be sent to a client
+///
+/// Sets of updates are kept separate until more than the maximum number are waiting, at which point they're
+/// coalesced into a single set (see `coalesce_updates()`). This stops the queue from growing without limit
+/// when a client can't keep up with the updates that are being generated.
+///
+pub struct PendingUpdates {
+    /// The maximum number of sets of updates that can be waiting before they're coalesced
+    max_pending: usize,
+
+    /// The sets of updates that are waiting to be sent
+    pending: VecDeque<Vec<Update>>
+}
+
+impl PendingUpdates {
+    ///
+    /// Creates a new queue of pending updates
+    ///
+    pub fn new(max_pending: usize) -> PendingUpdates {
+        PendingUpdates {
+            max_pending:    max_pending,
+            pending:        VecDeque::new()
+        }
+    }
+
+    ///
+    /// Adds a set of updates to the end of the queue
+    ///
+    pub fn push(&mut self, updates: Vec<Update>) {
+        self.pending.push_back(updates);
+
+        if self.pending.len() > self.max_pending {
+            let all_updates = self.pending.drain(..).flatten().collect();
+            self.pending.push_back(coalesce_updates(all_updates));
+        }
+    }
+
+    ///
+    /// Removes the set of updates at the front of the queue
+    ///
+    pub fn pop(&mut self) -> Option<Vec<Update>> {
+        self.pending.pop_front()
+    }
+
+    ///
+    /// The number of sets of updates that are waiting in this queue
+    ///
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    ///
+    /// True if there are no updates waiting in this queue
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl UpdateWindow {
+    ///
+    /// Creates a new update window
+    ///
+    pub fn new(max_unacknowledged: usize) -> UpdateWindow {
+        UpdateWindow {
+            core: Arc::new(Mutex::new(UpdateWindowCore {
+                max_unacknowledged: max_unacknowledged.max(1),
+                unacknowledged:     0,
+                waker:              None
+            }))
+        }
+    }
+
+    ///
+    /// Indicates that the client has processed a set of updates
+    ///
+    pub fn acknowledge(&self) {
+        let mut core = self.core.lock().unwrap();
+
+        core.unacknowledged = core.unacknowledged.saturating_sub(1);
+        if let Some(waker) = core.waker.take() {
+            waker.wake();
+        }
+    }
+
+    ///
+    /// The number of sets of updates that have been sent and not acknowledged yet
+    ///
+    pub fn unacknowledged(&self) -> usize {
+        self.core.lock().unwrap().unacknowledged
+    }
+
+    ///
+    /// Reserves space for sending a set of updates, returning false (and waking the specified task when there's space)
+    /// if the window is full
+    ///
+    fn reserve(&self, waker: &Waker) -> bool {
+        let mut core = self.core.lock().unwrap();
+
+        if core.unacknowledged < core.max_unacknowledged {
+            core.unacknowledged += 1;
+            true
+        } else {
+            core.waker = Some(waker.clone());
+            false
+        }
+    }
+}
+
+///
+/// Creates a stream that returns the updates from a session, but only while the client has acknowledged enough of the
+/// updates it was sent earlier
+///
+/// Updates keep being read from the session while the window is full, so they don't build up anywhere else: they wait
+/// in a `PendingUpdates` queue, which coalesces them once more than `max_pending` sets are waiting.
+///
+pub fn coalesced_update_stream<UpdateStream>(updates: UpdateStream, max_pending: usize, window: UpdateWindow) -> HttpUpdateStream
+where UpdateStream: 'static+Send+Unpin+Stream<Item=Result<Vec<Update>, ()>> {
+    let mut updates     = updates;
+    let mut pending     = PendingUpdates::new(max_pending);
+    let mut finished    = false;
+
+    Box::pin(stream::poll_fn(move |context| {
+        // Read everything that's ready from the session
+        while !finished {
+            match updates.poll_next_unpin(context) {
+                Poll::Ready(Some(Ok(next)))     => pending.push(next),
+                Poll::Ready(Some(Err(err)))     => { return Poll::Ready(Some(Err(err))); }
+                Poll::Ready(None)               => { finished = true; }
+                Poll::Pending                   => { break; }
+            }
+        }
+
+        if pending.is_empty() {
+            // Nothing to send
+            if finished { Poll::Ready(None) } else { Poll::Pending }
+        } else if window.reserve(context.waker()) {
+            // Return the oldest set of updates that's waiting
+            Poll::Ready(pending.pop().map(|next| Ok(next)))
+        } else {
+            // Client hasn't acknowledged enough updates yet: wait until it does (or more updates arrive)
+            Poll::Pending
+        }
+    }))
+}
+
+///
+/// Combines a list of updates so that the updates that affect the same thing are merged together
+///
+/// HTML diffs replace any earlier diffs to the same node or its children, viewmodel changes replace any earlier change to the
+/// same property and canvas updates to the same canvas are joined together. An update is only merged into an earlier one if
+/// nothing in between depends on the order: HTML and canvas updates are never moved past each other, and nothing is moved
+/// past an update that starts a new session or replaces the whole UI.
+///
+pub fn coalesce_updates(updates: Vec<Update>) -> Vec<Update> {
+    let mut result: Vec<Update> = vec![];
+
+    for update in updates {
+        match find_merge_target(&result, &update) {
+            Some(index) => merge_update(&mut result[index], update),
+            None        => result.push(update)
+        }
+    }
+
+    result
+}
+
+///
+/// Finds the index of the update in a list that a new update can be merged into
+///
+fn find_merge_target(updates: &[Update], new_update: &Update) -> Option<usize> {
+    use self::Update::*;
+
+    for (index, existing) in updates.iter().enumerate().rev() {
+        match (existing, new_update) {
+            (UpdateHtml(_), UpdateHtml(_))              |
+            (UpdateViewModel(_), UpdateViewModel(_))    |
+            (UpdateCanvas(_), UpdateCanvas(_))          |
+            (UpdateCommands(_), UpdateCommands(_))      => { return Some(index); }
+
+            // The HTML can replace a canvas so canvas updates must stay in the same order relative to HTML updates
+            (UpdateHtml(_), UpdateCanvas(_))            |
+            (UpdateCanvas(_), UpdateHtml(_))            => { return None; }
+
+            // Viewmodel properties and commands are tracked independently of the HTML, so updates can be moved past them
+            (EditHtml(_), UpdateViewModel(_))           |
+            (EditHtml(_), UpdateCommands(_))            |
+            (UpdateHtml(_), _)                          |
+            (UpdateViewModel(_), _)                     |
+            (UpdateCanvas(_), _)                        |
+            (UpdateCommands(_), _)                      => { }
+
+            // Other updates (new sessions, new UIs, etc) can't be merged and nothing can be moved past them
+            _                                           => { return None; }
+        }
+    }
+
+    None
+}
+
+///
+/// Merges a later update into an earlier one of the same type
+///
+fn merge_update(existing: &mut Update, new_update: Update) {
+    use self::Update::*;
+
+    match (existing, new_update) {
+        (UpdateHtml(existing_diffs), UpdateHtml(new_diffs)) => {
+            for diff in new_diffs {
+                // A diff replaces the node at its address, so any earlier diffs for that node or its children are no longer needed
+                existing_diffs.retain(|existing_diff| !existing_diff.address.starts_with(&diff.address));
+                existing_diffs.push(diff);
+            }
+        }
+
+        (UpdateViewModel(existing_updates), UpdateViewModel(new_updates)) => {
+            for update in new_updates {
+                match existing_updates.iter().position(|existing_update| existing_update.controller_path() == update.controller_path()) {
+                    Some(index) => {
+                        let merged              = merge_viewmodel_changes(existing_updates[index].updates(), update.updates());
+                        existing_updates[index] = ViewModelUpdate::new(update.controller_path().clone(), merged);
+                    }
+
+                    None        => existing_updates.push(update)
+                }
+            }
+        }
+
+        (UpdateCanvas(existing_updates), UpdateCanvas(new_updates)) => {
+            for update in new_updates {
+                // Canvas updates are joined together when they're for the same canvas as the last update
+                let update = match existing_updates.last_mut() {
+                    Some(last_update)   => last_update.append(update),
+                    None                => Err(update)
+                };
+
+                if let Err(update) = update {
+                    existing_updates.push(update);
+                }
+            }
+        }
+
+        (UpdateCommands(existing_updates), UpdateCommands(new_updates)) => {
+            existing_updates.extend(new_updates);
+        }
+
+        (existing, new_update) => {
+            panic!("Cannot merge {:?} into {:?}", new_update, existing);
+        }
+    }
+}
+
+///
+/// Merges a later set of viewmodel changes into an earlier set, keeping only the most recent value for each property
+///
+fn merge_viewmodel_changes(existing_changes: &[ViewModelChange], new_changes: &[ViewModelChange]) -> Vec<ViewModelChange> {
+    let mut merged = existing_changes.to_vec();
+
+    for change in new_changes {
+        match merged.iter().position(|existing_change| property_name(existing_change) == property_name(change)) {
+            Some(index) => merged[index] = change.clone(),
+            None        => merged.push(change.clone())
+        }
+    }
+
+    merged
+}
+
+///
+/// Retrieves the name of the property affected by a viewmodel change
+///
+fn property_name(change: &ViewModelChange) -> &str {
+    match change {
+        ViewModelChange::NewProperty(name, _)       => name,
+        ViewModelChange::PropertyChanged(name, _)   => name
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::canvas_update::*;
+
+    fn html(address: Vec<u32>, text: &str) -> HtmlDiff {
+        HtmlDiff::new(address, &Control::label().with(text), text.to_string())
+    }
+
+    fn property(name: &str, value: i32) -> ViewModelChange {
+        ViewModelChange::PropertyChanged(name.to_string(), PropertyValue::Int(value))
+    }
+
+    #[test]
+    fn later_html_replaces_earlier_html_at_same_address() {
+        let updates = coalesce_updates(vec![
+            Update::UpdateHtml(vec![html(vec![0, 1], "One"), html(vec![2], "Two")]),
+            Update::UpdateHtml(vec![html(vec![0, 1], "Three")])
+        ]);
+
+        assert!(updates == vec![Update::UpdateHtml(vec![html(vec![2], "Two"), html(vec![0, 1], "Three")])]);
+    }
+
+    #[test]
+    fn html_is_not_merged_past_edits() {
+        let edit    = Update::EditHtml(vec![HtmlEdit::Move { address: vec![0], from: 2, to: 0 }]);
+        let updates = coalesce_updates(vec![
+            Update::UpdateHtml(vec![html(vec![0, 1], "One")]),
+            edit.clone(),
+            Update::UpdateHtml(vec![html(vec![0, 1], "Two")])
+        ]);
+
+        // The address in the later update refers to the tree after the edit, so it has to stay where it is
+        assert!(updates == vec![
+            Update::UpdateHtml(vec![html(vec![0, 1], "One")]),
+            edit,
+            Update::UpdateHtml(vec![html(vec![0, 1], "Two")])
+        ]);
+    }
+
+    #[test]
+    fn parent_html_replaces_children_but_child_html_is_kept_after_parent() {
+        let updates = coalesce_updates(vec![
+            Update::UpdateHtml(vec![html(vec![0, 1], "Child")]),
+            Update::UpdateHtml(vec![html(vec![0], "Parent")]),
+            Update::UpdateHtml(vec![html(vec![0, 2], "Other child")])
+        ]);
+
+        assert!(updates == vec![Update::UpdateHtml(vec![html(vec![0], "Parent"), html(vec![0, 2], "Other child")])]);
+    }
+
+    #[test]
+    fn viewmodel_keeps_latest_value() {
+        let updates = coalesce_updates(vec![
+            Update::UpdateViewModel(vec![ViewModelUpdate::new(vec!["A".to_string()], vec![property("X", 1), property("Y", 2)])]),
+            Update::UpdateHtml(vec![html(vec![0], "One")]),
+            Update::UpdateViewModel(vec![ViewModelUpdate::new(vec!["A".to_string()], vec![property("X", 3)])])
+        ]);
+
+        assert!(updates == vec![
+            Update::UpdateViewModel(vec![ViewModelUpdate::new(vec!["A".to_string()], vec![property("X", 3), property("Y", 2)])]),
+            Update::UpdateHtml(vec![html(vec![0], "One")])
+        ]);
+    }
+
+    #[test]
+    fn canvas_updates_are_not_moved_past_html() {
+        let canvas  = |updates: &str| CanvasUpdate::new("Controller".to_string(), "Canvas".to_string(), updates.to_string());
+        let updates = coalesce_updates(vec![
+            Update::UpdateCanvas(vec![canvas("A")]),
+            Update::UpdateHtml(vec![html(vec![0], "One")]),
+            Update::UpdateCanvas(vec![canvas("B")]),
+            Update::UpdateCanvas(vec![canvas("C")])
+        ]);
+
+        assert!(updates == vec![
+            Update::UpdateCanvas(vec![canvas("A")]),
+            Update::UpdateHtml(vec![html(vec![0], "One")]),
+            Update::UpdateCanvas(vec![canvas("BC")])
+        ]);
+    }
+
+    #[test]
+    fn updates_are_not_merged_past_new_ui() {
+        let new_ui  = Update::NewUserInterfaceHtml("<flo-empty></flo-empty>".to_string(), Control::empty().to_json(), vec![]);
+        let updates = coalesce_updates(vec![
+            Update::UpdateHtml(vec![html(vec![0], "One")]),
+            new_ui.clone(),
+            Update::UpdateHtml(vec![html(vec![0], "Two")])
+        ]);
+
+        assert!(updates == vec![
+            Update::UpdateHtml(vec![html(vec![0], "One")]),
+            new_ui,
+            Update::UpdateHtml(vec![html(vec![0], "Two")])
+        ]);
+    }
+
+    #[test]
+    fn updates_are_coalesced_while_client_is_stalled() {
+        let (sender, receiver)  = futures::channel::mpsc::unbounded();
+        let window              = UpdateWindow::new(1);
+        let mut stream          = coalesced_update_stream(receiver, 1, window.clone());
+
+        let waker               = futures::task::noop_waker();
+        let mut context         = futures::task::Context::from_waker(&waker);
+
+        // First set of updates is sent immediately
+        sender.unbounded_send(Ok(vec![Update::UpdateHtml(vec![html(vec![0], "One")])])).unwrap();
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(Ok(vec![Update::UpdateHtml(vec![html(vec![0], "One")])]))));
+
+        // Client doesn't acknowledge the first set of updates, so later updates are held back
+        for text in ["Two", "Three", "Four", "Five"].iter() {
+            sender.unbounded_send(Ok(vec![Update::UpdateHtml(vec![html(vec![0], text)])])).unwrap();
+            assert!(stream.poll_next_unpin(&mut context) == Poll::Pending);
+        }
+        assert!(window.unacknowledged() == 1);
+
+        // Once the client catches up, the updates that were held back have been merged
+        window.acknowledge();
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(Ok(vec![Update::UpdateHtml(vec![html(vec![0], "Five")])]))));
+
+        window.acknowledge();
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Pending);
+    }
+
+    #[test]
+    fn pending_updates_only_coalesce_when_full() {
+        let mut pending = PendingUpdates::new(2);
+
+        pending.push(vec![Update::UpdateHtml(vec![html(vec![0], "One")])]);
+        pending.push(vec![Update::UpdateHtml(vec![html(vec![0], "Two")])]);
+        assert!(pending.len() == 2);
+
+        pending.push(vec![Update::UpdateHtml(vec![html(vec![0], "Three")])]);
+        assert!(pending.len() == 1);
+        assert!(pending.pop() == Some(vec![Update::UpdateHtml(vec![html(vec![0], "Three")])]));
+        assert!(pending.is_empty());
+    }
+}
@@ -7,19 +7,86 @@ use flo_logging::*;
 
 use futures::*;
 use futures::future;
+use futures::future::{BoxFuture};
+use futures::channel::oneshot;
 use uuid::*;
 
 use std::sync::*;
 use std::collections::*;
+use std::time::{Duration, Instant};
+
+/// The length of time a session is kept for after it was last used by default
+pub const DEFAULT_SESSION_GRACE_PERIOD: Duration = Duration::from_secs(300);
+
+///
+/// A session and the state of the websocket that's connected to it
+///
+struct WebSession<CoreController: Controller> {
+    /// The HTTP session
+    session: Arc<Mutex<HttpSession<UiSession<CoreController>>>>,
+
+    /// Identifies the websocket most recently connected to this session
+    socket_id: u64,
+
+    /// Used to tell the most recently connected websocket that it has been replaced
+    replace_socket: Option<oneshot::Sender<()>>,
+
+    /// When this session was last used (or None if there is a websocket connected to it)
+    inactive_since: Option<Instant>,
+
+    /// Used to stop the session's run loop when it expires
+    stop_session: Option<oneshot::Sender<()>>
+}
+
+///
+/// Represents a websocket connected to a session
+///
+pub struct SessionSocket {
+    /// The session that the socket is connected to
+    session_id: String,
+
+    /// The ID of this socket within its session
+    socket_id: u64,
+
+    /// Signalled when a newer socket connects to the same session
+    replaced: Option<oneshot::Receiver<()>>
+}
+
+impl SessionSocket {
+    ///
+    /// The ID of the session that this socket is connected to
+    ///
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    ///
+    /// Returns a future that completes when this socket should be closed because a newer socket has connected
+    /// to the same session (or the session has been removed)
+    ///
+    pub fn when_replaced(&mut self) -> BoxFuture<'static, ()> {
+        match self.replaced.take() {
+            Some(replaced)  => replaced.map(|_| ()).boxed(),
+            None            => future::pending().boxed()
+        }
+    }
+}
 
 ///
 /// Manages the active sessions
 ///
+/// Sessions are kept while a websocket is connected to them, and for a grace period after they were last
+/// used. This means that a client can reconnect to a session if its websocket is dropped: the updates for
+/// the new websocket begin with the current state of the UI.
+///
 pub struct WebSessions<CoreController: Controller> {
     log: LogPublisher,
 
+    /// How long a session is kept for after it was last used
+    grace_period: Duration,
+
     /// The sessions
-    sessions: Mutex<HashMap<String, Arc<Mutex<HttpSession<UiSession<CoreController>>>>>>
+    sessions: Mutex<HashMap<String, WebSession<CoreController>>>
 }
 
 impl<CoreController: Controller+'static> WebSessions<CoreController> {
@@ -28,15 +95,26 @@ impl<CoreController: Controller+'static> WebSessions<CoreController> {
     ///
     pub fn new() -> WebSessions<CoreController> {
         WebSessions {
-            log:        LogPublisher::new(module_path!()),
-            sessions:   Mutex::new(HashMap::new())
+            log:            LogPublisher::new(module_path!()),
+            grace_period:   DEFAULT_SESSION_GRACE_PERIOD,
+            sessions:       Mutex::new(HashMap::new())
         }
     }
 
+    ///
+    /// Changes how long a session is kept for after it was last used
+    ///
+    pub fn with_grace_period(mut self, grace_period: Duration) -> WebSessions<CoreController> {
+        self.grace_period = grace_period;
+        self
+    }
+
     ///
     /// Creates a new session and returns its ID, and a future that will run the session and complete when the session is complete
     ///
     pub fn new_session(&self, controller: CoreController, base_path: &str) -> (String, impl Future<Output=()>) {
+        self.remove_expired_sessions();
+
         // Generate a session ID using the UUID library
         let session_id          = Uuid::new_v4().to_simple().to_string();
 
@@ -49,12 +127,20 @@ impl<CoreController: Controller+'static> WebSessions<CoreController> {
         let (ui_session, ui_run_loop)   = UiSession::new(controller);
         let (http_ui, http_run_loop)    = HttpUserInterface::new(Arc::new(ui_session), session_uri);
         let http_session                = HttpSession::new(Arc::new(http_ui));
+        let (stop_session, stopped)     = oneshot::channel();
 
         // Store the new session and associate it with this ID
-        self.sessions.lock().unwrap().insert(session_id.clone(), Arc::new(Mutex::new(http_session)));
+        self.sessions.lock().unwrap().insert(session_id.clone(), WebSession {
+            session:        Arc::new(Mutex::new(http_session)),
+            socket_id:      0,
+            replace_socket: None,
+            inactive_since: Some(Instant::now()),
+            stop_session:   Some(stop_session)
+        });
 
-        // Session ends when either the HTTP events or the UI events stop
-        let run_loop                    = future::select(ui_run_loop.boxed(), http_run_loop.boxed()).map(|_| ());
+        // Session ends when either the HTTP events or the UI events stop, or when the session expires
+        let run_loop                    = future::select(ui_run_loop.boxed(), http_run_loop.boxed());
+        let run_loop                    = future::select(run_loop, stopped).map(|_| ());
 
         // Return the session
         (session_id, run_loop)
@@ -64,7 +150,94 @@ impl<CoreController: Controller+'static> WebSessions<CoreController> {
     /// Retrieves the session with the specified ID form this object
     ///
     pub fn get_session(&self, session_id: &str) -> Option<Arc<Mutex<HttpSession<UiSession<CoreController>>>>> {
-        self.sessions.lock().unwrap().get(session_id).cloned()
+        let mut sessions = self.sessions.lock().unwrap();
+        let web_session  = sessions.get_mut(session_id)?;
+
+        // Using the session restarts its grace period
+        if web_session.inactive_since.is_some() {
+            web_session.inactive_since = Some(Instant::now());
+        }
+
+        Some(Arc::clone(&web_session.session))
+    }
+
+    ///
+    /// Connects a websocket to the session with the specified ID
+    ///
+    /// If there's already a websocket connected to the session, it's replaced by the new one (the `when_replaced()`
+    /// future of the old socket will complete so that it can be closed).
+    ///
+    pub fn connect_socket(&self, session_id: &str) -> Option<(Arc<Mutex<HttpSession<UiSession<CoreController>>>>, SessionSocket)> {
+        self.remove_expired_sessions();
+
+        let mut sessions        = self.sessions.lock().unwrap();
+        let web_session         = sessions.get_mut(session_id)?;
+        let (replace, replaced) = oneshot::channel();
+
+        // Tell the existing socket to close
+        if let Some(replace_socket) = web_session.replace_socket.take() {
+            self.log.log((Level::Info, format!("Replacing websocket for session ID {}", session_id)));
+            replace_socket.send(()).ok();
+        }
+
+        // The new socket is now the current one
+        web_session.socket_id       += 1;
+        web_session.replace_socket  = Some(replace);
+        web_session.inactive_since  = None;
+
+        let socket = SessionSocket {
+            session_id: session_id.to_string(),
+            socket_id:  web_session.socket_id,
+            replaced:   Some(replaced)
+        };
+
+        Some((Arc::clone(&web_session.session), socket))
+    }
+
+    ///
+    /// Indicates that a websocket has disconnected from its session, which starts the grace period for the session
+    ///
+    /// Nothing happens if the socket has been replaced by a newer one.
+    ///
+    pub fn disconnect_socket(&self, socket: &SessionSocket) {
+        let mut sessions = self.sessions.lock().unwrap();
+
+        if let Some(web_session) = sessions.get_mut(&socket.session_id) {
+            if web_session.socket_id == socket.socket_id {
+                web_session.replace_socket  = None;
+                web_session.inactive_since  = Some(Instant::now());
+            }
+        }
+    }
+
+    ///
+    /// Removes any sessions that have not been used for longer than the grace period, returning the number of sessions that were removed
+    ///
+    pub fn remove_expired_sessions(&self) -> usize {
+        self.remove_sessions_expired_at(Instant::now())
+    }
+
+    ///
+    /// Removes the sessions that have expired by the specified time
+    ///
+    fn remove_sessions_expired_at(&self, now: Instant) -> usize {
+        let grace_period    = self.grace_period;
+        let mut sessions    = self.sessions.lock().unwrap();
+        let expired         = sessions.iter()
+            .filter(|(_, web_session)| web_session.inactive_since.map(|since| now.saturating_duration_since(since) > grace_period).unwrap_or(false))
+            .map(|(session_id, _)| session_id.clone())
+            .collect::<Vec<_>>();
+
+        for session_id in expired.iter() {
+            self.log.log((Level::Info, format!("Session ID {} has expired", session_id)));
+
+            // Stop the run loop for the session
+            if let Some(stop_session) = sessions.remove(session_id).and_then(|web_session| web_session.stop_session) {
+                stop_session.send(()).ok();
+            }
+        }
+
+        expired.len()
     }
 
     ///
@@ -74,3 +247,54 @@ impl<CoreController: Controller+'static> WebSessions<CoreController> {
         &self.log
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::null_session::*;
+
+    use futures::executor;
+
+    #[test]
+    fn newer_socket_replaces_older_socket() {
+        let sessions            = WebSessions::new();
+        let (session_id, _)     = sessions.new_session(NullSession::new(), "test");
+
+        let (_, mut old_socket) = sessions.connect_socket(&session_id).unwrap();
+        let (_, new_socket)     = sessions.connect_socket(&session_id).unwrap();
+
+        // Old socket is told to close
+        executor::block_on(old_socket.when_replaced());
+
+        // Disconnecting the old socket doesn't start the grace period, as the new socket is still connected
+        sessions.disconnect_socket(&old_socket);
+        assert!(sessions.remove_sessions_expired_at(Instant::now() + DEFAULT_SESSION_GRACE_PERIOD * 2) == 0);
+
+        sessions.disconnect_socket(&new_socket);
+        assert!(sessions.remove_sessions_expired_at(Instant::now() + DEFAULT_SESSION_GRACE_PERIOD * 2) == 1);
+        assert!(sessions.get_session(&session_id).is_none());
+    }
+
+    #[test]
+    fn sessions_are_kept_during_grace_period() {
+        let sessions            = WebSessions::new().with_grace_period(Duration::from_secs(60));
+        let (session_id, _)     = sessions.new_session(NullSession::new(), "test");
+
+        let (_, socket)         = sessions.connect_socket(&session_id).unwrap();
+        sessions.disconnect_socket(&socket);
+
+        assert!(sessions.remove_sessions_expired_at(Instant::now() + Duration::from_secs(30)) == 0);
+        assert!(sessions.connect_socket(&session_id).is_some());
+    }
+
+    #[test]
+    fn expired_session_stops_run_loop() {
+        let sessions                = WebSessions::new().with_grace_period(Duration::from_secs(60));
+        let (session_id, run_loop)  = sessions.new_session(NullSession::new(), "test");
+
+        assert!(sessions.remove_sessions_expired_at(Instant::now() + Duration::from_secs(120)) == 1);
+        assert!(sessions.get_session(&session_id).is_none());
+
+        executor::block_on(run_loop);
+    }
+}
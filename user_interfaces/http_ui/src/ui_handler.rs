@@ -1,6 +1,14 @@
 use super::event::*;
 use super::update::*;
 
+use flate2::{Compression};
+use flate2::write::{GzEncoder};
+
+use std::io::{Write};
+
+/// Responses with JSON shorter than this are never compressed, as the saving isn't worth the time spent compressing them
+pub const MIN_COMPRESSED_RESPONSE_SIZE: usize = 1024;
+
 ///
 /// Structure of a request sent to the UI handler
 ///
@@ -21,3 +29,73 @@ pub struct UiHandlerResponse {
     /// Updates generated for this request
     pub updates: Vec<Update>
 }
+
+///
+/// The body of a UI handler response, encoded ready to send to the client
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct EncodedUiHandlerResponse {
+    /// The value of the `Content-Encoding` header for this response (or None if the header should be left out)
+    pub content_encoding: Option<&'static str>,
+
+    /// The body of the response
+    pub body: Vec<u8>
+}
+
+impl UiHandlerResponse {
+    ///
+    /// Encodes this response as JSON, compressed with gzip if the client accepts it and the response is large enough to be worth compressing
+    ///
+    pub fn encode(&self, accepts_gzip: bool) -> EncodedUiHandlerResponse {
+        let json = serde_json::to_vec(self).unwrap();
+
+        if accepts_gzip && json.len() >= MIN_COMPRESSED_RESPONSE_SIZE {
+            let mut encoder = GzEncoder::new(vec![], Compression::fast());
+            encoder.write_all(&json).unwrap();
+
+            EncodedUiHandlerResponse {
+                content_encoding:   Some("gzip"),
+                body:               encoder.finish().unwrap()
+            }
+        } else {
+            EncodedUiHandlerResponse {
+                content_encoding:   None,
+                body:               json
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use flate2::read::{GzDecoder};
+
+    use std::io::{Read};
+
+    #[test]
+    fn small_responses_are_not_compressed() {
+        let response = UiHandlerResponse { updates: vec![Update::NewSession("session".to_string())] };
+        let encoded  = response.encode(true);
+
+        assert!(encoded.content_encoding == None);
+        assert!(encoded.body == serde_json::to_vec(&response).unwrap());
+    }
+
+    #[test]
+    fn large_responses_are_compressed_if_accepted() {
+        let response = UiHandlerResponse { updates: (0..100).map(|idx| Update::NewSession(format!("session {}", idx))).collect() };
+        let json     = serde_json::to_vec(&response).unwrap();
+
+        assert!(response.encode(false).content_encoding == None);
+
+        let encoded  = response.encode(true);
+        assert!(encoded.content_encoding == Some("gzip"));
+        assert!(encoded.body.len() < json.len());
+
+        let mut decoded = vec![];
+        GzDecoder::new(&encoded.body[..]).read_to_end(&mut decoded).unwrap();
+        assert!(decoded == json);
+    }
+}
@@ -32,6 +32,30 @@ impl HtmlDiff {
     }
 }
 
+///
+/// Describes an edit to the HTML tree that can move nodes around as well as replacing them
+///
+/// Edits are applied in order, so the addresses and indices in each edit are for the document
+/// with the edits before it already applied.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum HtmlEdit {
+    /// Replaces a node in the document
+    Replace(HtmlDiff),
+
+    /// Changes the classes of the node at the specified address without replacing it
+    SetClasses { address: Vec<u32>, classes: Vec<String> },
+
+    /// Inserts a new node into the children of the node at the specified address
+    Insert { address: Vec<u32>, index: u32, ui_tree: serde_json::Value, new_html: String },
+
+    /// Removes one of the children of the node at the specified address
+    Delete { address: Vec<u32>, index: u32 },
+
+    /// Moves one of the children of the node at the specified address from one index to another
+    Move { address: Vec<u32>, from: u32, to: u32 }
+}
+
 ///
 /// Represents an instruction to perform an update in the web interface
 ///
@@ -76,6 +100,11 @@ pub enum Update {
     ///
     UpdateHtml(Vec<HtmlDiff>),
 
+    ///
+    /// Specifies how the HTML should be edited when controls are moved around
+    ///
+    EditHtml(Vec<HtmlEdit>),
+
     ///
     /// Specifies that a canvas should be updated
     ///
@@ -28,6 +28,16 @@ pub trait ActixSession {
     ///
     fn get_session(&self, session_id: &str) -> Option<Arc<Mutex<HttpSession<Self::CoreUi>>>>;
 
+    ///
+    /// Connects a websocket to the session with the specified ID, replacing any websocket that's already connected
+    ///
+    fn connect_socket(&self, session_id: &str) -> Option<(Arc<Mutex<HttpSession<Self::CoreUi>>>, SessionSocket)>;
+
+    ///
+    /// Indicates that a websocket has disconnected from its session
+    ///
+    fn disconnect_socket(&self, socket: &SessionSocket);
+
     ///
     /// Retrieves the log for this session
     ///
@@ -55,6 +65,22 @@ impl<CoreController: HttpController+Controller+'static> ActixSession for WebSess
         WebSessions::<CoreController>::get_session(self, session_id)
     }
 
+    ///
+    /// Connects a websocket to the session with the specified ID, replacing any websocket that's already connected
+    ///
+    #[inline]
+    fn connect_socket(&self, session_id: &str) -> Option<(Arc<Mutex<HttpSession<Self::CoreUi>>>, SessionSocket)> {
+        WebSessions::<CoreController>::connect_socket(self, session_id)
+    }
+
+    ///
+    /// Indicates that a websocket has disconnected from its session
+    ///
+    #[inline]
+    fn disconnect_socket(&self, socket: &SessionSocket) {
+        WebSessions::<CoreController>::disconnect_socket(self, socket)
+    }
+
     ///
     /// Retrieves the log for this session
     ///
@@ -1,5 +1,6 @@
 use super::actix_session::*;
 use super::session_resource_handler::*;
+use super::static_file_handler::*;
 
 use flo_http_ui::*;
 
@@ -126,14 +127,25 @@ fn handle_ui_request<Session: ActixSession+Sync+Send+'static>(req: HttpRequest,
         }
     };
 
+    // The response is compressed if it's large enough and the client supports it
+    let accepts_gzip = accepts_gzip(&req);
+
     // Turn the UI response into a JSON response
     response
         .map(move |response| {
             response.map(|response| {
-                HttpResponse::Ok()
+                let encoded         = response.encode(accepts_gzip);
+
+                let mut json        = HttpResponse::Ok();
+                json
                     .append_header((http::header::CONTENT_TYPE, "application/json; charset=utf-8"))
-                    .json(response)
-                })
+                    .append_header((http::header::VARY, "Accept-Encoding"));
+                if let Some(content_encoding) = encoded.content_encoding {
+                    json.append_header((http::header::CONTENT_ENCODING, content_encoding));
+                }
+
+                json.body(encoded.body)
+            })
         })
 }
 
@@ -29,7 +29,8 @@ lazy_static! {
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum ResourceType {
     Image,
-    Canvas
+    Canvas,
+    ControlTree
 }
 
 impl ResourceType {
@@ -71,6 +72,16 @@ fn decode_url(path: &str) -> Option<ResourceUrl> {
     // Split up the components using the URL path separator
     let components: Vec<_> = path.split('/').collect();
 
+    // The control tree is just the session ID and 'tree'
+    if components.len() == 2 && components[1] == "tree" {
+        return Some(ResourceUrl {
+            session_id:         components[0].to_string(),
+            resource_type:      ResourceType::ControlTree,
+            controller_path:    vec![],
+            resource_name:      String::new()
+        });
+    }
+
     // Must be at least a session ID, resource type and resource name
     if components.len() < 3 {
         // Not enough components for a valid path
@@ -219,6 +230,18 @@ fn handle_canvas_request<Session: ActixSession>(_req: HttpRequest, session: &Htt
     }
 }
 
+///
+/// Produces a HTTP response for a request for the current control tree of a session
+///
+fn handle_control_tree_request<Session: ActixSession>(_req: HttpRequest, session: &HttpSession<Session::CoreUi>) -> impl Future<Output=Result<HttpResponse, Error>> {
+    let ui_json = session.http_ui().current_ui_json();
+
+    future::ok(HttpResponse::Ok()
+        .append_header((http::header::CONTENT_TYPE, "application/json; charset=utf-8"))
+        .append_header((http::header::CACHE_CONTROL, "no-cache"))
+        .json(ui_json))
+}
+
 ///
 /// Handler for get requests for a session
 ///
@@ -239,8 +262,9 @@ pub fn session_resource_handler<Session: 'static+ActixSession>(req: HttpRequest)
             if let Some(session) = session {
                 // URL is in a valid format and the session could be found
                 match resource.resource_type {
-                    ResourceType::Image     => Box::pin(handle_image_request::<Session>(req, &*session.lock().unwrap(), resource.controller_path, resource.resource_name)),
-                    ResourceType::Canvas    => Box::pin(handle_canvas_request::<Session>(req, &*session.lock().unwrap(), resource.controller_path, resource.resource_name))
+                    ResourceType::Image         => Box::pin(handle_image_request::<Session>(req, &*session.lock().unwrap(), resource.controller_path, resource.resource_name)),
+                    ResourceType::Canvas        => Box::pin(handle_canvas_request::<Session>(req, &*session.lock().unwrap(), resource.controller_path, resource.resource_name)),
+                    ResourceType::ControlTree   => Box::pin(handle_control_tree_request::<Session>(req, &*session.lock().unwrap()))
                 }
             } else {
                 // URL is in a valid format but the session could not be found
@@ -278,4 +302,15 @@ mod test {
         assert!(decoded.controller_path == vec!["controller1".to_string(), "controller2".to_string()]);
         assert!(decoded.resource_name == "image.png".to_string());
     }
+
+    #[test]
+    fn can_decode_control_tree_uri() {
+        let decoded = decode_url("/some-session-id/tree");
+
+        assert!(decoded.is_some());
+
+        let decoded = decoded.unwrap();
+        assert!(decoded.session_id == "some-session-id".to_string());
+        assert!(decoded.resource_type == ResourceType::ControlTree);
+    }
 }
@@ -21,9 +21,18 @@ use std::sync::*;
 /// Struct used to represent a websocket session
 ///
 struct FloWsSession<Session: ActixSession+'static> {
+    /// The sessions that the session for this websocket belongs to
+    sessions: Arc<Session>,
+
     /// The session that belongs to this websocket
     session: Arc<Mutex<HttpSession<Session::CoreUi>>>,
 
+    /// The connection between this websocket and its session
+    socket: SessionSocket,
+
+    /// Tracks the updates that have been sent to the client but not acknowledged yet
+    update_window: UpdateWindow,
+
     /// The event sink for this session
     event_sink: BoxFuture<'static, WeakPublisher<Vec<Event>>>
 }
@@ -32,12 +41,15 @@ impl<Session: ActixSession+'static> FloWsSession<Session> {
     ///
     /// Creates a new websocket session
     ///
-    pub fn new(session: Arc<Mutex<HttpSession<Session::CoreUi>>>) -> FloWsSession<Session> {
+    pub fn new(sessions: Arc<Session>, session: Arc<Mutex<HttpSession<Session::CoreUi>>>, socket: SessionSocket) -> FloWsSession<Session> {
         let event_sink = future::ready(session.lock().unwrap().http_ui().get_input_sink());
 
         FloWsSession {
-            session:    session,
-            event_sink: Box::pin(event_sink)
+            sessions:       sessions,
+            session:        session,
+            socket:         socket,
+            update_window:  UpdateWindow::new(DEFAULT_MAX_UNACKNOWLEDGED_UPDATES),
+            event_sink:     Box::pin(event_sink)
         }
     }
 
@@ -45,8 +57,9 @@ impl<Session: ActixSession+'static> FloWsSession<Session> {
     /// Starts sending updates to this actor (once a context is available)
     ///
     pub fn start_sending_updates(&mut self, ctx: &mut ws::WebsocketContext<Self>) {
-        // Retrieve the stream of updates we need to send to the websocket
-        let update_stream = self.session.lock().unwrap().http_ui().get_updates();
+        // Retrieve the stream of updates we need to send to the websocket (the websocket buffers everything it's asked to
+        // send, so updates are held back and coalesced until the client acknowledges the ones it has already received)
+        let update_stream = self.session.lock().unwrap().get_coalesced_updates(self.update_window.clone());
         let update_stream = fut::wrap_stream::<_, Self>(update_stream);
 
         // Updates are sent to the websocket
@@ -57,16 +70,39 @@ impl<Session: ActixSession+'static> FloWsSession<Session> {
         // Spawn the updates on the context
         ctx.spawn(update_stream.finish());
     }
+
+    ///
+    /// Closes this websocket if a newer websocket connects to the same session
+    ///
+    pub fn close_when_replaced(&mut self, ctx: &mut ws::WebsocketContext<Self>) {
+        let replaced = fut::wrap_future::<_, Self>(self.socket.when_replaced());
+        let replaced = replaced.map(|_, _actor, ctx: &mut ws::WebsocketContext<Self>| {
+            ctx.close(Some(ws::CloseReason { code: ws::CloseCode::Normal, description: Some("Session connected to another websocket".to_string()) }));
+            ctx.stop();
+        });
+
+        ctx.spawn(replaced);
+    }
 }
 
 impl<Session: ActixSession+'static> Actor for FloWsSession<Session> {
     type Context = ws::WebsocketContext<Self>;
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        // The session is kept for a grace period so that the client can reconnect
+        self.sessions.disconnect_socket(&self.socket);
+    }
 }
 
 impl<Session: ActixSession+'static> StreamHandler<Result<ws::Message, ws::ProtocolError>> for FloWsSession<Session> {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
         // Text messages are decoded as arrays of HTTP events and sent to the event sink
         match msg {
+            Ok(ws::Message::Text(ref message)) if &**message == UPDATE_ACKNOWLEDGEMENT => {
+                // Client has finished processing a set of updates
+                self.update_window.acknowledge();
+            },
+
             Ok(ws::Message::Text(message)) => {
                 // Parse the JSON message
                 let json = serde_json::from_str::<Vec<Event>>(&message);
@@ -89,6 +125,11 @@ impl<Session: ActixSession+'static> StreamHandler<Result<ws::Message, ws::Protoc
 
             Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
 
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            },
+
             Err(_protocol_error) => { }
             _ => (),
         }
@@ -96,6 +137,7 @@ impl<Session: ActixSession+'static> StreamHandler<Result<ws::Message, ws::Protoc
 
     fn started(&mut self, ctx: &mut Self::Context) {
         self.start_sending_updates(ctx);
+        self.close_when_replaced(ctx);
     }
 }
 
@@ -114,30 +156,28 @@ pub fn session_websocket_handler<Session: 'static+ActixSession>(req: HttpRequest
             tail.to_string()
         };
 
-        // Look up the session
         let session_state   = req.app_data::<Arc<Session>>().cloned().expect("Flowbetween session state");
-        let session         = session_state.get_session(&session_id).clone();
 
         Box::pin(async move {
-            if let Some(session) = session {
-                // Start a new websocket for this session
-                let session = FloWsSession::<Session>::new(session);
+            // Need to perform the handshake manually due to the need to set up the sending stream (actix's model assumes a strict request/response format which is not what we do)
+            let mut response = match ws::handshake(&req) {
+                Ok(response)    => response,
+                Err(err)        => { return Err(Error::from(err)); }
+            };
 
-                // Need to perform the handshake manually due to the need to set up the sending stream (actix's model assumes a strict request/response format which is not what we do)
-                let response = ws::handshake(&req).map_err(|e| Error::from(e));
-                let response = response.map(move |mut response| {
-                    // Create the stream
-                    let stream = payload;
+            // Look up the session (if the client is reconnecting, this replaces the websocket it was using before)
+            if let Some((session, socket)) = session_state.connect_socket(&session_id) {
+                // Start a new websocket for this session (its updates start with the current state of the UI)
+                let session = FloWsSession::<Session>::new(session_state, session, socket);
 
-                    // Apply to the context
-                    let ctx = ws::WebsocketContext::create(session, stream);
+                // Create the stream
+                let stream = payload;
 
-                    // Generate the response
-                    response.streaming(ctx)
-                });
+                // Apply to the context
+                let ctx = ws::WebsocketContext::create(session, stream);
 
                 // Generate the websocket response
-                response
+                Ok(response.streaming(ctx))
             } else {
                 // Session not found
                 Ok(HttpResponse::NotFound().body("Not found"))
@@ -5,48 +5,102 @@ use actix_web::http::Method;
 use futures::future;
 use futures::future::{LocalBoxFuture};
 
+use std::sync::*;
+
 ///
 /// Creates the standard static file handler
 ///
 pub fn flowbetween_static_file_handler() -> impl Fn(HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
-    static_file_handler(flowbetween_static_files())
+    static_file_handler(Arc::new(flowbetween_static_files()))
+}
+
+///
+/// True if a request indicates that the client accepts responses compressed with gzip
+///
+pub (crate) fn accepts_gzip(req: &HttpRequest) -> bool {
+    req.headers().get_all(http::header::ACCEPT_ENCODING)
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(','))
+        .any(|encoding| {
+            let mut parts   = encoding.split(';').map(|part| part.trim());
+            let name        = parts.next().unwrap_or("");
+            let rejected    = parts.any(|param| param.strip_prefix("q=").and_then(|quality| quality.parse::<f32>().ok()) == Some(0.0));
+
+            name.eq_ignore_ascii_case("gzip") && !rejected
+        })
+}
+
+///
+/// Returns the encoding to use for a static file requested by a particular request
+///
+fn preferred_encoding(req: &HttpRequest) -> ContentEncoding {
+    if accepts_gzip(req) { ContentEncoding::Gzip } else { ContentEncoding::Identity }
 }
 
 ///
 /// Creates a handler for serving static files from a service
 ///
-pub fn static_file_handler(static_files: StaticService) -> impl Fn(HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
+/// Files can be added to or removed from the service while the handler is in use.
+///
+pub fn static_file_handler(static_files: Arc<StaticService>) -> impl Fn(HttpRequest) -> LocalBoxFuture<'static, Result<HttpResponse, Error>> {
     move |req: HttpRequest| {
         // The tail specifies the file
         let tail = req.match_info().get("tail");
 
         // Find the file at this path
-        let file = tail.and_then(|tail| static_files.file_for_path(tail));
+        let file = tail.and_then(|tail| static_files.file_for_path_with_encoding(tail, preferred_encoding(&req)));
 
-        if let Some(file) = file {
+        if let Some((file, encoding)) = file {
             // File exists
-            let content_type    = file.mime_type().to_string();
+            let content_type    = file.content_type().to_string();
             let etag            = file.etag();
+            let (_, content)    = file.content_with_encoding(encoding);
+
+            // The client can skip downloading the file if it already has the current version
+            let not_modified    = req.headers().get_all(http::header::IF_NONE_MATCH)
+                .filter_map(|header| header.to_str().ok())
+                .any(|if_none_match| file.matches_etag(if_none_match));
 
             // Found a file
-            if req.method() == &Method::GET {
+            if not_modified && (req.method() == &Method::GET || req.method() == &Method::HEAD) {
+                // Just the headers that describe the cached version
+                let not_modified    = HttpResponse::NotModified()
+                    .append_header((http::header::ETAG, etag))
+                    .append_header((http::header::CACHE_CONTROL, "public, max-age=60"))
+                    .append_header((http::header::VARY, "Accept-Encoding"))
+                    .finish();
+
+                Box::pin(future::ok(not_modified))
+            } else if req.method() == &Method::GET {
                 // Append the body and return
-                let found           = HttpResponse::Ok()
+                let mut found       = HttpResponse::Ok();
+                found
                     .append_header((http::header::ETAG, etag))
                     .append_header((http::header::CONTENT_TYPE, content_type))
-                    .append_header((http::header::CONTENT_LENGTH, format!("{}", file.content().len())))
+                    .append_header((http::header::CONTENT_LENGTH, format!("{}", content.len())))
                     .append_header((http::header::CACHE_CONTROL, "public, max-age=60"))
-                    .body(Vec::from(file.content()));
+                    .append_header((http::header::VARY, "Accept-Encoding"));
+                if let Some(content_encoding) = encoding.content_encoding_header() {
+                    found.append_header((http::header::CONTENT_ENCODING, content_encoding));
+                }
+
+                let found           = found.body(Vec::from(content));
 
                 Box::pin(future::ok(found))
             } else if req.method() == &Method::HEAD {
                 // Just the headers
-                let found           = HttpResponse::Ok()
+                let mut found       = HttpResponse::Ok();
+                found
                     .append_header((http::header::ETAG, etag))
                     .append_header((http::header::CONTENT_TYPE, content_type))
-                    .append_header((http::header::CONTENT_LENGTH, format!("{}", file.content().len())))
+                    .append_header((http::header::CONTENT_LENGTH, format!("{}", content.len())))
                     .append_header((http::header::CACHE_CONTROL, "public, max-age=60"))
-                    .finish();
+                    .append_header((http::header::VARY, "Accept-Encoding"));
+                if let Some(content_encoding) = encoding.content_encoding_header() {
+                    found.append_header((http::header::CONTENT_ENCODING, content_encoding));
+                }
+
+                let found           = found.finish();
 
                 Box::pin(future::ok(found))
             } else {